@@ -1,5 +1,51 @@
+use sha2::{Digest, Sha256};
+use subtle_encoding::hex;
+
+use crate::application::ics20_fungible_token_transfer::denom::{Coin, DenomTrace};
+use crate::application::ics20_fungible_token_transfer::error::Error;
 use crate::ics04_channel::context::{ChannelKeeper, ChannelReader};
+use crate::ics24_host::identifier::{ChannelId, PortId};
+use crate::signer::Signer;
+
+/// The version of the ICS20 application, used when deriving the escrow addresses.
+pub const ICS20_VERSION: &str = "ics20-1";
+
+/// The bank abstraction the ICS20 application relies on to move, mint and burn tokens.
+pub trait BankKeeper {
+    /// Transfers `coin` from the `from` account to the `to` account.
+    fn send_coins(&mut self, from: &Signer, to: &Signer, coin: &Coin) -> Result<(), Error>;
+
+    /// Creates `coin` out of thin air and credits it to `account`.
+    fn mint_coins(&mut self, account: &Signer, coin: &Coin) -> Result<(), Error>;
+
+    /// Debits `coin` from `account` and destroys it.
+    fn burn_coins(&mut self, account: &Signer, coin: &Coin) -> Result<(), Error>;
+}
 
 /// Captures all the dependencies which the ICS20 module requires to be able to dispatch and
 /// process IBC messages.
-pub trait Ics20Context: ChannelReader + ChannelKeeper + Clone {}
+pub trait Ics20Context: ChannelReader + ChannelKeeper + BankKeeper + Clone {
+    /// Returns the denomination trace whose hash is `hash`, if any.
+    fn get_denom_trace(&self, hash: &str) -> Option<DenomTrace>;
+
+    /// Stores the given denomination trace, indexed by its hash.
+    fn store_denom_trace(&mut self, denom_trace: DenomTrace) -> Result<(), Error>;
+
+    /// Returns the address of the account which escrows the tokens sent over the given port and
+    /// channel.
+    fn get_channel_escrow_address(&self, port_id: &PortId, channel_id: &ChannelId) -> Signer {
+        get_escrow_address(port_id, channel_id)
+    }
+}
+
+/// Derives the escrow address of the given port and channel, following the Cosmos SDK: the first
+/// 20 bytes of `SHA256(version || 0x00 || "{port}/{channel}")`, hex-encoded.
+pub fn get_escrow_address(port_id: &PortId, channel_id: &ChannelId) -> Signer {
+    let mut hasher = Sha256::new();
+    hasher.update(ICS20_VERSION.as_bytes());
+    hasher.update(&[0u8]);
+    hasher.update(format!("{}/{}", port_id, channel_id).as_bytes());
+
+    let digest = hasher.finalize();
+    Signer::new(String::from_utf8(hex::encode_upper(&digest[..20])).unwrap())
+}
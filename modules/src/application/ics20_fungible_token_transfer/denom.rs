@@ -0,0 +1,231 @@
+//! Denomination tracing for ICS20 fungible tokens, as specified in ADR 001 of ibc-go:
+//! https://github.com/cosmos/ibc-go/blob/main/docs/architecture/adr-001-coin-source-tracing.md
+
+use std::convert::TryFrom;
+use std::fmt;
+
+use sha2::{Digest, Sha256};
+use subtle_encoding::hex;
+
+use ibc_proto::ibc::apps::transfer::v1::DenomTrace as RawDenomTrace;
+
+use crate::application::ics20_fungible_token_transfer::error::{Error, Kind};
+use crate::ics24_host::identifier::{ChannelId, PortId};
+
+/// The prefix of the denomination of vouchers, i.e., tokens that have been received over IBC.
+pub const IBC_DENOM_PREFIX: &str = "ibc/";
+
+/// A token amount of a given denomination.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Coin {
+    pub denom: String,
+    pub amount: u64,
+}
+
+impl Coin {
+    pub fn new(denom: impl Into<String>, amount: u64) -> Self {
+        Self {
+            denom: denom.into(),
+            amount,
+        }
+    }
+}
+
+impl fmt::Display for Coin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.amount, self.denom)
+    }
+}
+
+/// The base denomination of a token, together with the path (i.e., sequence of port and channel
+/// identifiers) the token has been transferred over.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DenomTrace {
+    /// The `{port}/{channel}` pairs the token has been transferred over, separated by `/`.
+    pub path: String,
+    /// The denomination of the token on its source chain.
+    pub base_denom: String,
+}
+
+impl DenomTrace {
+    /// Parses a full denomination path (e.g. `transfer/channel-0/uatom`) into a denomination trace.
+    ///
+    /// As in ibc-go, the path consists of the leading `{port}/{channel}` pairs whose channel is a
+    /// valid channel identifier, and everything else is the base denomination, which may itself
+    /// contain `/` (e.g. `gamm/pool/1`).
+    pub fn parse(full_denom_path: &str) -> Self {
+        let items: Vec<&str> = full_denom_path.split('/').collect();
+
+        let mut path_len = 0;
+        while path_len + 2 < items.len() && is_channel_id(items[path_len + 1]) {
+            path_len += 2;
+        }
+
+        Self {
+            path: items[..path_len].join("/"),
+            base_denom: items[path_len..].join("/"),
+        }
+    }
+
+    /// Returns the full denomination path, i.e., `{path}/{base_denom}`.
+    pub fn get_full_denom_path(&self) -> String {
+        if self.path.is_empty() {
+            self.base_denom.clone()
+        } else {
+            format!("{}/{}", self.path, self.base_denom)
+        }
+    }
+
+    /// Returns the upper-case hex-encoded SHA256 hash of the full denomination path.
+    pub fn hash(&self) -> String {
+        let digest = Sha256::digest(self.get_full_denom_path().as_bytes());
+        String::from_utf8(hex::encode_upper(digest)).unwrap()
+    }
+
+    /// Returns the denomination of the token on this chain: the base denomination for native
+    /// tokens, or `ibc/{hash}` for vouchers.
+    pub fn ibc_denom(&self) -> String {
+        if self.path.is_empty() {
+            self.base_denom.clone()
+        } else {
+            format!("{}{}", IBC_DENOM_PREFIX, self.hash())
+        }
+    }
+}
+
+impl fmt::Display for DenomTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.get_full_denom_path())
+    }
+}
+
+impl TryFrom<RawDenomTrace> for DenomTrace {
+    type Error = Error;
+
+    fn try_from(raw: RawDenomTrace) -> Result<Self, Self::Error> {
+        if raw.base_denom.trim().is_empty() {
+            return Err(Kind::InvalidDenomTrace(raw.path).into());
+        }
+
+        Ok(Self {
+            path: raw.path,
+            base_denom: raw.base_denom,
+        })
+    }
+}
+
+impl From<DenomTrace> for RawDenomTrace {
+    fn from(value: DenomTrace) -> Self {
+        RawDenomTrace {
+            path: value.path,
+            base_denom: value.base_denom,
+        }
+    }
+}
+
+/// Returns true if `id` is a channel identifier generated by a channel handshake, i.e.
+/// `channel-{N}`.
+fn is_channel_id(id: &str) -> bool {
+    id.strip_prefix("channel-")
+        .map_or(false, |n| n.parse::<u64>().is_ok())
+}
+
+/// Returns the prefix added to the denomination of a token received over the given port and
+/// channel, i.e., `{port}/{channel}/`.
+pub fn get_denom_prefix(port_id: &PortId, channel_id: &ChannelId) -> String {
+    format!("{}/{}/", port_id, channel_id)
+}
+
+/// Returns the denomination obtained by prefixing `denom` with the given port and channel.
+pub fn get_prefixed_denom(port_id: &PortId, channel_id: &ChannelId, denom: &str) -> String {
+    format!("{}{}", get_denom_prefix(port_id, channel_id), denom)
+}
+
+/// Returns true if the token with the full denomination path `denom`, sent over the given
+/// source port and channel, originally came from the receiving chain. In that case, the
+/// receiving chain unescrows the tokens instead of minting vouchers.
+pub fn receiver_chain_is_source(
+    source_port: &PortId,
+    source_channel: &ChannelId,
+    denom: &str,
+) -> bool {
+    denom.starts_with(&get_denom_prefix(source_port, source_channel))
+}
+
+/// Returns true if the token with the full denomination path `denom`, sent over the given
+/// source port and channel, did not originally come from the receiving chain. In that case, the
+/// sending chain escrows the tokens instead of burning vouchers.
+pub fn sender_chain_is_source(
+    source_port: &PortId,
+    source_channel: &ChannelId,
+    denom: &str,
+) -> bool {
+    !receiver_chain_is_source(source_port, source_channel, denom)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn denom_trace_parsing() {
+        let native = DenomTrace::parse("uatom");
+        assert_eq!(native.path, "");
+        assert_eq!(native.base_denom, "uatom");
+        assert_eq!(native.ibc_denom(), "uatom");
+
+        let voucher = DenomTrace::parse("transfer/channel-0/transfer/channel-1/uatom");
+        assert_eq!(voucher.path, "transfer/channel-0/transfer/channel-1");
+        assert_eq!(voucher.base_denom, "uatom");
+        assert_eq!(
+            voucher.get_full_denom_path(),
+            "transfer/channel-0/transfer/channel-1/uatom"
+        );
+
+        let pool = DenomTrace::parse("gamm/pool/1");
+        assert_eq!(pool.path, "");
+        assert_eq!(pool.base_denom, "gamm/pool/1");
+
+        let pool_voucher = DenomTrace::parse("transfer/channel-0/gamm/pool/1");
+        assert_eq!(pool_voucher.path, "transfer/channel-0");
+        assert_eq!(pool_voucher.base_denom, "gamm/pool/1");
+
+        let trailing = DenomTrace::parse("transfer/channel-0");
+        assert_eq!(trailing.path, "");
+        assert_eq!(trailing.base_denom, "transfer/channel-0");
+    }
+
+    #[test]
+    fn ibc_denom_hash() {
+        // The voucher denomination is the hash of the full denomination path.
+        let trace = DenomTrace::parse("transfer/channel-0/uatom");
+        assert_eq!(
+            trace.ibc_denom(),
+            "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2"
+        );
+    }
+
+    #[test]
+    fn source_chain() {
+        let port = PortId::from_str("transfer").unwrap();
+        let channel = ChannelId::from_str("channel-0").unwrap();
+
+        assert!(receiver_chain_is_source(
+            &port,
+            &channel,
+            "transfer/channel-0/uatom"
+        ));
+        assert!(sender_chain_is_source(&port, &channel, "uatom"));
+        assert!(sender_chain_is_source(
+            &port,
+            &channel,
+            "transfer/channel-1/uatom"
+        ));
+        assert_eq!(
+            get_prefixed_denom(&port, &channel, "uatom"),
+            "transfer/channel-0/uatom"
+        );
+    }
+}
@@ -1,7 +1,9 @@
 use anomaly::{BoxError, Context};
 use thiserror::Error;
 
+use crate::ics04_channel::channel::Order;
 use crate::ics24_host::identifier::{ChannelId, PortId};
+use crate::signer::Signer;
 
 pub type Error = anomaly::Error<Kind>;
 
@@ -19,10 +21,43 @@ pub enum Kind {
     #[error("Missing channel for port_id {0} and channel_id {1} ")]
     ChannelNotFound(PortId, ChannelId),
 
+    #[error("the ICS20 module does not hold the capability of port {0} and channel {1}")]
+    MissingChannelCapability(PortId, ChannelId),
+
     #[error(
         "Destination channel not found in the counterparty of port_id {0} and channel_id {1} "
     )]
     DestinationChannelNotFound(PortId, ChannelId),
+
+    #[error("missing token in the transfer message")]
+    MissingToken,
+
+    #[error("invalid token amount {0}")]
+    InvalidAmount(String),
+
+    #[error("invalid denomination trace with path {0}")]
+    InvalidDenomTrace(String),
+
+    #[error("denomination trace not found for denomination {0}")]
+    DenomTraceNotFound(String),
+
+    #[error("invalid packet data")]
+    InvalidPacketData,
+
+    #[error("invalid acknowledgement")]
+    InvalidAcknowledgement,
+
+    #[error("insufficient funds: account {0} does not hold {1}")]
+    InsufficientFunds(Signer, String),
+
+    #[error("balance overflow: account {0} cannot be credited {1}")]
+    BalanceOverflow(Signer, String),
+
+    #[error("invalid channel ordering {0}, expected {1}")]
+    InvalidChannelOrder(Order, Order),
+
+    #[error("invalid version {0}, expected {1}")]
+    InvalidVersion(String, String),
 }
 
 impl Kind {
@@ -0,0 +1,145 @@
+//! The ICS20 application module, which hooks the token transfer logic into the ICS26 router.
+
+use std::error::Error;
+
+use prost_types::Any;
+use tendermint_proto::Protobuf;
+
+use crate::application::ics20_fungible_token_transfer::context::{Ics20Context, ICS20_VERSION};
+use crate::application::ics20_fungible_token_transfer::error::Kind;
+use crate::application::ics20_fungible_token_transfer::msgs::transfer::{self, MsgTransfer};
+use crate::application::ics20_fungible_token_transfer::packet::FungibleTokenPacketAcknowledgement;
+use crate::application::ics20_fungible_token_transfer::relay_application_logic::{
+    on_acknowledgement_packet::on_acknowledgement_packet, on_recv_packet::on_recv_packet,
+    on_timeout_packet::on_timeout_packet, send_transfer::send_transfer,
+};
+use crate::handler::HandlerOutput;
+use crate::ics04_channel::channel::{ChannelEnd, Order};
+use crate::ics04_channel::context::ChannelKeeper;
+use crate::ics04_channel::packet::Packet;
+use crate::ics24_host::identifier::{ChannelId, PortId};
+use crate::ics26_routing::router::{Module, ModuleCapabilities};
+use crate::signer::Signer;
+
+/// The ICS20 fungible token transfer module, usually bound to the `transfer` port.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Ics20Module;
+
+impl<Ctx: Ics20Context> Module<Ctx> for Ics20Module {
+    fn on_chan_open_init(
+        &self,
+        _ctx: &mut Ctx,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        channel_end: &ChannelEnd,
+    ) -> Result<(), Box<dyn Error>> {
+        validate_channel(channel_end)
+    }
+
+    fn on_chan_open_try(
+        &self,
+        _ctx: &mut Ctx,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        channel_end: &ChannelEnd,
+        counterparty_version: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        validate_channel(channel_end)?;
+        validate_version(counterparty_version)
+    }
+
+    fn on_chan_open_ack(
+        &self,
+        _ctx: &mut Ctx,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        counterparty_version: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        validate_version(counterparty_version)
+    }
+
+    fn on_recv_packet(&self, ctx: &mut Ctx, packet: &Packet, _relayer: &Signer) -> Vec<u8> {
+        on_recv_packet(ctx, packet).to_json_bytes()
+    }
+
+    fn on_acknowledgement_packet(
+        &self,
+        ctx: &mut Ctx,
+        packet: &Packet,
+        acknowledgement: &[u8],
+        _relayer: &Signer,
+    ) -> Result<(), Box<dyn Error>> {
+        let acknowledgement = FungibleTokenPacketAcknowledgement::from_json_bytes(acknowledgement)?;
+        Ok(on_acknowledgement_packet(ctx, packet, &acknowledgement)?)
+    }
+
+    fn on_timeout_packet(
+        &self,
+        ctx: &mut Ctx,
+        packet: &Packet,
+        _relayer: &Signer,
+    ) -> Result<(), Box<dyn Error>> {
+        Ok(on_timeout_packet(ctx, packet)?)
+    }
+
+    fn deliver(
+        &self,
+        ctx: &mut Ctx,
+        capabilities: &ModuleCapabilities,
+        msg: &Any,
+    ) -> Option<Result<HandlerOutput<()>, Box<dyn Error>>> {
+        if msg.type_url != transfer::TYPE_URL {
+            return None;
+        }
+
+        let msg = match MsgTransfer::decode_vec(&msg.value) {
+            Ok(msg) => msg,
+            Err(e) => return Some(Err(e.into())),
+        };
+
+        // Transfers are sent by the module bound to their source port.
+        if msg.source_port != *capabilities.port_id() {
+            return None;
+        }
+
+        Some(deliver_transfer(ctx, capabilities, msg))
+    }
+}
+
+/// Sends the tokens of a `MsgTransfer` in a packet, and stores the packet commitment.
+fn deliver_transfer<Ctx: Ics20Context>(
+    ctx: &mut Ctx,
+    capabilities: &ModuleCapabilities,
+    msg: MsgTransfer,
+) -> Result<HandlerOutput<()>, Box<dyn Error>> {
+    let channel_cap = capabilities
+        .channel_capability(&msg.source_channel)
+        .ok_or_else(|| {
+            Kind::MissingChannelCapability(msg.source_port.clone(), msg.source_channel.clone())
+        })?;
+    let output = send_transfer(ctx, channel_cap, msg)?;
+
+    ctx.store_packet_result(output.result)?;
+
+    Ok(HandlerOutput::builder()
+        .with_log(output.log)
+        .with_events(output.events)
+        .with_result(()))
+}
+
+/// ICS20 channels must be unordered, and use the ICS20 version.
+fn validate_channel(channel_end: &ChannelEnd) -> Result<(), Box<dyn Error>> {
+    if *channel_end.ordering() != Order::Unordered {
+        return Err(Kind::InvalidChannelOrder(*channel_end.ordering(), Order::Unordered).into());
+    }
+
+    validate_version(&channel_end.version())
+}
+
+fn validate_version(version: &str) -> Result<(), Box<dyn Error>> {
+    if version != ICS20_VERSION {
+        return Err(Kind::InvalidVersion(version.to_string(), ICS20_VERSION.to_string()).into());
+    }
+
+    Ok(())
+}
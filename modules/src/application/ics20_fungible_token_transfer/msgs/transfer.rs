@@ -84,6 +84,8 @@ impl From<MsgTransfer> for RawMsgTransfer {
 
 #[cfg(test)]
 pub mod test_util {
+    use ibc_proto::cosmos::base::v1beta1::Coin;
+
     use crate::{
         ics24_host::identifier::{ChannelId, PortId},
         test_utils::get_dummy_account_id,
@@ -99,7 +101,10 @@ pub mod test_util {
         MsgTransfer {
             source_port: PortId::default(),
             source_channel: ChannelId::default(),
-            token: None,
+            token: Some(Coin {
+                denom: "uatom".to_string(),
+                amount: "10".to_string(),
+            }),
             sender: id.clone(),
             receiver: id,
             timeout_timestamp: 1,
@@ -0,0 +1,156 @@
+//! The ICS20 packet data and acknowledgement, JSON-encoded as specified in:
+//! https://github.com/cosmos/ics/tree/master/spec/ics-020-fungible-token-transfer#data-structures
+
+use serde_derive::{Deserialize, Serialize};
+
+use ibc_proto::ibc::apps::transfer::v1::FungibleTokenPacketData as RawFungibleTokenPacketData;
+
+use crate::application::ics20_fungible_token_transfer::error::{Error, Kind};
+
+/// The base64 encoding of the single byte `0x01`, which is the result of a successful ICS20
+/// acknowledgement.
+const SUCCESSFUL_ACK_RESULT: &str = "AQ==";
+
+/// The data carried by an ICS20 packet. The fields are declared in alphabetical order, so that the
+/// JSON encoding matches the (sorted) encoding of the Cosmos SDK.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct FungibleTokenPacketData {
+    /// The token amount to be transferred, encoded as a string.
+    #[serde(with = "amount")]
+    pub amount: u64,
+    /// The full denomination path of the token to be transferred.
+    pub denom: String,
+    /// The recipient address on the destination chain.
+    pub receiver: String,
+    /// The sender address on the source chain.
+    pub sender: String,
+}
+
+impl FungibleTokenPacketData {
+    pub fn from_json_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let data: Self =
+            serde_json::from_slice(bytes).map_err(|e| Kind::InvalidPacketData.context(e))?;
+
+        if data.amount == 0 {
+            return Err(Kind::InvalidAmount(data.amount.to_string()).into());
+        }
+        if data.denom.trim().is_empty() {
+            return Err(Kind::InvalidPacketData.context("empty denomination").into());
+        }
+
+        Ok(data)
+    }
+
+    pub fn to_json_bytes(&self) -> Vec<u8> {
+        serde_json::to_vec(self).unwrap()
+    }
+}
+
+impl From<RawFungibleTokenPacketData> for FungibleTokenPacketData {
+    fn from(raw: RawFungibleTokenPacketData) -> Self {
+        FungibleTokenPacketData {
+            amount: raw.amount,
+            denom: raw.denom,
+            receiver: raw.receiver,
+            sender: raw.sender,
+        }
+    }
+}
+
+impl From<FungibleTokenPacketData> for RawFungibleTokenPacketData {
+    fn from(value: FungibleTokenPacketData) -> Self {
+        RawFungibleTokenPacketData {
+            denom: value.denom,
+            amount: value.amount,
+            sender: value.sender,
+            receiver: value.receiver,
+        }
+    }
+}
+
+/// The acknowledgement written by the receiving chain of an ICS20 packet.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum FungibleTokenPacketAcknowledgement {
+    /// The packet was processed successfully.
+    #[serde(rename = "result")]
+    Success(String),
+    /// The packet processing failed with the given error.
+    #[serde(rename = "error")]
+    Error(String),
+}
+
+impl FungibleTokenPacketAcknowledgement {
+    pub fn success() -> Self {
+        Self::Success(SUCCESSFUL_ACK_RESULT.to_string())
+    }
+
+    pub fn is_successful(&self) -> bool {
+        matches!(self, Self::Success(_))
+    }
+
+    pub fn from_json_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        serde_json::from_slice(bytes).map_err(|e| Kind::InvalidAcknowledgement.context(e).into())
+    }
+
+    pub fn to_json_bytes(&self) -> Vec<u8> {
+        serde_json::to_vec(self).unwrap()
+    }
+}
+
+/// (De)serializes the packet amount as a string, following the JSON encoding of `uint64` in
+/// Protobuf.
+mod amount {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(amount: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&amount.to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn packet_data_json() {
+        let data = FungibleTokenPacketData {
+            amount: 100,
+            denom: "transfer/channel-0/uatom".to_string(),
+            receiver: "cosmos1receiver".to_string(),
+            sender: "cosmos1sender".to_string(),
+        };
+
+        let bytes = data.to_json_bytes();
+        assert_eq!(
+            String::from_utf8(bytes.clone()).unwrap(),
+            r#"{"amount":"100","denom":"transfer/channel-0/uatom","receiver":"cosmos1receiver","sender":"cosmos1sender"}"#
+        );
+        assert_eq!(
+            FungibleTokenPacketData::from_json_bytes(&bytes).unwrap(),
+            data
+        );
+
+        // Zero amounts and malformed data are rejected.
+        let zero = FungibleTokenPacketData { amount: 0, ..data };
+        assert!(FungibleTokenPacketData::from_json_bytes(&zero.to_json_bytes()).is_err());
+        assert!(FungibleTokenPacketData::from_json_bytes(&[0]).is_err());
+    }
+
+    #[test]
+    fn acknowledgement_json() {
+        let success = FungibleTokenPacketAcknowledgement::success();
+        assert_eq!(success.to_json_bytes(), br#"{"result":"AQ=="}"#.to_vec());
+
+        let error = FungibleTokenPacketAcknowledgement::Error("insufficient funds".to_string());
+        let decoded =
+            FungibleTokenPacketAcknowledgement::from_json_bytes(&error.to_json_bytes()).unwrap();
+        assert!(!decoded.is_successful());
+        assert_eq!(decoded, error);
+    }
+}
@@ -1,3 +1,6 @@
 //! This module implements the processing logic for ICS20 (token transfer) message.
 
+pub mod on_acknowledgement_packet;
+pub mod on_recv_packet;
+pub mod on_timeout_packet;
 pub mod send_transfer;
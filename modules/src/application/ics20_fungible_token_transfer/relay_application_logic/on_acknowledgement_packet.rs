@@ -0,0 +1,22 @@
+use crate::application::ics20_fungible_token_transfer::context::Ics20Context;
+use crate::application::ics20_fungible_token_transfer::error::Error;
+use crate::application::ics20_fungible_token_transfer::packet::FungibleTokenPacketAcknowledgement;
+use crate::application::ics20_fungible_token_transfer::relay_application_logic::on_timeout_packet::refund_packet_token;
+use crate::ics04_channel::packet::Packet;
+
+/// Processes the acknowledgement of an ICS20 packet sent by this chain. If the receiving chain
+/// failed to process the packet, the tokens are refunded to the sender.
+pub fn on_acknowledgement_packet<Ctx>(
+    ctx: &mut Ctx,
+    packet: &Packet,
+    acknowledgement: &FungibleTokenPacketAcknowledgement,
+) -> Result<(), Error>
+where
+    Ctx: Ics20Context,
+{
+    if acknowledgement.is_successful() {
+        return Ok(());
+    }
+
+    refund_packet_token(ctx, packet)
+}
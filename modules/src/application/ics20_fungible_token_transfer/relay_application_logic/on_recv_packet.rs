@@ -0,0 +1,58 @@
+use crate::application::ics20_fungible_token_transfer::context::Ics20Context;
+use crate::application::ics20_fungible_token_transfer::denom::{
+    get_denom_prefix, get_prefixed_denom, receiver_chain_is_source, Coin, DenomTrace,
+};
+use crate::application::ics20_fungible_token_transfer::error::Error;
+use crate::application::ics20_fungible_token_transfer::packet::{
+    FungibleTokenPacketAcknowledgement, FungibleTokenPacketData,
+};
+use crate::ics04_channel::packet::Packet;
+use crate::signer::Signer;
+
+/// Processes an ICS20 packet received by this chain, and returns the acknowledgement to be written.
+/// Tokens returning to this chain are unescrowed, while tokens coming from another chain are
+/// minted as vouchers. Any failure is reported in an error acknowledgement.
+pub fn on_recv_packet<Ctx>(ctx: &mut Ctx, packet: &Packet) -> FungibleTokenPacketAcknowledgement
+where
+    Ctx: Ics20Context,
+{
+    match process_recv_packet(ctx, packet) {
+        Ok(()) => FungibleTokenPacketAcknowledgement::success(),
+        Err(e) => FungibleTokenPacketAcknowledgement::Error(e.to_string()),
+    }
+}
+
+fn process_recv_packet<Ctx>(ctx: &mut Ctx, packet: &Packet) -> Result<(), Error>
+where
+    Ctx: Ics20Context,
+{
+    let data = FungibleTokenPacketData::from_json_bytes(&packet.data)?;
+    let receiver = Signer::new(&data.receiver);
+
+    if receiver_chain_is_source(&packet.source_port, &packet.source_channel, &data.denom) {
+        // The token originally came from this chain: remove the prefix added by the sender and
+        // release the escrowed tokens.
+        let prefix = get_denom_prefix(&packet.source_port, &packet.source_channel);
+        let unprefixed_denom = &data.denom[prefix.len()..];
+        let coin = Coin::new(DenomTrace::parse(unprefixed_denom).ibc_denom(), data.amount);
+
+        let escrow_address =
+            ctx.get_channel_escrow_address(&packet.destination_port, &packet.destination_channel);
+        ctx.send_coins(&escrow_address, &receiver, &coin)
+    } else {
+        // The token comes from another chain: prefix the denomination with the destination port
+        // and channel, and mint the corresponding vouchers.
+        let prefixed_denom = get_prefixed_denom(
+            &packet.destination_port,
+            &packet.destination_channel,
+            &data.denom,
+        );
+        let denom_trace = DenomTrace::parse(&prefixed_denom);
+        let coin = Coin::new(denom_trace.ibc_denom(), data.amount);
+
+        if ctx.get_denom_trace(&denom_trace.hash()).is_none() {
+            ctx.store_denom_trace(denom_trace)?;
+        }
+        ctx.mint_coins(&receiver, &coin)
+    }
+}
@@ -0,0 +1,35 @@
+use crate::application::ics20_fungible_token_transfer::context::Ics20Context;
+use crate::application::ics20_fungible_token_transfer::denom::{
+    sender_chain_is_source, Coin, DenomTrace,
+};
+use crate::application::ics20_fungible_token_transfer::error::Error;
+use crate::application::ics20_fungible_token_transfer::packet::FungibleTokenPacketData;
+use crate::ics04_channel::packet::Packet;
+use crate::signer::Signer;
+
+/// Processes the timeout of an ICS20 packet sent by this chain, refunding the tokens to the sender.
+pub fn on_timeout_packet<Ctx>(ctx: &mut Ctx, packet: &Packet) -> Result<(), Error>
+where
+    Ctx: Ics20Context,
+{
+    refund_packet_token(ctx, packet)
+}
+
+/// Reverts the effects of `send_transfer`: escrowed tokens are released back to the sender, and
+/// burned vouchers are minted again.
+pub(crate) fn refund_packet_token<Ctx>(ctx: &mut Ctx, packet: &Packet) -> Result<(), Error>
+where
+    Ctx: Ics20Context,
+{
+    let data = FungibleTokenPacketData::from_json_bytes(&packet.data)?;
+    let sender = Signer::new(&data.sender);
+    let coin = Coin::new(DenomTrace::parse(&data.denom).ibc_denom(), data.amount);
+
+    if sender_chain_is_source(&packet.source_port, &packet.source_channel, &data.denom) {
+        let escrow_address =
+            ctx.get_channel_escrow_address(&packet.source_port, &packet.source_channel);
+        ctx.send_coins(&escrow_address, &sender, &coin)
+    } else {
+        ctx.mint_coins(&sender, &coin)
+    }
+}
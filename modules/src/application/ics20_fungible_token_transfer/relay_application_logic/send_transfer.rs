@@ -1,13 +1,21 @@
 use crate::application::ics20_fungible_token_transfer::context::Ics20Context;
+use crate::application::ics20_fungible_token_transfer::denom::{
+    sender_chain_is_source, Coin, IBC_DENOM_PREFIX,
+};
 use crate::application::ics20_fungible_token_transfer::error::{Error, Kind};
 use crate::application::ics20_fungible_token_transfer::msgs::transfer::MsgTransfer;
+use crate::application::ics20_fungible_token_transfer::packet::FungibleTokenPacketData;
 use crate::handler::HandlerOutput;
 use crate::ics04_channel::handler::send_packet::send_packet;
 use crate::ics04_channel::packet::Packet;
 use crate::ics04_channel::packet::PacketResult;
+use crate::ics05_port::capabilities::Capability;
 
+/// Sends the tokens of `msg` over its source channel, whose capability `channel_cap` the ICS20
+/// module presents.
 pub(crate) fn send_transfer<Ctx>(
-    ctx: &Ctx,
+    ctx: &mut Ctx,
+    channel_cap: &Capability,
     msg: MsgTransfer,
 ) -> Result<HandlerOutput<PacketResult>, Error>
 where
@@ -34,7 +42,31 @@ where
             Kind::SequenceSendNotFound(msg.source_port.clone(), msg.source_channel.clone())
         })?;
 
-    //TODO: Application LOGIC.
+    let token = msg.token.ok_or(Kind::MissingToken)?;
+    let amount: u64 = token
+        .amount
+        .parse()
+        .map_err(|e| Kind::InvalidAmount(token.amount.clone()).context(e))?;
+    if amount == 0 {
+        return Err(Kind::InvalidAmount(token.amount).into());
+    }
+    let coin = Coin::new(token.denom, amount);
+
+    // Vouchers are sent with their full denomination path, which is recovered from the trace.
+    let full_denom_path = match coin.denom.strip_prefix(IBC_DENOM_PREFIX) {
+        Some(hash) => ctx
+            .get_denom_trace(hash)
+            .ok_or_else(|| Kind::DenomTraceNotFound(coin.denom.clone()))?
+            .get_full_denom_path(),
+        None => coin.denom.clone(),
+    };
+
+    let data = FungibleTokenPacketData {
+        amount,
+        denom: full_denom_path.clone(),
+        receiver: msg.receiver.to_string(),
+        sender: msg.sender.to_string(),
+    };
 
     let packet = Packet {
         sequence,
@@ -42,14 +74,185 @@ where
         source_channel: msg.source_channel,
         destination_port,
         destination_channel: destination_channel.clone(),
-        data: vec![0],
+        data: data.to_json_bytes(),
         timeout_height: msg.timeout_height,
         timeout_timestamp: msg.timeout_timestamp,
     };
 
-    let handler_output =
-        send_packet(ctx, packet).map_err(|e| Kind::HandlerRaisedError.context(e))?;
+    let handler_output = send_packet(&*ctx, channel_cap, packet.clone())
+        .map_err(|e| Kind::HandlerRaisedError.context(e))?;
+
+    // Tokens native to this chain (or coming from a chain other than the receiver) are escrowed,
+    // while vouchers returning to their source chain are burned.
+    if sender_chain_is_source(
+        &packet.source_port,
+        &packet.source_channel,
+        &full_denom_path,
+    ) {
+        let escrow_address =
+            ctx.get_channel_escrow_address(&packet.source_port, &packet.source_channel);
+        ctx.send_coins(&msg.sender, &escrow_address, &coin)?;
+    } else {
+        ctx.burn_coins(&msg.sender, &coin)?;
+    }
 
-    //TODO:  add event/atributes and writes to the store issued by the application logic for packet sending.
     Ok(handler_output)
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use ibc_proto::cosmos::base::v1beta1::Coin as RawCoin;
+
+    use crate::application::ics20_fungible_token_transfer::context::{
+        get_escrow_address, Ics20Context,
+    };
+    use crate::application::ics20_fungible_token_transfer::denom::{Coin, DenomTrace};
+    use crate::application::ics20_fungible_token_transfer::msgs::transfer::test_util::get_dummy_msg_transfer;
+    use crate::application::ics20_fungible_token_transfer::packet::FungibleTokenPacketAcknowledgement;
+    use crate::application::ics20_fungible_token_transfer::relay_application_logic::on_acknowledgement_packet::on_acknowledgement_packet;
+    use crate::application::ics20_fungible_token_transfer::relay_application_logic::on_recv_packet::on_recv_packet;
+    use crate::application::ics20_fungible_token_transfer::relay_application_logic::on_timeout_packet::on_timeout_packet;
+    use crate::application::ics20_fungible_token_transfer::relay_application_logic::send_transfer::send_transfer;
+    use crate::events::IbcEvent;
+    use crate::ics03_connection::connection::ConnectionEnd;
+    use crate::ics03_connection::connection::Counterparty as ConnectionCounterparty;
+    use crate::ics03_connection::connection::State as ConnectionState;
+    use crate::ics03_connection::version::get_compatible_versions;
+    use crate::ics04_channel::channel::{ChannelEnd, Counterparty, Order, State};
+    use crate::ics04_channel::packet::Packet;
+    use crate::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+    use crate::mock::context::MockContext;
+    use crate::signer::Signer;
+    use crate::Height;
+
+    /// Returns a context with an open channel over the default port and channel identifiers.
+    fn context_with_channel() -> MockContext {
+        let channel_end = ChannelEnd::new(
+            State::Open,
+            Order::default(),
+            Counterparty::new(PortId::default(), Some(ChannelId::default())),
+            vec![ConnectionId::default()],
+            "ics20-1".to_string(),
+        );
+
+        let connection_end = ConnectionEnd::new(
+            ConnectionState::Open,
+            ClientId::default(),
+            ConnectionCounterparty::new(
+                ClientId::default(),
+                Some(ConnectionId::default()),
+                Default::default(),
+            ),
+            get_compatible_versions(),
+            Duration::from_secs(0),
+        );
+
+        MockContext::default()
+            .with_client(&ClientId::default(), Height::default())
+            .with_connection(ConnectionId::default(), connection_end)
+            .with_port_capability(PortId::default())
+            .with_channel(PortId::default(), ChannelId::default(), channel_end)
+            .with_send_sequence(PortId::default(), ChannelId::default(), 1.into())
+    }
+
+    /// Sends `amount` tokens of denomination `denom` from `sender` to `receiver`, and returns the
+    /// packet that was sent.
+    fn transfer(
+        ctx: &mut MockContext,
+        sender: &Signer,
+        receiver: &Signer,
+        denom: &str,
+        amount: u64,
+    ) -> Packet {
+        let mut msg = get_dummy_msg_transfer(10);
+        msg.sender = sender.clone();
+        msg.receiver = receiver.clone();
+        msg.token = Some(RawCoin {
+            denom: denom.to_string(),
+            amount: amount.to_string(),
+        });
+
+        let channel_cap = ctx.channel_capability(&PortId::default(), &ChannelId::default());
+        let output = send_transfer(ctx, &channel_cap, msg).unwrap();
+        match output.events.first() {
+            Some(IbcEvent::SendPacket(send_packet)) => send_packet.packet.clone(),
+            _ => panic!("send_transfer did not emit a SendPacket event"),
+        }
+    }
+
+    #[test]
+    fn transfer_round_trip() {
+        let alice = Signer::new("alice");
+        let bob = Signer::new("bob");
+        let escrow = get_escrow_address(&PortId::default(), &ChannelId::default());
+
+        let mut ctx_a = context_with_channel().with_balance(alice.clone(), Coin::new("uatom", 100));
+        let mut ctx_b = context_with_channel();
+
+        // Chain A escrows its native tokens, and chain B mints the corresponding vouchers.
+        let packet = transfer(&mut ctx_a, &alice, &bob, "uatom", 30);
+        assert_eq!(ctx_a.balance(&alice, "uatom"), 70);
+        assert_eq!(ctx_a.balance(&escrow, "uatom"), 30);
+
+        let ack = on_recv_packet(&mut ctx_b, &packet);
+        assert!(ack.is_successful());
+
+        let trace = DenomTrace::parse("defaultPort/channel-0/uatom");
+        let voucher = trace.ibc_denom();
+        assert_eq!(ctx_b.balance(&bob, &voucher), 30);
+        assert_eq!(ctx_b.get_denom_trace(&trace.hash()), Some(trace));
+
+        // Returning the vouchers burns them on chain B and unescrows the tokens on chain A.
+        let packet = transfer(&mut ctx_b, &bob, &alice, &voucher, 30);
+        assert_eq!(ctx_b.balance(&bob, &voucher), 0);
+
+        let ack = on_recv_packet(&mut ctx_a, &packet);
+        assert!(ack.is_successful());
+        assert_eq!(ctx_a.balance(&alice, "uatom"), 100);
+        assert_eq!(ctx_a.balance(&escrow, "uatom"), 0);
+
+        // Tokens that are not held by the escrow account cannot be unescrowed.
+        let ack = on_recv_packet(&mut ctx_a, &packet);
+        assert!(!ack.is_successful());
+    }
+
+    #[test]
+    fn transfer_refunds() {
+        let alice = Signer::new("alice");
+        let bob = Signer::new("bob");
+
+        let mut ctx = context_with_channel().with_balance(alice.clone(), Coin::new("uatom", 100));
+
+        // Senders cannot transfer more than their balance.
+        let mut msg = get_dummy_msg_transfer(10);
+        msg.sender = alice.clone();
+        msg.token = Some(RawCoin {
+            denom: "uatom".to_string(),
+            amount: "101".to_string(),
+        });
+        let channel_cap = ctx.channel_capability(&PortId::default(), &ChannelId::default());
+        assert!(send_transfer(&mut ctx, &channel_cap, msg).is_err());
+
+        // Timed out packets are refunded.
+        let packet = transfer(&mut ctx, &alice, &bob, "uatom", 40);
+        assert_eq!(ctx.balance(&alice, "uatom"), 60);
+        on_timeout_packet(&mut ctx, &packet).unwrap();
+        assert_eq!(ctx.balance(&alice, "uatom"), 100);
+
+        // So are packets acknowledged with an error, but not successfully acknowledged ones.
+        let packet = transfer(&mut ctx, &alice, &bob, "uatom", 40);
+        on_acknowledgement_packet(
+            &mut ctx,
+            &packet,
+            &FungibleTokenPacketAcknowledgement::success(),
+        )
+        .unwrap();
+        assert_eq!(ctx.balance(&alice, "uatom"), 60);
+
+        let error = FungibleTokenPacketAcknowledgement::Error("failure".to_string());
+        on_acknowledgement_packet(&mut ctx, &packet, &error).unwrap();
+        assert_eq!(ctx.balance(&alice, "uatom"), 100);
+    }
+}
@@ -7,6 +7,7 @@ use crate::ics02_client::events as ClientEvents;
 use crate::ics02_client::events::NewBlock;
 use crate::ics03_connection::events as ConnectionEvents;
 use crate::ics04_channel::events as ChannelEvents;
+use crate::ics04_channel::packet::Packet;
 use crate::Height;
 use prost::alloc::fmt::Formatter;
 use std::fmt;
@@ -158,6 +159,19 @@ impl IbcEvent {
         }
     }
 
+    /// The packet this event pertains to, if any.
+    pub fn packet(&self) -> Option<&Packet> {
+        match self {
+            IbcEvent::SendPacket(ev) => Some(&ev.packet),
+            IbcEvent::ReceivePacket(ev) => Some(&ev.packet),
+            IbcEvent::WriteAcknowledgement(ev) => Some(&ev.packet),
+            IbcEvent::AcknowledgePacket(ev) => Some(&ev.packet),
+            IbcEvent::TimeoutPacket(ev) => Some(&ev.packet),
+            IbcEvent::TimeoutOnClosePacket(ev) => Some(&ev.packet),
+            _ => None,
+        }
+    }
+
     pub fn set_height(&mut self, height: Height) {
         match self {
             IbcEvent::NewBlock(ev) => ev.set_height(height),
@@ -12,7 +12,9 @@ use crate::events::IbcEventType;
 use crate::ics02_client::client_type::ClientType;
 use crate::ics02_client::error::{Error, Kind};
 use crate::ics02_client::height::Height;
+use crate::ics06_solomachine::consensus_state::ConsensusState as SoloMachineConsensusState;
 use crate::ics07_tendermint::consensus_state;
+use crate::ics09_localhost::consensus_state::ConsensusState as LocalhostConsensusState;
 use crate::ics23_commitment::commitment::CommitmentRoot;
 use crate::ics24_host::identifier::ClientId;
 #[cfg(any(test, feature = "mocks"))]
@@ -21,6 +23,12 @@ use crate::mock::client_state::MockConsensusState;
 pub const TENDERMINT_CONSENSUS_STATE_TYPE_URL: &str =
     "/ibc.lightclients.tendermint.v1.ConsensusState";
 
+pub const SOLOMACHINE_CONSENSUS_STATE_TYPE_URL: &str =
+    "/ibc.lightclients.solomachine.v1.ConsensusState";
+
+pub const LOCALHOST_CONSENSUS_STATE_TYPE_URL: &str =
+    "/ibc.lightclients.localhost.v1.ConsensusState";
+
 pub const MOCK_CONSENSUS_STATE_TYPE_URL: &str = "/ibc.mock.ConsensusState";
 
 #[dyn_clonable::clonable]
@@ -42,6 +50,8 @@ pub trait ConsensusState: Clone + std::fmt::Debug + Send + Sync {
 #[serde(tag = "type")]
 pub enum AnyConsensusState {
     Tendermint(consensus_state::ConsensusState),
+    Solomachine(SoloMachineConsensusState),
+    Localhost(LocalhostConsensusState),
 
     #[cfg(any(test, feature = "mocks"))]
     Mock(MockConsensusState),
@@ -57,6 +67,11 @@ impl AnyConsensusState {
                     .map_err(|_| Kind::NegativeConsensusStateTimestamp(value.to_string()))
             }
 
+            Self::Solomachine(sm_state) => Ok(sm_state.timestamp),
+
+            // The loopback client does not track time, so timeouts on timestamps never pass.
+            Self::Localhost(_) => Ok(0),
+
             #[cfg(any(test, feature = "mocks"))]
             Self::Mock(mock_state) => Ok(mock_state.timestamp()),
         }
@@ -65,6 +80,8 @@ impl AnyConsensusState {
     pub fn client_type(&self) -> ClientType {
         match self {
             AnyConsensusState::Tendermint(_cs) => ClientType::Tendermint,
+            AnyConsensusState::Solomachine(_cs) => ClientType::Solomachine,
+            AnyConsensusState::Localhost(_cs) => ClientType::Localhost,
 
             #[cfg(any(test, feature = "mocks"))]
             AnyConsensusState::Mock(_cs) => ClientType::Mock,
@@ -86,6 +103,16 @@ impl TryFrom<Any> for AnyConsensusState {
                     .map_err(|e| Kind::InvalidRawConsensusState.context(e))?,
             )),
 
+            SOLOMACHINE_CONSENSUS_STATE_TYPE_URL => Ok(AnyConsensusState::Solomachine(
+                SoloMachineConsensusState::decode_vec(&value.value)
+                    .map_err(|e| Kind::InvalidRawConsensusState.context(e))?,
+            )),
+
+            // Loopback consensus states carry no data, there is no message to decode.
+            LOCALHOST_CONSENSUS_STATE_TYPE_URL => {
+                Ok(AnyConsensusState::Localhost(LocalhostConsensusState::new()))
+            }
+
             #[cfg(any(test, feature = "mocks"))]
             MOCK_CONSENSUS_STATE_TYPE_URL => Ok(AnyConsensusState::Mock(
                 MockConsensusState::decode_vec(&value.value)
@@ -104,6 +131,14 @@ impl From<AnyConsensusState> for Any {
                 type_url: TENDERMINT_CONSENSUS_STATE_TYPE_URL.to_string(),
                 value: value.encode_vec().unwrap(),
             },
+            AnyConsensusState::Solomachine(value) => Any {
+                type_url: SOLOMACHINE_CONSENSUS_STATE_TYPE_URL.to_string(),
+                value: value.encode_vec().unwrap(),
+            },
+            AnyConsensusState::Localhost(_) => Any {
+                type_url: LOCALHOST_CONSENSUS_STATE_TYPE_URL.to_string(),
+                value: vec![],
+            },
             #[cfg(any(test, feature = "mocks"))]
             AnyConsensusState::Mock(value) => Any {
                 type_url: MOCK_CONSENSUS_STATE_TYPE_URL.to_string(),
@@ -2,12 +2,18 @@ use crate::downcast;
 use crate::ics02_client::client_consensus::{AnyConsensusState, ConsensusState};
 use crate::ics02_client::client_state::{AnyClientState, ClientState};
 use crate::ics02_client::client_type::ClientType;
+use crate::ics02_client::context::ClientReader;
 use crate::ics02_client::error::Kind;
 use crate::ics02_client::header::{AnyHeader, Header};
+use crate::ics02_client::misbehaviour::{AnyMisbehaviour, Misbehaviour};
 use crate::ics03_connection::connection::ConnectionEnd;
+use crate::ics03_connection::context::ConnectionReader;
 use crate::ics04_channel::channel::ChannelEnd;
+use crate::ics04_channel::context::ChannelReader;
 use crate::ics04_channel::packet::Sequence;
+use crate::ics06_solomachine::client_def::SoloMachineClient;
 use crate::ics07_tendermint::client_def::TendermintClient;
+use crate::ics09_localhost::client_def::LocalhostClient;
 use crate::ics23_commitment::commitment::{CommitmentPrefix, CommitmentProofBytes, CommitmentRoot};
 use crate::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
 use crate::Height;
@@ -19,17 +25,42 @@ pub trait ClientDef: Clone {
     type Header: Header;
     type ClientState: ClientState;
     type ConsensusState: ConsensusState;
+    type Misbehaviour: Misbehaviour;
 
-    /// TODO
+    /// Verifies an incoming `header` against the client state and the consensus states stored by
+    /// the host (accessible via `ctx`) for client `client_id`. Returns the new client state and
+    /// the consensus state obtained from the header.
     fn check_header_and_update_state(
         &self,
+        ctx: &dyn ClientReader,
+        client_id: ClientId,
         client_state: Self::ClientState,
         header: Self::Header,
     ) -> Result<(Self::ClientState, Self::ConsensusState), Box<dyn std::error::Error>>;
 
+    /// Verifies that `misbehaviour` is a valid proof of misbehaviour for client `client_id`, i.e.,
+    /// that it consists of two conflicting headers which both pass verification against the
+    /// consensus states stored by the host. Returns the client state, frozen at the height of
+    /// the misbehaviour.
+    fn check_misbehaviour_and_update_state(
+        &self,
+        ctx: &dyn ClientReader,
+        client_id: ClientId,
+        client_state: Self::ClientState,
+        misbehaviour: Self::Misbehaviour,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>>;
+
     /// Verification functions as specified in:
     /// https://github.com/cosmos/ics/tree/master/spec/ics-002-client-semantics
     ///
+    /// Each of these functions also receives the context of the host chain, `ctx`. Clients which
+    /// track a remote chain check the `proof` against a `root` and ignore it, whereas the
+    /// localhost client reads the expected values directly from the host store.
+    ///
+    /// On success, each of them returns the client state after the verification, which the host
+    /// must store. Most clients return `client_state` unchanged, but the solo machine client
+    /// advances its sequence with every proof it verifies.
+    ///
     /// Verify a `proof` that the consensus state of a given client (at height `consensus_height`)
     /// matches the input `consensus_state`. The parameter `counterparty_height` represent the
     /// height of the counterparty chain that this proof assumes (i.e., the height at which this
@@ -37,43 +68,51 @@ pub trait ClientDef: Clone {
     #[allow(clippy::too_many_arguments)]
     fn verify_client_consensus_state(
         &self,
+        ctx: &dyn ConnectionReader,
         client_state: &Self::ClientState,
         height: Height,
         prefix: &CommitmentPrefix,
         proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
         client_id: &ClientId,
         consensus_height: Height,
         expected_consensus_state: &AnyConsensusState,
-    ) -> Result<(), Box<dyn std::error::Error>>;
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>>;
 
     /// Verify a `proof` that a connection state matches that of the input `connection_end`.
+    #[allow(clippy::too_many_arguments)]
     fn verify_connection_state(
         &self,
+        ctx: &dyn ConnectionReader,
         client_state: &Self::ClientState,
         height: Height,
         prefix: &CommitmentPrefix,
         proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
         connection_id: Option<&ConnectionId>,
         expected_connection_end: &ConnectionEnd,
-    ) -> Result<(), Box<dyn std::error::Error>>;
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>>;
 
     /// Verify a `proof` that a channel state matches that of the input `channel_end`.
     #[allow(clippy::too_many_arguments)]
     fn verify_channel_state(
         &self,
+        ctx: &dyn ChannelReader,
         client_state: &Self::ClientState,
         height: Height,
         prefix: &CommitmentPrefix,
         proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
         port_id: &PortId,
         channel_id: &ChannelId,
         expected_channel_end: &ChannelEnd,
-    ) -> Result<(), Box<dyn std::error::Error>>;
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>>;
 
     /// Verify the client state for this chain that it is stored on the counterparty chain.
     #[allow(clippy::too_many_arguments)]
     fn verify_client_full_state(
         &self,
+        ctx: &dyn ConnectionReader,
         _client_state: &Self::ClientState,
         height: Height,
         root: &CommitmentRoot,
@@ -81,62 +120,76 @@ pub trait ClientDef: Clone {
         client_id: &ClientId,
         proof: &CommitmentProofBytes,
         client_state: &AnyClientState,
-    ) -> Result<(), Box<dyn std::error::Error>>;
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>>;
 
     /// Verify a `proof` that a packet has been commited.
     #[allow(clippy::too_many_arguments)]
     fn verify_packet_data(
         &self,
+        ctx: &dyn ChannelReader,
         client_state: &Self::ClientState,
         height: Height,
+        prefix: &CommitmentPrefix,
         proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
         port_id: &PortId,
         channel_id: &ChannelId,
         seq: &Sequence,
-        commitment: String,
-    ) -> Result<(), Box<dyn std::error::Error>>;
+        commitment: Vec<u8>,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>>;
 
     /// Verify a `proof` that a packet has been commited.
     #[allow(clippy::too_many_arguments)]
     fn verify_packet_acknowledgement(
         &self,
+        ctx: &dyn ChannelReader,
         client_state: &Self::ClientState,
         height: Height,
+        prefix: &CommitmentPrefix,
         proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
         port_id: &PortId,
         channel_id: &ChannelId,
         seq: &Sequence,
         ack: Vec<u8>,
-    ) -> Result<(), Box<dyn std::error::Error>>;
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>>;
 
     /// Verify a `proof` that of the next_seq_received.
     #[allow(clippy::too_many_arguments)]
     fn verify_next_sequence_recv(
         &self,
+        ctx: &dyn ChannelReader,
         client_state: &Self::ClientState,
         height: Height,
+        prefix: &CommitmentPrefix,
         proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
         port_id: &PortId,
         channel_id: &ChannelId,
         seq: &Sequence,
-    ) -> Result<(), Box<dyn std::error::Error>>;
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>>;
 
     /// Verify a `proof` that a packet has not been received.
     #[allow(clippy::too_many_arguments)]
     fn verify_packet_receipt_absence(
         &self,
+        ctx: &dyn ChannelReader,
         client_state: &Self::ClientState,
         height: Height,
+        prefix: &CommitmentPrefix,
         proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
         port_id: &PortId,
         channel_id: &ChannelId,
         seq: &Sequence,
-    ) -> Result<(), Box<dyn std::error::Error>>;
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>>;
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum AnyClient {
     Tendermint(TendermintClient),
+    Solomachine(SoloMachineClient),
+    Localhost(LocalhostClient),
 
     #[cfg(any(test, feature = "mocks"))]
     Mock(MockClient),
@@ -146,6 +199,8 @@ impl AnyClient {
     pub fn from_client_type(client_type: ClientType) -> AnyClient {
         match client_type {
             ClientType::Tendermint => Self::Tendermint(TendermintClient),
+            ClientType::Solomachine => Self::Solomachine(SoloMachineClient),
+            ClientType::Localhost => Self::Localhost(LocalhostClient),
 
             #[cfg(any(test, feature = "mocks"))]
             ClientType::Mock => Self::Mock(MockClient),
@@ -158,10 +213,13 @@ impl ClientDef for AnyClient {
     type Header = AnyHeader;
     type ClientState = AnyClientState;
     type ConsensusState = AnyConsensusState;
+    type Misbehaviour = AnyMisbehaviour;
 
     /// Validates an incoming `header` against the latest consensus state of this client.
     fn check_header_and_update_state(
         &self,
+        ctx: &dyn ClientReader,
+        client_id: ClientId,
         client_state: AnyClientState,
         header: AnyHeader,
     ) -> Result<(AnyClientState, AnyConsensusState), Box<dyn std::error::Error>> {
@@ -174,7 +232,7 @@ impl ClientDef for AnyClient {
                 .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Tendermint))?;
 
                 let (new_state, new_consensus) =
-                    client.check_header_and_update_state(client_state, header)?;
+                    client.check_header_and_update_state(ctx, client_id, client_state, header)?;
 
                 Ok((
                     AnyClientState::Tendermint(new_state),
@@ -182,6 +240,38 @@ impl ClientDef for AnyClient {
                 ))
             }
 
+            Self::Solomachine(client) => {
+                let (client_state, header) = downcast!(
+                    client_state => AnyClientState::Solomachine,
+                    header => AnyHeader::Solomachine,
+                )
+                .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Solomachine))?;
+
+                let (new_state, new_consensus) =
+                    client.check_header_and_update_state(ctx, client_id, client_state, header)?;
+
+                Ok((
+                    AnyClientState::Solomachine(new_state),
+                    AnyConsensusState::Solomachine(new_consensus),
+                ))
+            }
+
+            Self::Localhost(client) => {
+                let (client_state, header) = downcast!(
+                    client_state => AnyClientState::Localhost,
+                    header => AnyHeader::Localhost,
+                )
+                .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Localhost))?;
+
+                let (new_state, new_consensus) =
+                    client.check_header_and_update_state(ctx, client_id, client_state, header)?;
+
+                Ok((
+                    AnyClientState::Localhost(new_state),
+                    AnyConsensusState::Localhost(new_consensus),
+                ))
+            }
+
             #[cfg(any(test, feature = "mocks"))]
             Self::Mock(client) => {
                 let (client_state, header) = downcast!(
@@ -191,7 +281,7 @@ impl ClientDef for AnyClient {
                 .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Mock))?;
 
                 let (new_state, new_consensus) =
-                    client.check_header_and_update_state(client_state, header)?;
+                    client.check_header_and_update_state(ctx, client_id, client_state, header)?;
 
                 Ok((
                     AnyClientState::Mock(new_state),
@@ -201,16 +291,96 @@ impl ClientDef for AnyClient {
         }
     }
 
+    fn check_misbehaviour_and_update_state(
+        &self,
+        ctx: &dyn ClientReader,
+        client_id: ClientId,
+        client_state: AnyClientState,
+        misbehaviour: AnyMisbehaviour,
+    ) -> Result<AnyClientState, Box<dyn std::error::Error>> {
+        match self {
+            Self::Tendermint(client) => {
+                let (client_state, misbehaviour) = downcast!(
+                    client_state => AnyClientState::Tendermint,
+                    misbehaviour => AnyMisbehaviour::Tendermint,
+                )
+                .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Tendermint))?;
+
+                let new_state = client.check_misbehaviour_and_update_state(
+                    ctx,
+                    client_id,
+                    client_state,
+                    misbehaviour,
+                )?;
+
+                Ok(AnyClientState::Tendermint(new_state))
+            }
+
+            Self::Solomachine(client) => {
+                let (client_state, misbehaviour) = downcast!(
+                    client_state => AnyClientState::Solomachine,
+                    misbehaviour => AnyMisbehaviour::Solomachine,
+                )
+                .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Solomachine))?;
+
+                let new_state = client.check_misbehaviour_and_update_state(
+                    ctx,
+                    client_id,
+                    client_state,
+                    misbehaviour,
+                )?;
+
+                Ok(AnyClientState::Solomachine(new_state))
+            }
+
+            Self::Localhost(client) => {
+                let client_state = downcast!(
+                    client_state => AnyClientState::Localhost
+                )
+                .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Localhost))?;
+
+                let new_state = client.check_misbehaviour_and_update_state(
+                    ctx,
+                    client_id,
+                    client_state,
+                    misbehaviour,
+                )?;
+
+                Ok(AnyClientState::Localhost(new_state))
+            }
+
+            #[cfg(any(test, feature = "mocks"))]
+            Self::Mock(client) => {
+                let (client_state, misbehaviour) = downcast!(
+                    client_state => AnyClientState::Mock,
+                    misbehaviour => AnyMisbehaviour::Mock,
+                )
+                .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Mock))?;
+
+                let new_state = client.check_misbehaviour_and_update_state(
+                    ctx,
+                    client_id,
+                    client_state,
+                    misbehaviour,
+                )?;
+
+                Ok(AnyClientState::Mock(new_state))
+            }
+        }
+    }
+
     fn verify_client_consensus_state(
         &self,
+        ctx: &dyn ConnectionReader,
         client_state: &Self::ClientState,
         height: Height,
         prefix: &CommitmentPrefix,
         proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
         client_id: &ClientId,
         consensus_height: Height,
         expected_consensus_state: &AnyConsensusState,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
         match self {
             Self::Tendermint(client) => {
                 let client_state = downcast!(
@@ -218,15 +388,61 @@ impl ClientDef for AnyClient {
                 )
                 .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Tendermint))?;
 
-                client.verify_client_consensus_state(
-                    client_state,
-                    height,
-                    prefix,
-                    proof,
-                    client_id,
-                    consensus_height,
-                    expected_consensus_state,
+                client
+                    .verify_client_consensus_state(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        client_id,
+                        consensus_height,
+                        expected_consensus_state,
+                    )
+                    .map(AnyClientState::Tendermint)
+            }
+
+            Self::Solomachine(client) => {
+                let client_state = downcast!(
+                    client_state => AnyClientState::Solomachine
                 )
+                .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Solomachine))?;
+
+                client
+                    .verify_client_consensus_state(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        client_id,
+                        consensus_height,
+                        expected_consensus_state,
+                    )
+                    .map(AnyClientState::Solomachine)
+            }
+
+            Self::Localhost(client) => {
+                let client_state = downcast!(
+                    client_state => AnyClientState::Localhost
+                )
+                .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Localhost))?;
+
+                client
+                    .verify_client_consensus_state(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        client_id,
+                        consensus_height,
+                        expected_consensus_state,
+                    )
+                    .map(AnyClientState::Localhost)
             }
 
             #[cfg(any(test, feature = "mocks"))]
@@ -236,41 +452,87 @@ impl ClientDef for AnyClient {
                 )
                 .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Mock))?;
 
-                client.verify_client_consensus_state(
-                    client_state,
-                    height,
-                    prefix,
-                    proof,
-                    client_id,
-                    consensus_height,
-                    expected_consensus_state,
-                )
+                client
+                    .verify_client_consensus_state(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        client_id,
+                        consensus_height,
+                        expected_consensus_state,
+                    )
+                    .map(AnyClientState::Mock)
             }
         }
     }
 
     fn verify_connection_state(
         &self,
+        ctx: &dyn ConnectionReader,
         client_state: &AnyClientState,
         height: Height,
         prefix: &CommitmentPrefix,
         proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
         connection_id: Option<&ConnectionId>,
         expected_connection_end: &ConnectionEnd,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
         match self {
             Self::Tendermint(client) => {
                 let client_state = downcast!(client_state => AnyClientState::Tendermint)
                     .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Tendermint))?;
 
-                client.verify_connection_state(
-                    client_state,
-                    height,
-                    prefix,
-                    proof,
-                    connection_id,
-                    expected_connection_end,
-                )
+                client
+                    .verify_connection_state(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        connection_id,
+                        expected_connection_end,
+                    )
+                    .map(AnyClientState::Tendermint)
+            }
+
+            Self::Solomachine(client) => {
+                let client_state = downcast!(client_state => AnyClientState::Solomachine)
+                    .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Solomachine))?;
+
+                client
+                    .verify_connection_state(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        connection_id,
+                        expected_connection_end,
+                    )
+                    .map(AnyClientState::Solomachine)
+            }
+
+            Self::Localhost(client) => {
+                let client_state = downcast!(client_state => AnyClientState::Localhost)
+                    .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Localhost))?;
+
+                client
+                    .verify_connection_state(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        connection_id,
+                        expected_connection_end,
+                    )
+                    .map(AnyClientState::Localhost)
             }
 
             #[cfg(any(test, feature = "mocks"))]
@@ -278,42 +540,90 @@ impl ClientDef for AnyClient {
                 let client_state = downcast!(client_state => AnyClientState::Mock)
                     .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Mock))?;
 
-                client.verify_connection_state(
-                    client_state,
-                    height,
-                    prefix,
-                    proof,
-                    connection_id,
-                    expected_connection_end,
-                )
+                client
+                    .verify_connection_state(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        connection_id,
+                        expected_connection_end,
+                    )
+                    .map(AnyClientState::Mock)
             }
         }
     }
 
     fn verify_channel_state(
         &self,
+        ctx: &dyn ChannelReader,
         client_state: &AnyClientState,
         height: Height,
         prefix: &CommitmentPrefix,
         proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
         port_id: &PortId,
         channel_id: &ChannelId,
         expected_channel_end: &ChannelEnd,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
         match self {
             Self::Tendermint(client) => {
                 let client_state = downcast!(client_state => AnyClientState::Tendermint)
                     .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Tendermint))?;
 
-                client.verify_channel_state(
-                    client_state,
-                    height,
-                    prefix,
-                    proof,
-                    port_id,
-                    channel_id,
-                    expected_channel_end,
-                )
+                client
+                    .verify_channel_state(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        expected_channel_end,
+                    )
+                    .map(AnyClientState::Tendermint)
+            }
+
+            Self::Solomachine(client) => {
+                let client_state = downcast!(client_state => AnyClientState::Solomachine)
+                    .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Solomachine))?;
+
+                client
+                    .verify_channel_state(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        expected_channel_end,
+                    )
+                    .map(AnyClientState::Solomachine)
+            }
+
+            Self::Localhost(client) => {
+                let client_state = downcast!(client_state => AnyClientState::Localhost)
+                    .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Localhost))?;
+
+                client
+                    .verify_channel_state(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        expected_channel_end,
+                    )
+                    .map(AnyClientState::Localhost)
             }
 
             #[cfg(any(test, feature = "mocks"))]
@@ -321,21 +631,26 @@ impl ClientDef for AnyClient {
                 let client_state = downcast!(client_state => AnyClientState::Mock)
                     .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Mock))?;
 
-                client.verify_channel_state(
-                    client_state,
-                    height,
-                    prefix,
-                    proof,
-                    port_id,
-                    channel_id,
-                    expected_channel_end,
-                )
+                client
+                    .verify_channel_state(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        expected_channel_end,
+                    )
+                    .map(AnyClientState::Mock)
             }
         }
     }
 
     fn verify_client_full_state(
         &self,
+        ctx: &dyn ConnectionReader,
         client_state: &Self::ClientState,
         height: Height,
         root: &CommitmentRoot,
@@ -343,7 +658,7 @@ impl ClientDef for AnyClient {
         client_id: &ClientId,
         proof: &CommitmentProofBytes,
         client_state_on_counterparty: &AnyClientState,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
         match self {
             Self::Tendermint(client) => {
                 let client_state = downcast!(
@@ -351,15 +666,58 @@ impl ClientDef for AnyClient {
                 )
                 .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Tendermint))?;
 
-                client.verify_client_full_state(
-                    client_state,
-                    height,
-                    root,
-                    prefix,
-                    client_id,
-                    proof,
-                    client_state_on_counterparty,
+                client
+                    .verify_client_full_state(
+                        ctx,
+                        client_state,
+                        height,
+                        root,
+                        prefix,
+                        client_id,
+                        proof,
+                        client_state_on_counterparty,
+                    )
+                    .map(AnyClientState::Tendermint)
+            }
+
+            Self::Solomachine(client) => {
+                let client_state = downcast!(
+                    client_state => AnyClientState::Solomachine
+                )
+                .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Solomachine))?;
+
+                client
+                    .verify_client_full_state(
+                        ctx,
+                        client_state,
+                        height,
+                        root,
+                        prefix,
+                        client_id,
+                        proof,
+                        client_state_on_counterparty,
+                    )
+                    .map(AnyClientState::Solomachine)
+            }
+
+            Self::Localhost(client) => {
+                let client_state = downcast!(
+                    client_state => AnyClientState::Localhost
                 )
+                .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Localhost))?;
+
+                client
+                    .verify_client_full_state(
+                        ctx,
+                        client_state,
+                        height,
+                        root,
+                        prefix,
+                        client_id,
+                        proof,
+                        client_state_on_counterparty,
+                    )
+                    .map(AnyClientState::Localhost)
             }
 
             #[cfg(any(test, feature = "mocks"))]
@@ -369,28 +727,34 @@ impl ClientDef for AnyClient {
                 )
                 .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Mock))?;
 
-                client.verify_client_full_state(
-                    client_state,
-                    height,
-                    root,
-                    prefix,
-                    client_id,
-                    proof,
-                    client_state_on_counterparty,
-                )
+                client
+                    .verify_client_full_state(
+                        ctx,
+                        client_state,
+                        height,
+                        root,
+                        prefix,
+                        client_id,
+                        proof,
+                        client_state_on_counterparty,
+                    )
+                    .map(AnyClientState::Mock)
             }
         }
     }
     fn verify_packet_data(
         &self,
+        ctx: &dyn ChannelReader,
         client_state: &Self::ClientState,
         height: Height,
+        prefix: &CommitmentPrefix,
         proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
         port_id: &PortId,
         channel_id: &ChannelId,
         seq: &Sequence,
-        commitment: String,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+        commitment: Vec<u8>,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
         match self {
             Self::Tendermint(client) => {
                 let client_state = downcast!(
@@ -398,15 +762,64 @@ impl ClientDef for AnyClient {
                 )
                 .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Tendermint))?;
 
-                client.verify_packet_data(
-                    client_state,
-                    height,
-                    proof,
-                    port_id,
-                    channel_id,
-                    seq,
-                    commitment,
+                client
+                    .verify_packet_data(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        seq,
+                        commitment,
+                    )
+                    .map(AnyClientState::Tendermint)
+            }
+
+            Self::Solomachine(client) => {
+                let client_state = downcast!(
+                    client_state => AnyClientState::Solomachine
                 )
+                .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Solomachine))?;
+
+                client
+                    .verify_packet_data(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        seq,
+                        commitment,
+                    )
+                    .map(AnyClientState::Solomachine)
+            }
+
+            Self::Localhost(client) => {
+                let client_state = downcast!(
+                    client_state => AnyClientState::Localhost
+                )
+                .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Localhost))?;
+
+                client
+                    .verify_packet_data(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        seq,
+                        commitment,
+                    )
+                    .map(AnyClientState::Localhost)
             }
 
             #[cfg(any(test, feature = "mocks"))]
@@ -416,29 +829,37 @@ impl ClientDef for AnyClient {
                 )
                 .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Mock))?;
 
-                client.verify_packet_data(
-                    client_state,
-                    height,
-                    proof,
-                    port_id,
-                    channel_id,
-                    seq,
-                    commitment,
-                )
+                client
+                    .verify_packet_data(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        seq,
+                        commitment,
+                    )
+                    .map(AnyClientState::Mock)
             }
         }
     }
 
     fn verify_packet_acknowledgement(
         &self,
+        ctx: &dyn ChannelReader,
         client_state: &Self::ClientState,
         height: Height,
+        prefix: &CommitmentPrefix,
         proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
         port_id: &PortId,
         channel_id: &ChannelId,
         seq: &Sequence,
         ack: Vec<u8>,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
         match self {
             Self::Tendermint(client) => {
                 let client_state = downcast!(
@@ -446,15 +867,64 @@ impl ClientDef for AnyClient {
                 )
                 .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Tendermint))?;
 
-                client.verify_packet_acknowledgement(
-                    client_state,
-                    height,
-                    proof,
-                    port_id,
-                    channel_id,
-                    seq,
-                    ack,
+                client
+                    .verify_packet_acknowledgement(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        seq,
+                        ack,
+                    )
+                    .map(AnyClientState::Tendermint)
+            }
+
+            Self::Solomachine(client) => {
+                let client_state = downcast!(
+                    client_state => AnyClientState::Solomachine
                 )
+                .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Solomachine))?;
+
+                client
+                    .verify_packet_acknowledgement(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        seq,
+                        ack,
+                    )
+                    .map(AnyClientState::Solomachine)
+            }
+
+            Self::Localhost(client) => {
+                let client_state = downcast!(
+                    client_state => AnyClientState::Localhost
+                )
+                .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Localhost))?;
+
+                client
+                    .verify_packet_acknowledgement(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        seq,
+                        ack,
+                    )
+                    .map(AnyClientState::Localhost)
             }
 
             #[cfg(any(test, feature = "mocks"))]
@@ -464,28 +934,36 @@ impl ClientDef for AnyClient {
                 )
                 .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Mock))?;
 
-                client.verify_packet_acknowledgement(
-                    client_state,
-                    height,
-                    proof,
-                    port_id,
-                    channel_id,
-                    seq,
-                    ack,
-                )
+                client
+                    .verify_packet_acknowledgement(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        seq,
+                        ack,
+                    )
+                    .map(AnyClientState::Mock)
             }
         }
     }
 
     fn verify_next_sequence_recv(
         &self,
+        ctx: &dyn ChannelReader,
         client_state: &Self::ClientState,
         height: Height,
+        prefix: &CommitmentPrefix,
         proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
         port_id: &PortId,
         channel_id: &ChannelId,
         seq: &Sequence,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
         match self {
             Self::Tendermint(client) => {
                 let client_state = downcast!(
@@ -493,14 +971,61 @@ impl ClientDef for AnyClient {
                 )
                 .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Tendermint))?;
 
-                client.verify_next_sequence_recv(
-                    client_state,
-                    height,
-                    proof,
-                    port_id,
-                    channel_id,
-                    seq,
+                client
+                    .verify_next_sequence_recv(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        seq,
+                    )
+                    .map(AnyClientState::Tendermint)
+            }
+
+            Self::Solomachine(client) => {
+                let client_state = downcast!(
+                    client_state => AnyClientState::Solomachine
                 )
+                .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Solomachine))?;
+
+                client
+                    .verify_next_sequence_recv(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        seq,
+                    )
+                    .map(AnyClientState::Solomachine)
+            }
+
+            Self::Localhost(client) => {
+                let client_state = downcast!(
+                    client_state => AnyClientState::Localhost
+                )
+                .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Localhost))?;
+
+                client
+                    .verify_next_sequence_recv(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        seq,
+                    )
+                    .map(AnyClientState::Localhost)
             }
 
             #[cfg(any(test, feature = "mocks"))]
@@ -510,26 +1035,34 @@ impl ClientDef for AnyClient {
                 )
                 .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Mock))?;
 
-                client.verify_next_sequence_recv(
-                    client_state,
-                    height,
-                    proof,
-                    port_id,
-                    channel_id,
-                    seq,
-                )
+                client
+                    .verify_next_sequence_recv(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        seq,
+                    )
+                    .map(AnyClientState::Mock)
             }
         }
     }
     fn verify_packet_receipt_absence(
         &self,
+        ctx: &dyn ChannelReader,
         client_state: &Self::ClientState,
         height: Height,
+        prefix: &CommitmentPrefix,
         proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
         port_id: &PortId,
         channel_id: &ChannelId,
         seq: &Sequence,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
         match self {
             Self::Tendermint(client) => {
                 let client_state = downcast!(
@@ -537,14 +1070,61 @@ impl ClientDef for AnyClient {
                 )
                 .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Tendermint))?;
 
-                client.verify_packet_receipt_absence(
-                    client_state,
-                    height,
-                    proof,
-                    port_id,
-                    channel_id,
-                    seq,
+                client
+                    .verify_packet_receipt_absence(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        seq,
+                    )
+                    .map(AnyClientState::Tendermint)
+            }
+
+            Self::Solomachine(client) => {
+                let client_state = downcast!(
+                    client_state => AnyClientState::Solomachine
                 )
+                .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Solomachine))?;
+
+                client
+                    .verify_packet_receipt_absence(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        seq,
+                    )
+                    .map(AnyClientState::Solomachine)
+            }
+
+            Self::Localhost(client) => {
+                let client_state = downcast!(
+                    client_state => AnyClientState::Localhost
+                )
+                .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Localhost))?;
+
+                client
+                    .verify_packet_receipt_absence(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        seq,
+                    )
+                    .map(AnyClientState::Localhost)
             }
 
             #[cfg(any(test, feature = "mocks"))]
@@ -554,14 +1134,19 @@ impl ClientDef for AnyClient {
                 )
                 .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Mock))?;
 
-                client.verify_packet_receipt_absence(
-                    client_state,
-                    height,
-                    proof,
-                    port_id,
-                    channel_id,
-                    seq,
-                )
+                client
+                    .verify_packet_receipt_absence(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        seq,
+                    )
+                    .map(AnyClientState::Mock)
             }
         }
     }
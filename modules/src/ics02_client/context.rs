@@ -6,7 +6,7 @@ use crate::ics02_client::client_consensus::AnyConsensusState;
 use crate::ics02_client::client_state::AnyClientState;
 use crate::ics02_client::client_type::ClientType;
 use crate::ics02_client::error::Error;
-use crate::ics02_client::handler::ClientResult::{self, Create, Update, Upgrade};
+use crate::ics02_client::handler::ClientResult::{self, Create, Misbehaviour, Update, Upgrade};
 use crate::ics24_host::identifier::ClientId;
 use crate::Height;
 
@@ -16,6 +16,14 @@ pub trait ClientReader {
     fn client_state(&self, client_id: &ClientId) -> Option<AnyClientState>;
     fn consensus_state(&self, client_id: &ClientId, height: Height) -> Option<AnyConsensusState>;
 
+    /// Returns the current height of the local chain. The localhost client cannot be updated past
+    /// this height.
+    fn host_height(&self) -> Height;
+
+    /// Returns the current timestamp of the local chain, in nanoseconds since the Unix epoch.
+    /// Light clients use it to check that their trusted state is within the trusting period.
+    fn host_timestamp(&self) -> u64;
+
     /// Returns a natural number, counting how many clients have been created thus far.
     /// The value of this counter should increase only via method `ClientKeeper::increase_client_counter`.
     fn client_counter(&self) -> u64;
@@ -48,6 +56,10 @@ pub trait ClientKeeper {
                 Ok(())
             }
             Upgrade(_) => unimplemented!(),
+            Misbehaviour(res) => {
+                self.store_client_state(res.client_id, res.client_state)?;
+                Ok(())
+            }
         }
     }
 
@@ -58,7 +70,7 @@ pub trait ClientKeeper {
         client_type: ClientType,
     ) -> Result<(), Error>;
 
-    /// Called upon successful client creation and update
+    /// Called upon successful client creation, update and misbehaviour
     fn store_client_state(
         &mut self,
         client_id: ClientId,
@@ -38,6 +38,12 @@ pub enum Kind {
     #[error("header verification failed")]
     HeaderVerificationFailure,
 
+    #[error("misbehaviour handling failed")]
+    MisbehaviourHandlingFailure,
+
+    #[error("the misbehaviour of client {1} was submitted for client {0}")]
+    MisbehaviourClientIdMismatch(ClientId, ClientId),
+
     #[error("unknown client state type: {0}")]
     UnknownClientStateType(String),
 
@@ -280,6 +280,12 @@ impl TryFrom<RawObject> for ClientMisbehaviour {
     }
 }
 
+impl From<Attributes> for ClientMisbehaviour {
+    fn from(attrs: Attributes) -> Self {
+        ClientMisbehaviour(attrs)
+    }
+}
+
 impl From<ClientMisbehaviour> for IbcEvent {
     fn from(v: ClientMisbehaviour) -> Self {
         IbcEvent::ClientMisbehaviour(v)
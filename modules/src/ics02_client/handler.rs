@@ -6,6 +6,7 @@ use crate::ics02_client::error::Error;
 use crate::ics02_client::msgs::ClientMsg;
 
 pub mod create_client;
+pub mod misbehaviour;
 pub mod update_client;
 pub mod upgrade_client;
 
@@ -14,6 +15,7 @@ pub enum ClientResult {
     Create(create_client::Result),
     Update(update_client::Result),
     Upgrade(upgrade_client::Result),
+    Misbehaviour(misbehaviour::Result),
 }
 
 /// General entry point for processing any message related to ICS2 (client functions) protocols.
@@ -25,8 +27,6 @@ where
         ClientMsg::CreateClient(msg) => create_client::process(ctx, msg),
         ClientMsg::UpdateClient(msg) => update_client::process(ctx, msg),
         ClientMsg::UpgradeClient(msg) => upgrade_client::process(ctx, msg),
-        _ => {
-            unimplemented!()
-        }
+        ClientMsg::Misbehaviour(msg) => misbehaviour::process(ctx, msg),
     }
 }
@@ -86,7 +86,7 @@ mod tests {
         let height = Height::new(0, 42);
 
         let msg = MsgCreateAnyClient::new(
-            MockClientState(MockHeader::new(height)).into(),
+            MockClientState::new(MockHeader::new(height)).into(),
             MockConsensusState(MockHeader::new(height)).into(),
             signer,
         )
@@ -132,7 +132,7 @@ mod tests {
 
         let create_client_msgs: Vec<MsgCreateAnyClient> = vec![
             MsgCreateAnyClient::new(
-                MockClientState(MockHeader::new(Height {
+                MockClientState::new(MockHeader::new(Height {
                     revision_height: 42,
                     ..height
                 }))
@@ -146,7 +146,7 @@ mod tests {
             )
             .unwrap(),
             MsgCreateAnyClient::new(
-                MockClientState(MockHeader::new(Height {
+                MockClientState::new(MockHeader::new(Height {
                     revision_height: 42,
                     ..height
                 }))
@@ -160,7 +160,7 @@ mod tests {
             )
             .unwrap(),
             MsgCreateAnyClient::new(
-                MockClientState(MockHeader::new(Height {
+                MockClientState::new(MockHeader::new(Height {
                     revision_height: 50,
                     ..height
                 }))
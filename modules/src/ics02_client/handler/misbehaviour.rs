@@ -0,0 +1,234 @@
+//! Protocol logic specific to processing ICS2 messages of type `MsgSubmitAnyMisbehaviour`.
+
+use crate::events::IbcEvent;
+use crate::handler::{HandlerOutput, HandlerResult};
+use crate::ics02_client::client_def::{AnyClient, ClientDef};
+use crate::ics02_client::client_state::{AnyClientState, ClientState};
+use crate::ics02_client::context::ClientReader;
+use crate::ics02_client::error::{Error, Kind};
+use crate::ics02_client::events::Attributes;
+use crate::ics02_client::handler::ClientResult;
+use crate::ics02_client::misbehaviour::Misbehaviour;
+use crate::ics02_client::msgs::misbehavior::MsgSubmitAnyMisbehaviour;
+use crate::ics24_host::identifier::ClientId;
+
+/// The result following the successful processing of a `MsgSubmitAnyMisbehaviour` message.
+/// This data type should be used with a qualified name `misbehaviour::Result` to avoid ambiguity.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Result {
+    pub client_id: ClientId,
+    pub client_state: AnyClientState,
+}
+
+pub fn process(
+    ctx: &dyn ClientReader,
+    msg: MsgSubmitAnyMisbehaviour,
+) -> HandlerResult<ClientResult, Error> {
+    let mut output = HandlerOutput::builder();
+
+    let MsgSubmitAnyMisbehaviour {
+        client_id,
+        misbehaviour,
+        signer: _,
+    } = msg;
+
+    if misbehaviour.client_id() != &client_id {
+        return Err(Kind::MisbehaviourClientIdMismatch(
+            client_id,
+            misbehaviour.client_id().clone(),
+        )
+        .into());
+    }
+
+    // Read client type from the host chain store. The client should already exist.
+    let client_type = ctx
+        .client_type(&client_id)
+        .ok_or_else(|| Kind::ClientNotFound(client_id.clone()))?;
+
+    let client_def = AnyClient::from_client_type(client_type);
+
+    // Read client state from the host chain store.
+    let client_state = ctx
+        .client_state(&client_id)
+        .ok_or_else(|| Kind::ClientNotFound(client_id.clone()))?;
+
+    if client_state.is_frozen() {
+        return Err(Kind::ClientFrozen(client_id).into());
+    }
+
+    let consensus_height = misbehaviour.height();
+
+    // Verify both conflicting headers and freeze the client. The frozen client state will be
+    // later persisted by the keeper.
+    let new_client_state = client_def
+        .check_misbehaviour_and_update_state(ctx, client_id.clone(), client_state, misbehaviour)
+        .map_err(|e| Kind::MisbehaviourHandlingFailure.context(e.to_string()))?;
+
+    let result = ClientResult::Misbehaviour(Result {
+        client_id: client_id.clone(),
+        client_state: new_client_state,
+    });
+
+    let event_attributes = Attributes {
+        client_id,
+        client_type,
+        consensus_height,
+        ..Default::default()
+    };
+    output.emit(IbcEvent::ClientMisbehaviour(event_attributes.into()));
+
+    Ok(output.with_result(result))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::events::IbcEvent;
+    use crate::handler::HandlerOutput;
+    use crate::ics02_client::client_state::ClientState;
+    use crate::ics02_client::context::{ClientKeeper, ClientReader};
+    use crate::ics02_client::error::Kind;
+    use crate::ics02_client::handler::{dispatch, ClientResult};
+    use crate::ics02_client::misbehaviour::AnyMisbehaviour;
+    use crate::ics02_client::msgs::misbehavior::MsgSubmitAnyMisbehaviour;
+    use crate::ics02_client::msgs::update_client::MsgUpdateAnyClient;
+    use crate::ics02_client::msgs::ClientMsg;
+    use crate::ics24_host::identifier::ClientId;
+    use crate::mock::context::MockContext;
+    use crate::mock::header::MockHeader;
+    use crate::mock::misbehaviour::Misbehaviour;
+    use crate::test_utils::get_dummy_account_id;
+    use crate::Height;
+
+    fn misbehaviour(client_id: &ClientId, height: Height) -> AnyMisbehaviour {
+        AnyMisbehaviour::Mock(Misbehaviour {
+            client_id: client_id.clone(),
+            header1: MockHeader::new(height),
+            header2: MockHeader {
+                height,
+                timestamp: 1,
+            },
+        })
+    }
+
+    #[test]
+    fn test_misbehaviour_ok() {
+        let client_id = ClientId::default();
+        let ctx = MockContext::default().with_client(&client_id, Height::new(0, 42));
+
+        let msg = MsgSubmitAnyMisbehaviour {
+            client_id: client_id.clone(),
+            misbehaviour: misbehaviour(&client_id, Height::new(0, 46)),
+            signer: get_dummy_account_id(),
+        };
+
+        let output = dispatch(&ctx, ClientMsg::Misbehaviour(msg));
+
+        match output {
+            Ok(HandlerOutput {
+                result,
+                mut events,
+                log,
+            }) => {
+                assert_eq!(events.len(), 1);
+                let event = events.pop().unwrap();
+                assert!(
+                    matches!(event, IbcEvent::ClientMisbehaviour(e) if e.client_id() == &client_id)
+                );
+                assert!(log.is_empty());
+
+                match result {
+                    ClientResult::Misbehaviour(res) => {
+                        assert_eq!(res.client_id, client_id);
+                        assert!(res.client_state.is_frozen());
+                    }
+                    _ => panic!("misbehaviour handler result has incorrect type"),
+                }
+            }
+            Err(err) => {
+                panic!("unexpected error: {}", err);
+            }
+        }
+    }
+
+    #[test]
+    fn test_misbehaviour_nonexisting_client() {
+        let client_id = ClientId::default();
+        let ctx = MockContext::default().with_client(&client_id, Height::new(0, 42));
+        let unknown_client_id = ClientId::from_str("nonexistingclient").unwrap();
+
+        let msg = MsgSubmitAnyMisbehaviour {
+            client_id: unknown_client_id.clone(),
+            misbehaviour: misbehaviour(&unknown_client_id, Height::new(0, 46)),
+            signer: get_dummy_account_id(),
+        };
+
+        let output = dispatch(&ctx, ClientMsg::Misbehaviour(msg));
+
+        match output {
+            Err(err) => {
+                assert_eq!(err.kind(), &Kind::ClientNotFound(unknown_client_id));
+            }
+            _ => panic!("expected an error"),
+        }
+    }
+
+    #[test]
+    fn test_misbehaviour_of_another_client() {
+        let client_id = ClientId::default();
+        let other_client_id = ClientId::from_str("otherclient").unwrap();
+        let ctx = MockContext::default()
+            .with_client(&client_id, Height::new(0, 42))
+            .with_client(&other_client_id, Height::new(0, 42));
+
+        // The misbehaviour of a client cannot freeze another one.
+        let msg = MsgSubmitAnyMisbehaviour {
+            client_id: client_id.clone(),
+            misbehaviour: misbehaviour(&other_client_id, Height::new(0, 46)),
+            signer: get_dummy_account_id(),
+        };
+
+        let output = dispatch(&ctx, ClientMsg::Misbehaviour(msg));
+
+        match output {
+            Err(err) => {
+                assert_eq!(
+                    err.kind(),
+                    &Kind::MisbehaviourClientIdMismatch(client_id, other_client_id)
+                );
+            }
+            _ => panic!("expected an error"),
+        }
+    }
+
+    #[test]
+    fn test_update_frozen_client() {
+        let client_id = ClientId::default();
+        let mut ctx = MockContext::default().with_client(&client_id, Height::new(0, 42));
+
+        let msg = MsgSubmitAnyMisbehaviour {
+            client_id: client_id.clone(),
+            misbehaviour: misbehaviour(&client_id, Height::new(0, 46)),
+            signer: get_dummy_account_id(),
+        };
+        let output = dispatch(&ctx, ClientMsg::Misbehaviour(msg)).unwrap();
+        ctx.store_client_result(output.result).unwrap();
+        assert!(ctx.client_state(&client_id).unwrap().is_frozen());
+
+        // A frozen client rejects any further update.
+        let msg = MsgUpdateAnyClient {
+            client_id: client_id.clone(),
+            header: MockHeader::new(Height::new(0, 50)).into(),
+            signer: get_dummy_account_id(),
+        };
+        let output = dispatch(&ctx, ClientMsg::UpdateClient(msg));
+
+        match output {
+            Err(err) => {
+                assert_eq!(err.kind(), &Kind::ClientFrozen(client_id));
+            }
+            _ => panic!("expected an error"),
+        }
+    }
+}
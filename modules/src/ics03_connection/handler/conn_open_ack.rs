@@ -57,6 +57,13 @@ pub(crate) fn process(
         }
     }?;
 
+    // The counterparty connection end is only known from this message on, the proofs are about it.
+    new_conn_end.set_counterparty(Counterparty::new(
+        new_conn_end.counterparty().client_id().clone(),
+        Some(msg.counterparty_connection_id().clone()),
+        new_conn_end.counterparty().prefix().clone(),
+    ));
+
     // Proof verification.
     let expected_conn = ConnectionEnd::new(
         State::TryOpen,
@@ -64,15 +71,15 @@ pub(crate) fn process(
         Counterparty::new(
             // The counterparty is the local chain.
             new_conn_end.client_id().clone(), // The local client identifier.
-            Some(msg.counterparty_connection_id().clone()), // This chain's connection id as known on counterparty.
-            ctx.commitment_prefix(),                        // Local commitment prefix.
+            Some(msg.connection_id().clone()), // Local connection id.
+            ctx.commitment_prefix(),          // Local commitment prefix.
         ),
         vec![msg.version().clone()],
         new_conn_end.delay_period(),
     );
 
     // 2. Pass the details to the verification function.
-    verify_proofs(
+    let client_state = verify_proofs(
         ctx,
         msg.client_state(),
         &new_conn_end,
@@ -88,6 +95,7 @@ pub(crate) fn process(
     let result = ConnectionResult {
         connection_id: msg.connection_id().clone(),
         connection_id_state: ConnectionIdState::Reused,
+        client_state: Some((new_conn_end.client_id().clone(), client_state)),
         connection_end: new_conn_end,
     };
 
@@ -42,6 +42,7 @@ pub(crate) fn process(
         connection_id: conn_id.clone(),
         connection_id_state: ConnectionIdState::Generated,
         connection_end: new_connection_end,
+        client_state: None,
     };
 
     let event_attributes = Attributes {
@@ -79,7 +79,7 @@ pub(crate) fn process(
     );
 
     // 2. Pass the details to the verification function.
-    verify_proofs(
+    let client_state = verify_proofs(
         ctx,
         msg.client_state(),
         &new_connection_end,
@@ -107,6 +107,7 @@ pub(crate) fn process(
         } else {
             ConnectionIdState::Reused
         },
+        client_state: Some((new_connection_end.client_id().clone(), client_state)),
         connection_end: new_connection_end,
     };
 
@@ -10,16 +10,30 @@ use crate::ics23_commitment::commitment::CommitmentProofBytes;
 use crate::proofs::{ConsensusProof, Proofs};
 use crate::Height;
 
-/// Entry point for verifying all proofs bundled in any ICS3 message.
+/// Entry point for verifying all proofs bundled in any ICS3 message. Returns the state of the
+/// client after the verification, which the host must store.
 pub fn verify_proofs(
     ctx: &dyn ConnectionReader,
     client_state: Option<AnyClientState>,
     connection_end: &ConnectionEnd,
     expected_conn: &ConnectionEnd,
     proofs: &Proofs,
-) -> Result<(), Error> {
-    verify_connection_proof(
+) -> Result<AnyClientState, Error> {
+    // Fetch the client state (IBC client on the local/host chain).
+    let host_client_state = ctx
+        .client_state(connection_end.client_id())
+        .ok_or_else(|| Kind::MissingClient(connection_end.client_id().clone()))?;
+
+    // The client must not be frozen.
+    if host_client_state.is_frozen() {
+        return Err(Kind::FrozenClient(connection_end.client_id().clone()).into());
+    }
+
+    // All the proofs of a message are made at the same height, so they are all verified against
+    // the stored client state, and the state returned by the last verification is kept.
+    let mut verified_client_state = verify_connection_proof(
         ctx,
+        &host_client_state,
         connection_end,
         expected_conn,
         proofs.height(),
@@ -28,8 +42,9 @@ pub fn verify_proofs(
 
     // If the message includes a client state, then verify the proof for that state.
     if let Some(expected_client_state) = client_state {
-        verify_client_proof(
+        verified_client_state = verify_client_proof(
             ctx,
+            &host_client_state,
             connection_end,
             expected_client_state,
             proofs.height(),
@@ -42,15 +57,16 @@ pub fn verify_proofs(
 
     // If a consensus proof is attached to the message, then verify it.
     if let Some(proof) = proofs.consensus_proof() {
-        Ok(verify_consensus_proof(
+        verified_client_state = verify_consensus_proof(
             ctx,
+            &host_client_state,
             connection_end,
             proofs.height(),
             &proof,
-        )?)
-    } else {
-        Ok(())
+        )?;
     }
+
+    Ok(verified_client_state)
 }
 
 /// Verifies the authenticity and semantic correctness of a commitment `proof`. The commitment
@@ -58,32 +74,18 @@ pub fn verify_proofs(
 /// which created this proof). This object must match the state of `expected_conn`.
 pub fn verify_connection_proof(
     ctx: &dyn ConnectionReader,
+    client_state: &AnyClientState,
     connection_end: &ConnectionEnd,
     expected_conn: &ConnectionEnd,
     proof_height: Height,
     proof: &CommitmentProofBytes,
-) -> Result<(), Error> {
-    // Fetch the client state (IBC client on the local/host chain).
-    let client_state = ctx
-        .client_state(connection_end.client_id())
-        .ok_or_else(|| Kind::MissingClient(connection_end.client_id().clone()))?;
-
-    // The client must not be frozen.
-    if client_state.is_frozen() {
-        return Err(Kind::FrozenClient(connection_end.client_id().clone()).into());
-    }
-
+) -> Result<AnyClientState, Error> {
     // The client must have the consensus state for the height where this proof was created.
-    if ctx
+    let consensus_state = ctx
         .client_consensus_state(connection_end.client_id(), proof_height)
-        .is_none()
-    {
-        return Err(Kind::MissingClientConsensusState(
-            proof_height,
-            connection_end.client_id().clone(),
-        )
-        .into());
-    }
+        .ok_or_else(|| {
+            Kind::MissingClientConsensusState(proof_height, connection_end.client_id().clone())
+        })?;
 
     let client_def = AnyClient::from_client_type(client_state.client_type());
 
@@ -92,10 +94,12 @@ pub fn verify_connection_proof(
     // error as this is the connection id on the counterparty chain that must always be present.
     Ok(client_def
         .verify_connection_state(
-            &client_state,
+            ctx,
+            client_state,
             proof_height,
             connection_end.counterparty().prefix(),
             proof,
+            consensus_state.root(),
             connection_end.counterparty().connection_id(),
             expected_conn,
         )
@@ -111,20 +115,12 @@ pub fn verify_connection_proof(
 /// `proof` is correct.
 pub fn verify_client_proof(
     ctx: &dyn ConnectionReader,
+    client_state: &AnyClientState,
     connection_end: &ConnectionEnd,
     expected_client_state: AnyClientState,
     proof_height: Height,
     proof: &CommitmentProofBytes,
-) -> Result<(), Error> {
-    // Fetch the local client state (IBC client running on the host chain).
-    let client_state = ctx
-        .client_state(connection_end.client_id())
-        .ok_or_else(|| Kind::MissingClient(connection_end.client_id().clone()))?;
-
-    if client_state.is_frozen() {
-        return Err(Kind::FrozenClient(connection_end.client_id().clone()).into());
-    }
-
+) -> Result<AnyClientState, Error> {
     let consensus_state = ctx
         .client_consensus_state(connection_end.client_id(), proof_height)
         .ok_or_else(|| {
@@ -135,7 +131,8 @@ pub fn verify_client_proof(
 
     Ok(client_def
         .verify_client_full_state(
-            &client_state,
+            ctx,
+            client_state,
             proof_height,
             consensus_state.root(),
             connection_end.counterparty().prefix(),
@@ -151,18 +148,16 @@ pub fn verify_client_proof(
 
 pub fn verify_consensus_proof(
     ctx: &dyn ConnectionReader,
+    client_state: &AnyClientState,
     connection_end: &ConnectionEnd,
     proof_height: Height,
     proof: &ConsensusProof,
-) -> Result<(), Error> {
-    // Fetch the client state (IBC client on the local chain).
-    let client_state = ctx
-        .client_state(connection_end.client_id())
-        .ok_or_else(|| Kind::MissingClient(connection_end.client_id().clone()))?;
-
-    if client_state.is_frozen() {
-        return Err(Kind::FrozenClient(connection_end.client_id().clone()).into());
-    }
+) -> Result<AnyClientState, Error> {
+    let consensus_state = ctx
+        .client_consensus_state(connection_end.client_id(), proof_height)
+        .ok_or_else(|| {
+            Kind::MissingClientConsensusState(proof_height, connection_end.client_id().clone())
+        })?;
 
     // Fetch the expected consensus state from the historical (local) header data.
     let expected_consensus = ctx
@@ -173,10 +168,12 @@ pub fn verify_consensus_proof(
 
     Ok(client
         .verify_client_consensus_state(
-            &client_state,
+            ctx,
+            client_state,
             proof_height,
             connection_end.counterparty().prefix(),
             proof.proof(),
+            consensus_state.root(),
             connection_end.counterparty().client_id(),
             proof.height(),
             &expected_consensus,
@@ -6,7 +6,10 @@ use anomaly::fail;
 use serde::{Deserialize, Serialize};
 use tendermint_proto::Protobuf;
 
-use ibc_proto::ibc::core::channel::v1::{Channel as RawChannel, Counterparty as RawCounterparty};
+use ibc_proto::ibc::core::channel::v1::{
+    Channel as RawChannel, Counterparty as RawCounterparty,
+    IdentifiedChannel as RawIdentifiedChannel,
+};
 
 use crate::events::IbcEventType;
 use crate::ics02_client::height::Height;
@@ -93,6 +96,68 @@ impl From<ChannelEnd> for RawChannel {
     }
 }
 
+/// A channel end along with the port and channel identifiers under which it is stored.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct IdentifiedChannelEnd {
+    pub port_id: PortId,
+    pub channel_id: ChannelId,
+    pub channel_end: ChannelEnd,
+}
+
+impl IdentifiedChannelEnd {
+    pub fn new(port_id: PortId, channel_id: ChannelId, channel_end: ChannelEnd) -> Self {
+        IdentifiedChannelEnd {
+            port_id,
+            channel_id,
+            channel_end,
+        }
+    }
+}
+
+impl Protobuf<RawIdentifiedChannel> for IdentifiedChannelEnd {}
+
+impl TryFrom<RawIdentifiedChannel> for IdentifiedChannelEnd {
+    type Error = anomaly::Error<Kind>;
+
+    fn try_from(value: RawIdentifiedChannel) -> Result<Self, Self::Error> {
+        let raw_channel_end = RawChannel {
+            state: value.state,
+            ordering: value.ordering,
+            counterparty: value.counterparty,
+            connection_hops: value.connection_hops,
+            version: value.version,
+        };
+
+        Ok(IdentifiedChannelEnd {
+            port_id: value
+                .port_id
+                .parse()
+                .map_err(|e| Kind::IdentifierError.context(e))?,
+            channel_id: value
+                .channel_id
+                .parse()
+                .map_err(|e| Kind::IdentifierError.context(e))?,
+            channel_end: raw_channel_end.try_into()?,
+        })
+    }
+}
+
+impl From<IdentifiedChannelEnd> for RawIdentifiedChannel {
+    fn from(value: IdentifiedChannelEnd) -> Self {
+        let raw_channel_end = RawChannel::from(value.channel_end);
+
+        RawIdentifiedChannel {
+            state: raw_channel_end.state,
+            ordering: raw_channel_end.ordering,
+            counterparty: raw_channel_end.counterparty,
+            connection_hops: raw_channel_end.connection_hops,
+            version: raw_channel_end.version,
+            port_id: value.port_id.to_string(),
+            channel_id: value.channel_id.to_string(),
+        }
+    }
+}
+
 impl ChannelEnd {
     /// Creates a new ChannelEnd in state Uninitialized and other fields parametrized.
     pub fn new(
@@ -250,7 +315,7 @@ impl From<Counterparty> for RawCounterparty {
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub enum Order {
     None = 0,
     Unordered,
@@ -387,7 +452,7 @@ pub mod test_util {
             ordering: 1,
             counterparty: Some(get_dummy_raw_counterparty()),
             connection_hops: vec![ConnectionId::default().to_string()],
-            version: "ics20".to_string(), // The version is not validated.
+            version: "ics20-1".to_string(),
         }
     }
 }
@@ -397,10 +462,12 @@ mod tests {
     use std::convert::TryFrom;
     use std::str::FromStr;
 
-    use ibc_proto::ibc::core::channel::v1::Channel as RawChannel;
+    use ibc_proto::ibc::core::channel::v1::{
+        Channel as RawChannel, IdentifiedChannel as RawIdentifiedChannel,
+    };
 
     use crate::ics04_channel::channel::test_util::get_dummy_raw_channel_end;
-    use crate::ics04_channel::channel::ChannelEnd;
+    use crate::ics04_channel::channel::{ChannelEnd, IdentifiedChannelEnd};
 
     #[test]
     fn channel_end_try_from_raw() {
@@ -491,6 +558,31 @@ mod tests {
         }
     }
 
+    #[test]
+    fn identified_channel_end_try_from_raw() {
+        let raw_channel_end = get_dummy_raw_channel_end();
+        let raw_identified = RawIdentifiedChannel {
+            state: raw_channel_end.state,
+            ordering: raw_channel_end.ordering,
+            counterparty: raw_channel_end.counterparty,
+            connection_hops: raw_channel_end.connection_hops,
+            version: raw_channel_end.version,
+            port_id: "transfer".to_string(),
+            channel_id: "channel-0".to_string(),
+        };
+
+        let identified = IdentifiedChannelEnd::try_from(raw_identified.clone()).unwrap();
+        assert_eq!(identified.port_id.as_str(), "transfer");
+        assert_eq!(identified.channel_id.as_str(), "channel-0");
+        assert_eq!(RawIdentifiedChannel::from(identified), raw_identified);
+
+        let bad_port = RawIdentifiedChannel {
+            port_id: "".to_string(),
+            ..raw_identified
+        };
+        assert!(IdentifiedChannelEnd::try_from(bad_port).is_err());
+    }
+
     #[test]
     fn parse_channel_ordering_type() {
         use super::Order;
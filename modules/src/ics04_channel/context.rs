@@ -6,16 +6,18 @@ use crate::ics02_client::client_consensus::AnyConsensusState;
 use crate::ics02_client::client_state::AnyClientState;
 use crate::ics03_connection::connection::ConnectionEnd;
 use crate::ics04_channel::channel::ChannelEnd;
+use crate::ics04_channel::error::{Error, Kind};
 use crate::ics04_channel::handler::{ChannelIdState, ChannelResult};
-use crate::ics04_channel::{error::Error, packet::Receipt};
-use crate::ics05_port::capabilities::Capability;
+use crate::ics04_channel::packet::Receipt;
+use crate::ics05_port::capabilities::{Capability, CapabilityName};
+use crate::ics05_port::context::{CapabilityKeeper, PortReader};
 use crate::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
 use crate::Height;
 
 use super::packet::{PacketResult, Sequence};
 
 /// A context supplying all the necessary read-only dependencies for processing any `ChannelMsg`.
-pub trait ChannelReader {
+pub trait ChannelReader: PortReader {
     /// Returns the ChannelEnd for the given `port_id` and `chan_id`.
     fn channel_end(&self, port_channel_id: &(PortId, ChannelId)) -> Option<ChannelEnd>;
 
@@ -34,7 +36,42 @@ pub trait ChannelReader {
         height: Height,
     ) -> Option<AnyConsensusState>;
 
-    fn authenticated_capability(&self, port_id: &PortId) -> Result<Capability, Error>;
+    /// Succeeds if `capability` is the capability of the given (bound) port.
+    fn authenticate_port_capability(
+        &self,
+        port_id: &PortId,
+        capability: &Capability,
+    ) -> Result<(), Error> {
+        if self.lookup_module_by_port(port_id).is_none() {
+            return Err(Kind::NoPortCapability(port_id.clone()).into());
+        }
+        if !self.authenticate(capability, port_id) {
+            return Err(Kind::InvalidPortCapability.into());
+        }
+
+        Ok(())
+    }
+
+    /// Succeeds if `capability` is the capability of the given channel, which the host created
+    /// during the channel opening handshake.
+    fn authenticate_channel_capability(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        capability: &Capability,
+    ) -> Result<(), Error> {
+        let name = CapabilityName::channel(port_id, channel_id);
+        if self.get_capability(&name).is_none() {
+            return Err(Kind::NoChannelCapability(port_id.clone(), channel_id.clone()).into());
+        }
+
+        self.authenticate_capability(&name, capability)
+            .map_err(|e| {
+                Kind::InvalidChannelCapability(port_id.clone(), channel_id.clone())
+                    .context(e)
+                    .into()
+            })
+    }
 
     fn get_next_sequence_send(&self, port_channel_id: &(PortId, ChannelId)) -> Option<Sequence>;
 
@@ -42,14 +79,35 @@ pub trait ChannelReader {
 
     fn get_next_sequence_ack(&self, port_channel_id: &(PortId, ChannelId)) -> Option<Sequence>;
 
-    fn get_packet_commitment(&self, key: &(PortId, ChannelId, Sequence)) -> Option<String>;
+    fn get_packet_commitment(&self, key: &(PortId, ChannelId, Sequence)) -> Option<Vec<u8>>;
 
     fn get_packet_receipt(&self, key: &(PortId, ChannelId, Sequence)) -> Option<Receipt>;
 
-    fn get_packet_acknowledgement(&self, key: &(PortId, ChannelId, Sequence)) -> Option<String>;
+    fn get_packet_acknowledgement(&self, key: &(PortId, ChannelId, Sequence)) -> Option<Vec<u8>>;
+
+    /// A hashing function for packet commitments (SHA-256 on Cosmos SDK chains).
+    fn hash(&self, value: Vec<u8>) -> Vec<u8>;
 
-    /// A hashing function for packet commitments  
-    fn hash(&self, value: String) -> String;
+    /// Computes the commitment to a packet the way ibc-go does, i.e. the hash of the timeout
+    /// timestamp, the timeout height revision number and revision height (all big-endian),
+    /// followed by the hash of the packet data.
+    fn packet_commitment(
+        &self,
+        data: &[u8],
+        timeout_height: Height,
+        timeout_timestamp: u64,
+    ) -> Vec<u8> {
+        let mut input = timeout_timestamp.to_be_bytes().to_vec();
+        input.extend(&timeout_height.revision_number.to_be_bytes());
+        input.extend(&timeout_height.revision_height.to_be_bytes());
+        input.extend(self.hash(data.to_vec()));
+        self.hash(input)
+    }
+
+    /// Computes the commitment to a packet acknowledgement, i.e. the hash of the acknowledgement.
+    fn ack_commitment(&self, ack: &[u8]) -> Vec<u8> {
+        self.hash(ack.to_vec())
+    }
 
     /// Returns the current height of the local chain.
     fn host_height(&self) -> Height;
@@ -65,8 +123,11 @@ pub trait ChannelReader {
 
 /// A context supplying all the necessary write-only dependencies (i.e., storage writing facility)
 /// for processing any `ChannelMsg`.
-pub trait ChannelKeeper {
-    fn store_channel_result(&mut self, result: ChannelResult) -> Result<(), Error> {
+pub trait ChannelKeeper: CapabilityKeeper {
+    /// Stores the outcome of a channel handshake step. When the step allocated a new channel,
+    /// returns the capability created for that channel, to be handed over to the module bound to
+    /// the channel port.
+    fn store_channel_result(&mut self, result: ChannelResult) -> Result<Option<Capability>, Error> {
         // The handler processed this channel & some modifications occurred, store the new end.
         self.store_channel(
             (result.port_id.clone(), result.channel_id.clone()),
@@ -75,9 +136,22 @@ pub trait ChannelKeeper {
 
         // The channel identifier was freshly brewed.
         // Increase counter & initialize seq. nrs.
+        let mut channel_cap = None;
         if matches!(result.channel_id_state, ChannelIdState::Generated) {
             self.increase_channel_counter();
 
+            // Create the capability of the new channel.
+            channel_cap = Some(
+                self.new_capability(CapabilityName::channel(&result.port_id, &result.channel_id))
+                    .map_err(|e| {
+                        Kind::ChannelCapabilityStoreFailed(
+                            result.port_id.clone(),
+                            result.channel_id.clone(),
+                        )
+                        .context(e)
+                    })?,
+            );
+
             // Associate also the channel end to its connection.
             self.store_connection_channels(
                 result.channel_end.connection_hops()[0].clone(),
@@ -96,7 +170,7 @@ pub trait ChannelKeeper {
             self.store_next_sequence_ack((result.port_id, result.channel_id), 1.into())?;
         }
 
-        Ok(())
+        Ok(channel_cap)
     }
 
     fn store_packet_result(&mut self, general_result: PacketResult) -> Result<(), Error> {
@@ -78,6 +78,15 @@ pub enum Kind {
     #[error("the module associated with the port does not have the capability it needs")]
     InvalidPortCapability,
 
+    #[error("the channel {1} on port {0} has no capability associated")]
+    NoChannelCapability(PortId, ChannelId),
+
+    #[error("the capability does not grant access to channel {1} on port {0}")]
+    InvalidChannelCapability(PortId, ChannelId),
+
+    #[error("failed to store the capability of channel {1} on port {0}")]
+    ChannelCapabilityStoreFailed(PortId, ChannelId),
+
     #[error("single version must be negociated on connection before opening channel")]
     InvalidVersionLengthConnection,
 
@@ -166,6 +175,9 @@ pub enum Kind {
 
     #[error("Missing sequence number for ack packets")]
     MissingNextAckSeq,
+
+    #[error("The application module bound to port {0} failed to process the message")]
+    AppModuleCallbackFailed(PortId),
 }
 
 impl Kind {
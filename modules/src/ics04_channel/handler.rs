@@ -1,13 +1,15 @@
 //! This module implements the processing logic for ICS4 (channel) messages.
 
 use crate::handler::HandlerOutput;
+use crate::ics02_client::client_state::AnyClientState;
 use crate::ics04_channel::channel::ChannelEnd;
 use crate::ics04_channel::context::ChannelReader;
-use crate::ics04_channel::error::Error;
+use crate::ics04_channel::error::{Error, Kind};
 use crate::ics04_channel::msgs::ChannelMsg;
 use crate::ics04_channel::{msgs::PacketMsg, packet::PacketResult};
 use crate::ics05_port::capabilities::Capability;
-use crate::ics24_host::identifier::{ChannelId, PortId};
+use crate::ics24_host::identifier::{ChannelId, ClientId, PortId};
+use crate::ics26_routing::router::Module;
 
 pub mod acknowledgement;
 pub mod chan_close_confirm;
@@ -39,38 +41,111 @@ pub struct ChannelResult {
     pub port_id: PortId,
     pub channel_id: ChannelId,
     pub channel_id_state: ChannelIdState,
-    pub channel_cap: Capability,
     pub channel_end: ChannelEnd,
+    /// The client which verified the proofs of the message, if any, with its state after the
+    /// verification.
+    pub client_state: Option<(ClientId, AnyClientState)>,
 }
 
 /// General entry point for processing any type of message related to the ICS4 channel open and
-/// channel close handshake protocols.
+/// channel close handshake protocols. The caller presents the `capability` of the module bound to
+/// the channel port: the port capability for `MsgChannelOpenInit` and `MsgChannelOpenTry`, and
+/// the channel capability for the other messages.
 pub fn channel_dispatch<Ctx>(
     ctx: &Ctx,
+    capability: &Capability,
     msg: ChannelMsg,
 ) -> Result<HandlerOutput<ChannelResult>, Error>
 where
     Ctx: ChannelReader,
 {
     match msg {
-        ChannelMsg::ChannelOpenInit(msg) => chan_open_init::process(ctx, msg),
-        ChannelMsg::ChannelOpenTry(msg) => chan_open_try::process(ctx, msg),
-        ChannelMsg::ChannelOpenAck(msg) => chan_open_ack::process(ctx, msg),
-        ChannelMsg::ChannelOpenConfirm(msg) => chan_open_confirm::process(ctx, msg),
-        ChannelMsg::ChannelCloseInit(msg) => chan_close_init::process(ctx, msg),
-        ChannelMsg::ChannelCloseConfirm(msg) => chan_close_confirm::process(ctx, msg),
+        ChannelMsg::ChannelOpenInit(msg) => chan_open_init::process(ctx, capability, msg),
+        ChannelMsg::ChannelOpenTry(msg) => chan_open_try::process(ctx, capability, msg),
+        ChannelMsg::ChannelOpenAck(msg) => chan_open_ack::process(ctx, capability, msg),
+        ChannelMsg::ChannelOpenConfirm(msg) => chan_open_confirm::process(ctx, capability, msg),
+        ChannelMsg::ChannelCloseInit(msg) => chan_close_init::process(ctx, capability, msg),
+        ChannelMsg::ChannelCloseConfirm(msg) => chan_close_confirm::process(ctx, capability, msg),
     }
 }
 
-/// Dispatcher for processing any type of message related to the ICS4 packet protocols.
-pub fn packet_dispatch<Ctx>(ctx: &Ctx, msg: PacketMsg) -> Result<HandlerOutput<PacketResult>, Error>
+/// Dispatcher for processing any type of message related to the ICS4 packet protocols. The caller
+/// presents the `channel_cap` capability of the module bound to the channel.
+pub fn packet_dispatch<Ctx>(
+    ctx: &Ctx,
+    channel_cap: &Capability,
+    msg: PacketMsg,
+) -> Result<HandlerOutput<PacketResult>, Error>
 where
     Ctx: ChannelReader,
 {
     match msg {
-        PacketMsg::RecvPacket(msg) => recv_packet::process(ctx, msg),
-        PacketMsg::AckPacket(msg) => acknowledgement::process(ctx, msg),
-        PacketMsg::ToPacket(msg) => timeout::process(ctx, msg),
-        PacketMsg::ToClosePacket(msg) => timeout_on_close::process(ctx, msg),
+        PacketMsg::RecvPacket(msg) => recv_packet::process(ctx, channel_cap, msg),
+        PacketMsg::AckPacket(msg) => acknowledgement::process(ctx, channel_cap, msg),
+        PacketMsg::ToPacket(msg) => timeout::process(ctx, channel_cap, msg),
+        PacketMsg::ToClosePacket(msg) => timeout_on_close::process(ctx, channel_cap, msg),
     }
 }
+
+/// Invokes the callback of the application module bound to the channel port, once the channel
+/// handshake message `msg` has been processed into `result`.
+pub fn channel_callback<Ctx>(
+    ctx: &mut Ctx,
+    module: &dyn Module<Ctx>,
+    msg: &ChannelMsg,
+    result: &ChannelResult,
+) -> Result<(), Error> {
+    let (port_id, channel_id) = (&result.port_id, &result.channel_id);
+
+    match msg {
+        ChannelMsg::ChannelOpenInit(_) => {
+            module.on_chan_open_init(ctx, port_id, channel_id, &result.channel_end)
+        }
+        ChannelMsg::ChannelOpenTry(msg) => module.on_chan_open_try(
+            ctx,
+            port_id,
+            channel_id,
+            &result.channel_end,
+            &msg.counterparty_version,
+        ),
+        ChannelMsg::ChannelOpenAck(msg) => {
+            module.on_chan_open_ack(ctx, port_id, channel_id, &msg.counterparty_version)
+        }
+        ChannelMsg::ChannelOpenConfirm(_) => module.on_chan_open_confirm(ctx, port_id, channel_id),
+        ChannelMsg::ChannelCloseInit(_) => module.on_chan_close_init(ctx, port_id, channel_id),
+        ChannelMsg::ChannelCloseConfirm(_) => {
+            module.on_chan_close_confirm(ctx, port_id, channel_id)
+        }
+    }
+    .map_err(|e| {
+        Kind::AppModuleCallbackFailed(port_id.clone())
+            .context(e.to_string())
+            .into()
+    })
+}
+
+/// Invokes the callback of the application module bound to the port of the packet, once the
+/// packet message `msg` has been processed. For received packets, returns the acknowledgement
+/// produced by the module.
+pub fn packet_callback<Ctx>(
+    ctx: &mut Ctx,
+    module: &dyn Module<Ctx>,
+    msg: &PacketMsg,
+) -> Result<Option<Vec<u8>>, Error> {
+    let result = match msg {
+        PacketMsg::RecvPacket(msg) => {
+            return Ok(Some(module.on_recv_packet(ctx, &msg.packet, &msg.signer)));
+        }
+        PacketMsg::AckPacket(msg) => {
+            module.on_acknowledgement_packet(ctx, &msg.packet, &msg.acknowledgement, &msg.signer)
+        }
+        PacketMsg::ToPacket(msg) => module.on_timeout_packet(ctx, &msg.packet, &msg.signer),
+        PacketMsg::ToClosePacket(msg) => module.on_timeout_packet(ctx, &msg.packet, &msg.signer),
+    };
+
+    result.map(|_| None).map_err(|e| {
+        Kind::AppModuleCallbackFailed(msg.port_id().clone())
+            .context(e.to_string())
+            .into()
+    })
+}
@@ -1,5 +1,6 @@
 use crate::events::IbcEvent;
 use crate::handler::{HandlerOutput, HandlerResult};
+use crate::ics02_client::client_state::AnyClientState;
 use crate::ics02_client::height::Height;
 use crate::ics03_connection::connection::State as ConnectionState;
 use crate::ics04_channel::channel::State;
@@ -9,7 +10,8 @@ use crate::ics04_channel::handler::verify::verify_packet_acknowledgement_proofs;
 use crate::ics04_channel::msgs::acknowledgement::MsgAcknowledgement;
 use crate::ics04_channel::packet::{PacketResult, Sequence};
 use crate::ics04_channel::{context::ChannelReader, error::Error, error::Kind};
-use crate::ics24_host::identifier::{ChannelId, PortId};
+use crate::ics05_port::capabilities::Capability;
+use crate::ics24_host::identifier::{ChannelId, ClientId, PortId};
 
 #[derive(Clone, Debug)]
 pub struct AckPacketResult {
@@ -17,10 +19,13 @@ pub struct AckPacketResult {
     pub channel_id: ChannelId,
     pub seq: Sequence,
     pub seq_number: Option<Sequence>,
+    /// The client which verified the proofs of the message, with its state after the verification.
+    pub client_state: (ClientId, AnyClientState),
 }
 
 pub fn process(
     ctx: &dyn ChannelReader,
+    channel_cap: &Capability,
     msg: MsgAcknowledgement,
 ) -> HandlerResult<PacketResult, Error> {
     let mut output = HandlerOutput::builder();
@@ -38,7 +43,8 @@ pub fn process(
         return Err(Kind::ChannelClosed(packet.source_channel.clone()).into());
     }
 
-    let _channel_cap = ctx.authenticated_capability(&packet.source_port)?;
+    // The caller must own the capability of the source channel.
+    ctx.authenticate_channel_capability(&packet.source_port, &packet.source_channel, channel_cap)?;
 
     let counterparty = Counterparty::new(
         packet.destination_port.clone(),
@@ -63,8 +69,6 @@ pub fn process(
         );
     }
 
-    let client_id = connection_end.client_id().clone();
-
     // Verify packet commitment
     let packet_commitment = ctx
         .get_packet_commitment(&(
@@ -74,23 +78,25 @@ pub fn process(
         ))
         .ok_or(Kind::PacketCommitmentNotFound(packet.sequence))?;
 
-    let input = format!(
-        "{:?},{:?},{:?}",
-        packet.timeout_timestamp, packet.timeout_height, packet.data,
-    );
-
-    if packet_commitment != ctx.hash(input) {
+    if packet_commitment
+        != ctx.packet_commitment(
+            &packet.data,
+            packet.timeout_height,
+            packet.timeout_timestamp,
+        )
+    {
         return Err(Kind::IncorrectPacketCommitment(packet.sequence).into());
     }
 
     // Verify the acknowledgement proof
-    verify_packet_acknowledgement_proofs(
+    let client_state = verify_packet_acknowledgement_proofs(
         ctx,
         &packet,
         msg.acknowledgement().clone(),
-        client_id,
+        &connection_end,
         msg.proofs(),
     )?;
+    let client_state = (connection_end.client_id().clone(), client_state);
 
     let result = if source_channel_end.order_matches(&Order::Ordered) {
         let next_seq_ack = ctx
@@ -106,6 +112,7 @@ pub fn process(
             channel_id: packet.source_channel.clone(),
             seq: packet.sequence,
             seq_number: Some(next_seq_ack.increment()),
+            client_state,
         })
     } else {
         PacketResult::Ack(AckPacketResult {
@@ -113,6 +120,7 @@ pub fn process(
             channel_id: packet.source_channel.clone(),
             seq: packet.sequence,
             seq_number: None,
+            client_state,
         })
     };
 
@@ -165,13 +173,12 @@ mod tests {
         .unwrap();
         let packet = msg.packet.clone();
 
-        let input = format!(
-            "{:?},{:?},{:?}",
+        let data = ChannelReader::packet_commitment(
+            &context,
+            &packet.data,
+            packet.timeout_height,
             packet.timeout_timestamp,
-            packet.timeout_height.clone(),
-            packet.data.clone()
         );
-        let data = ChannelReader::hash(&context, input);
 
         let source_channel_end = ChannelEnd::new(
             State::Open,
@@ -244,7 +251,11 @@ mod tests {
         .collect();
 
         for test in tests {
-            let res = process(&test.ctx, test.msg.clone());
+            let channel_cap = test.ctx.channel_capability(
+                &test.msg.packet.source_port,
+                &test.msg.packet.source_channel,
+            );
+            let res = process(&test.ctx, &channel_cap, test.msg.clone());
             // Additionally check the events and the output objects in the result.
             match res {
                 Ok(proto_output) => {
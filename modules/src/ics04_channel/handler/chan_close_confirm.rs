@@ -9,9 +9,11 @@ use crate::ics04_channel::events::Attributes;
 use crate::ics04_channel::handler::verify::verify_channel_proofs;
 use crate::ics04_channel::handler::{ChannelIdState, ChannelResult};
 use crate::ics04_channel::msgs::chan_close_confirm::MsgChannelCloseConfirm;
+use crate::ics05_port::capabilities::Capability;
 
 pub(crate) fn process(
     ctx: &dyn ChannelReader,
+    channel_cap: &Capability,
     msg: MsgChannelCloseConfirm,
 ) -> HandlerResult<ChannelResult, Error> {
     let mut output = HandlerOutput::builder();
@@ -26,8 +28,8 @@ pub(crate) fn process(
         return Err(Kind::ChannelClosed(msg.channel_id().clone()).into());
     }
 
-    // Channel capabilities
-    let channel_cap = ctx.authenticated_capability(&msg.port_id().clone())?;
+    // The caller must own the capability of the channel.
+    ctx.authenticate_channel_capability(msg.port_id(), msg.channel_id(), channel_cap)?;
 
     // An OPEN IBC connection running on the local (host) chain should exist.
     if channel_end.connection_hops().len() != 1 {
@@ -63,7 +65,7 @@ pub(crate) fn process(
         channel_end.version(),
     );
 
-    verify_channel_proofs(
+    let client_state = verify_channel_proofs(
         ctx,
         &channel_end,
         &conn,
@@ -81,8 +83,8 @@ pub(crate) fn process(
         port_id: msg.port_id().clone(),
         channel_id: msg.channel_id().clone(),
         channel_id_state: ChannelIdState::Reused,
-        channel_cap,
         channel_end,
+        client_state: Some((conn.client_id().clone(), client_state)),
     };
 
     let event_attributes = Attributes {
@@ -9,9 +9,11 @@ use crate::ics04_channel::events::Attributes;
 use crate::ics04_channel::handler::verify::verify_channel_proofs;
 use crate::ics04_channel::handler::{ChannelIdState, ChannelResult};
 use crate::ics04_channel::msgs::chan_open_ack::MsgChannelOpenAck;
+use crate::ics05_port::capabilities::Capability;
 
 pub(crate) fn process(
     ctx: &dyn ChannelReader,
+    channel_cap: &Capability,
     msg: MsgChannelOpenAck,
 ) -> HandlerResult<ChannelResult, Error> {
     let mut output = HandlerOutput::builder();
@@ -26,8 +28,8 @@ pub(crate) fn process(
         return Err(Kind::InvalidChannelState(msg.channel_id().clone(), channel_end.state).into());
     }
 
-    // Channel capabilities
-    let channel_cap = ctx.authenticated_capability(&msg.port_id().clone())?;
+    // The caller must own the capability of the channel.
+    ctx.authenticate_channel_capability(msg.port_id(), msg.channel_id(), channel_cap)?;
 
     // An OPEN IBC connection running on the local (host) chain should exist.
 
@@ -65,8 +67,11 @@ pub(crate) fn process(
         expected_connection_hops,
         msg.counterparty_version().clone(),
     );
+    // The counterparty channel end is only known from this message on, the proofs are about it.
+    channel_end.set_counterparty_channel_id(msg.counterparty_channel_id.clone());
+
     //2. Verify proofs
-    verify_channel_proofs(
+    let client_state = verify_channel_proofs(
         ctx,
         &channel_end,
         &conn,
@@ -80,14 +85,13 @@ pub(crate) fn process(
     // Transition the channel end to the new state & pick a version.
     channel_end.set_state(State::Open);
     channel_end.set_version(msg.counterparty_version().clone());
-    channel_end.set_counterparty_channel_id(msg.counterparty_channel_id.clone());
 
     let result = ChannelResult {
         port_id: msg.port_id().clone(),
         channel_id: msg.channel_id().clone(),
         channel_id_state: ChannelIdState::Reused,
-        channel_cap,
         channel_end,
+        client_state: Some((conn.client_id().clone(), client_state)),
     };
 
     let event_attributes = Attributes {
@@ -299,7 +303,10 @@ mod tests {
         .collect();
 
         for test in tests {
-            let res = channel_dispatch(&test.ctx, test.msg.clone());
+            let channel_cap = test
+                .ctx
+                .channel_capability(test.msg.port_id(), test.msg.channel_id().unwrap());
+            let res = channel_dispatch(&test.ctx, &channel_cap, test.msg.clone());
             // Additionally check the events and the output objects in the result.
             match res {
                 Ok(proto_output) => {
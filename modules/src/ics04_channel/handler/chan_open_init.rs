@@ -8,16 +8,19 @@ use crate::ics04_channel::error::{Error, Kind};
 use crate::ics04_channel::events::Attributes;
 use crate::ics04_channel::handler::{ChannelIdState, ChannelResult};
 use crate::ics04_channel::msgs::chan_open_init::MsgChannelOpenInit;
+use crate::ics05_port::capabilities::Capability;
 use crate::ics24_host::identifier::ChannelId;
 
 pub(crate) fn process(
     ctx: &dyn ChannelReader,
+    port_cap: &Capability,
     msg: MsgChannelOpenInit,
 ) -> HandlerResult<ChannelResult, Error> {
     let mut output = HandlerOutput::builder();
 
-    // Channel capabilities
-    let channel_cap = ctx.authenticated_capability(&msg.port_id().clone())?;
+    // The caller must own the capability of the port. The new channel gets a fresh capability
+    // once the result is stored.
+    ctx.authenticate_port_capability(msg.port_id(), port_cap)?;
 
     if msg.channel().connection_hops().len() != 1 {
         return Err(
@@ -71,7 +74,7 @@ pub(crate) fn process(
         channel_id: chan_id.clone(),
         channel_end: new_channel_end,
         channel_id_state: ChannelIdState::Generated,
-        channel_cap,
+        client_state: None,
     };
 
     let event_attributes = Attributes {
@@ -162,7 +165,8 @@ mod tests {
         .collect();
 
         for test in tests {
-            let res = channel_dispatch(&test.ctx, test.msg.clone());
+            let port_cap = test.ctx.port_capability(test.msg.port_id());
+            let res = channel_dispatch(&test.ctx, &port_cap, test.msg.clone());
             // Additionally check the events and the output objects in the result.
             match res {
                 Ok(proto_output) => {
@@ -10,10 +10,12 @@ use crate::ics04_channel::events::Attributes;
 use crate::ics04_channel::handler::verify::verify_channel_proofs;
 use crate::ics04_channel::handler::{ChannelIdState, ChannelResult};
 use crate::ics04_channel::msgs::chan_open_try::MsgChannelOpenTry;
+use crate::ics05_port::capabilities::Capability;
 use crate::ics24_host::identifier::ChannelId;
 
 pub(crate) fn process(
     ctx: &dyn ChannelReader,
+    port_cap: &Capability,
     msg: MsgChannelOpenTry,
 ) -> HandlerResult<ChannelResult, Error> {
     let mut output = HandlerOutput::builder();
@@ -99,8 +101,9 @@ pub(crate) fn process(
         return Err(Kind::ChannelFeatureNotSuportedByConnection.into());
     }
 
-    // Channel capabilities
-    let channel_cap = ctx.authenticated_capability(&msg.port_id().clone())?;
+    // The caller must own the capability of the port. A new channel gets a fresh capability once
+    // the result is stored, while a channel opened by a previous `ChanOpenInit` keeps its own.
+    ctx.authenticate_port_capability(msg.port_id(), port_cap)?;
 
     if msg.channel().version().is_empty() {
         return Err(Kind::InvalidVersion.into());
@@ -127,7 +130,7 @@ pub(crate) fn process(
     );
 
     // 2. Actual proofs are verified now.
-    verify_channel_proofs(
+    let client_state = verify_channel_proofs(
         ctx,
         &new_channel_end,
         &conn,
@@ -143,7 +146,6 @@ pub(crate) fn process(
 
     let result = ChannelResult {
         port_id: msg.port_id().clone(),
-        channel_cap,
         channel_id_state: if matches!(msg.previous_channel_id, None) {
             ChannelIdState::Generated
         } else {
@@ -151,6 +153,7 @@ pub(crate) fn process(
         },
         channel_id: channel_id.clone(),
         channel_end: new_channel_end,
+        client_state: Some((conn.client_id().clone(), client_state)),
     };
 
     let event_attributes = Attributes {
@@ -358,7 +361,8 @@ mod tests {
         .collect();
 
         for test in tests {
-            let res = channel_dispatch(&test.ctx, test.msg.clone());
+            let port_cap = test.ctx.port_capability(test.msg.port_id());
+            let res = channel_dispatch(&test.ctx, &port_cap, test.msg.clone());
             // Additionally check the events and the output objects in the result.
             match res {
                 Ok(handler_output) => {
@@ -1,5 +1,6 @@
 use crate::events::IbcEvent;
 use crate::handler::{HandlerOutput, HandlerResult};
+use crate::ics02_client::client_state::AnyClientState;
 use crate::ics02_client::height::Height;
 use crate::ics03_connection::connection::State as ConnectionState;
 use crate::ics04_channel::channel::{Counterparty, Order, State};
@@ -9,7 +10,8 @@ use crate::ics04_channel::events::ReceivePacket;
 use crate::ics04_channel::handler::verify::verify_packet_recv_proofs;
 use crate::ics04_channel::msgs::recv_packet::MsgRecvPacket;
 use crate::ics04_channel::packet::{PacketResult, Receipt, Sequence};
-use crate::ics24_host::identifier::{ChannelId, PortId};
+use crate::ics05_port::capabilities::Capability;
+use crate::ics24_host::identifier::{ChannelId, ClientId, PortId};
 
 #[derive(Clone, Debug)]
 pub struct RecvPacketResult {
@@ -18,9 +20,15 @@ pub struct RecvPacketResult {
     pub seq: Sequence,
     pub seq_number: Sequence,
     pub receipt: Option<Receipt>,
+    /// The client which verified the proofs of the message, with its state after the verification.
+    pub client_state: (ClientId, AnyClientState),
 }
 
-pub fn process(ctx: &dyn ChannelReader, msg: MsgRecvPacket) -> HandlerResult<PacketResult, Error> {
+pub fn process(
+    ctx: &dyn ChannelReader,
+    channel_cap: &Capability,
+    msg: MsgRecvPacket,
+) -> HandlerResult<PacketResult, Error> {
     let mut output = HandlerOutput::builder();
 
     let packet = &msg.packet;
@@ -45,7 +53,12 @@ pub fn process(ctx: &dyn ChannelReader, msg: MsgRecvPacket) -> HandlerResult<Pac
         .into());
     }
 
-    let _channel_cap = ctx.authenticated_capability(&packet.destination_port)?;
+    // The caller must own the capability of the destination channel.
+    ctx.authenticate_channel_capability(
+        &packet.destination_port,
+        &packet.destination_channel,
+        channel_cap,
+    )?;
 
     let counterparty = Counterparty::new(
         packet.source_port.clone(),
@@ -68,8 +81,6 @@ pub fn process(ctx: &dyn ChannelReader, msg: MsgRecvPacket) -> HandlerResult<Pac
         return Err(Kind::ConnectionNotOpen(dest_channel_end.connection_hops()[0].clone()).into());
     }
 
-    let client_id = connection_end.client_id().clone();
-
     // Check if packet height is newer than the height of the local host chain
     let latest_height = ctx.host_height();
     if (!packet.timeout_height.is_zero()) && (packet.timeout_height <= latest_height) {
@@ -82,7 +93,8 @@ pub fn process(ctx: &dyn ChannelReader, msg: MsgRecvPacket) -> HandlerResult<Pac
         return Err(Kind::LowPacketTimestamp.into());
     }
 
-    verify_packet_recv_proofs(ctx, &packet, client_id, &msg.proofs)?;
+    let client_state = verify_packet_recv_proofs(ctx, &packet, &connection_end, &msg.proofs)?;
+    let client_state = (connection_end.client_id().clone(), client_state);
 
     let result = if dest_channel_end.order_matches(&Order::Ordered) {
         let next_seq_recv = ctx
@@ -99,6 +111,7 @@ pub fn process(ctx: &dyn ChannelReader, msg: MsgRecvPacket) -> HandlerResult<Pac
             seq: packet.sequence,
             seq_number: next_seq_recv.increment(),
             receipt: None,
+            client_state,
         })
     } else {
         let packet_rec = ctx.get_packet_receipt(&(
@@ -117,6 +130,7 @@ pub fn process(ctx: &dyn ChannelReader, msg: MsgRecvPacket) -> HandlerResult<Pac
                     seq: packet.sequence,
                     seq_number: 1.into(),
                     receipt: Some(Receipt::Ok),
+                    client_state,
                 })
             }
         }
@@ -273,7 +287,11 @@ mod tests {
         .collect();
 
         for test in tests {
-            let res = process(&test.ctx, test.msg.clone());
+            let channel_cap = test.ctx.channel_capability(
+                &test.msg.packet.destination_port,
+                &test.msg.packet.destination_channel,
+            );
+            let res = process(&test.ctx, &channel_cap, test.msg.clone());
             // Additionally check the events and the output objects in the result.
             match res {
                 Ok(proto_output) => {
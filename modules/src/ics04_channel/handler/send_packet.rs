@@ -6,6 +6,7 @@ use crate::ics04_channel::channel::State;
 use crate::ics04_channel::events::SendPacket;
 use crate::ics04_channel::packet::{PacketResult, Sequence};
 use crate::ics04_channel::{context::ChannelReader, error::Error, error::Kind, packet::Packet};
+use crate::ics05_port::capabilities::Capability;
 use crate::ics24_host::identifier::{ChannelId, PortId};
 use crate::Height;
 
@@ -20,7 +21,13 @@ pub struct SendPacketResult {
     pub data: Vec<u8>,
 }
 
-pub fn send_packet(ctx: &dyn ChannelReader, packet: Packet) -> HandlerResult<PacketResult, Error> {
+/// Processes the sending of `packet` on behalf of an application module, which proves that it
+/// owns the source channel by presenting the channel capability `channel_cap`.
+pub fn send_packet(
+    ctx: &dyn ChannelReader,
+    channel_cap: &Capability,
+    packet: Packet,
+) -> HandlerResult<PacketResult, Error> {
     let mut output = HandlerOutput::builder();
 
     let source_channel_end = ctx
@@ -34,7 +41,8 @@ pub fn send_packet(ctx: &dyn ChannelReader, packet: Packet) -> HandlerResult<Pac
         return Err(Kind::ChannelClosed(packet.source_channel).into());
     }
 
-    let _channel_cap = ctx.authenticated_capability(&packet.source_port)?;
+    // Only the holder of the channel capability may send packets over the channel.
+    ctx.authenticate_channel_capability(&packet.source_port, &packet.source_channel, channel_cap)?;
 
     let counterparty = Counterparty::new(
         packet.destination_port.clone(),
@@ -127,9 +135,11 @@ mod tests {
     use crate::ics03_connection::connection::State as ConnectionState;
     use crate::ics03_connection::version::get_compatible_versions;
     use crate::ics04_channel::channel::{ChannelEnd, Counterparty, Order, State};
+    use crate::ics04_channel::context::ChannelReader;
     use crate::ics04_channel::handler::send_packet::send_packet;
     use crate::ics04_channel::packet::test_utils::get_dummy_raw_packet;
     use crate::ics04_channel::packet::Packet;
+    use crate::ics05_port::capabilities::Capability;
     use crate::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
     use crate::mock::context::MockContext;
 
@@ -220,7 +230,12 @@ mod tests {
         .collect();
 
         for test in tests {
-            let res = send_packet(&test.ctx, test.packet.clone());
+            // The sender presents the capability of the channel, if the channel was opened.
+            let channel_cap = test
+                .ctx
+                .channel_capability(&test.packet.source_port, &test.packet.source_channel);
+
+            let res = send_packet(&test.ctx, &channel_cap, test.packet.clone());
             // Additionally check the events and the output objects in the result.
             match res {
                 Ok(proto_output) => {
@@ -253,4 +268,49 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn send_packet_with_forged_capability() {
+        let mut packet: Packet = get_dummy_raw_packet(1, 6).try_into().unwrap();
+        packet.sequence = 1.into();
+
+        let channel_end = ChannelEnd::new(
+            State::Open,
+            Order::default(),
+            Counterparty::new(PortId::default(), Some(ChannelId::default())),
+            vec![ConnectionId::default()],
+            "ics20".to_string(),
+        );
+
+        let connection_end = ConnectionEnd::new(
+            ConnectionState::Open,
+            ClientId::default(),
+            ConnectionCounterparty::new(
+                ClientId::default(),
+                Some(ConnectionId::default()),
+                Default::default(),
+            ),
+            get_compatible_versions(),
+            Duration::from_secs(0),
+        );
+
+        // A second channel on the same port, whose capability is held by another module.
+        let other_channel_id = ChannelId::new(1);
+
+        let ctx = MockContext::default()
+            .with_client(&ClientId::default(), Height::default())
+            .with_connection(ConnectionId::default(), connection_end)
+            .with_port_capability(PortId::default())
+            .with_channel(PortId::default(), ChannelId::default(), channel_end.clone())
+            .with_channel(PortId::default(), other_channel_id.clone(), channel_end)
+            .with_send_sequence(PortId::default(), ChannelId::default(), 1.into());
+
+        let channel_cap = ctx.channel_capability(&PortId::default(), &ChannelId::default());
+        let other_channel_cap = ctx.channel_capability(&PortId::default(), &other_channel_id);
+        assert_ne!(channel_cap, other_channel_cap);
+
+        assert!(send_packet(&ctx, &channel_cap, packet.clone()).is_ok());
+        assert!(send_packet(&ctx, &other_channel_cap, packet.clone()).is_err());
+        assert!(send_packet(&ctx, &Capability::new(u64::MAX), packet).is_err());
+    }
 }
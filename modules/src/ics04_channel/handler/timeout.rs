@@ -1,5 +1,6 @@
 use crate::events::IbcEvent;
 use crate::handler::{HandlerOutput, HandlerResult};
+use crate::ics02_client::client_state::AnyClientState;
 use crate::ics04_channel::channel::State;
 use crate::ics04_channel::channel::{ChannelEnd, Counterparty, Order};
 use crate::ics04_channel::events::TimeoutPacket;
@@ -9,7 +10,8 @@ use crate::ics04_channel::handler::verify::{
 use crate::ics04_channel::msgs::timeout::MsgTimeout;
 use crate::ics04_channel::packet::{PacketResult, Sequence};
 use crate::ics04_channel::{context::ChannelReader, error::Error, error::Kind};
-use crate::ics24_host::identifier::{ChannelId, PortId};
+use crate::ics05_port::capabilities::Capability;
+use crate::ics24_host::identifier::{ChannelId, ClientId, PortId};
 
 #[derive(Clone, Debug)]
 pub struct TimeoutPacketResult {
@@ -17,9 +19,15 @@ pub struct TimeoutPacketResult {
     pub channel_id: ChannelId,
     pub seq: Sequence,
     pub channel: Option<ChannelEnd>,
+    /// The client which verified the proofs of the message, with its state after the verification.
+    pub client_state: (ClientId, AnyClientState),
 }
 
-pub fn process(ctx: &dyn ChannelReader, msg: MsgTimeout) -> HandlerResult<PacketResult, Error> {
+pub fn process(
+    ctx: &dyn ChannelReader,
+    channel_cap: &Capability,
+    msg: MsgTimeout,
+) -> HandlerResult<PacketResult, Error> {
     let mut output = HandlerOutput::builder();
 
     let packet = &msg.packet;
@@ -35,7 +43,8 @@ pub fn process(ctx: &dyn ChannelReader, msg: MsgTimeout) -> HandlerResult<Packet
         return Err(Kind::ChannelClosed(packet.source_channel.clone()).into());
     }
 
-    let _channel_cap = ctx.authenticated_capability(&packet.source_port)?;
+    // The caller must own the capability of the source channel.
+    ctx.authenticate_channel_capability(&packet.source_port, &packet.source_channel, channel_cap)?;
 
     let counterparty = Counterparty::new(
         packet.destination_port.clone(),
@@ -90,12 +99,13 @@ pub fn process(ctx: &dyn ChannelReader, msg: MsgTimeout) -> HandlerResult<Packet
         ))
         .ok_or(Kind::PacketCommitmentNotFound(packet.sequence))?;
 
-    let input = format!(
-        "{:?},{:?},{:?}",
-        packet.timeout_timestamp, packet.timeout_height, packet.data,
-    );
-
-    if packet_commitment != ChannelReader::hash(ctx, input) {
+    if packet_commitment
+        != ctx.packet_commitment(
+            &packet.data,
+            packet.timeout_height,
+            packet.timeout_timestamp,
+        )
+    {
         return Err(Kind::IncorrectPacketCommitment(packet.sequence).into());
     }
 
@@ -105,9 +115,9 @@ pub fn process(ctx: &dyn ChannelReader, msg: MsgTimeout) -> HandlerResult<Packet
                 Kind::InvalidPacketSequence(packet.sequence, msg.next_sequence_recv).into(),
             );
         }
-        verify_next_sequence_recv(
+        let client_state = verify_next_sequence_recv(
             ctx,
-            client_id,
+            &connection_end,
             packet.clone(),
             msg.next_sequence_recv,
             &msg.proofs.clone(),
@@ -119,15 +129,22 @@ pub fn process(ctx: &dyn ChannelReader, msg: MsgTimeout) -> HandlerResult<Packet
             channel_id: packet.source_channel.clone(),
             seq: packet.sequence,
             channel: Some(source_channel_end),
+            client_state: (client_id, client_state),
         })
     } else {
-        verify_packet_receipt_absence(ctx, client_id, packet.clone(), &msg.proofs.clone())?;
+        let client_state = verify_packet_receipt_absence(
+            ctx,
+            &connection_end,
+            packet.clone(),
+            &msg.proofs.clone(),
+        )?;
 
         PacketResult::Timeout(TimeoutPacketResult {
             port_id: packet.source_port.clone(),
             channel_id: packet.source_channel.clone(),
             seq: packet.sequence,
             channel: None,
+            client_state: (client_id, client_state),
         })
     };
 
@@ -185,13 +202,12 @@ mod tests {
         let mut msg_ok = msg.clone();
         msg_ok.packet.timeout_timestamp = Default::default();
 
-        let input = format!(
-            "{:?},{:?},{:?}",
+        let data = ChannelReader::packet_commitment(
+            &context,
+            &msg_ok.packet.data,
+            msg_ok.packet.timeout_height,
             msg_ok.packet.timeout_timestamp,
-            msg_ok.packet.timeout_height.clone(),
-            msg_ok.packet.data.clone()
         );
-        let data = ChannelReader::hash(&context, input);
 
         let source_channel_end = ChannelEnd::new(
             State::Open,
@@ -303,7 +319,11 @@ mod tests {
         .collect();
 
         for test in tests {
-            let res = process(&test.ctx, test.msg.clone());
+            let channel_cap = test.ctx.channel_capability(
+                &test.msg.packet.source_port,
+                &test.msg.packet.source_channel,
+            );
+            let res = process(&test.ctx, &channel_cap, test.msg.clone());
             // Additionally check the events and the output objects in the result.
             match res {
                 Ok(proto_output) => {
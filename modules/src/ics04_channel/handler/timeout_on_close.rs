@@ -12,9 +12,11 @@ use crate::ics04_channel::packet::PacketResult;
 use crate::ics04_channel::{
     context::ChannelReader, error::Error, error::Kind, handler::timeout::TimeoutPacketResult,
 };
+use crate::ics05_port::capabilities::Capability;
 
 pub fn process(
     ctx: &dyn ChannelReader,
+    channel_cap: &Capability,
     msg: MsgTimeoutOnClose,
 ) -> HandlerResult<PacketResult, Error> {
     let mut output = HandlerOutput::builder();
@@ -28,7 +30,8 @@ pub fn process(
                 .context(packet.source_channel.to_string())
         })?;
 
-    let _channel_cap = ctx.authenticated_capability(&packet.source_port)?;
+    // The caller must own the capability of the source channel.
+    ctx.authenticate_channel_capability(&packet.source_port, &packet.source_channel, channel_cap)?;
 
     let counterparty = Counterparty::new(
         packet.destination_port.clone(),
@@ -47,8 +50,6 @@ pub fn process(
         .connection_end(&source_channel_end.connection_hops()[0])
         .ok_or_else(|| Kind::MissingConnection(source_channel_end.connection_hops()[0].clone()))?;
 
-    let client_id = connection_end.client_id().clone();
-
     //verify the packet was sent, check the store
     let packet_commitment = ctx
         .get_packet_commitment(&(
@@ -58,12 +59,13 @@ pub fn process(
         ))
         .ok_or(Kind::PacketCommitmentNotFound(packet.sequence))?;
 
-    let input = format!(
-        "{:?},{:?},{:?}",
-        packet.timeout_timestamp, packet.timeout_height, packet.data,
-    );
-
-    if packet_commitment != ChannelReader::hash(ctx, input) {
+    if packet_commitment
+        != ctx.packet_commitment(
+            &packet.data,
+            packet.timeout_height,
+            packet.timeout_timestamp,
+        )
+    {
         return Err(Kind::IncorrectPacketCommitment(packet.sequence).into());
     }
 
@@ -87,6 +89,8 @@ pub fn process(
         source_channel_end.version(),
     );
 
+    // Both proofs are made at the same height, so they are verified against the stored client
+    // state, and the state returned by the second verification is kept.
     verify_channel_proofs(
         ctx,
         &source_channel_end,
@@ -95,15 +99,17 @@ pub fn process(
         &msg.proofs.clone(),
     )?;
 
+    let client_id = connection_end.client_id().clone();
+
     let result = if source_channel_end.order_matches(&Order::Ordered) {
         if packet.sequence < msg.next_sequence_recv {
             return Err(
                 Kind::InvalidPacketSequence(packet.sequence, msg.next_sequence_recv).into(),
             );
         }
-        verify_next_sequence_recv(
+        let client_state = verify_next_sequence_recv(
             ctx,
-            client_id,
+            &connection_end,
             packet.clone(),
             msg.next_sequence_recv,
             &msg.proofs.clone(),
@@ -114,15 +120,22 @@ pub fn process(
             channel_id: packet.source_channel.clone(),
             seq: packet.sequence,
             channel: Some(source_channel_end),
+            client_state: (client_id, client_state),
         })
     } else {
-        verify_packet_receipt_absence(ctx, client_id, packet.clone(), &msg.proofs.clone())?;
+        let client_state = verify_packet_receipt_absence(
+            ctx,
+            &connection_end,
+            packet.clone(),
+            &msg.proofs.clone(),
+        )?;
 
         PacketResult::Timeout(TimeoutPacketResult {
             port_id: packet.source_port.clone(),
             channel_id: packet.source_channel.clone(),
             seq: packet.sequence,
             channel: None,
+            client_state: (client_id, client_state),
         })
     };
 
@@ -180,13 +193,12 @@ mod tests {
         .unwrap();
         let packet = msg.packet.clone();
 
-        let input = format!(
-            "{:?},{:?},{:?}",
+        let data = ChannelReader::packet_commitment(
+            &context,
+            &msg.packet.data,
+            msg.packet.timeout_height,
             msg.packet.timeout_timestamp,
-            msg.packet.timeout_height.clone(),
-            msg.packet.data.clone()
         );
-        let data = ChannelReader::hash(&context, input);
 
         let source_channel_end = ChannelEnd::new(
             State::Open,
@@ -257,7 +269,11 @@ mod tests {
         .collect();
 
         for test in tests {
-            let res = process(&test.ctx, test.msg.clone());
+            let channel_cap = test.ctx.channel_capability(
+                &test.msg.packet.source_port,
+                &test.msg.packet.source_channel,
+            );
+            let res = process(&test.ctx, &channel_cap, test.msg.clone());
             // Additionally check the events and the output objects in the result.
             match res {
                 Ok(proto_output) => {
@@ -1,21 +1,23 @@
-use crate::ics02_client::client_state::ClientState;
+use crate::ics02_client::client_consensus::ConsensusState;
+use crate::ics02_client::client_state::{AnyClientState, ClientState};
 use crate::ics02_client::{client_def::AnyClient, client_def::ClientDef};
 use crate::ics03_connection::connection::ConnectionEnd;
 use crate::ics04_channel::channel::ChannelEnd;
 use crate::ics04_channel::context::ChannelReader;
 use crate::ics04_channel::error::{Error, Kind};
 use crate::ics04_channel::packet::{Packet, Sequence};
-use crate::ics24_host::identifier::ClientId;
 use crate::proofs::Proofs;
 
 /// Entry point for verifying all proofs bundled in any ICS4 message for channel protocols.
+/// Like the other functions of this module, returns the state of the client after the
+/// verification, which the host must store.
 pub fn verify_channel_proofs(
     ctx: &dyn ChannelReader,
     channel_end: &ChannelEnd,
     connection_end: &ConnectionEnd,
     expected_chan: &ChannelEnd,
     proofs: &Proofs,
-) -> Result<(), Error> {
+) -> Result<AnyClientState, Error> {
     // This is the client which will perform proof verification.
     let client_id = connection_end.client_id().clone();
 
@@ -28,12 +30,9 @@ pub fn verify_channel_proofs(
         return Err(Kind::FrozenClient(client_id).into());
     }
 
-    if ctx
+    let consensus_state = ctx
         .client_consensus_state(&client_id, proofs.height())
-        .is_none()
-    {
-        return Err(Kind::MissingClientConsensusState(client_id, proofs.height()).into());
-    }
+        .ok_or_else(|| Kind::MissingClientConsensusState(client_id.clone(), proofs.height()))?;
 
     let client_def = AnyClient::from_client_type(client_state.client_type());
 
@@ -41,10 +40,12 @@ pub fn verify_channel_proofs(
     // A counterparty channel id of None in not possible, and is checked by validate_basic in msg.
     Ok(client_def
         .verify_channel_state(
+            ctx,
             &client_state,
             proofs.height(),
             connection_end.counterparty().prefix(),
             proofs.object_proof(),
+            consensus_state.root(),
             &channel_end.counterparty().port_id(),
             &channel_end.counterparty().channel_id().unwrap(),
             expected_chan,
@@ -56,9 +57,11 @@ pub fn verify_channel_proofs(
 pub fn verify_packet_recv_proofs(
     ctx: &dyn ChannelReader,
     packet: &Packet,
-    client_id: ClientId,
+    connection_end: &ConnectionEnd,
     proofs: &Proofs,
-) -> Result<(), Error> {
+) -> Result<AnyClientState, Error> {
+    let client_id = connection_end.client_id().clone();
+
     let client_state = ctx
         .client_state(&client_id)
         .ok_or_else(|| Kind::MissingClientState(client_id.clone()))?;
@@ -68,27 +71,27 @@ pub fn verify_packet_recv_proofs(
         return Err(Kind::FrozenClient(client_id).into());
     }
 
-    if ctx
+    let consensus_state = ctx
         .client_consensus_state(&client_id, proofs.height())
-        .is_none()
-    {
-        return Err(Kind::MissingClientConsensusState(client_id, proofs.height()).into());
-    }
+        .ok_or_else(|| Kind::MissingClientConsensusState(client_id.clone(), proofs.height()))?;
 
     let client_def = AnyClient::from_client_type(client_state.client_type());
 
-    let input = format!(
-        "{:?},{:?},{:?}",
-        packet.timeout_timestamp, packet.timeout_height, packet.data
+    let commitment = ctx.packet_commitment(
+        &packet.data,
+        packet.timeout_height,
+        packet.timeout_timestamp,
     );
-    let commitment = ctx.hash(input);
 
     // Verify the proof for the packet against the chain store.
     Ok(client_def
         .verify_packet_data(
+            ctx,
             &client_state,
             proofs.height(),
+            connection_end.counterparty().prefix(),
             proofs.object_proof(),
+            consensus_state.root(),
             &packet.source_port,
             &packet.source_channel,
             &packet.sequence,
@@ -102,9 +105,11 @@ pub fn verify_packet_acknowledgement_proofs(
     ctx: &dyn ChannelReader,
     packet: &Packet,
     acknowledgement: Vec<u8>,
-    client_id: ClientId,
+    connection_end: &ConnectionEnd,
     proofs: &Proofs,
-) -> Result<(), Error> {
+) -> Result<AnyClientState, Error> {
+    let client_id = connection_end.client_id().clone();
+
     let client_state = ctx
         .client_state(&client_id)
         .ok_or_else(|| Kind::MissingClientState(client_id.clone()))?;
@@ -114,16 +119,23 @@ pub fn verify_packet_acknowledgement_proofs(
         return Err(Kind::FrozenClient(client_id).into());
     }
 
+    let consensus_state = ctx
+        .client_consensus_state(&client_id, proofs.height())
+        .ok_or_else(|| Kind::MissingClientConsensusState(client_id.clone(), proofs.height()))?;
+
     let client_def = AnyClient::from_client_type(client_state.client_type());
 
     // Verify the proof for the packet against the chain store.
     Ok(client_def
         .verify_packet_acknowledgement(
+            ctx,
             &client_state,
             proofs.height(),
+            connection_end.counterparty().prefix(),
             proofs.object_proof(),
-            &packet.source_port,
-            &packet.source_channel,
+            consensus_state.root(),
+            &packet.destination_port,
+            &packet.destination_channel,
             &packet.sequence,
             acknowledgement,
         )
@@ -133,11 +145,13 @@ pub fn verify_packet_acknowledgement_proofs(
 /// Entry point for verifying all timeout proofs.
 pub fn verify_next_sequence_recv(
     ctx: &dyn ChannelReader,
-    client_id: ClientId,
+    connection_end: &ConnectionEnd,
     packet: Packet,
     seq: Sequence,
     proofs: &Proofs,
-) -> Result<(), Error> {
+) -> Result<AnyClientState, Error> {
+    let client_id = connection_end.client_id().clone();
+
     let client_state = ctx
         .client_state(&client_id)
         .ok_or_else(|| Kind::MissingClientState(client_id.clone()))?;
@@ -147,14 +161,21 @@ pub fn verify_next_sequence_recv(
         return Err(Kind::FrozenClient(client_id).into());
     }
 
+    let consensus_state = ctx
+        .client_consensus_state(&client_id, proofs.height())
+        .ok_or_else(|| Kind::MissingClientConsensusState(client_id.clone(), proofs.height()))?;
+
     let client_def = AnyClient::from_client_type(client_state.client_type());
 
     // Verify the proof for the packet against the chain store.
     Ok(client_def
         .verify_next_sequence_recv(
+            ctx,
             &client_state,
             proofs.height(),
+            connection_end.counterparty().prefix(),
             proofs.object_proof(),
+            consensus_state.root(),
             &packet.destination_port,
             &packet.destination_channel,
             &seq,
@@ -164,10 +185,12 @@ pub fn verify_next_sequence_recv(
 
 pub fn verify_packet_receipt_absence(
     ctx: &dyn ChannelReader,
-    client_id: ClientId,
+    connection_end: &ConnectionEnd,
     packet: Packet,
     proofs: &Proofs,
-) -> Result<(), Error> {
+) -> Result<AnyClientState, Error> {
+    let client_id = connection_end.client_id().clone();
+
     let client_state = ctx
         .client_state(&client_id)
         .ok_or_else(|| Kind::MissingClientState(client_id.clone()))?;
@@ -177,14 +200,21 @@ pub fn verify_packet_receipt_absence(
         return Err(Kind::FrozenClient(client_id).into());
     }
 
+    let consensus_state = ctx
+        .client_consensus_state(&client_id, proofs.height())
+        .ok_or_else(|| Kind::MissingClientConsensusState(client_id.clone(), proofs.height()))?;
+
     let client_def = AnyClient::from_client_type(client_state.client_type());
 
     // Verify the proof for the packet against the chain store.
     Ok(client_def
         .verify_packet_receipt_absence(
+            ctx,
             &client_state,
             proofs.height(),
+            connection_end.counterparty().prefix(),
             proofs.object_proof(),
+            consensus_state.root(),
             &packet.destination_port,
             &packet.destination_channel,
             &packet.sequence,
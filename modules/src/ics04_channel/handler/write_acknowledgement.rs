@@ -2,6 +2,7 @@ use crate::ics04_channel::channel::State;
 use crate::ics04_channel::events::WriteAcknowledgement;
 use crate::ics04_channel::packet::{Packet, PacketResult, Sequence};
 use crate::ics04_channel::{context::ChannelReader, error::Error, error::Kind};
+use crate::ics05_port::capabilities::Capability;
 use crate::ics24_host::identifier::{ChannelId, PortId};
 use crate::{
     events::IbcEvent,
@@ -18,6 +19,7 @@ pub struct WriteAckPacketResult {
 
 pub fn process(
     ctx: &dyn ChannelReader,
+    channel_cap: &Capability,
     packet: Packet,
     ack: Vec<u8>,
 ) -> HandlerResult<PacketResult, Error> {
@@ -41,7 +43,12 @@ pub fn process(
         );
     }
 
-    let _channel_cap = ctx.authenticated_capability(&packet.destination_port)?;
+    // The caller must own the capability of the destination channel.
+    ctx.authenticate_channel_capability(
+        &packet.destination_port,
+        &packet.destination_channel,
+        channel_cap,
+    )?;
 
     // NOTE: IBC app modules might have written the acknowledgement synchronously on
     // the OnRecvPacket callback so we need to check if the acknowledgement is already
@@ -192,7 +199,11 @@ mod tests {
         .collect();
 
         for test in tests {
-            let res = process(&test.ctx, test.packet.clone(), test.ack);
+            let channel_cap = test.ctx.channel_capability(
+                &test.packet.destination_port,
+                &test.packet.destination_channel,
+            );
+            let res = process(&test.ctx, &channel_cap, test.packet.clone(), test.ack);
             // Additionally check the events and the output objects in the result.
             match res {
                 Ok(proto_output) => {
@@ -9,6 +9,7 @@ use crate::ics04_channel::msgs::chan_open_ack::MsgChannelOpenAck;
 use crate::ics04_channel::msgs::chan_open_confirm::MsgChannelOpenConfirm;
 use crate::ics04_channel::msgs::chan_open_init::MsgChannelOpenInit;
 use crate::ics04_channel::msgs::chan_open_try::MsgChannelOpenTry;
+use crate::ics24_host::identifier::{ChannelId, PortId};
 
 use self::{recv_packet::MsgRecvPacket, timeout::MsgTimeout, timeout_on_close::MsgTimeoutOnClose};
 
@@ -39,6 +40,31 @@ pub enum ChannelMsg {
     ChannelCloseConfirm(MsgChannelCloseConfirm),
 }
 
+impl ChannelMsg {
+    /// The port of the channel this message pertains to.
+    pub fn port_id(&self) -> &PortId {
+        match self {
+            ChannelMsg::ChannelOpenInit(msg) => &msg.port_id,
+            ChannelMsg::ChannelOpenTry(msg) => &msg.port_id,
+            ChannelMsg::ChannelOpenAck(msg) => &msg.port_id,
+            ChannelMsg::ChannelOpenConfirm(msg) => &msg.port_id,
+            ChannelMsg::ChannelCloseInit(msg) => &msg.port_id,
+            ChannelMsg::ChannelCloseConfirm(msg) => &msg.port_id,
+        }
+    }
+
+    /// The channel this message pertains to, unless the message opens a new channel.
+    pub fn channel_id(&self) -> Option<&ChannelId> {
+        match self {
+            ChannelMsg::ChannelOpenInit(_) | ChannelMsg::ChannelOpenTry(_) => None,
+            ChannelMsg::ChannelOpenAck(msg) => Some(&msg.channel_id),
+            ChannelMsg::ChannelOpenConfirm(msg) => Some(&msg.channel_id),
+            ChannelMsg::ChannelCloseInit(msg) => Some(&msg.channel_id),
+            ChannelMsg::ChannelCloseConfirm(msg) => Some(&msg.channel_id),
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum PacketMsg {
     RecvPacket(MsgRecvPacket),
@@ -46,3 +72,26 @@ pub enum PacketMsg {
     ToPacket(MsgTimeout),
     ToClosePacket(MsgTimeoutOnClose),
 }
+
+impl PacketMsg {
+    /// The port of the application module which processes this message on the local chain: the
+    /// destination port for received packets, and the source port otherwise.
+    pub fn port_id(&self) -> &PortId {
+        match self {
+            PacketMsg::RecvPacket(msg) => &msg.packet.destination_port,
+            PacketMsg::AckPacket(msg) => &msg.packet.source_port,
+            PacketMsg::ToPacket(msg) => &msg.packet.source_port,
+            PacketMsg::ToClosePacket(msg) => &msg.packet.source_port,
+        }
+    }
+
+    /// The channel of the packet end on this chain.
+    pub fn channel_id(&self) -> &ChannelId {
+        match self {
+            PacketMsg::RecvPacket(msg) => &msg.packet.destination_channel,
+            PacketMsg::AckPacket(msg) => &msg.packet.source_channel,
+            PacketMsg::ToPacket(msg) => &msg.packet.source_channel,
+            PacketMsg::ToClosePacket(msg) => &msg.packet.source_channel,
+        }
+    }
+}
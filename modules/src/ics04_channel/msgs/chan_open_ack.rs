@@ -145,7 +145,7 @@ pub mod test_util {
             port_id: PortId::default().to_string(),
             channel_id: ChannelId::default().to_string(),
             counterparty_channel_id: ChannelId::default().to_string(),
-            counterparty_version: "".to_string(),
+            counterparty_version: "ics20-1".to_string(),
             proof_try: get_dummy_proof(),
             proof_height: Some(Height {
                 revision_number: 0,
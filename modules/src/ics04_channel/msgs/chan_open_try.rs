@@ -159,7 +159,7 @@ pub mod test_util {
             port_id: PortId::default().to_string(),
             previous_channel_id: ChannelId::default().to_string(),
             channel: Some(get_dummy_raw_channel_end()),
-            counterparty_version: "".to_string(),
+            counterparty_version: "ics20-1".to_string(),
             proof_init: get_dummy_proof(),
             proof_height: Some(Height {
                 revision_number: 0,
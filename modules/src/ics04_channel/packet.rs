@@ -4,8 +4,9 @@ use serde_derive::{Deserialize, Serialize};
 
 use ibc_proto::ibc::core::channel::v1::Packet as RawPacket;
 
+use crate::ics02_client::client_state::AnyClientState;
 use crate::ics04_channel::error::Kind;
-use crate::ics24_host::identifier::{ChannelId, PortId};
+use crate::ics24_host::identifier::{ChannelId, ClientId, PortId};
 use crate::Height;
 
 use super::handler::{
@@ -32,6 +33,19 @@ pub enum PacketResult {
     Timeout(TimeoutPacketResult),
 }
 
+impl PacketResult {
+    /// The client which verified the proofs of the message, if any, with its state after the
+    /// verification.
+    pub fn client_state(&self) -> Option<(ClientId, AnyClientState)> {
+        match self {
+            PacketResult::Send(_) | PacketResult::WriteAck(_) => None,
+            PacketResult::Recv(res) => Some(res.client_state.clone()),
+            PacketResult::Ack(res) => Some(res.client_state.clone()),
+            PacketResult::Timeout(res) => Some(res.client_state.clone()),
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub enum Receipt {
     Ok,
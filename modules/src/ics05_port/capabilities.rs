@@ -1,18 +1,50 @@
-//! Capabilities: this is a placeholder.
+//! Capabilities are unforgeable keys which grant their holder the right to use a port or a
+//! channel. Each capability is allocated by the host with a unique index, and stored under a
+//! `CapabilityName` so that the handlers can authenticate the capabilities they are presented.
 
-#[derive(Clone, Debug, PartialEq)]
+use std::fmt;
+
+use crate::ics24_host::identifier::{ChannelId, PortId};
+use crate::ics24_host::Path;
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Capability {
     index: u64,
 }
 
 impl Capability {
-    pub fn new() -> Capability {
-        Self { index: 0x0 }
+    /// Capabilities are only created by `CapabilityKeeper::new_capability`, which allocates a
+    /// fresh index and stores the capability, so that they cannot be forged.
+    pub(crate) fn new(index: u64) -> Capability {
+        Self { index }
+    }
+
+    pub fn index(&self) -> u64 {
+        self.index
+    }
+}
+
+/// The name under which the host stores a capability.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CapabilityName(String);
+
+impl CapabilityName {
+    /// The name of the capability of a port, i.e., `ports/{port}`.
+    pub fn port(port_id: &PortId) -> Self {
+        Self(Path::Ports(port_id.clone()).to_string())
+    }
+
+    /// The name of the capability of a channel, i.e., `capabilities/ports/{port}/channels/{channel}`.
+    pub fn channel(port_id: &PortId, channel_id: &ChannelId) -> Self {
+        Self(format!(
+            "capabilities/ports/{}/channels/{}",
+            port_id, channel_id
+        ))
     }
 }
 
-impl Default for Capability {
-    fn default() -> Self {
-        Self::new()
+impl fmt::Display for CapabilityName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
     }
 }
@@ -1,10 +1,107 @@
-use crate::ics05_port::capabilities::Capability;
+use crate::ics05_port::capabilities::{Capability, CapabilityName};
+use crate::ics05_port::error::{Error, Kind};
 use crate::ics24_host::identifier::PortId;
 
+/// A context supplying read access to the capabilities allocated by the host.
+pub trait CapabilityReader {
+    /// Returns the capability stored under `name`, if any.
+    fn get_capability(&self, name: &CapabilityName) -> Option<Capability>;
+
+    /// Succeeds if `capability` is the one stored under `name`.
+    fn authenticate_capability(
+        &self,
+        name: &CapabilityName,
+        capability: &Capability,
+    ) -> Result<(), Error> {
+        match self.get_capability(name) {
+            Some(stored) if stored == *capability => Ok(()),
+            Some(_) => Err(Kind::InvalidCapability(name.clone()).into()),
+            None => Err(Kind::CapabilityNotFound(name.clone()).into()),
+        }
+    }
+
+    /// Returns a counter on the number of capabilities allocated thus far, which is also the
+    /// index of the next capability. The value of this counter should increase only via method
+    /// `CapabilityKeeper::increase_capability_counter`.
+    fn capability_counter(&self) -> u64;
+}
+
+/// A context supplying the storage facility for capabilities.
+pub trait CapabilityKeeper: CapabilityReader {
+    fn store_capability(
+        &mut self,
+        name: CapabilityName,
+        capability: Capability,
+    ) -> Result<(), Error>;
+
+    /// Called upon the allocation of a capability, to keep capability indices unique.
+    fn increase_capability_counter(&mut self);
+
+    /// Allocates a fresh capability and stores it under `name`, unless that name is taken.
+    fn new_capability(&mut self, name: CapabilityName) -> Result<Capability, Error> {
+        if self.get_capability(&name).is_some() {
+            return Err(Kind::CapabilityAlreadyExists(name).into());
+        }
+
+        let capability = Capability::new(self.capability_counter());
+        self.store_capability(name, capability.clone())?;
+        self.increase_capability_counter();
+
+        Ok(capability)
+    }
+}
+
 // A context supplying all the necessary read-only dependencies for processing any information regarding a port.
-pub trait PortReader {
-    fn lookup_module_by_port(&self, port_id: &PortId) -> Option<Capability>;
-    fn authenticate(&self, key: &Capability, port_id: &PortId) -> bool;
+pub trait PortReader: CapabilityReader {
+    /// Returns the capability of the given port, if the port is bound.
+    fn lookup_module_by_port(&self, port_id: &PortId) -> Option<Capability> {
+        self.get_capability(&CapabilityName::port(port_id))
+    }
+
+    fn authenticate(&self, key: &Capability, port_id: &PortId) -> bool {
+        self.authenticate_capability(&CapabilityName::port(port_id), key)
+            .is_ok()
+    }
 }
 
-//  Result<Capability, Error>//return Ok(Capability::new());
+/// A context supplying the facility to bind ports.
+pub trait PortKeeper: PortReader + CapabilityKeeper {
+    /// Binds the given port, and returns its capability. Fails if the port is already bound.
+    fn bind_port(&mut self, port_id: &PortId) -> Result<Capability, Error> {
+        if self.lookup_module_by_port(port_id).is_some() {
+            return Err(Kind::PortAlreadyBound(port_id.clone()).into());
+        }
+
+        self.new_capability(CapabilityName::port(port_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ics05_port::capabilities::{Capability, CapabilityName};
+    use crate::ics05_port::context::{CapabilityKeeper, CapabilityReader, PortKeeper, PortReader};
+    use crate::ics24_host::identifier::{ChannelId, PortId};
+    use crate::mock::context::MockContext;
+
+    #[test]
+    fn bind_port_and_authenticate() {
+        let mut ctx = MockContext::default();
+        let port_id = PortId::default();
+
+        let port_cap = ctx.bind_port(&port_id).unwrap();
+        assert_eq!(ctx.lookup_module_by_port(&port_id), Some(port_cap.clone()));
+        assert!(ctx.authenticate(&port_cap, &port_id));
+
+        // A port cannot be bound twice, and forged capabilities are rejected.
+        assert!(ctx.bind_port(&port_id).is_err());
+        assert!(!ctx.authenticate(&Capability::new(u64::MAX), &port_id));
+
+        // Capabilities of distinct objects are distinct, even if they share a port.
+        let name = CapabilityName::channel(&port_id, &ChannelId::default());
+        let channel_cap = ctx.new_capability(name.clone()).unwrap();
+        assert_ne!(channel_cap, port_cap);
+        assert!(ctx.authenticate_capability(&name, &channel_cap).is_ok());
+        assert!(ctx.authenticate_capability(&name, &port_cap).is_err());
+        assert!(ctx.new_capability(name).is_err());
+    }
+}
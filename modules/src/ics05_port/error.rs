@@ -1,12 +1,27 @@
 use anomaly::{BoxError, Context};
 use thiserror::Error;
 
+use crate::ics05_port::capabilities::CapabilityName;
+use crate::ics24_host::identifier::PortId;
+
 pub type Error = anomaly::Error<Kind>;
 
 #[derive(Clone, Debug, Error)]
 pub enum Kind {
     #[error("port unknown")]
     UnknownPort,
+
+    #[error("port {0} is already bound")]
+    PortAlreadyBound(PortId),
+
+    #[error("a capability named {0} already exists")]
+    CapabilityAlreadyExists(CapabilityName),
+
+    #[error("no capability named {0} exists")]
+    CapabilityNotFound(CapabilityName),
+
+    #[error("the capability does not match the one named {0}")]
+    InvalidCapability(CapabilityName),
 }
 
 impl Kind {
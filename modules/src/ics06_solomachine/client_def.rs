@@ -0,0 +1,696 @@
+use prost::Message;
+
+use ibc_proto::cosmos::tx::signing::v1beta1::signature_descriptor::{
+    data::Sum, Data as RawSignatureData,
+};
+use ibc_proto::ibc::lightclients::solomachine::v1::{
+    ChannelStateData, ClientStateData, ConnectionStateData, ConsensusStateData, DataType,
+    HeaderData, NextSequenceRecvData, PacketAcknowledgementData, PacketCommitmentData,
+    PacketReceiptAbsenceData, SignBytes, TimestampedSignatureData,
+};
+
+use crate::ics02_client::client_consensus::AnyConsensusState;
+use crate::ics02_client::client_def::ClientDef;
+use crate::ics02_client::client_state::AnyClientState;
+use crate::ics02_client::context::ClientReader;
+use crate::ics03_connection::connection::ConnectionEnd;
+use crate::ics03_connection::context::ConnectionReader;
+use crate::ics04_channel::channel::ChannelEnd;
+use crate::ics04_channel::context::ChannelReader;
+use crate::ics04_channel::packet::Sequence;
+use crate::ics06_solomachine::client_state::ClientState;
+use crate::ics06_solomachine::consensus_state::ConsensusState;
+use crate::ics06_solomachine::error::{Error, Kind};
+use crate::ics06_solomachine::header::Header;
+use crate::ics06_solomachine::misbehaviour::{Misbehaviour, SignatureAndData};
+use crate::ics06_solomachine::public_key::PublicKey;
+use crate::ics23_commitment::commitment::{CommitmentPrefix, CommitmentProofBytes, CommitmentRoot};
+use crate::ics23_commitment::merkle::apply_prefix;
+use crate::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+use crate::ics24_host::Path;
+use crate::Height;
+
+/// The solo machine client. Instead of Merkle proofs, solo machines prove their state with
+/// signatures over the expected values, made at the current sequence of the client.
+///
+/// The sequence advances with each header update, and the verification functions return the
+/// client state at the next sequence, so that a proof cannot be replayed once the host stores it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SoloMachineClient;
+
+impl ClientDef for SoloMachineClient {
+    type Header = Header;
+    type ClientState = ClientState;
+    type ConsensusState = ConsensusState;
+    type Misbehaviour = Misbehaviour;
+
+    fn check_header_and_update_state(
+        &self,
+        _ctx: &dyn ClientReader,
+        _client_id: ClientId,
+        client_state: Self::ClientState,
+        header: Self::Header,
+    ) -> Result<(Self::ClientState, Self::ConsensusState), Box<dyn std::error::Error>> {
+        verify_not_frozen(&client_state)?;
+
+        if header.sequence != client_state.sequence {
+            return Err(Kind::MismatchedSequence(client_state.sequence, header.sequence).into());
+        }
+        verify_timestamp(&client_state, header.timestamp)?;
+
+        // The header is signed with the current key, over the new key and diversifier.
+        let data = encode(&HeaderData {
+            new_pub_key: Some(header.new_public_key.clone().into()),
+            new_diversifier: header.new_diversifier.clone(),
+        });
+        let sign_bytes = sign_bytes(
+            header.sequence,
+            header.timestamp,
+            &client_state.consensus_state.diversifier,
+            DataType::Header,
+            data,
+        );
+        verify_signature(
+            &client_state.consensus_state.public_key,
+            &sign_bytes,
+            &header.signature,
+        )?;
+
+        let consensus_state = ConsensusState::new(
+            header.new_public_key,
+            header.new_diversifier,
+            header.timestamp,
+        );
+        let client_state = ClientState {
+            sequence: header.sequence + 1,
+            consensus_state: consensus_state.clone(),
+            ..client_state
+        };
+
+        Ok((client_state, consensus_state))
+    }
+
+    fn check_misbehaviour_and_update_state(
+        &self,
+        _ctx: &dyn ClientReader,
+        _client_id: ClientId,
+        client_state: Self::ClientState,
+        misbehaviour: Self::Misbehaviour,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        verify_not_frozen(&client_state)?;
+
+        let Misbehaviour {
+            sequence,
+            signature_one,
+            signature_two,
+            ..
+        } = misbehaviour;
+
+        if signature_one.data == signature_two.data {
+            return Err(Kind::InvalidMisbehaviour
+                .context("the signed data is identical")
+                .into());
+        }
+        if signature_one.signature == signature_two.signature {
+            return Err(Kind::InvalidMisbehaviour
+                .context("the signatures are identical")
+                .into());
+        }
+
+        // Both signatures must have been made by the solo machine at the same sequence.
+        for signature in &[&signature_one, &signature_two] {
+            verify_signature_and_data(&client_state, sequence, signature)?;
+        }
+
+        Ok(client_state.with_frozen_sequence(sequence))
+    }
+
+    fn verify_client_consensus_state(
+        &self,
+        _ctx: &dyn ConnectionReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        client_id: &ClientId,
+        consensus_height: Height,
+        expected_consensus_state: &AnyConsensusState,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        let path = Path::ClientConsensusState {
+            client_id: client_id.clone(),
+            epoch: consensus_height.revision_number,
+            height: consensus_height.revision_height,
+        };
+        let data = encode(&ConsensusStateData {
+            path: prefixed_path(prefix, path)?,
+            consensus_state: Some(expected_consensus_state.clone().into()),
+        });
+
+        verify_proof(client_state, height, proof, DataType::ConsensusState, data)
+    }
+
+    fn verify_connection_state(
+        &self,
+        _ctx: &dyn ConnectionReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        connection_id: Option<&ConnectionId>,
+        expected_connection_end: &ConnectionEnd,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        let connection_id = connection_id.ok_or(Kind::MissingCounterpartyConnectionId)?;
+        let path = Path::Connections(connection_id.clone());
+        let data = encode(&ConnectionStateData {
+            path: prefixed_path(prefix, path)?,
+            connection: Some(expected_connection_end.clone().into()),
+        });
+
+        verify_proof(client_state, height, proof, DataType::ConnectionState, data)
+    }
+
+    fn verify_channel_state(
+        &self,
+        _ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        expected_channel_end: &ChannelEnd,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        let path = Path::ChannelEnds(port_id.clone(), channel_id.clone());
+        let data = encode(&ChannelStateData {
+            path: prefixed_path(prefix, path)?,
+            channel: Some(expected_channel_end.clone().into()),
+        });
+
+        verify_proof(client_state, height, proof, DataType::ChannelState, data)
+    }
+
+    fn verify_client_full_state(
+        &self,
+        _ctx: &dyn ConnectionReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        _root: &CommitmentRoot,
+        prefix: &CommitmentPrefix,
+        client_id: &ClientId,
+        proof: &CommitmentProofBytes,
+        expected_client_state: &AnyClientState,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        let path = Path::ClientState(client_id.clone());
+        let data = encode(&ClientStateData {
+            path: prefixed_path(prefix, path)?,
+            client_state: Some(expected_client_state.clone().into()),
+        });
+
+        verify_proof(client_state, height, proof, DataType::ClientState, data)
+    }
+
+    fn verify_packet_data(
+        &self,
+        _ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        seq: &Sequence,
+        commitment: Vec<u8>,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        let path = Path::Commitments {
+            port_id: port_id.clone(),
+            channel_id: channel_id.clone(),
+            sequence: *seq,
+        };
+        let data = encode(&PacketCommitmentData {
+            path: prefixed_path(prefix, path)?,
+            commitment,
+        });
+
+        verify_proof(
+            client_state,
+            height,
+            proof,
+            DataType::PacketCommitment,
+            data,
+        )
+    }
+
+    fn verify_packet_acknowledgement(
+        &self,
+        _ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        seq: &Sequence,
+        ack: Vec<u8>,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        let path = Path::Acks {
+            port_id: port_id.clone(),
+            channel_id: channel_id.clone(),
+            sequence: *seq,
+        };
+        let data = encode(&PacketAcknowledgementData {
+            path: prefixed_path(prefix, path)?,
+            acknowledgement: ack,
+        });
+
+        verify_proof(
+            client_state,
+            height,
+            proof,
+            DataType::PacketAcknowledgement,
+            data,
+        )
+    }
+
+    fn verify_next_sequence_recv(
+        &self,
+        _ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        seq: &Sequence,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        let path = Path::SeqRecvs(port_id.clone(), channel_id.clone());
+        let data = encode(&NextSequenceRecvData {
+            path: prefixed_path(prefix, path)?,
+            next_seq_recv: u64::from(*seq),
+        });
+
+        verify_proof(
+            client_state,
+            height,
+            proof,
+            DataType::NextSequenceRecv,
+            data,
+        )
+    }
+
+    fn verify_packet_receipt_absence(
+        &self,
+        _ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        seq: &Sequence,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        let path = Path::Receipts {
+            port_id: port_id.clone(),
+            channel_id: channel_id.clone(),
+            sequence: *seq,
+        };
+        let data = encode(&PacketReceiptAbsenceData {
+            path: prefixed_path(prefix, path)?,
+        });
+
+        verify_proof(
+            client_state,
+            height,
+            proof,
+            DataType::PacketReceiptAbsence,
+            data,
+        )
+    }
+}
+
+/// Verifies that `proof` is a signature of the solo machine over `data`, made at the current
+/// sequence of the client, which must match the proof `height`. Returns the client state at the
+/// next sequence.
+fn verify_proof(
+    client_state: &ClientState,
+    height: Height,
+    proof: &CommitmentProofBytes,
+    data_type: DataType,
+    data: Vec<u8>,
+) -> Result<ClientState, Box<dyn std::error::Error>> {
+    verify_not_frozen(client_state)?;
+
+    if height.revision_number != 0 {
+        return Err(Kind::InvalidRevisionNumber(height.revision_number).into());
+    }
+    if height.revision_height != client_state.sequence {
+        return Err(Kind::MismatchedSequence(client_state.sequence, height.revision_height).into());
+    }
+
+    let proof_bytes: Vec<u8> = proof.clone().into();
+    let proof = TimestampedSignatureData::decode(proof_bytes.as_slice())
+        .map_err(|e| Kind::InvalidProof.context(e))?;
+
+    let signature = SignatureAndData {
+        signature: proof.signature_data,
+        data_type,
+        data,
+        timestamp: proof.timestamp,
+    };
+
+    verify_signature_and_data(client_state, client_state.sequence, &signature)?;
+
+    Ok(ClientState {
+        sequence: client_state.sequence + 1,
+        ..client_state.clone()
+    })
+}
+
+/// Verifies that `signature` was made by the solo machine at the given `sequence`.
+fn verify_signature_and_data(
+    client_state: &ClientState,
+    sequence: u64,
+    signature: &SignatureAndData,
+) -> Result<(), Error> {
+    verify_timestamp(client_state, signature.timestamp)?;
+
+    let sign_bytes = sign_bytes(
+        sequence,
+        signature.timestamp,
+        &client_state.consensus_state.diversifier,
+        signature.data_type,
+        signature.data.clone(),
+    );
+
+    verify_signature(
+        &client_state.consensus_state.public_key,
+        &sign_bytes,
+        &signature.signature,
+    )
+}
+
+/// Verifies a signature, encoded as the Cosmos SDK `SignatureDescriptor.Data`. Only single
+/// signatures are supported.
+fn verify_signature(
+    public_key: &PublicKey,
+    sign_bytes: &[u8],
+    signature_data: &[u8],
+) -> Result<(), Error> {
+    let signature_data = RawSignatureData::decode(signature_data)
+        .map_err(|e| Kind::InvalidSignatureData.context(e))?;
+
+    match signature_data.sum {
+        Some(Sum::Single(single)) => public_key.verify(sign_bytes, &single.signature),
+        _ => Err(Kind::InvalidSignatureData
+            .context("only single signatures are supported")
+            .into()),
+    }
+}
+
+fn verify_not_frozen(client_state: &ClientState) -> Result<(), Error> {
+    if client_state.is_frozen() {
+        return Err(Kind::FrozenClient(client_state.frozen_sequence).into());
+    }
+
+    Ok(())
+}
+
+/// The timestamps signed by the solo machine cannot go back in time.
+fn verify_timestamp(client_state: &ClientState, timestamp: u64) -> Result<(), Error> {
+    let current = client_state.consensus_state.timestamp;
+    if timestamp < current {
+        return Err(Kind::TimestampRegression(timestamp, current).into());
+    }
+
+    Ok(())
+}
+
+/// The bytes signed by the solo machine.
+pub fn sign_bytes(
+    sequence: u64,
+    timestamp: u64,
+    diversifier: &str,
+    data_type: DataType,
+    data: Vec<u8>,
+) -> Vec<u8> {
+    encode(&SignBytes {
+        sequence,
+        timestamp,
+        diversifier: diversifier.to_string(),
+        data_type: data_type as i32,
+        data,
+    })
+}
+
+/// The `path` prefixed by `prefix`, formatted as `/{prefix}/{path}`.
+fn prefixed_path(prefix: &CommitmentPrefix, path: Path) -> Result<Vec<u8>, Error> {
+    let merkle_path =
+        apply_prefix(prefix, vec![path.to_string()]).map_err(|e| Kind::InvalidProof.context(e))?;
+
+    Ok(merkle_path
+        .key_path
+        .iter()
+        .map(|key| format!("/{}", key))
+        .collect::<String>()
+        .into_bytes())
+}
+
+fn encode(message: &impl Message) -> Vec<u8> {
+    let mut buf = Vec::new();
+    message.encode(&mut buf).unwrap();
+    buf
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use ed25519_dalek::{Keypair, Signer};
+
+    use ibc_proto::cosmos::tx::signing::v1beta1::signature_descriptor::{
+        data::{Single, Sum},
+        Data as RawSignatureData,
+    };
+    use ibc_proto::ibc::lightclients::solomachine::v1::{
+        ConnectionStateData, DataType, HeaderData, TimestampedSignatureData,
+    };
+
+    use crate::ics02_client::client_def::ClientDef;
+    use crate::ics03_connection::connection::{
+        ConnectionEnd, Counterparty as ConnectionCounterparty, State as ConnectionState,
+    };
+    use crate::ics03_connection::version::get_compatible_versions;
+    use crate::ics06_solomachine::client_def::{
+        encode, prefixed_path, sign_bytes, SoloMachineClient,
+    };
+    use crate::ics06_solomachine::client_state::test_util::{
+        get_dummy_keypair, get_dummy_public_key, get_dummy_solomachine_client_state,
+        DUMMY_DIVERSIFIER,
+    };
+    use crate::ics06_solomachine::client_state::ClientState;
+    use crate::ics06_solomachine::header::Header;
+    use crate::ics06_solomachine::misbehaviour::{Misbehaviour, SignatureAndData};
+    use crate::ics23_commitment::commitment::{CommitmentPrefix, CommitmentRoot};
+    use crate::ics24_host::identifier::{ClientId, ConnectionId};
+    use crate::ics24_host::Path;
+    use crate::mock::context::MockContext;
+    use crate::Height;
+
+    /// Signs the given data with `keypair`, encoding the signature as a single signature.
+    fn sign(
+        keypair: &Keypair,
+        sequence: u64,
+        timestamp: u64,
+        data_type: DataType,
+        data: Vec<u8>,
+    ) -> Vec<u8> {
+        let sign_bytes = sign_bytes(sequence, timestamp, DUMMY_DIVERSIFIER, data_type, data);
+        let signature = keypair.sign(&sign_bytes);
+
+        encode(&RawSignatureData {
+            sum: Some(Sum::Single(Single {
+                mode: 1,
+                signature: signature.to_bytes().to_vec(),
+            })),
+        })
+    }
+
+    fn header(keypair: &Keypair, new_keypair: &Keypair, sequence: u64, timestamp: u64) -> Header {
+        let data = encode(&HeaderData {
+            new_pub_key: Some(get_dummy_public_key(new_keypair).into()),
+            new_diversifier: DUMMY_DIVERSIFIER.to_string(),
+        });
+
+        Header {
+            sequence,
+            timestamp,
+            signature: sign(keypair, sequence, timestamp, DataType::Header, data),
+            new_public_key: get_dummy_public_key(new_keypair),
+            new_diversifier: DUMMY_DIVERSIFIER.to_string(),
+        }
+    }
+
+    #[test]
+    fn update_rotates_key() {
+        let ctx = MockContext::default();
+        let client = SoloMachineClient;
+        let keypair = get_dummy_keypair(1);
+        let new_keypair = get_dummy_keypair(2);
+        let client_state = get_dummy_solomachine_client_state(&keypair, 1);
+
+        // Headers must be signed with the current key, at the current sequence.
+        let forged = header(&new_keypair, &new_keypair, 1, 20);
+        assert!(client
+            .check_header_and_update_state(&ctx, ClientId::default(), client_state.clone(), forged)
+            .is_err());
+
+        let stale = header(&keypair, &new_keypair, 2, 20);
+        assert!(client
+            .check_header_and_update_state(&ctx, ClientId::default(), client_state.clone(), stale)
+            .is_err());
+
+        let (new_state, consensus_state) = client
+            .check_header_and_update_state(
+                &ctx,
+                ClientId::default(),
+                client_state,
+                header(&keypair, &new_keypair, 1, 20),
+            )
+            .unwrap();
+        assert_eq!(new_state.latest_height(), Height::new(0, 2));
+        assert_eq!(
+            consensus_state.public_key,
+            get_dummy_public_key(&new_keypair)
+        );
+        assert_eq!(consensus_state.timestamp, 20);
+
+        // The previous key cannot sign any further header.
+        let replayed = header(&keypair, &keypair, 2, 30);
+        assert!(client
+            .check_header_and_update_state(&ctx, ClientId::default(), new_state, replayed)
+            .is_err());
+    }
+
+    #[test]
+    fn misbehaviour_freezes_client() {
+        let ctx = MockContext::default();
+        let client = SoloMachineClient;
+        let keypair = get_dummy_keypair(1);
+        let client_state = get_dummy_solomachine_client_state(&keypair, 1);
+
+        let signature_and_data = |data: &[u8]| SignatureAndData {
+            signature: sign(&keypair, 1, 20, DataType::ChannelState, data.to_vec()),
+            data_type: DataType::ChannelState,
+            data: data.to_vec(),
+            timestamp: 20,
+        };
+        let misbehaviour = Misbehaviour {
+            client_id: ClientId::default(),
+            sequence: 1,
+            signature_one: signature_and_data(b"one"),
+            signature_two: signature_and_data(b"two"),
+        };
+
+        // Signing the same data twice is not misbehaviour.
+        let not_misbehaviour = Misbehaviour {
+            signature_two: signature_and_data(b"one"),
+            ..misbehaviour.clone()
+        };
+        assert!(client
+            .check_misbehaviour_and_update_state(
+                &ctx,
+                ClientId::default(),
+                client_state.clone(),
+                not_misbehaviour
+            )
+            .is_err());
+
+        let frozen = client
+            .check_misbehaviour_and_update_state(
+                &ctx,
+                ClientId::default(),
+                client_state,
+                misbehaviour,
+            )
+            .unwrap();
+        assert!(frozen.is_frozen());
+        assert_eq!(frozen.frozen_sequence, 1);
+    }
+
+    #[test]
+    fn verify_connection_state() {
+        let ctx = MockContext::default();
+        let client = SoloMachineClient;
+        let keypair = get_dummy_keypair(1);
+        let client_state = get_dummy_solomachine_client_state(&keypair, 1);
+        let prefix = CommitmentPrefix::from(b"ibc".to_vec());
+        let root = CommitmentRoot::from_bytes(&[]);
+        let connection_id = ConnectionId::default();
+
+        let connection_end = ConnectionEnd::new(
+            ConnectionState::Init,
+            ClientId::default(),
+            ConnectionCounterparty::new(ClientId::default(), None, prefix.clone()),
+            get_compatible_versions(),
+            Duration::from_secs(0),
+        );
+        let data = encode(&ConnectionStateData {
+            path: prefixed_path(&prefix, Path::Connections(connection_id.clone())).unwrap(),
+            connection: Some(connection_end.clone().into()),
+        });
+        let proof = encode(&TimestampedSignatureData {
+            signature_data: sign(&keypair, 1, 20, DataType::ConnectionState, data),
+            timestamp: 20,
+        });
+
+        let verify =
+            |client_state: &ClientState, height: Height, connection_end: &ConnectionEnd| {
+                client.verify_connection_state(
+                    &ctx,
+                    client_state,
+                    height,
+                    &prefix,
+                    &proof.clone().into(),
+                    &root,
+                    Some(&connection_id),
+                    connection_end,
+                )
+            };
+
+        // The proof height must be the sequence of the client, and the proof must match the
+        // expected connection end.
+        assert!(verify(&client_state, Height::new(0, 2), &connection_end).is_err());
+
+        let mut other_connection_end = connection_end.clone();
+        other_connection_end.set_state(ConnectionState::TryOpen);
+        assert!(verify(&client_state, Height::new(0, 1), &other_connection_end).is_err());
+
+        let verified_state = verify(&client_state, Height::new(0, 1), &connection_end).unwrap();
+        assert_eq!(verified_state.sequence, 2);
+
+        // The verification advances the sequence, so the same proof cannot be verified again.
+        assert!(verify(&verified_state, Height::new(0, 1), &connection_end).is_err());
+        assert!(verify(&verified_state, Height::new(0, 2), &connection_end).is_err());
+
+        // Proofs must decode as signature data.
+        let garbage = vec![1, 2, 3];
+        assert!(client
+            .verify_connection_state(
+                &ctx,
+                &client_state,
+                Height::new(0, 1),
+                &prefix,
+                &garbage.into(),
+                &root,
+                Some(&connection_id),
+                &ConnectionEnd::default(),
+            )
+            .is_err());
+    }
+}
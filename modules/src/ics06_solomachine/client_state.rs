@@ -0,0 +1,150 @@
+use std::convert::{TryFrom, TryInto};
+use std::str::FromStr;
+
+use serde::Serialize;
+use tendermint_proto::Protobuf;
+
+use ibc_proto::ibc::lightclients::solomachine::v1::ClientState as RawClientState;
+
+use crate::ics02_client::client_state::AnyClientState;
+use crate::ics02_client::client_type::ClientType;
+use crate::ics06_solomachine::consensus_state::ConsensusState;
+use crate::ics06_solomachine::error::{Error, Kind};
+use crate::ics24_host::identifier::ChainId;
+use crate::Height;
+
+/// The state of a solo machine client. The latest height of a solo machine is its current
+/// sequence, with revision number 0.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ClientState {
+    pub sequence: u64,
+    /// The sequence at which the client was frozen because of misbehaviour, or 0 if not frozen.
+    pub frozen_sequence: u64,
+    pub consensus_state: ConsensusState,
+    pub allow_update_after_proposal: bool,
+}
+
+impl ClientState {
+    pub fn new(
+        sequence: u64,
+        consensus_state: ConsensusState,
+        allow_update_after_proposal: bool,
+    ) -> Result<ClientState, Error> {
+        if sequence == 0 {
+            return Err(Kind::InvalidRawClientState
+                .context("sequence cannot be zero")
+                .into());
+        }
+
+        Ok(Self {
+            sequence,
+            frozen_sequence: 0,
+            consensus_state,
+            allow_update_after_proposal,
+        })
+    }
+
+    pub fn latest_height(&self) -> Height {
+        Height::new(0, self.sequence)
+    }
+
+    pub fn is_frozen(&self) -> bool {
+        self.frozen_sequence != 0
+    }
+
+    /// Returns the client state frozen at `frozen_sequence`, following a misbehaviour.
+    pub fn with_frozen_sequence(self, frozen_sequence: u64) -> Self {
+        ClientState {
+            frozen_sequence,
+            ..self
+        }
+    }
+}
+
+impl crate::ics02_client::client_state::ClientState for ClientState {
+    /// Solo machines do not belong to a chain, they are identified by their diversifier instead.
+    fn chain_id(&self) -> ChainId {
+        ChainId::from_str(&self.consensus_state.diversifier).unwrap_or_default()
+    }
+
+    fn client_type(&self) -> ClientType {
+        ClientType::Solomachine
+    }
+
+    fn latest_height(&self) -> Height {
+        self.latest_height()
+    }
+
+    fn is_frozen(&self) -> bool {
+        self.is_frozen()
+    }
+
+    fn wrap_any(self) -> AnyClientState {
+        AnyClientState::Solomachine(self)
+    }
+}
+
+impl Protobuf<RawClientState> for ClientState {}
+
+impl TryFrom<RawClientState> for ClientState {
+    type Error = Error;
+
+    fn try_from(raw: RawClientState) -> Result<Self, Self::Error> {
+        let consensus_state = raw
+            .consensus_state
+            .ok_or_else(|| Kind::InvalidRawClientState.context("missing consensus state"))?
+            .try_into()?;
+
+        Ok(Self {
+            sequence: raw.sequence,
+            frozen_sequence: raw.frozen_sequence,
+            consensus_state,
+            allow_update_after_proposal: raw.allow_update_after_proposal,
+        })
+    }
+}
+
+impl From<ClientState> for RawClientState {
+    fn from(value: ClientState) -> Self {
+        RawClientState {
+            sequence: value.sequence,
+            frozen_sequence: value.frozen_sequence,
+            consensus_state: Some(value.consensus_state.into()),
+            allow_update_after_proposal: value.allow_update_after_proposal,
+        }
+    }
+}
+
+#[cfg(any(test, feature = "mocks"))]
+pub mod test_util {
+    use ed25519_dalek::{Keypair, PublicKey as Ed25519PublicKey, SecretKey};
+
+    use crate::ics06_solomachine::client_state::ClientState;
+    use crate::ics06_solomachine::consensus_state::ConsensusState;
+    use crate::ics06_solomachine::public_key::PublicKey;
+
+    pub const DUMMY_DIVERSIFIER: &str = "diversifier";
+    pub const DUMMY_TIMESTAMP: u64 = 10;
+
+    /// Returns a deterministic key pair, derived from `seed`.
+    pub fn get_dummy_keypair(seed: u8) -> Keypair {
+        let secret = SecretKey::from_bytes(&[seed; 32]).unwrap();
+        let public = Ed25519PublicKey::from(&secret);
+        Keypair { secret, public }
+    }
+
+    pub fn get_dummy_public_key(keypair: &Keypair) -> PublicKey {
+        PublicKey::Ed25519(keypair.public.to_bytes().to_vec())
+    }
+
+    /// Returns a solo machine client state at the given sequence, whose signing key is `keypair`.
+    pub fn get_dummy_solomachine_client_state(keypair: &Keypair, sequence: u64) -> ClientState {
+        let consensus_state = ConsensusState::new(
+            get_dummy_public_key(keypair),
+            DUMMY_DIVERSIFIER.to_string(),
+            DUMMY_TIMESTAMP,
+        );
+
+        ClientState::new(sequence, consensus_state, false).unwrap()
+    }
+}
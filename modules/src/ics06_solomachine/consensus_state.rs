@@ -0,0 +1,89 @@
+use std::convert::{TryFrom, TryInto};
+
+use serde::Serialize;
+use tendermint_proto::Protobuf;
+
+use ibc_proto::ibc::lightclients::solomachine::v1::ConsensusState as RawConsensusState;
+
+use crate::ics02_client::client_consensus::AnyConsensusState;
+use crate::ics02_client::client_type::ClientType;
+use crate::ics06_solomachine::error::{Error, Kind};
+use crate::ics06_solomachine::public_key::PublicKey;
+use crate::ics23_commitment::commitment::CommitmentRoot;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ConsensusState {
+    /// The key with which the solo machine currently signs.
+    pub public_key: PublicKey,
+    /// Allows the same public key to be used by several solo machine clients.
+    pub diversifier: String,
+    pub timestamp: u64,
+    /// Solo machines do not commit to their state, hence this root is always empty.
+    #[serde(skip)]
+    root: CommitmentRoot,
+}
+
+impl ConsensusState {
+    pub fn new(public_key: PublicKey, diversifier: String, timestamp: u64) -> Self {
+        Self {
+            public_key,
+            diversifier,
+            timestamp,
+            root: CommitmentRoot::from_bytes(&[]),
+        }
+    }
+}
+
+impl crate::ics02_client::client_consensus::ConsensusState for ConsensusState {
+    fn client_type(&self) -> ClientType {
+        ClientType::Solomachine
+    }
+
+    fn root(&self) -> &CommitmentRoot {
+        &self.root
+    }
+
+    fn validate_basic(&self) -> Result<(), Box<dyn std::error::Error>> {
+        if self.diversifier.trim().is_empty() {
+            return Err(Kind::InvalidRawConsensusState
+                .context("empty diversifier")
+                .into());
+        }
+        if self.timestamp == 0 {
+            return Err(Kind::InvalidRawConsensusState
+                .context("timestamp cannot be zero")
+                .into());
+        }
+
+        Ok(())
+    }
+
+    fn wrap_any(self) -> AnyConsensusState {
+        AnyConsensusState::Solomachine(self)
+    }
+}
+
+impl Protobuf<RawConsensusState> for ConsensusState {}
+
+impl TryFrom<RawConsensusState> for ConsensusState {
+    type Error = Error;
+
+    fn try_from(raw: RawConsensusState) -> Result<Self, Self::Error> {
+        let public_key = raw
+            .public_key
+            .ok_or_else(|| Kind::InvalidRawConsensusState.context("missing public key"))?
+            .try_into()?;
+
+        Ok(Self::new(public_key, raw.diversifier, raw.timestamp))
+    }
+}
+
+impl From<ConsensusState> for RawConsensusState {
+    fn from(value: ConsensusState) -> Self {
+        RawConsensusState {
+            public_key: Some(value.public_key.into()),
+            diversifier: value.diversifier,
+            timestamp: value.timestamp,
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use anomaly::{BoxError, Context};
+use thiserror::Error;
+
+pub type Error = anomaly::Error<Kind>;
+
+#[derive(Clone, Debug, Error)]
+pub enum Kind {
+    #[error("invalid raw client state")]
+    InvalidRawClientState,
+
+    #[error("invalid raw client consensus state")]
+    InvalidRawConsensusState,
+
+    #[error("invalid raw header")]
+    InvalidRawHeader,
+
+    #[error("invalid raw misbehaviour")]
+    InvalidRawMisbehaviour,
+
+    #[error("invalid public key")]
+    InvalidPublicKey,
+
+    #[error("unsupported public key type: {0}")]
+    UnsupportedPublicKeyType(String),
+
+    #[error("invalid signature data")]
+    InvalidSignatureData,
+
+    #[error("signature verification failed")]
+    SignatureVerificationFailed,
+
+    #[error("invalid proof")]
+    InvalidProof,
+
+    #[error("client is frozen at sequence {0}")]
+    FrozenClient(u64),
+
+    #[error("client sequence {0} does not match the sequence {1}")]
+    MismatchedSequence(u64, u64),
+
+    #[error("solo machine heights must have revision number 0, got {0}")]
+    InvalidRevisionNumber(u64),
+
+    #[error("timestamp {0} must not be lower than the consensus state timestamp {1}")]
+    TimestampRegression(u64, u64),
+
+    #[error("missing counterparty connection identifier")]
+    MissingCounterpartyConnectionId,
+
+    #[error("invalid misbehaviour")]
+    InvalidMisbehaviour,
+}
+
+impl Kind {
+    pub fn context(self, source: impl Into<BoxError>) -> Context<Self> {
+        Context::new(self, Some(source.into()))
+    }
+}
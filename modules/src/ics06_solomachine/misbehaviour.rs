@@ -0,0 +1,123 @@
+use std::convert::{TryFrom, TryInto};
+
+use tendermint_proto::Protobuf;
+
+use ibc_proto::ibc::lightclients::solomachine::v1::{
+    DataType, Misbehaviour as RawMisbehaviour, SignatureAndData as RawSignatureAndData,
+};
+
+use crate::ics02_client::misbehaviour::AnyMisbehaviour;
+use crate::ics06_solomachine::error::{Error, Kind};
+use crate::ics24_host::identifier::ClientId;
+use crate::Height;
+
+/// Evidence that a solo machine signed two different messages at the same sequence.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Misbehaviour {
+    pub client_id: ClientId,
+    pub sequence: u64,
+    pub signature_one: SignatureAndData,
+    pub signature_two: SignatureAndData,
+}
+
+/// A signature of the solo machine, along with the data it signed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SignatureAndData {
+    pub signature: Vec<u8>,
+    pub data_type: DataType,
+    pub data: Vec<u8>,
+    pub timestamp: u64,
+}
+
+impl crate::ics02_client::misbehaviour::Misbehaviour for Misbehaviour {
+    fn client_id(&self) -> &ClientId {
+        &self.client_id
+    }
+
+    fn height(&self) -> Height {
+        Height::new(0, self.sequence)
+    }
+
+    fn wrap_any(self) -> AnyMisbehaviour {
+        AnyMisbehaviour::Solomachine(self)
+    }
+}
+
+impl Protobuf<RawMisbehaviour> for Misbehaviour {}
+
+impl TryFrom<RawMisbehaviour> for Misbehaviour {
+    type Error = Error;
+
+    fn try_from(raw: RawMisbehaviour) -> Result<Self, Self::Error> {
+        if raw.sequence == 0 {
+            return Err(Kind::InvalidRawMisbehaviour
+                .context("sequence cannot be zero")
+                .into());
+        }
+
+        Ok(Self {
+            client_id: raw
+                .client_id
+                .parse()
+                .map_err(|e| Kind::InvalidRawMisbehaviour.context(e))?,
+            sequence: raw.sequence,
+            signature_one: raw
+                .signature_one
+                .ok_or_else(|| Kind::InvalidRawMisbehaviour.context("missing signature one"))?
+                .try_into()?,
+            signature_two: raw
+                .signature_two
+                .ok_or_else(|| Kind::InvalidRawMisbehaviour.context("missing signature two"))?
+                .try_into()?,
+        })
+    }
+}
+
+impl From<Misbehaviour> for RawMisbehaviour {
+    fn from(value: Misbehaviour) -> Self {
+        RawMisbehaviour {
+            client_id: value.client_id.to_string(),
+            sequence: value.sequence,
+            signature_one: Some(value.signature_one.into()),
+            signature_two: Some(value.signature_two.into()),
+        }
+    }
+}
+
+impl TryFrom<RawSignatureAndData> for SignatureAndData {
+    type Error = Error;
+
+    fn try_from(raw: RawSignatureAndData) -> Result<Self, Self::Error> {
+        if raw.signature.is_empty() || raw.data.is_empty() || raw.timestamp == 0 {
+            return Err(Kind::InvalidRawMisbehaviour
+                .context("signature, data and timestamp cannot be empty")
+                .into());
+        }
+
+        Ok(Self {
+            signature: raw.signature,
+            data_type: DataType::from_i32(raw.data_type).ok_or_else(|| {
+                Kind::InvalidRawMisbehaviour.context(format!("unknown data type {}", raw.data_type))
+            })?,
+            data: raw.data,
+            timestamp: raw.timestamp,
+        })
+    }
+}
+
+impl From<SignatureAndData> for RawSignatureAndData {
+    fn from(value: SignatureAndData) -> Self {
+        RawSignatureAndData {
+            signature: value.signature,
+            data_type: value.data_type as i32,
+            data: value.data,
+            timestamp: value.timestamp,
+        }
+    }
+}
+
+impl std::fmt::Display for Misbehaviour {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "{:?} sequence: {}", self.client_id, self.sequence)
+    }
+}
@@ -0,0 +1,80 @@
+use std::convert::TryFrom;
+
+use ed25519_dalek::Verifier as _;
+use k256::ecdsa::signature::Verifier as _;
+use prost_types::Any;
+use serde_derive::{Deserialize, Serialize};
+
+use crate::ics06_solomachine::error::{Error, Kind};
+
+pub const SECP256K1_PUBLIC_KEY_TYPE_URL: &str = "/cosmos.crypto.secp256k1.PubKey";
+pub const ED25519_PUBLIC_KEY_TYPE_URL: &str = "/cosmos.crypto.ed25519.PubKey";
+
+/// The public key of a solo machine, with which it signs headers and proofs. Keys are kept in
+/// their raw encoding (compressed SEC1 for secp256k1), and decoded upon verification.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum PublicKey {
+    Secp256k1(Vec<u8>),
+    Ed25519(Vec<u8>),
+}
+
+impl PublicKey {
+    /// Verifies that `signature` is a signature of `message` under this public key.
+    pub fn verify(&self, message: &[u8], signature: &[u8]) -> Result<(), Error> {
+        match self {
+            Self::Secp256k1(key) => {
+                let point = k256::EncodedPoint::from_bytes(key)
+                    .map_err(|_| Kind::InvalidPublicKey.context("invalid secp256k1 key"))?;
+                let key = k256::ecdsa::VerifyingKey::from_encoded_point(&point)
+                    .map_err(|_| Kind::InvalidPublicKey.context("invalid secp256k1 key"))?;
+                let signature = k256::ecdsa::Signature::try_from(signature)
+                    .map_err(|e| Kind::InvalidSignatureData.context(e.to_string()))?;
+
+                key.verify(message, &signature)
+                    .map_err(|_| Kind::SignatureVerificationFailed.into())
+            }
+            Self::Ed25519(key) => {
+                let key = ed25519_dalek::PublicKey::from_bytes(key)
+                    .map_err(|e| Kind::InvalidPublicKey.context(e.to_string()))?;
+                let signature = ed25519_dalek::Signature::try_from(signature)
+                    .map_err(|e| Kind::InvalidSignatureData.context(e.to_string()))?;
+
+                key.verify(message, &signature)
+                    .map_err(|_| Kind::SignatureVerificationFailed.into())
+            }
+        }
+    }
+}
+
+impl TryFrom<Any> for PublicKey {
+    type Error = Error;
+
+    fn try_from(raw: Any) -> Result<Self, Self::Error> {
+        // Both key types are encoded as a message with a single `bytes` field.
+        let key: Vec<u8> = prost::Message::decode(raw.value.as_slice())
+            .map_err(|e| Kind::InvalidPublicKey.context(e))?;
+
+        match raw.type_url.as_str() {
+            SECP256K1_PUBLIC_KEY_TYPE_URL => Ok(Self::Secp256k1(key)),
+            ED25519_PUBLIC_KEY_TYPE_URL => Ok(Self::Ed25519(key)),
+            _ => Err(Kind::UnsupportedPublicKeyType(raw.type_url).into()),
+        }
+    }
+}
+
+impl From<PublicKey> for Any {
+    fn from(value: PublicKey) -> Self {
+        let (type_url, key) = match value {
+            PublicKey::Secp256k1(key) => (SECP256K1_PUBLIC_KEY_TYPE_URL, key),
+            PublicKey::Ed25519(key) => (ED25519_PUBLIC_KEY_TYPE_URL, key),
+        };
+
+        let mut buf = Vec::new();
+        prost::Message::encode(&key, &mut buf).unwrap();
+
+        Any {
+            type_url: type_url.to_string(),
+            value: buf,
+        }
+    }
+}
@@ -1,15 +1,33 @@
+use std::convert::TryFrom;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use tendermint_light_client::operations::{
+    CommitValidator, ProdCommitValidator, ProdVotingPowerCalculator, VotingPowerCalculator,
+};
+use tendermint_proto::Protobuf;
+
+use ibc_proto::ibc::core::commitment::v1::MerkleProof as RawMerkleProof;
+
+use crate::downcast;
 use crate::ics02_client::client_consensus::AnyConsensusState;
 use crate::ics02_client::client_def::ClientDef;
 use crate::ics02_client::client_state::AnyClientState;
+use crate::ics02_client::context::ClientReader;
 use crate::ics03_connection::connection::ConnectionEnd;
+use crate::ics03_connection::context::ConnectionReader;
 use crate::ics04_channel::channel::ChannelEnd;
+use crate::ics04_channel::context::ChannelReader;
 use crate::ics04_channel::packet::Sequence;
 use crate::ics07_tendermint::client_state::ClientState;
 use crate::ics07_tendermint::consensus_state::ConsensusState;
+use crate::ics07_tendermint::error::{Error, Kind};
 use crate::ics07_tendermint::header::Header;
+use crate::ics07_tendermint::misbehaviour::Misbehaviour;
 use crate::ics23_commitment::commitment::{CommitmentPrefix, CommitmentProofBytes, CommitmentRoot};
+use crate::ics23_commitment::merkle::{apply_prefix, cosmos_specs, MerkleProof};
 use crate::ics24_host::identifier::ConnectionId;
 use crate::ics24_host::identifier::{ChannelId, ClientId, PortId};
+use crate::ics24_host::Path;
 use crate::Height;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -19,9 +37,12 @@ impl ClientDef for TendermintClient {
     type Header = Header;
     type ClientState = ClientState;
     type ConsensusState = ConsensusState;
+    type Misbehaviour = Misbehaviour;
 
     fn check_header_and_update_state(
         &self,
+        ctx: &dyn ClientReader,
+        client_id: ClientId,
         client_state: Self::ClientState,
         header: Self::Header,
     ) -> Result<(Self::ClientState, Self::ConsensusState), Box<dyn std::error::Error>> {
@@ -32,7 +53,13 @@ impl ClientDef for TendermintClient {
             );
         }
 
-        // TODO: Additional verifications should be implemented here.
+        let trusted_consensus_state = trusted_consensus_state(ctx, &client_id, &header)?;
+        verify_header(
+            &client_state,
+            &trusted_consensus_state,
+            &header,
+            host_time(ctx),
+        )?;
 
         Ok((
             client_state.with_header(header.clone()),
@@ -40,104 +67,492 @@ impl ClientDef for TendermintClient {
         ))
     }
 
+    fn check_misbehaviour_and_update_state(
+        &self,
+        ctx: &dyn ClientReader,
+        client_id: ClientId,
+        client_state: Self::ClientState,
+        misbehaviour: Self::Misbehaviour,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        let Misbehaviour {
+            header1, header2, ..
+        } = misbehaviour;
+
+        // Either a fork (same height, different blocks) or a violation of BFT time monotonicity.
+        if header1.compatible_with(&header2) {
+            return Err(Kind::InvalidMisbehaviour
+                .context("headers are not conflicting")
+                .into());
+        }
+
+        // Both headers must be valid updates from the (possibly distinct) trusted states.
+        let now = host_time(ctx);
+        for header in &[&header1, &header2] {
+            let trusted_consensus_state = trusted_consensus_state(ctx, &client_id, header)?;
+            verify_header(&client_state, &trusted_consensus_state, header, now)?;
+        }
+
+        Ok(client_state.with_frozen_height(header1.height()))
+    }
+
     fn verify_client_consensus_state(
         &self,
-        _client_state: &Self::ClientState,
-        _height: Height,
-        _prefix: &CommitmentPrefix,
-        _proof: &CommitmentProofBytes,
-        _client_id: &ClientId,
-        _consensus_height: Height,
-        _expected_consensus_state: &AnyConsensusState,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        todo!()
+        _ctx: &dyn ConnectionReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        client_id: &ClientId,
+        consensus_height: Height,
+        expected_consensus_state: &AnyConsensusState,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        client_state.verify_height(height)?;
+
+        let path = Path::ClientConsensusState {
+            client_id: client_id.clone(),
+            epoch: consensus_height.revision_number,
+            height: consensus_height.revision_height,
+        };
+        let value = expected_consensus_state.encode_vec()?;
+
+        verify_membership(prefix, proof, root, path, value)?;
+
+        Ok(client_state.clone())
     }
 
     fn verify_connection_state(
         &self,
-        _client_state: &Self::ClientState,
-        _height: Height,
-        _prefix: &CommitmentPrefix,
-        _proof: &CommitmentProofBytes,
-        _connection_id: Option<&ConnectionId>,
-        _expected_connection_end: &ConnectionEnd,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        todo!()
+        _ctx: &dyn ConnectionReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        connection_id: Option<&ConnectionId>,
+        expected_connection_end: &ConnectionEnd,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        client_state.verify_height(height)?;
+
+        let connection_id = connection_id.ok_or(Kind::MissingCounterpartyConnectionId)?;
+        let path = Path::Connections(connection_id.clone());
+        let value = expected_connection_end.encode_vec()?;
+
+        verify_membership(prefix, proof, root, path, value)?;
+
+        Ok(client_state.clone())
     }
 
     fn verify_channel_state(
         &self,
-        _client_state: &Self::ClientState,
-        _height: Height,
-        _prefix: &CommitmentPrefix,
-        _proof: &CommitmentProofBytes,
-        _port_id: &PortId,
-        _channel_id: &ChannelId,
-        _expected_channel_end: &ChannelEnd,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        todo!()
+        _ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        expected_channel_end: &ChannelEnd,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        client_state.verify_height(height)?;
+
+        let path = Path::ChannelEnds(port_id.clone(), channel_id.clone());
+        let value = expected_channel_end.encode_vec()?;
+
+        verify_membership(prefix, proof, root, path, value)?;
+
+        Ok(client_state.clone())
     }
 
     fn verify_client_full_state(
         &self,
-        _client_state: &Self::ClientState,
-        _height: Height,
-        _root: &CommitmentRoot,
-        _prefix: &CommitmentPrefix,
-        _client_id: &ClientId,
-        _proof: &CommitmentProofBytes,
-        _expected_client_state: &AnyClientState,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        unimplemented!()
+        _ctx: &dyn ConnectionReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        root: &CommitmentRoot,
+        prefix: &CommitmentPrefix,
+        client_id: &ClientId,
+        proof: &CommitmentProofBytes,
+        expected_client_state: &AnyClientState,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        client_state.verify_height(height)?;
+
+        let path = Path::ClientState(client_id.clone());
+        let value = expected_client_state.encode_vec()?;
+
+        verify_membership(prefix, proof, root, path, value)?;
+
+        Ok(client_state.clone())
     }
 
     fn verify_packet_data(
         &self,
-        _client_state: &Self::ClientState,
-        _height: Height,
-        _proof: &CommitmentProofBytes,
-        _port_id: &PortId,
-        _channel_id: &ChannelId,
-        _seq: &Sequence,
-        _data: String,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        todo!()
+        _ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        seq: &Sequence,
+        commitment: Vec<u8>,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        client_state.verify_height(height)?;
+
+        let path = Path::Commitments {
+            port_id: port_id.clone(),
+            channel_id: channel_id.clone(),
+            sequence: *seq,
+        };
+
+        verify_membership(prefix, proof, root, path, commitment)?;
+
+        Ok(client_state.clone())
     }
 
     fn verify_packet_acknowledgement(
         &self,
-        _client_state: &Self::ClientState,
-        _height: Height,
-        _proof: &CommitmentProofBytes,
-        _port_id: &PortId,
-        _channel_id: &ChannelId,
-        _seq: &Sequence,
-        _data: Vec<u8>,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        todo!()
+        ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        seq: &Sequence,
+        ack: Vec<u8>,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        client_state.verify_height(height)?;
+
+        let path = Path::Acks {
+            port_id: port_id.clone(),
+            channel_id: channel_id.clone(),
+            sequence: *seq,
+        };
+
+        verify_membership(prefix, proof, root, path, ctx.ack_commitment(&ack))?;
+
+        Ok(client_state.clone())
     }
 
     fn verify_next_sequence_recv(
         &self,
-        _client_state: &Self::ClientState,
-        _height: Height,
-        _proof: &CommitmentProofBytes,
-        _port_id: &PortId,
-        _channel_id: &ChannelId,
-        _seq: &Sequence,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        todo!()
+        _ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        seq: &Sequence,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        client_state.verify_height(height)?;
+
+        let path = Path::SeqRecvs(port_id.clone(), channel_id.clone());
+        // The sequence is stored as a big-endian encoded `u64`.
+        let value = u64::from(*seq).to_be_bytes().to_vec();
+
+        verify_membership(prefix, proof, root, path, value)?;
+
+        Ok(client_state.clone())
     }
 
     fn verify_packet_receipt_absence(
         &self,
-        _client_state: &Self::ClientState,
-        _height: Height,
-        _proof: &CommitmentProofBytes,
-        _port_id: &PortId,
-        _channel_id: &ChannelId,
-        _seq: &Sequence,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        todo!()
+        _ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        seq: &Sequence,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        client_state.verify_height(height)?;
+
+        let path = Path::Receipts {
+            port_id: port_id.clone(),
+            channel_id: channel_id.clone(),
+            sequence: *seq,
+        };
+
+        verify_non_membership(prefix, proof, root, path)?;
+
+        Ok(client_state.clone())
+    }
+}
+
+/// Fetches the consensus state which `header` claims to build upon.
+fn trusted_consensus_state(
+    ctx: &dyn ClientReader,
+    client_id: &ClientId,
+    header: &Header,
+) -> Result<ConsensusState, Error> {
+    ctx.consensus_state(client_id, header.trusted_height)
+        .and_then(|cs| downcast!(cs => AnyConsensusState::Tendermint))
+        .ok_or_else(|| Kind::MissingTrustedConsensusState(header.trusted_height).into())
+}
+
+/// The current time of the host chain.
+fn host_time(ctx: &dyn ClientReader) -> SystemTime {
+    UNIX_EPOCH + Duration::from_nanos(ctx.host_timestamp())
+}
+
+/// Light client verification of an untrusted `header` against the `trusted` consensus state,
+/// following the Tendermint light client specification. Adjacent headers must be signed by more
+/// than 2/3 of the validator set announced by the trusted state; non-adjacent headers (skipping
+/// verification) must additionally be signed by at least `client_state.trust_level` of the
+/// trusted validator set.
+fn verify_header(
+    client_state: &ClientState,
+    trusted: &ConsensusState,
+    header: &Header,
+    now: SystemTime,
+) -> Result<(), Error> {
+    let untrusted = &header.signed_header;
+
+    // Basic checks of the header against the client and the trusted height.
+    if header.height().revision_number != client_state.latest_height.revision_number {
+        return Err(Kind::MismatchedRevisions(
+            client_state.latest_height.revision_number,
+            header.height().revision_number,
+        )
+        .into());
+    }
+    if untrusted.header.chain_id.as_str() != client_state.chain_id.as_str() {
+        return Err(Kind::InvalidHeader
+            .context(format!(
+                "header chain id {} does not match the client chain id {}",
+                untrusted.header.chain_id, client_state.chain_id
+            ))
+            .into());
+    }
+    if header.trusted_height >= header.height() {
+        return Err(Kind::InvalidHeaderHeight(header.height(), header.trusted_height).into());
+    }
+
+    // The header must be self-consistent: the commit is for this header, and the validator set
+    // provided is the one which the header designates.
+    if untrusted.commit.height != untrusted.header.height {
+        return Err(Kind::InvalidHeader
+            .context("commit height does not match the header height")
+            .into());
+    }
+    if untrusted.commit.block_id.hash != untrusted.header.hash() {
+        return Err(Kind::InvalidHeader
+            .context("commit does not match the header hash")
+            .into());
+    }
+    if header.validator_set.hash() != untrusted.header.validators_hash {
+        return Err(Kind::InvalidValidatorSet
+            .context("header validator set does not match the header validators hash")
+            .into());
+    }
+    if header.trusted_validator_set.hash() != trusted.next_validators_hash {
+        return Err(Kind::InvalidValidatorSet
+            .context("trusted validator set does not match the trusted next validators hash")
+            .into());
+    }
+
+    // Time checks: the trusted state must be within the trusting period, the header must not be
+    // from the future (modulo clock drift), and BFT time must be monotonic.
+    let trusted_time = SystemTime::from(trusted.timestamp);
+    let header_time = SystemTime::from(untrusted.header.time);
+    let trusting_period_end = trusted_time
+        .checked_add(client_state.trusting_period)
+        .ok_or_else(|| Kind::TimestampOverflow.context("trusting period end"))?;
+    if trusting_period_end <= now {
+        return Err(Kind::TrustingPeriodExpired(trusted.timestamp).into());
+    }
+    let drifted_now = now
+        .checked_add(client_state.max_clock_drift)
+        .ok_or_else(|| Kind::TimestampOverflow.context("maximum clock drift"))?;
+    if header_time >= drifted_now {
+        return Err(Kind::HeaderFromFuture(untrusted.header.time).into());
+    }
+    if header_time <= trusted_time {
+        return Err(Kind::NonMonotonicBftTime(untrusted.header.time, trusted.timestamp).into());
+    }
+
+    ProdCommitValidator::default()
+        .validate(untrusted, &header.validator_set)
+        .map_err(|e| Kind::InvalidCommit.context(e.to_string()))?;
+
+    let calculator = ProdVotingPowerCalculator::default();
+    if header.height().revision_height == header.trusted_height.revision_height + 1 {
+        // Adjacent header: the validator set must be the one announced by the trusted state.
+        if untrusted.header.validators_hash != trusted.next_validators_hash {
+            return Err(Kind::InvalidValidatorSet
+                .context("validators hash does not match the trusted next validators hash")
+                .into());
+        }
+    } else {
+        // Non-adjacent header: enough of the trusted validators must have signed the header.
+        calculator
+            .check_enough_trust(
+                untrusted,
+                &header.trusted_validator_set,
+                client_state.trust_level,
+            )
+            .map_err(|e| Kind::InsufficientVotingPower.context(e.to_string()))?;
+    }
+
+    // In both cases, more than 2/3 of the header's own validator set must have signed it.
+    calculator
+        .check_signers_overlap(untrusted, &header.validator_set)
+        .map_err(|e| Kind::InsufficientVotingPower.context(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Verifies that `value` is committed at the given `path` (prefixed by `prefix`) under `root`.
+fn verify_membership(
+    prefix: &CommitmentPrefix,
+    proof: &CommitmentProofBytes,
+    root: &CommitmentRoot,
+    path: Path,
+    value: Vec<u8>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let merkle_path = apply_prefix(prefix, vec![path.to_string()])?;
+    let merkle_proof: MerkleProof = RawMerkleProof::try_from(proof.clone())?.into();
+
+    merkle_proof.verify_membership(&cosmos_specs(), root, merkle_path, value)?;
+    Ok(())
+}
+
+/// Verifies that nothing is committed at the given `path` (prefixed by `prefix`) under `root`.
+fn verify_non_membership(
+    prefix: &CommitmentPrefix,
+    proof: &CommitmentProofBytes,
+    root: &CommitmentRoot,
+    path: Path,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let merkle_path = apply_prefix(prefix, vec![path.to_string()])?;
+    let merkle_proof: MerkleProof = RawMerkleProof::try_from(proof.clone())?.into();
+
+    merkle_proof.verify_non_membership(&cosmos_specs(), root, merkle_path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::downcast;
+    use crate::ics02_client::client_def::ClientDef;
+    use crate::ics02_client::client_state::AnyClientState;
+    use crate::ics02_client::client_type::ClientType;
+    use crate::ics02_client::context::ClientReader;
+    use crate::ics07_tendermint::client_def::TendermintClient;
+    use crate::ics07_tendermint::client_state::ClientState;
+    use crate::ics07_tendermint::header::Header;
+    use crate::ics24_host::identifier::{ChainId, ClientId};
+    use crate::mock::context::MockContext;
+    use crate::mock::host::{HostBlock, HostType};
+    use crate::Height;
+
+    fn chain_id() -> ChainId {
+        ChainId::new("mockgaia".to_string(), 1)
+    }
+
+    /// A context hosting a Tendermint client whose latest (trusted) height is `1-20`.
+    fn context(timestamp: Duration) -> (MockContext, ClientId, ClientState) {
+        let client_id = ClientId::new(ClientType::Tendermint, 0).unwrap();
+        let ctx = MockContext::new(chain_id(), HostType::Mock, 5, Height::new(1, 5))
+            .with_timestamp(timestamp.as_nanos() as u64)
+            .with_client_parametrized(
+                &client_id,
+                Height::new(1, 20),
+                Some(ClientType::Tendermint),
+                None,
+            );
+        let client_state =
+            downcast!(ctx.client_state(&client_id).unwrap() => AnyClientState::Tendermint).unwrap();
+
+        (ctx, client_id, client_state)
+    }
+
+    fn header(height: u64) -> Header {
+        Header {
+            trusted_height: Height::new(1, 20),
+            ..Header::from(HostBlock::generate_tm_block(chain_id(), height))
+        }
+    }
+
+    #[test]
+    fn accepts_valid_headers() {
+        let (ctx, client_id, client_state) = context(Duration::from_secs(30));
+
+        // Adjacent and non-adjacent updates.
+        for height in &[21, 25] {
+            let (new_state, _) = TendermintClient
+                .check_header_and_update_state(
+                    &ctx,
+                    client_id.clone(),
+                    client_state.clone(),
+                    header(*height),
+                )
+                .unwrap();
+            assert_eq!(new_state.latest_height, Height::new(1, *height));
+        }
+    }
+
+    #[test]
+    fn rejects_forged_header() {
+        let (ctx, client_id, client_state) = context(Duration::from_secs(30));
+
+        // Altering the header without re-signing it breaks the link with the commit.
+        let mut forged = header(21);
+        forged.signed_header.header.time = header(22).signed_header.header.time;
+
+        let res =
+            TendermintClient.check_header_and_update_state(&ctx, client_id, client_state, forged);
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn rejects_header_outside_trusting_period() {
+        let (_, _, client_state) = context(Duration::from_secs(30));
+        let expiry = client_state.trusting_period + Duration::from_secs(30);
+        let (ctx, client_id, client_state) = context(expiry);
+
+        let res = TendermintClient.check_header_and_update_state(
+            &ctx,
+            client_id,
+            client_state,
+            header(21),
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn rejects_overflowing_periods() {
+        let (ctx, client_id, client_state) = context(Duration::from_secs(30));
+
+        let periods = vec![
+            ClientState {
+                trusting_period: Duration::from_secs(u64::MAX),
+                ..client_state.clone()
+            },
+            ClientState {
+                max_clock_drift: Duration::from_secs(u64::MAX),
+                ..client_state
+            },
+        ];
+
+        for client_state in periods {
+            let res = TendermintClient.check_header_and_update_state(
+                &ctx,
+                client_id.clone(),
+                client_state,
+                header(21),
+            );
+            assert!(res.is_err());
+        }
     }
 }
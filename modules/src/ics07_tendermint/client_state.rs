@@ -110,6 +110,27 @@ impl ClientState {
         }
     }
 
+    /// Returns the client state frozen at `frozen_height`, following a misbehaviour.
+    pub fn with_frozen_height(self, frozen_height: Height) -> Self {
+        ClientState {
+            frozen_height,
+            ..self
+        }
+    }
+
+    /// Verify that the client is at a sufficient height and unfrozen at the given height.
+    pub fn verify_height(&self, height: Height) -> Result<(), Error> {
+        if self.latest_height < height {
+            return Err(Kind::InsufficientHeight(self.latest_height, height).into());
+        }
+
+        if !self.frozen_height.is_zero() && self.frozen_height <= height {
+            return Err(Kind::FrozenClient(self.frozen_height).into());
+        }
+
+        Ok(())
+    }
+
     /// Helper function to verify the upgrade client procedure.
     /// Resets all fields except the blockchain-specific ones.
     pub fn zero_custom_fields(mut client_state: Self) -> Self {
@@ -1,7 +1,9 @@
 use anomaly::{BoxError, Context};
+use tendermint::Time;
 use thiserror::Error;
 
 use crate::ics24_host::error::ValidationKind;
+use crate::Height;
 
 pub type Error = anomaly::Error<Kind>;
 
@@ -39,6 +41,48 @@ pub enum Kind {
 
     #[error("invalid raw misbehaviour")]
     InvalidRawMisbehaviour,
+
+    #[error("client latest height {0} is lower than the proof height {1}")]
+    InsufficientHeight(Height, Height),
+
+    #[error("client is frozen at height {0}")]
+    FrozenClient(Height),
+
+    #[error("missing counterparty connection identifier")]
+    MissingCounterpartyConnectionId,
+
+    #[error("missing trusted consensus state at height {0}")]
+    MissingTrustedConsensusState(Height),
+
+    #[error("client revision number {0} does not match the header revision number {1}")]
+    MismatchedRevisions(u64, u64),
+
+    #[error("header height {0} must be greater than the trusted height {1}")]
+    InvalidHeaderHeight(Height, Height),
+
+    #[error("invalid validator set")]
+    InvalidValidatorSet,
+
+    #[error("trusted consensus state at time {0:?} is outside of the trusting period")]
+    TrustingPeriodExpired(Time),
+
+    #[error("header time {0:?} is too far in the future")]
+    HeaderFromFuture(Time),
+
+    #[error("header time {0:?} must be greater than the trusted consensus state time {1:?}")]
+    NonMonotonicBftTime(Time, Time),
+
+    #[error("timestamp overflow")]
+    TimestampOverflow,
+
+    #[error("invalid commit")]
+    InvalidCommit,
+
+    #[error("insufficient voting power in the header commit")]
+    InsufficientVotingPower,
+
+    #[error("invalid misbehaviour")]
+    InvalidMisbehaviour,
 }
 
 impl Kind {
@@ -0,0 +1,596 @@
+use crate::ics02_client::client_consensus::AnyConsensusState;
+use crate::ics02_client::client_def::ClientDef;
+use crate::ics02_client::client_state::{AnyClientState, ClientState as _};
+use crate::ics02_client::context::ClientReader;
+use crate::ics02_client::misbehaviour::AnyMisbehaviour;
+use crate::ics03_connection::connection::ConnectionEnd;
+use crate::ics03_connection::context::ConnectionReader;
+use crate::ics04_channel::channel::ChannelEnd;
+use crate::ics04_channel::context::ChannelReader;
+use crate::ics04_channel::packet::Sequence;
+use crate::ics09_localhost::client_state::ClientState;
+use crate::ics09_localhost::consensus_state::ConsensusState;
+use crate::ics09_localhost::error::{Error, Kind};
+use crate::ics09_localhost::header::Header;
+use crate::ics23_commitment::commitment::{CommitmentPrefix, CommitmentProofBytes, CommitmentRoot};
+use crate::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+use crate::Height;
+
+/// The loopback client, which allows two modules of the same chain to communicate over IBC. It
+/// ignores proofs and commitment roots altogether: the verification functions instead compare the
+/// expected values with those found in the store of the host chain, accessible via `ctx`.
+///
+/// Note that the host store is read at its current height, not at the proof height; the proof
+/// height only has to be reached by the client.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LocalhostClient;
+
+impl ClientDef for LocalhostClient {
+    type Header = Header;
+    type ClientState = ClientState;
+    type ConsensusState = ConsensusState;
+    type Misbehaviour = AnyMisbehaviour;
+
+    fn check_header_and_update_state(
+        &self,
+        ctx: &dyn ClientReader,
+        _client_id: ClientId,
+        client_state: Self::ClientState,
+        header: Self::Header,
+    ) -> Result<(Self::ClientState, Self::ConsensusState), Box<dyn std::error::Error>> {
+        if header.height <= client_state.latest_height() {
+            return Err(Kind::LowHeaderHeight(header.height, client_state.latest_height()).into());
+        }
+
+        let host_height = ctx.host_height();
+        if header.height > host_height {
+            return Err(Kind::HeaderInTheFuture(header.height, host_height).into());
+        }
+
+        Ok((client_state.with_header(header), ConsensusState::new()))
+    }
+
+    fn check_misbehaviour_and_update_state(
+        &self,
+        _ctx: &dyn ClientReader,
+        _client_id: ClientId,
+        _client_state: Self::ClientState,
+        _misbehaviour: Self::Misbehaviour,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        Err(Kind::MisbehaviourNotSupported.into())
+    }
+
+    /// The expected consensus state is that of the host chain, which the counterparty loopback
+    /// client does not store. It is thus only checked that the counterparty client was updated to
+    /// `consensus_height`.
+    fn verify_client_consensus_state(
+        &self,
+        ctx: &dyn ConnectionReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        _prefix: &CommitmentPrefix,
+        _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        client_id: &ClientId,
+        consensus_height: Height,
+        _expected_consensus_state: &AnyConsensusState,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        verify_height(client_state, height)?;
+
+        ctx.client_consensus_state(client_id, consensus_height)
+            .ok_or_else(|| {
+                Kind::MissingState(format!(
+                    "consensus state of client {} at height {}",
+                    client_id, consensus_height
+                ))
+            })?;
+
+        Ok(client_state.clone())
+    }
+
+    fn verify_connection_state(
+        &self,
+        ctx: &dyn ConnectionReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        _prefix: &CommitmentPrefix,
+        _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        connection_id: Option<&ConnectionId>,
+        expected_connection_end: &ConnectionEnd,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        verify_height(client_state, height)?;
+
+        let connection_id = connection_id
+            .ok_or_else(|| Kind::MissingState("counterparty connection identifier".into()))?;
+        let what = format!("connection end {}", connection_id);
+
+        let connection_end = ctx
+            .connection_end(connection_id)
+            .ok_or_else(|| Kind::MissingState(what.clone()))?;
+
+        verify_equal(&connection_end, expected_connection_end, what)?;
+
+        Ok(client_state.clone())
+    }
+
+    fn verify_channel_state(
+        &self,
+        ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        _prefix: &CommitmentPrefix,
+        _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        expected_channel_end: &ChannelEnd,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        verify_height(client_state, height)?;
+
+        let what = format!("channel end {}/{}", port_id, channel_id);
+
+        let channel_end = ctx
+            .channel_end(&(port_id.clone(), channel_id.clone()))
+            .ok_or_else(|| Kind::MissingState(what.clone()))?;
+
+        verify_equal(&channel_end, expected_channel_end, what)?;
+
+        Ok(client_state.clone())
+    }
+
+    /// Client states change with every update, hence only the type and the chain of the
+    /// counterparty client are checked.
+    fn verify_client_full_state(
+        &self,
+        ctx: &dyn ConnectionReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        _root: &CommitmentRoot,
+        _prefix: &CommitmentPrefix,
+        client_id: &ClientId,
+        _proof: &CommitmentProofBytes,
+        expected_client_state: &AnyClientState,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        verify_height(client_state, height)?;
+
+        let what = format!("client state of client {}", client_id);
+
+        let counterparty_client_state = ctx
+            .client_state(client_id)
+            .ok_or_else(|| Kind::MissingState(what.clone()))?;
+
+        verify_equal(
+            &(
+                counterparty_client_state.client_type(),
+                counterparty_client_state.chain_id(),
+            ),
+            &(
+                expected_client_state.client_type(),
+                expected_client_state.chain_id(),
+            ),
+            what,
+        )?;
+
+        Ok(client_state.clone())
+    }
+
+    fn verify_packet_data(
+        &self,
+        ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        _prefix: &CommitmentPrefix,
+        _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        seq: &Sequence,
+        commitment: Vec<u8>,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        verify_height(client_state, height)?;
+
+        let what = format!("packet commitment {}/{}/{}", port_id, channel_id, seq);
+
+        let stored_commitment = ctx
+            .get_packet_commitment(&(port_id.clone(), channel_id.clone(), *seq))
+            .ok_or_else(|| Kind::MissingState(what.clone()))?;
+
+        verify_equal(&stored_commitment, &commitment, what)?;
+
+        Ok(client_state.clone())
+    }
+
+    fn verify_packet_acknowledgement(
+        &self,
+        ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        _prefix: &CommitmentPrefix,
+        _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        seq: &Sequence,
+        ack: Vec<u8>,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        verify_height(client_state, height)?;
+
+        let what = format!("packet acknowledgement {}/{}/{}", port_id, channel_id, seq);
+
+        let stored_ack = ctx
+            .get_packet_acknowledgement(&(port_id.clone(), channel_id.clone(), *seq))
+            .ok_or_else(|| Kind::MissingState(what.clone()))?;
+
+        // The host stores the commitments to acknowledgements, not the acknowledgements themselves.
+        verify_equal(&stored_ack, &ctx.ack_commitment(&ack), what)?;
+
+        Ok(client_state.clone())
+    }
+
+    fn verify_next_sequence_recv(
+        &self,
+        ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        _prefix: &CommitmentPrefix,
+        _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        seq: &Sequence,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        verify_height(client_state, height)?;
+
+        let what = format!("next receive sequence of {}/{}", port_id, channel_id);
+
+        let next_sequence_recv = ctx
+            .get_next_sequence_recv(&(port_id.clone(), channel_id.clone()))
+            .ok_or_else(|| Kind::MissingState(what.clone()))?;
+
+        verify_equal(&next_sequence_recv, seq, what)?;
+
+        Ok(client_state.clone())
+    }
+
+    fn verify_packet_receipt_absence(
+        &self,
+        ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        _prefix: &CommitmentPrefix,
+        _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        seq: &Sequence,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        verify_height(client_state, height)?;
+
+        match ctx.get_packet_receipt(&(port_id.clone(), channel_id.clone(), *seq)) {
+            Some(_) => Err(Kind::StateMismatch(format!(
+                "packet receipt {}/{}/{}",
+                port_id, channel_id, seq
+            ))
+            .into()),
+            None => Ok(client_state.clone()),
+        }
+    }
+}
+
+/// Checks that the client has been updated to the proof height.
+fn verify_height(client_state: &ClientState, height: Height) -> Result<(), Error> {
+    if height > client_state.latest_height() {
+        return Err(Kind::InvalidProofHeight(height, client_state.latest_height()).into());
+    }
+
+    Ok(())
+}
+
+fn verify_equal<T: PartialEq>(stored: &T, expected: &T, what: String) -> Result<(), Error> {
+    if stored != expected {
+        return Err(Kind::StateMismatch(what).into());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::application::ics20_fungible_token_transfer::context::ICS20_VERSION;
+    use crate::application::ics20_fungible_token_transfer::module::Ics20Module;
+    use crate::ics02_client::client_consensus::AnyConsensusState;
+    use crate::ics02_client::client_def::ClientDef;
+    use crate::ics02_client::client_state::AnyClientState;
+    use crate::ics02_client::client_type::ClientType;
+    use crate::ics02_client::context::ClientReader;
+    use crate::ics02_client::header::AnyHeader;
+    use crate::ics02_client::msgs::create_client::MsgCreateAnyClient;
+    use crate::ics02_client::msgs::update_client::MsgUpdateAnyClient;
+    use crate::ics02_client::msgs::ClientMsg;
+    use crate::ics03_connection::connection::{
+        Counterparty as ConnectionCounterparty, State as ConnectionState,
+    };
+    use crate::ics03_connection::context::ConnectionReader;
+    use crate::ics03_connection::msgs::conn_open_ack::MsgConnectionOpenAck;
+    use crate::ics03_connection::msgs::conn_open_confirm::MsgConnectionOpenConfirm;
+    use crate::ics03_connection::msgs::conn_open_init::MsgConnectionOpenInit;
+    use crate::ics03_connection::msgs::conn_open_try::MsgConnectionOpenTry;
+    use crate::ics03_connection::msgs::ConnectionMsg;
+    use crate::ics04_channel::channel::{
+        ChannelEnd, Counterparty as ChannelCounterparty, Order, State as ChannelState,
+    };
+    use crate::ics04_channel::context::ChannelReader;
+    use crate::ics04_channel::msgs::chan_open_ack::MsgChannelOpenAck;
+    use crate::ics04_channel::msgs::chan_open_confirm::MsgChannelOpenConfirm;
+    use crate::ics04_channel::msgs::chan_open_init::MsgChannelOpenInit;
+    use crate::ics04_channel::msgs::chan_open_try::MsgChannelOpenTry;
+    use crate::ics04_channel::msgs::ChannelMsg;
+    use crate::ics09_localhost::client_def::LocalhostClient;
+    use crate::ics09_localhost::client_state::ClientState;
+    use crate::ics09_localhost::consensus_state::ConsensusState;
+    use crate::ics09_localhost::header::Header;
+    use crate::ics23_commitment::commitment::CommitmentProofBytes;
+    use crate::ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId};
+    use crate::ics26_routing::msgs::Ics26Envelope;
+    use crate::mock::context::MockContext;
+    use crate::mock::host::HostType;
+    use crate::proofs::{ConsensusProof, Proofs};
+    use crate::test_utils::get_dummy_account_id;
+    use crate::Height;
+
+    fn localhost_context() -> MockContext {
+        MockContext::new(
+            ChainId::new("mockgaia".to_string(), 1),
+            HostType::Mock,
+            20,
+            Height::new(1, 5),
+        )
+    }
+
+    /// Updates the given loopback client to the current height of the host, which is returned.
+    fn update_client(ctx: &mut MockContext, client_id: &ClientId) -> Height {
+        let height = ClientReader::host_height(ctx);
+        let msg = MsgUpdateAnyClient {
+            client_id: client_id.clone(),
+            header: AnyHeader::Localhost(Header::new(height)),
+            signer: get_dummy_account_id(),
+        };
+
+        ctx.deliver(Ics26Envelope::Ics2Msg(ClientMsg::UpdateClient(msg)))
+            .unwrap();
+
+        height
+    }
+
+    /// The loopback client ignores the content of the proofs, only their heights matter.
+    fn dummy_proofs(height: Height, consensus_height: Option<Height>) -> Proofs {
+        let proof = CommitmentProofBytes::from(vec![1]);
+
+        Proofs::new(
+            proof.clone(),
+            consensus_height.map(|_| proof.clone()),
+            consensus_height.map(|h| ConsensusProof::new(proof.clone(), h).unwrap()),
+            None,
+            height,
+        )
+        .unwrap()
+    }
+
+    fn latest_height(ctx: &MockContext, client_id: &ClientId) -> Height {
+        ClientReader::client_state(ctx, client_id)
+            .unwrap()
+            .latest_height()
+    }
+
+    #[test]
+    fn update_follows_host_height() {
+        let client = LocalhostClient;
+        let client_id = ClientId::new(ClientType::Localhost, 0).unwrap();
+        let mut ctx = localhost_context();
+        let client_state =
+            ClientState::new(ChainId::new("mockgaia".to_string(), 1), Height::new(1, 5));
+
+        // The client cannot go back in time, nor ahead of the host.
+        for height in &[Height::new(1, 4), Height::new(1, 5), Height::new(1, 6)] {
+            assert!(client
+                .check_header_and_update_state(
+                    &ctx,
+                    client_id.clone(),
+                    client_state.clone(),
+                    Header::new(*height),
+                )
+                .is_err());
+        }
+
+        ctx.advance_host_chain_height();
+
+        let (new_state, _) = client
+            .check_header_and_update_state(
+                &ctx,
+                client_id,
+                client_state,
+                Header::new(Height::new(1, 6)),
+            )
+            .unwrap();
+        assert_eq!(new_state.latest_height(), Height::new(1, 6));
+    }
+
+    #[test]
+    fn loopback_handshakes() {
+        let client_a = ClientId::new(ClientType::Localhost, 0).unwrap();
+        let client_b = ClientId::new(ClientType::Localhost, 1).unwrap();
+        let port_id = PortId::default();
+        let signer = get_dummy_account_id();
+
+        let mut ctx = localhost_context()
+            .with_port_capability(port_id.clone())
+            .with_module(port_id.clone(), Ics20Module);
+
+        // Both ends of the loopback are clients of the host chain, created at its current height.
+        for _ in 0..2 {
+            let client_state = ClientState::new(
+                ChainId::new("mockgaia".to_string(), 1),
+                ClientReader::host_height(&ctx),
+            );
+            let msg = MsgCreateAnyClient::new(
+                AnyClientState::Localhost(client_state),
+                AnyConsensusState::Localhost(ConsensusState::new()),
+                signer.clone(),
+            )
+            .unwrap();
+
+            ctx.deliver(Ics26Envelope::Ics2Msg(ClientMsg::CreateClient(msg)))
+                .unwrap();
+        }
+
+        let prefix = ctx.commitment_prefix();
+
+        // Connection handshake, from client A to client B.
+        let conn_a = ConnectionId::new(0);
+        let conn_b = ConnectionId::new(1);
+
+        let versions = ctx.get_compatible_versions();
+        let msg = MsgConnectionOpenInit {
+            client_id: client_a.clone(),
+            counterparty: ConnectionCounterparty::new(client_b.clone(), None, prefix.clone()),
+            version: versions[0].clone(),
+            delay_period: Duration::from_secs(0),
+            signer: signer.clone(),
+        };
+        ctx.deliver(Ics26Envelope::Ics3Msg(ConnectionMsg::ConnectionOpenInit(
+            msg,
+        )))
+        .unwrap();
+
+        let proof_height = update_client(&mut ctx, &client_b);
+        let msg = MsgConnectionOpenTry {
+            previous_connection_id: None,
+            client_id: client_b.clone(),
+            client_state: ClientReader::client_state(&ctx, &client_a),
+            counterparty: ConnectionCounterparty::new(
+                client_a.clone(),
+                Some(conn_a.clone()),
+                prefix.clone(),
+            ),
+            counterparty_versions: ConnectionReader::connection_end(&ctx, &conn_a)
+                .unwrap()
+                .versions(),
+            proofs: dummy_proofs(proof_height, Some(latest_height(&ctx, &client_a))),
+            delay_period: Duration::from_secs(0),
+            signer: signer.clone(),
+        };
+        ctx.deliver(Ics26Envelope::Ics3Msg(ConnectionMsg::ConnectionOpenTry(
+            Box::new(msg),
+        )))
+        .unwrap();
+
+        let proof_height = update_client(&mut ctx, &client_a);
+        let msg = MsgConnectionOpenAck {
+            connection_id: conn_a.clone(),
+            counterparty_connection_id: conn_b.clone(),
+            client_state: ClientReader::client_state(&ctx, &client_b),
+            proofs: dummy_proofs(proof_height, Some(latest_height(&ctx, &client_b))),
+            version: ConnectionReader::connection_end(&ctx, &conn_b)
+                .unwrap()
+                .versions()[0]
+                .clone(),
+            signer: signer.clone(),
+        };
+        ctx.deliver(Ics26Envelope::Ics3Msg(ConnectionMsg::ConnectionOpenAck(
+            Box::new(msg),
+        )))
+        .unwrap();
+
+        let proof_height = update_client(&mut ctx, &client_b);
+        let msg = MsgConnectionOpenConfirm {
+            connection_id: conn_b.clone(),
+            proofs: dummy_proofs(proof_height, None),
+            signer: signer.clone(),
+        };
+        ctx.deliver(Ics26Envelope::Ics3Msg(
+            ConnectionMsg::ConnectionOpenConfirm(msg),
+        ))
+        .unwrap();
+
+        for conn_id in &[&conn_a, &conn_b] {
+            let connection_end = ConnectionReader::connection_end(&ctx, conn_id).unwrap();
+            assert_eq!(connection_end.state(), &ConnectionState::Open);
+        }
+
+        // Channel handshake, over the connection which was just opened.
+        let chan_a = ChannelId::new(0);
+        let chan_b = ChannelId::new(1);
+
+        let msg = MsgChannelOpenInit {
+            port_id: port_id.clone(),
+            channel: ChannelEnd::new(
+                ChannelState::Init,
+                Order::Unordered,
+                ChannelCounterparty::new(port_id.clone(), None),
+                vec![conn_a.clone()],
+                ICS20_VERSION.to_string(),
+            ),
+            signer: signer.clone(),
+        };
+        ctx.deliver(Ics26Envelope::Ics4ChannelMsg(ChannelMsg::ChannelOpenInit(
+            msg,
+        )))
+        .unwrap();
+
+        let proof_height = update_client(&mut ctx, &client_b);
+        let msg = MsgChannelOpenTry {
+            port_id: port_id.clone(),
+            previous_channel_id: None,
+            channel: ChannelEnd::new(
+                ChannelState::TryOpen,
+                Order::Unordered,
+                ChannelCounterparty::new(port_id.clone(), Some(chan_a.clone())),
+                vec![conn_b.clone()],
+                ICS20_VERSION.to_string(),
+            ),
+            counterparty_version: ICS20_VERSION.to_string(),
+            proofs: dummy_proofs(proof_height, None),
+            signer: signer.clone(),
+        };
+        ctx.deliver(Ics26Envelope::Ics4ChannelMsg(ChannelMsg::ChannelOpenTry(
+            msg,
+        )))
+        .unwrap();
+
+        let proof_height = update_client(&mut ctx, &client_a);
+        let msg = MsgChannelOpenAck {
+            port_id: port_id.clone(),
+            channel_id: chan_a.clone(),
+            counterparty_channel_id: chan_b.clone(),
+            counterparty_version: ICS20_VERSION.to_string(),
+            proofs: dummy_proofs(proof_height, None),
+            signer: signer.clone(),
+        };
+        ctx.deliver(Ics26Envelope::Ics4ChannelMsg(ChannelMsg::ChannelOpenAck(
+            msg,
+        )))
+        .unwrap();
+
+        let proof_height = update_client(&mut ctx, &client_b);
+        let msg = MsgChannelOpenConfirm::new(
+            port_id.clone(),
+            chan_b.clone(),
+            dummy_proofs(proof_height, None),
+            signer,
+        );
+        ctx.deliver(Ics26Envelope::Ics4ChannelMsg(
+            ChannelMsg::ChannelOpenConfirm(msg),
+        ))
+        .unwrap();
+
+        for chan_id in &[chan_a, chan_b] {
+            let channel_end = ctx
+                .channel_end(&(port_id.clone(), chan_id.clone()))
+                .unwrap();
+            assert_eq!(channel_end.state(), &ChannelState::Open);
+        }
+    }
+}
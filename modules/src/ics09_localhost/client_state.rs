@@ -0,0 +1,104 @@
+use std::convert::{TryFrom, TryInto};
+use std::str::FromStr;
+
+use serde::Serialize;
+use tendermint_proto::Protobuf;
+
+use ibc_proto::ibc::lightclients::localhost::v1::ClientState as RawClientState;
+
+use crate::ics02_client::client_state::AnyClientState;
+use crate::ics02_client::client_type::ClientType;
+use crate::ics09_localhost::error::{Error, Kind};
+use crate::ics09_localhost::header::Header;
+use crate::ics24_host::identifier::ChainId;
+use crate::Height;
+
+/// The state of a loopback client, i.e., of a client which tracks the host chain itself. Its
+/// latest height follows the height of the host chain, as it is updated.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ClientState {
+    pub chain_id: ChainId,
+    pub height: Height,
+}
+
+impl ClientState {
+    pub fn new(chain_id: ChainId, height: Height) -> Self {
+        Self { chain_id, height }
+    }
+
+    pub fn latest_height(&self) -> Height {
+        self.height
+    }
+
+    /// Returns the client state which results from updating this client to the given header.
+    pub fn with_header(self, header: Header) -> Self {
+        ClientState {
+            height: header.height,
+            ..self
+        }
+    }
+}
+
+impl crate::ics02_client::client_state::ClientState for ClientState {
+    fn chain_id(&self) -> ChainId {
+        self.chain_id.clone()
+    }
+
+    fn client_type(&self) -> ClientType {
+        ClientType::Localhost
+    }
+
+    fn latest_height(&self) -> Height {
+        self.height
+    }
+
+    /// The host chain cannot misbehave against itself, hence this client is never frozen.
+    fn is_frozen(&self) -> bool {
+        false
+    }
+
+    fn wrap_any(self) -> AnyClientState {
+        AnyClientState::Localhost(self)
+    }
+}
+
+impl Protobuf<RawClientState> for ClientState {}
+
+impl TryFrom<RawClientState> for ClientState {
+    type Error = Error;
+
+    fn try_from(raw: RawClientState) -> Result<Self, Self::Error> {
+        if raw.chain_id.trim().is_empty() {
+            return Err(Kind::InvalidRawClientState
+                .context("empty chain identifier")
+                .into());
+        }
+
+        let height: Height = raw
+            .height
+            .ok_or_else(|| Kind::InvalidRawClientState.context("missing height"))?
+            .try_into()
+            .map_err(|e| Kind::InvalidRawClientState.context(e))?;
+
+        if height.is_zero() {
+            return Err(Kind::InvalidRawClientState
+                .context("height cannot be zero")
+                .into());
+        }
+
+        Ok(Self {
+            chain_id: ChainId::from_str(raw.chain_id.as_str())
+                .map_err(|e| Kind::InvalidRawClientState.context(e))?,
+            height,
+        })
+    }
+}
+
+impl From<ClientState> for RawClientState {
+    fn from(value: ClientState) -> Self {
+        RawClientState {
+            chain_id: value.chain_id.to_string(),
+            height: Some(value.height.into()),
+        }
+    }
+}
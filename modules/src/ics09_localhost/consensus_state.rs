@@ -0,0 +1,45 @@
+use serde::Serialize;
+
+use crate::ics02_client::client_consensus::AnyConsensusState;
+use crate::ics02_client::client_type::ClientType;
+use crate::ics23_commitment::commitment::CommitmentRoot;
+
+/// The loopback client reads the host store directly, so its consensus states carry no data: they
+/// only record the heights to which the client was updated.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ConsensusState {
+    #[serde(skip)]
+    root: CommitmentRoot,
+}
+
+impl ConsensusState {
+    pub fn new() -> Self {
+        Self {
+            root: CommitmentRoot::from_bytes(&[]),
+        }
+    }
+}
+
+impl Default for ConsensusState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl crate::ics02_client::client_consensus::ConsensusState for ConsensusState {
+    fn client_type(&self) -> ClientType {
+        ClientType::Localhost
+    }
+
+    fn root(&self) -> &CommitmentRoot {
+        &self.root
+    }
+
+    fn validate_basic(&self) -> Result<(), Box<dyn std::error::Error>> {
+        Ok(())
+    }
+
+    fn wrap_any(self) -> AnyConsensusState {
+        AnyConsensusState::Localhost(self)
+    }
+}
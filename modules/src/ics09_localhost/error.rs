@@ -0,0 +1,39 @@
+use anomaly::{BoxError, Context};
+use thiserror::Error;
+
+use crate::Height;
+
+pub type Error = anomaly::Error<Kind>;
+
+#[derive(Clone, Debug, Error)]
+pub enum Kind {
+    #[error("invalid raw client state")]
+    InvalidRawClientState,
+
+    #[error("invalid raw header")]
+    InvalidRawHeader,
+
+    #[error("header height {0} must be higher than the client height {1}")]
+    LowHeaderHeight(Height, Height),
+
+    #[error("header height {0} is higher than the host height {1}")]
+    HeaderInTheFuture(Height, Height),
+
+    #[error("proof height {0} is higher than the client height {1}")]
+    InvalidProofHeight(Height, Height),
+
+    #[error("the localhost client does not support misbehaviour")]
+    MisbehaviourNotSupported,
+
+    #[error("missing {0} in the host store")]
+    MissingState(String),
+
+    #[error("{0} does not match the one in the host store")]
+    StateMismatch(String),
+}
+
+impl Kind {
+    pub fn context(self, source: impl Into<BoxError>) -> Context<Self> {
+        Context::new(self, Some(source.into()))
+    }
+}
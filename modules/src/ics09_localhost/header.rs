@@ -0,0 +1,66 @@
+use std::convert::TryFrom;
+
+use serde_derive::{Deserialize, Serialize};
+use tendermint_proto::Protobuf;
+
+use ibc_proto::ibc::core::client::v1::Height as RawHeight;
+
+use crate::ics02_client::client_type::ClientType;
+use crate::ics02_client::header::AnyHeader;
+use crate::ics09_localhost::error::{Error, Kind};
+use crate::Height;
+
+/// Header of the loopback client. It only carries the height of the host chain to which the client
+/// should be updated, and is encoded as such.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Header {
+    pub height: Height,
+}
+
+impl Header {
+    pub fn new(height: Height) -> Self {
+        Self { height }
+    }
+
+    pub fn height(&self) -> Height {
+        self.height
+    }
+}
+
+impl crate::ics02_client::header::Header for Header {
+    fn client_type(&self) -> ClientType {
+        ClientType::Localhost
+    }
+
+    fn height(&self) -> Height {
+        self.height
+    }
+
+    fn wrap_any(self) -> AnyHeader {
+        AnyHeader::Localhost(self)
+    }
+}
+
+impl Protobuf<RawHeight> for Header {}
+
+impl TryFrom<RawHeight> for Header {
+    type Error = Error;
+
+    fn try_from(raw: RawHeight) -> Result<Self, Self::Error> {
+        let height = Height::try_from(raw).map_err(|e| Kind::InvalidRawHeader.context(e))?;
+
+        if height.is_zero() {
+            return Err(Kind::InvalidRawHeader
+                .context("height cannot be zero")
+                .into());
+        }
+
+        Ok(Self { height })
+    }
+}
+
+impl From<Header> for RawHeight {
+    fn from(value: Header) -> Self {
+        value.height.into()
+    }
+}
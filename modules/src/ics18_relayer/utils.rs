@@ -1,6 +1,7 @@
 use crate::ics02_client::header::{AnyHeader, Header};
 use crate::ics02_client::msgs::update_client::MsgUpdateAnyClient;
 use crate::ics02_client::msgs::ClientMsg;
+use crate::ics07_tendermint::header::Header as TmHeader;
 use crate::ics18_relayer::context::Ics18Context;
 use crate::ics18_relayer::error::{Error, Kind};
 use crate::ics24_host::identifier::ClientId;
@@ -41,16 +42,27 @@ where
         .into());
     };
 
+    // Tendermint headers are verified against the latest consensus state of the client.
+    let header = match src_header {
+        AnyHeader::Tendermint(header) => AnyHeader::Tendermint(TmHeader {
+            trusted_height: dest_client_latest_height,
+            ..header
+        }),
+        header => header,
+    };
+
     // Client on destination chain can be updated.
     Ok(ClientMsg::UpdateClient(MsgUpdateAnyClient {
         client_id: client_id.clone(),
-        header: src_header,
+        header,
         signer: dest.signer(),
     }))
 }
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use crate::ics02_client::client_type::ClientType;
     use crate::ics02_client::header::Header;
     use crate::ics18_relayer::context::Ics18Context;
@@ -82,6 +94,9 @@ mod tests {
             5,
             chain_a_start_height,
         )
+        // Synthetic TM blocks on B have a time (in seconds) equal to their height, so the clock
+        // of A must be past the time of the headers it verifies.
+        .with_timestamp(Duration::from_secs(30).as_nanos() as u64)
         .with_client_parametrized(
             &client_on_a_for_b,
             client_on_a_for_b_height,
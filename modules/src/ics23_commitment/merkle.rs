@@ -1,16 +1,16 @@
 use ibc_proto::ibc::core::commitment::v1::MerklePath;
 use ibc_proto::ibc::core::commitment::v1::MerkleProof as RawMerkleProof;
+use ibc_proto::ics23::commitment_proof::Proof as Ics23Proof;
+use ibc_proto::ics23::CommitmentProof;
 
-use crate::ics23_commitment::commitment::{CommitmentPrefix, CommitmentProofBytes};
-use crate::ics23_commitment::error::Error;
+use crate::ics23_commitment::commitment::{CommitmentPrefix, CommitmentRoot};
+use crate::ics23_commitment::error::{Error, Kind};
+use ics23::{verify_membership, verify_non_membership};
 use tendermint::merkle::proof::Proof;
 
-pub fn apply_prefix(
-    prefix: &CommitmentPrefix,
-    mut path: Vec<String>,
-) -> Result<MerklePath, Box<dyn std::error::Error>> {
+pub fn apply_prefix(prefix: &CommitmentPrefix, mut path: Vec<String>) -> Result<MerklePath, Error> {
     if prefix.is_empty() {
-        return Err("empty prefix".into());
+        return Err(Kind::EmptyCommitmentPrefix.into());
     }
 
     let mut result: Vec<String> = vec![format!("{:?}", prefix)];
@@ -64,9 +64,167 @@ pub fn cosmos_specs() -> Vec<ibc_proto::ics23::ProofSpec> {
     ]
 }
 
+/// Domain type for a chain of ICS23 commitment proofs, ordered from the leaf (the proof of the
+/// value within its sub-store) to the root (the proof of the sub-store within the multi-store).
 #[derive(Clone, Debug, PartialEq)]
 pub struct MerkleProof {
-    pub proof: Vec<CommitmentProofBytes>,
+    pub proofs: Vec<CommitmentProof>,
+}
+
+impl MerkleProof {
+    /// Verifies that `value` is stored under the (prefixed) `keys` in the tree committed to by
+    /// `root`. Each proof is checked against the spec at the same index, and the root computed
+    /// by one proof becomes the value proven by the next one.
+    pub fn verify_membership(
+        &self,
+        specs: &[ibc_proto::ics23::ProofSpec],
+        root: &CommitmentRoot,
+        keys: MerklePath,
+        value: Vec<u8>,
+    ) -> Result<(), Error> {
+        self.validate(specs, root, &keys)?;
+        if value.is_empty() {
+            return Err(Kind::EmptyVerifiedValue.into());
+        }
+
+        let mut subroot = value.clone();
+        let mut value = value;
+
+        // Keys are ordered from root to leaf, proofs from leaf to root.
+        for ((proof, spec), key) in self
+            .proofs
+            .iter()
+            .zip(specs.iter())
+            .zip(keys.key_path.iter().rev())
+        {
+            match &proof.proof {
+                Some(Ics23Proof::Exist(existence_proof)) => {
+                    subroot = calculate_existence_root(existence_proof)?;
+                    if !verify_membership(proof, spec, &subroot, key.as_bytes(), &value) {
+                        return Err(Kind::VerificationFailure
+                            .context(format!("membership verification failed for key {}", key))
+                            .into());
+                    }
+                    value = subroot.clone();
+                }
+                _ => return Err(Kind::InvalidMerkleProof.into()),
+            }
+        }
+
+        if root.as_bytes() != subroot.as_slice() {
+            return Err(Kind::VerificationFailure
+                .context("calculated root does not match the commitment root")
+                .into());
+        }
+
+        Ok(())
+    }
+
+    /// Verifies that nothing is stored under the (prefixed) `keys` in the tree committed to by
+    /// `root`. The first proof must be a non-existence proof for the leaf key, the remaining ones
+    /// prove the computed sub-root up to `root`.
+    pub fn verify_non_membership(
+        &self,
+        specs: &[ibc_proto::ics23::ProofSpec],
+        root: &CommitmentRoot,
+        keys: MerklePath,
+    ) -> Result<(), Error> {
+        self.validate(specs, root, &keys)?;
+
+        // The key of the leaf is the last element of the path.
+        let key = keys.key_path.last().ok_or(Kind::NumberOfKeysMismatch)?;
+
+        match &self.proofs[0].proof {
+            Some(Ics23Proof::Nonexist(non_existence_proof)) => {
+                let subroot = calculate_non_existence_root(non_existence_proof)?;
+                if !verify_non_membership(&self.proofs[0], &specs[0], &subroot, key.as_bytes()) {
+                    return Err(Kind::VerificationFailure
+                        .context(format!(
+                            "non-membership verification failed for key {}",
+                            key
+                        ))
+                        .into());
+                }
+
+                if self.proofs.len() == 1 {
+                    return if root.as_bytes() == subroot.as_slice() {
+                        Ok(())
+                    } else {
+                        Err(Kind::VerificationFailure
+                            .context("calculated root does not match the commitment root")
+                            .into())
+                    };
+                }
+
+                // The remaining proofs establish that the computed sub-root is committed to by
+                // the root of the multi-store.
+                let keys = MerklePath {
+                    key_path: keys.key_path[..keys.key_path.len() - 1].to_vec(),
+                };
+                MerkleProof {
+                    proofs: self.proofs[1..].to_vec(),
+                }
+                .verify_membership(&specs[1..], root, keys, subroot)
+            }
+            _ => Err(Kind::InvalidMerkleProof.into()),
+        }
+    }
+
+    fn validate(
+        &self,
+        specs: &[ibc_proto::ics23::ProofSpec],
+        root: &CommitmentRoot,
+        keys: &MerklePath,
+    ) -> Result<(), Error> {
+        if self.proofs.is_empty() {
+            return Err(Kind::EmptyMerkleProof.into());
+        }
+        if root.as_bytes().is_empty() {
+            return Err(Kind::EmptyMerkleRoot.into());
+        }
+        if specs.len() != self.proofs.len() {
+            return Err(Kind::NumberOfSpecsMismatch.into());
+        }
+        if keys.key_path.len() != self.proofs.len() {
+            return Err(Kind::NumberOfKeysMismatch.into());
+        }
+        Ok(())
+    }
+}
+
+/// Computes the root committed to by an existence proof.
+fn calculate_existence_root(proof: &ibc_proto::ics23::ExistenceProof) -> Result<Vec<u8>, Error> {
+    ics23::calculate_existence_root(proof)
+        .map_err(|e| Kind::InvalidMerkleProof.context(e.to_string()).into())
+}
+
+/// Computes the root committed to by a non-existence proof, using whichever neighbor is present.
+fn calculate_non_existence_root(
+    proof: &ibc_proto::ics23::NonExistenceProof,
+) -> Result<Vec<u8>, Error> {
+    match (&proof.left, &proof.right) {
+        (Some(left), _) => calculate_existence_root(left),
+        (None, Some(right)) => calculate_existence_root(right),
+        (None, None) => Err(Kind::InvalidMerkleProof
+            .context("non-existence proof has no neighbors")
+            .into()),
+    }
+}
+
+impl From<RawMerkleProof> for MerkleProof {
+    fn from(proof: RawMerkleProof) -> Self {
+        Self {
+            proofs: proof.proofs,
+        }
+    }
+}
+
+impl From<MerkleProof> for RawMerkleProof {
+    fn from(proof: MerkleProof) -> Self {
+        Self {
+            proofs: proof.proofs,
+        }
+    }
 }
 
 // Merkle Proof serialization notes:
@@ -83,8 +241,7 @@ pub struct MerkleProof {
 //  - Vec<u8>: RawMerkleProof is not explicitly used but, serialized as Vec<u8>, it is
 //       included in all handshake messages that require proofs (i.e. all except the two `OpenInit`),
 //       and also in all queries that require proofs
-//  - MerkleProof: Domain type for RawMerkleProof, currently not used and identical to RawMerkleProof.
-//       This will change with verification implementation.
+//  - MerkleProof: Domain type for RawMerkleProof, used by the client verification functions.
 //  - CommitmentProof: Defined in ibc-rs as Vec<u8> and currently used in all its messages
 //
 // Here are a couple of flows that illustrate the different conversions:
@@ -92,9 +249,6 @@ pub struct MerkleProof {
 //    Vec<u8> -> CommitmentProof -> RawMerkleProof -> MerkleProof
 //
 // Relayer: from the proof in the  query response to the proof being included in a message
-//    TmProof -> RawProofOps => RawMerkleProof -> MerkleProof -> verify()
-//      -> MerkleProof -> RawMerkleProof -> CommitmentProof -> Vec<u8>
-// Note: current implementation for ^ is simplified since verification is not yet implemented:
 //    TmProof -> RawProofOps => RawMerkleProof -> CommitmentProof -> Vec<u8>
 //
 // Implementations of (de)serializers and conversions:
@@ -107,19 +261,6 @@ pub struct MerkleProof {
 //      TmProof <-> RawProofOps
 //  - cosmos.rs:abci_query() converts from query proof to Merkle proof:
 //      RawProofOps => RawMerkleProof
-//
-// impl TryFrom<RawMerkleProof> for MerkleProof {
-//     type Error = Error;
-//     fn try_from(value: RawMerkleProof) -> Result<Self, Self::Error> {
-//         Ok(MerkleProof { proof: value.proofs.into_iter().map(|v| v.into()).collect() })
-//     }
-// }
-//
-// impl From<MerkleProof> for RawMerkleProof {
-//     fn from(value: MerkleProof) -> Self {
-//         RawMerkleProof { proof: value.proof }
-//     }
-// }
 
 use prost::Message;
 
@@ -127,10 +268,10 @@ pub fn convert_tm_to_ics_merkle_proof(
     tm_proof: Option<Proof>,
 ) -> Result<Option<RawMerkleProof>, Error> {
     if let Some(proof) = tm_proof {
-        let mut mproofs: Vec<ibc_proto::ics23::CommitmentProof> = vec![];
+        let mut mproofs: Vec<CommitmentProof> = vec![];
         for (_i, op) in proof.ops.iter().enumerate() {
             let data = op.clone().data;
-            let mut parsed = ibc_proto::ics23::CommitmentProof { proof: None };
+            let mut parsed = CommitmentProof { proof: None };
             parsed.merge(data.as_slice()).unwrap();
             mproofs.append(&mut vec![parsed]);
         }
@@ -139,3 +280,150 @@ pub fn convert_tm_to_ics_merkle_proof(
         Ok(None)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use ibc_proto::ics23::commitment_proof::Proof;
+    use ibc_proto::ics23::{CommitmentProof, ExistenceProof, InnerOp, LeafOp, NonExistenceProof};
+
+    use crate::ics23_commitment::commitment::{CommitmentPrefix, CommitmentRoot};
+    use crate::ics23_commitment::merkle::{
+        apply_prefix, calculate_existence_root, cosmos_specs, MerkleProof,
+    };
+
+    const STORE_KEY: &str = "ibc";
+
+    fn leaf_op(prefix: Vec<u8>) -> Option<LeafOp> {
+        Some(LeafOp {
+            hash: 1,
+            prehash_key: 0,
+            prehash_value: 1,
+            length: 1,
+            prefix,
+        })
+    }
+
+    fn inner_op(prefix: Vec<u8>, suffix: Vec<u8>) -> InnerOp {
+        InnerOp {
+            hash: 1,
+            prefix,
+            suffix,
+        }
+    }
+
+    fn leaf(key: &[u8], value: &[u8]) -> ExistenceProof {
+        ExistenceProof {
+            key: key.to_vec(),
+            value: value.to_vec(),
+            leaf: leaf_op(vec![0, 2, 2]),
+            path: vec![],
+        }
+    }
+
+    /// Proof of the IBC sub-store root within a single-store multi-store.
+    fn store_proof(store_root: Vec<u8>) -> ExistenceProof {
+        ExistenceProof {
+            key: STORE_KEY.as_bytes().to_vec(),
+            value: store_root,
+            leaf: leaf_op(vec![0]),
+            path: vec![inner_op(vec![1], vec![])],
+        }
+    }
+
+    fn commitment_proof(proof: Proof) -> CommitmentProof {
+        CommitmentProof { proof: Some(proof) }
+    }
+
+    #[test]
+    fn verify_membership() {
+        let key = "clients/07-tendermint-0/clientState";
+        let value = b"client state".to_vec();
+
+        let mut iavl_proof = leaf(key.as_bytes(), &value);
+        iavl_proof.path = vec![inner_op(vec![1, 2, 3, 4, 32], vec![32; 33])];
+        let store_root = calculate_existence_root(&iavl_proof).unwrap();
+
+        let store_proof = store_proof(store_root);
+        let root = CommitmentRoot::from(calculate_existence_root(&store_proof).unwrap());
+
+        let merkle_proof = MerkleProof {
+            proofs: vec![
+                commitment_proof(Proof::Exist(iavl_proof)),
+                commitment_proof(Proof::Exist(store_proof)),
+            ],
+        };
+        let prefix = CommitmentPrefix::from(STORE_KEY.as_bytes().to_vec());
+        let path = || apply_prefix(&prefix, vec![key.to_string()]).unwrap();
+
+        assert!(merkle_proof
+            .verify_membership(&cosmos_specs(), &root, path(), value)
+            .is_ok());
+        assert!(merkle_proof
+            .verify_membership(&cosmos_specs(), &root, path(), b"other state".to_vec())
+            .is_err());
+        assert!(merkle_proof
+            .verify_membership(
+                &cosmos_specs(),
+                &CommitmentRoot::from(vec![1; 32]),
+                path(),
+                b"client state".to_vec()
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn verify_non_membership() {
+        let left_key = b"receipts/ports/transfer/channels/channel-0/sequences/1";
+        let key = "receipts/ports/transfer/channels/channel-0/sequences/2";
+        let right_key = b"receipts/ports/transfer/channels/channel-0/sequences/3";
+
+        let left_hash = calculate_existence_root(&leaf(left_key, &[1])).unwrap();
+        let right_hash = calculate_existence_root(&leaf(right_key, &[1])).unwrap();
+
+        // Two adjacent leaves sharing a single parent node.
+        let mut left = leaf(left_key, &[1]);
+        left.path = vec![inner_op(
+            vec![1, 2, 3, 4, 32],
+            [vec![32], right_hash].concat(),
+        )];
+        let mut right = leaf(right_key, &[1]);
+        right.path = vec![inner_op(
+            [vec![1, 2, 3, 4, 32], left_hash, vec![32]].concat(),
+            vec![],
+        )];
+        let store_root = calculate_existence_root(&left).unwrap();
+        assert_eq!(store_root, calculate_existence_root(&right).unwrap());
+
+        let store_proof = store_proof(store_root);
+        let root = CommitmentRoot::from(calculate_existence_root(&store_proof).unwrap());
+
+        let merkle_proof = MerkleProof {
+            proofs: vec![
+                commitment_proof(Proof::Nonexist(NonExistenceProof {
+                    key: key.as_bytes().to_vec(),
+                    left: Some(left),
+                    right: Some(right),
+                })),
+                commitment_proof(Proof::Exist(store_proof)),
+            ],
+        };
+        let prefix = CommitmentPrefix::from(STORE_KEY.as_bytes().to_vec());
+
+        assert!(merkle_proof
+            .verify_non_membership(
+                &cosmos_specs(),
+                &root,
+                apply_prefix(&prefix, vec![key.to_string()]).unwrap()
+            )
+            .is_ok());
+
+        // The left neighbor itself is present in the tree.
+        assert!(merkle_proof
+            .verify_non_membership(
+                &cosmos_specs(),
+                &root,
+                apply_prefix(&prefix, vec![String::from_utf8(left_key.to_vec()).unwrap()]).unwrap()
+            )
+            .is_err());
+    }
+}
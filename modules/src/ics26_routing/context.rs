@@ -1,8 +1,8 @@
-use crate::application::ics20_fungible_token_transfer::context::Ics20Context;
 use crate::ics02_client::context::{ClientKeeper, ClientReader};
 use crate::ics03_connection::context::{ConnectionKeeper, ConnectionReader};
 use crate::ics04_channel::context::{ChannelKeeper, ChannelReader};
 use crate::ics05_port::context::PortReader;
+use crate::ics26_routing::router::Router;
 
 /// This trait captures all the functional dependencies (i.e., context) which the ICS26 module
 /// requires to be able to dispatch and process IBC messages. In other words, this is the
@@ -15,7 +15,11 @@ pub trait Ics26Context:
     + ChannelKeeper
     + ChannelReader
     + PortReader
-    + Ics20Context
     + Clone
 {
+    /// Returns the router which maps ports to the application modules bound to them.
+    fn router(&self) -> &Router<Self>;
+
+    /// Returns the router mutably, to hand the capabilities of new channels over to modules.
+    fn router_mut(&mut self) -> &mut Router<Self>;
 }
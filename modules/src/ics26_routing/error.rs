@@ -1,6 +1,8 @@
 use anomaly::{BoxError, Context};
 use thiserror::Error;
 
+use crate::ics24_host::identifier::{ChannelId, PortId};
+
 pub type Error = anomaly::Error<Kind>;
 
 #[derive(Clone, Debug, Error, PartialEq, Eq)]
@@ -16,6 +18,15 @@ pub enum Kind {
 
     #[error("the message is malformed and cannot be decoded")]
     MalformedMessageBytes,
+
+    #[error("no module is bound to port {0}")]
+    RouteNotFound(PortId),
+
+    #[error("a module is already bound to port {0}")]
+    RouteAlreadyExists(PortId),
+
+    #[error("the module bound to port {0} does not hold the capability of channel {1}")]
+    ChannelCapabilityNotFound(PortId, ChannelId),
 }
 
 impl Kind {
@@ -1,27 +1,32 @@
 use prost_types::Any;
 use tendermint_proto::Protobuf;
 
-use crate::application::ics20_fungible_token_transfer::msgs::transfer;
-use crate::application::ics20_fungible_token_transfer::relay_application_logic::send_transfer::send_transfer as ics20_msg_dispatcher;
+use crate::ics02_client::client_state::AnyClientState;
 use crate::ics02_client::handler::dispatch as ics2_msg_dispatcher;
-use crate::ics02_client::msgs::{create_client, update_client, ClientMsg};
+use crate::ics02_client::msgs::{create_client, misbehavior, update_client, ClientMsg};
 use crate::ics03_connection::handler::dispatch as ics3_msg_dispatcher;
 use crate::ics03_connection::msgs::{
     conn_open_ack, conn_open_confirm, conn_open_init, conn_open_try, ConnectionMsg,
 };
+use crate::ics04_channel::handler::channel_callback as ics4_channel_callback;
 use crate::ics04_channel::handler::channel_dispatch as ics4_msg_dispatcher;
+use crate::ics04_channel::handler::packet_callback as ics04_packet_callback;
 use crate::ics04_channel::handler::packet_dispatch as ics04_packet_msg_dispatcher;
+use crate::ics04_channel::handler::write_acknowledgement;
 use crate::{events::IbcEvent, handler::HandlerOutput};
 
 use crate::ics04_channel::msgs::{
     acknowledgement, chan_close_confirm, chan_close_init, chan_open_ack, chan_open_confirm,
     chan_open_init, chan_open_try, recv_packet, timeout, timeout_on_close, ChannelMsg, PacketMsg,
 };
+use crate::ics05_port::capabilities::Capability;
+use crate::ics24_host::identifier::{ChannelId, ClientId, PortId};
 use crate::ics26_routing::context::Ics26Context;
 use crate::ics26_routing::error::{Error, Kind};
 use crate::ics26_routing::msgs::Ics26Envelope::{
-    self, Ics20Msg, Ics2Msg, Ics3Msg, Ics4ChannelMsg, Ics4PacketMsg,
+    self, AppMsg, Ics2Msg, Ics3Msg, Ics4ChannelMsg, Ics4PacketMsg,
 };
+use crate::ics26_routing::router::{ModuleCapabilities, Router};
 
 /// Mimics the DeliverTx ABCI interface, but a slightly lower level. No need for authentication
 /// info or signature checks here.
@@ -52,6 +57,11 @@ where
                     .map_err(|e| Kind::MalformedMessageBytes.context(e))?;
                 Ok(Ics2Msg(ClientMsg::UpdateClient(domain_msg)))
             }
+            misbehavior::TYPE_URL => {
+                let domain_msg = misbehavior::MsgSubmitAnyMisbehaviour::decode_vec(&any_msg.value)
+                    .map_err(|e| Kind::MalformedMessageBytes.context(e))?;
+                Ok(Ics2Msg(ClientMsg::Misbehaviour(domain_msg)))
+            }
 
             // ICS03
             conn_open_init::TYPE_URL => {
@@ -113,12 +123,6 @@ where
                         .map_err(|e| Kind::MalformedMessageBytes.context(e))?;
                 Ok(Ics4ChannelMsg(ChannelMsg::ChannelCloseConfirm(domain_msg)))
             }
-            // ICS20 - 04 - Send packet
-            transfer::TYPE_URL => {
-                let domain_msg = transfer::MsgTransfer::decode_vec(&any_msg.value)
-                    .map_err(|e| Kind::MalformedMessageBytes.context(e))?;
-                Ok(Ics20Msg(domain_msg))
-            }
             // ICS04 packet messages
             recv_packet::TYPE_URL => {
                 let domain_msg = recv_packet::MsgRecvPacket::decode_vec(&any_msg.value)
@@ -141,7 +145,8 @@ where
                 Ok(Ics4PacketMsg(PacketMsg::ToClosePacket(domain_msg)))
             }
 
-            _ => Err(Kind::UnknownMessageTypeUrl(any_msg.type_url)),
+            // Messages of the application modules, e.g. ICS20 transfers
+            _ => Ok(AppMsg(any_msg)),
         }?;
 
         // Process the envelope, and accumulate any events that were generated.
@@ -182,8 +187,10 @@ where
                 ics3_msg_dispatcher(ctx, msg).map_err(|e| Kind::HandlerRaisedError.context(e))?;
 
             // Apply any results to the host chain store.
+            let client_state = handler_output.result.client_state.clone();
             ctx.store_connection_result(handler_output.result)
                 .map_err(|e| Kind::KeeperRaisedError.context(e))?;
+            store_verified_client_state(ctx, client_state)?;
 
             HandlerOutput::builder()
                 .with_log(handler_output.log)
@@ -192,12 +199,41 @@ where
         }
 
         Ics4ChannelMsg(msg) => {
-            let handler_output =
-                ics4_msg_dispatcher(ctx, msg).map_err(|e| Kind::HandlerRaisedError.context(e))?;
+            let module = ctx
+                .router()
+                .get_route(msg.port_id())
+                .ok_or_else(|| Kind::RouteNotFound(msg.port_id().clone()))?;
+
+            // The module bound to the port presents the capability of the port, or of the channel.
+            let capabilities = module_capabilities(ctx.router(), msg.port_id())?;
+            let capability = match msg.channel_id() {
+                None => capabilities.port_capability().clone(),
+                Some(channel_id) => channel_capability(&capabilities, channel_id)?,
+            };
+
+            let handler_output = ics4_msg_dispatcher(ctx, &capability, msg.clone())
+                .map_err(|e| Kind::HandlerRaisedError.context(e))?;
+
+            // Let the application module bound to the port accept or reject the handshake step.
+            ics4_channel_callback(ctx, module.as_ref(), &msg, &handler_output.result)
+                .map_err(|e| Kind::HandlerRaisedError.context(e))?;
 
             // Apply any results to the host chain store.
-            ctx.store_channel_result(handler_output.result)
+            let (port_id, channel_id) = (
+                handler_output.result.port_id.clone(),
+                handler_output.result.channel_id.clone(),
+            );
+            let client_state = handler_output.result.client_state.clone();
+            let channel_cap = ctx
+                .store_channel_result(handler_output.result)
                 .map_err(|e| Kind::KeeperRaisedError.context(e))?;
+            store_verified_client_state(ctx, client_state)?;
+
+            // Hand the capability of a new channel over to the module bound to its port.
+            if let Some(channel_cap) = channel_cap {
+                ctx.router_mut()
+                    .claim_channel_capability(&port_id, channel_id, channel_cap)?;
+            }
 
             HandlerOutput::builder()
                 .with_log(handler_output.log)
@@ -205,31 +241,70 @@ where
                 .with_result(())
         }
 
-        Ics20Msg(msg) => {
-            let handler_output =
-                ics20_msg_dispatcher(ctx, msg).map_err(|e| Kind::HandlerRaisedError.context(e))?;
-
-            // Apply any results to the host chain store.
-            ctx.store_packet_result(handler_output.result)
-                .map_err(|e| Kind::KeeperRaisedError.context(e))?;
-
-            HandlerOutput::builder()
-                .with_log(handler_output.log)
-                .with_events(handler_output.events)
-                .with_result(())
+        AppMsg(msg) => {
+            // The first module which handles messages of this type processes it.
+            let modules: Vec<_> = ctx.router().modules().collect();
+            modules
+                .iter()
+                .find_map(|(module, capabilities)| module.deliver(ctx, capabilities, &msg))
+                .ok_or_else(|| Kind::UnknownMessageTypeUrl(msg.type_url.clone()))?
+                .map_err(|e| Kind::HandlerRaisedError.context(e.to_string()))?
         }
 
         Ics4PacketMsg(msg) => {
-            let handler_output = ics04_packet_msg_dispatcher(ctx, msg)
+            let module = ctx
+                .router()
+                .get_route(msg.port_id())
+                .ok_or_else(|| Kind::RouteNotFound(msg.port_id().clone()))?;
+
+            // The module bound to the port presents the capability of the channel.
+            let capabilities = module_capabilities(ctx.router(), msg.port_id())?;
+            let channel_cap = channel_capability(&capabilities, msg.channel_id())?;
+
+            let handler_output = ics04_packet_msg_dispatcher(ctx, &channel_cap, msg.clone())
+                .map_err(|e| Kind::HandlerRaisedError.context(e))?;
+
+            let mut log = handler_output.log;
+            let mut events = handler_output.events;
+
+            // Let the application module process the packet before any packet state is written,
+            // so that nothing is stored if it fails.
+            let ack = ics04_packet_callback(ctx, module.as_ref(), &msg)
                 .map_err(|e| Kind::HandlerRaisedError.context(e))?;
 
+            // The acknowledgement of a received packet, unless the module defers it.
+            let ack_result = match (&msg, ack) {
+                (PacketMsg::RecvPacket(msg), Some(ack)) if !ack.is_empty() => {
+                    let ack_output = write_acknowledgement::process(
+                        &*ctx,
+                        &channel_cap,
+                        msg.packet.clone(),
+                        ack,
+                    )
+                    .map_err(|e| Kind::HandlerRaisedError.context(e))?;
+
+                    log.extend(ack_output.log);
+                    events.extend(ack_output.events);
+
+                    Some(ack_output.result)
+                }
+                _ => None,
+            };
+
             // Apply any results to the host chain store.
+            let client_state = handler_output.result.client_state();
             ctx.store_packet_result(handler_output.result)
                 .map_err(|e| Kind::KeeperRaisedError.context(e))?;
+            store_verified_client_state(ctx, client_state)?;
+
+            if let Some(ack_result) = ack_result {
+                ctx.store_packet_result(ack_result)
+                    .map_err(|e| Kind::KeeperRaisedError.context(e))?;
+            }
 
             HandlerOutput::builder()
-                .with_log(handler_output.log)
-                .with_events(handler_output.events)
+                .with_log(log)
+                .with_events(events)
                 .with_result(())
         }
     };
@@ -237,16 +312,70 @@ where
     Ok(output)
 }
 
+/// Stores the state of the client which verified the proofs of a message, as returned by the
+/// verification.
+fn store_verified_client_state<Ctx>(
+    ctx: &mut Ctx,
+    client_state: Option<(ClientId, AnyClientState)>,
+) -> Result<(), Error>
+where
+    Ctx: Ics26Context,
+{
+    if let Some((client_id, client_state)) = client_state {
+        ctx.store_client_state(client_id, client_state)
+            .map_err(|e| Kind::KeeperRaisedError.context(e))?;
+    }
+
+    Ok(())
+}
+
+/// Returns the capabilities held by the module bound to the given port.
+fn module_capabilities<Ctx>(
+    router: &Router<Ctx>,
+    port_id: &PortId,
+) -> Result<ModuleCapabilities, Error> {
+    router
+        .capabilities(port_id)
+        .cloned()
+        .ok_or_else(|| Kind::RouteNotFound(port_id.clone()).into())
+}
+
+/// Returns the capability of the given channel, as held by the module bound to its port.
+fn channel_capability(
+    capabilities: &ModuleCapabilities,
+    channel_id: &ChannelId,
+) -> Result<Capability, Error> {
+    capabilities
+        .channel_capability(channel_id)
+        .cloned()
+        .ok_or_else(|| {
+            Kind::ChannelCapabilityNotFound(capabilities.port_id().clone(), channel_id.clone())
+                .into()
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use std::convert::TryFrom;
+    use std::error::Error;
+    use std::time::Duration;
 
+    use prost_types::Any;
+
+    use crate::application::ics20_fungible_token_transfer::denom::Coin;
+    use crate::application::ics20_fungible_token_transfer::module::Ics20Module;
     use crate::application::ics20_fungible_token_transfer::msgs::transfer::test_util::get_dummy_msg_transfer;
+    use crate::application::ics20_fungible_token_transfer::packet::FungibleTokenPacketData;
     use crate::events::IbcEvent;
     use crate::ics02_client::client_consensus::AnyConsensusState;
     use crate::ics02_client::client_state::AnyClientState;
+    use crate::ics02_client::misbehaviour::AnyMisbehaviour;
     use crate::ics02_client::msgs::{
-        create_client::MsgCreateAnyClient, update_client::MsgUpdateAnyClient, ClientMsg,
+        create_client::MsgCreateAnyClient, misbehavior::MsgSubmitAnyMisbehaviour,
+        update_client::MsgUpdateAnyClient, ClientMsg,
+    };
+    use crate::ics03_connection::connection::{
+        ConnectionEnd, Counterparty as ConnectionCounterparty, State as ConnectionState,
     };
     use crate::ics03_connection::msgs::{
         conn_open_ack::{test_util::get_dummy_raw_msg_conn_open_ack, MsgConnectionOpenAck},
@@ -254,7 +383,11 @@ mod tests {
         conn_open_try::{test_util::get_dummy_raw_msg_conn_open_try, MsgConnectionOpenTry},
         ConnectionMsg,
     };
+    use crate::ics03_connection::version::get_compatible_versions;
+    use crate::ics04_channel::channel::{ChannelEnd, Counterparty, Order, State};
+    use crate::ics04_channel::context::ChannelReader;
     use crate::ics04_channel::msgs::{
+        acknowledgement::{test_util::get_dummy_raw_msg_acknowledgement, MsgAcknowledgement},
         chan_close_confirm::{
             test_util::get_dummy_raw_msg_chan_close_confirm, MsgChannelCloseConfirm,
         },
@@ -267,13 +400,18 @@ mod tests {
         ChannelMsg, PacketMsg,
     };
 
-    use crate::ics24_host::identifier::ConnectionId;
+    use crate::ics04_channel::packet::Packet;
+    use crate::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
     use crate::ics26_routing::handler::dispatch;
     use crate::ics26_routing::msgs::Ics26Envelope;
+    use crate::ics26_routing::router::Module;
     use crate::mock::client_state::{MockClientState, MockConsensusState};
     use crate::mock::context::MockContext;
     use crate::mock::header::MockHeader;
+    use crate::mock::misbehaviour::Misbehaviour as MockMisbehaviour;
+    use crate::signer::Signer;
     use crate::test_utils::get_dummy_account_id;
+    use crate::tx_msg::Msg;
     use crate::Height;
 
     #[test]
@@ -297,10 +435,14 @@ mod tests {
         let update_client_height_after_second_send = Height::new(0, 36);
 
         // We reuse this same context across all tests. Nothing in particular needs parametrizing.
-        let mut ctx = MockContext::default();
+        // The ICS20 module is bound to the default port, and the signer is funded so that it can
+        // send tokens over ICS20.
+        let mut ctx = MockContext::default()
+            .with_module(PortId::default(), Ics20Module)
+            .with_balance(default_signer.clone(), Coin::new("uatom", 100));
 
         let create_client_msg = MsgCreateAnyClient::new(
-            AnyClientState::from(MockClientState(MockHeader::new(start_client_height))),
+            AnyClientState::from(MockClientState::new(MockHeader::new(start_client_height))),
             AnyConsensusState::from(MockConsensusState(MockHeader::new(start_client_height))),
             default_signer.clone(),
         )
@@ -362,9 +504,20 @@ mod tests {
         msg_to_on_close.packet.sequence = 2.into();
         msg_to_on_close.packet.timeout_height = msg_transfer_two.timeout_height;
         msg_to_on_close.packet.timeout_timestamp = msg_transfer_two.timeout_timestamp;
+        msg_to_on_close.packet.data = FungibleTokenPacketData {
+            amount: 10,
+            denom: "uatom".to_string(),
+            receiver: default_signer.to_string(),
+            sender: default_signer.to_string(),
+        }
+        .to_json_bytes();
 
         let msg_recv_packet = MsgRecvPacket::try_from(get_dummy_raw_msg_recv_packet(35)).unwrap();
 
+        // No application module is bound to the destination port of this packet.
+        let mut msg_recv_packet_unbound = msg_recv_packet.clone();
+        msg_recv_packet_unbound.packet.destination_port = "unbound".parse().unwrap();
+
         // First, create a client..
         let res = dispatch(
             &mut ctx,
@@ -468,9 +621,17 @@ mod tests {
             //ICS20-04-packet
             Test {
                 name: "Packet send".to_string(),
-                msg: Ics26Envelope::Ics20Msg(msg_transfer),
+                msg: Ics26Envelope::AppMsg(msg_transfer.to_any()),
                 want_pass: true,
             },
+            Test {
+                name: "Message not handled by any module".to_string(),
+                msg: Ics26Envelope::AppMsg(Any {
+                    type_url: "/unknown.v1.MsgUnknown".to_string(),
+                    value: vec![],
+                }),
+                want_pass: false,
+            },
             // The client update is required in this test, because the proof associated with
             // msg_recv_packet has the same height as the packet TO height (see get_dummy_raw_msg_recv_packet)
             Test {
@@ -482,6 +643,11 @@ mod tests {
                 })),
                 want_pass: true,
             },
+            Test {
+                name: "Receive packet fails because no module is bound to the port".to_string(),
+                msg: Ics26Envelope::Ics4PacketMsg(PacketMsg::RecvPacket(msg_recv_packet_unbound)),
+                want_pass: false,
+            },
             Test {
                 name: "Receive packet".to_string(),
                 msg: Ics26Envelope::Ics4PacketMsg(PacketMsg::RecvPacket(msg_recv_packet.clone())),
@@ -494,15 +660,15 @@ mod tests {
             },
             Test {
                 name: "Packet send".to_string(),
-                msg: Ics26Envelope::Ics20Msg(msg_transfer_two),
+                msg: Ics26Envelope::AppMsg(msg_transfer_two.to_any()),
                 want_pass: true,
             },
             Test {
                 name: "Client update successful".to_string(),
                 msg: Ics26Envelope::Ics2Msg(ClientMsg::UpdateClient(MsgUpdateAnyClient {
-                    client_id,
+                    client_id: client_id.clone(),
                     header: MockHeader::new(update_client_height_after_second_send).into(),
-                    signer: default_signer,
+                    signer: default_signer.clone(),
                 })),
                 want_pass: true,
             },
@@ -527,6 +693,33 @@ mod tests {
                 msg: Ics26Envelope::Ics4PacketMsg(PacketMsg::ToClosePacket(msg_to_on_close)),
                 want_pass: true,
             },
+            // ICS02 misbehaviour
+            Test {
+                name: "Client misbehaviour freezes the client".to_string(),
+                msg: Ics26Envelope::Ics2Msg(ClientMsg::Misbehaviour(MsgSubmitAnyMisbehaviour {
+                    client_id: client_id.clone(),
+                    misbehaviour: AnyMisbehaviour::Mock(MockMisbehaviour {
+                        client_id: client_id.clone(),
+                        header1: MockHeader::new(update_client_height_after_second_send),
+                        header2: MockHeader {
+                            height: update_client_height_after_second_send,
+                            timestamp: 1,
+                        },
+                    }),
+                    signer: default_signer.clone(),
+                })),
+                want_pass: true,
+            },
+            Test {
+                name: "Client update fails due to frozen client".to_string(),
+                msg: Ics26Envelope::Ics2Msg(ClientMsg::UpdateClient(MsgUpdateAnyClient {
+                    client_id,
+                    header: MockHeader::new(update_client_height_after_second_send.increment())
+                        .into(),
+                    signer: default_signer,
+                })),
+                want_pass: false,
+            },
         ]
         .into_iter()
         .collect();
@@ -544,4 +737,100 @@ mod tests {
             );
         }
     }
+
+    /// An application module which rejects every acknowledgement.
+    #[derive(Debug)]
+    struct RejectingModule;
+
+    impl Module<MockContext> for RejectingModule {
+        fn on_recv_packet(&self, _: &mut MockContext, _: &Packet, _: &Signer) -> Vec<u8> {
+            vec![]
+        }
+
+        fn on_acknowledgement_packet(
+            &self,
+            _ctx: &mut MockContext,
+            _packet: &Packet,
+            _acknowledgement: &[u8],
+            _relayer: &Signer,
+        ) -> Result<(), Box<dyn Error>> {
+            Err("acknowledgement rejected".into())
+        }
+    }
+
+    #[test]
+    fn no_packet_state_is_written_if_the_module_callback_fails() {
+        let client_height = Height::new(0, Height::default().revision_height + 2);
+
+        let msg = MsgAcknowledgement::try_from(get_dummy_raw_msg_acknowledgement(
+            client_height.revision_height,
+        ))
+        .unwrap();
+        let packet = msg.packet.clone();
+
+        let channel_end = ChannelEnd::new(
+            State::Open,
+            Order::Ordered,
+            Counterparty::new(
+                packet.destination_port.clone(),
+                Some(packet.destination_channel.clone()),
+            ),
+            vec![ConnectionId::default()],
+            "ics20".to_string(),
+        );
+
+        let connection_end = ConnectionEnd::new(
+            ConnectionState::Open,
+            ClientId::default(),
+            ConnectionCounterparty::new(
+                ClientId::default(),
+                Some(ConnectionId::default()),
+                Default::default(),
+            ),
+            get_compatible_versions(),
+            Duration::from_secs(0),
+        );
+
+        let context = MockContext::default();
+        let commitment = ChannelReader::packet_commitment(
+            &context,
+            &packet.data,
+            packet.timeout_height,
+            packet.timeout_timestamp,
+        );
+
+        let mut ctx = context
+            .with_client(&ClientId::default(), client_height)
+            .with_connection(ConnectionId::default(), connection_end)
+            .with_channel(
+                packet.source_port.clone(),
+                packet.source_channel.clone(),
+                channel_end,
+            )
+            .with_module(packet.source_port.clone(), RejectingModule)
+            .with_packet_commitment(
+                packet.source_port.clone(),
+                packet.source_channel.clone(),
+                packet.sequence,
+                commitment,
+            )
+            .with_ack_sequence(
+                packet.source_port.clone(),
+                packet.source_channel.clone(),
+                packet.sequence,
+            );
+
+        let res = dispatch(
+            &mut ctx,
+            Ics26Envelope::Ics4PacketMsg(PacketMsg::AckPacket(msg)),
+        );
+        assert!(res.is_err());
+
+        // The next sequence to be acknowledged on the ordered channel was not bumped
+        let port_channel_id: (PortId, ChannelId) = (packet.source_port, packet.source_channel);
+        assert_eq!(
+            ctx.get_next_sequence_ack(&port_channel_id),
+            Some(packet.sequence)
+        );
+    }
 }
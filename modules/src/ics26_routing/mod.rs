@@ -4,3 +4,4 @@ pub mod context;
 pub mod error;
 pub mod handler;
 pub mod msgs;
+pub mod router;
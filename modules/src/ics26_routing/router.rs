@@ -0,0 +1,324 @@
+//! The ICS26 router, which maps ports to the IBC application modules bound to them, and the
+//! callbacks these modules implement. The core IBC handlers invoke these callbacks once they have
+//! processed a channel handshake or packet message.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::sync::Arc;
+
+use prost_types::Any;
+
+use crate::handler::HandlerOutput;
+use crate::ics04_channel::channel::ChannelEnd;
+use crate::ics04_channel::packet::Packet;
+use crate::ics05_port::capabilities::Capability;
+use crate::ics24_host::identifier::{ChannelId, PortId};
+use crate::ics26_routing::error::{Error as RoutingError, Kind};
+use crate::signer::Signer;
+
+/// The callbacks of an IBC application module. The module state lives in the host context `Ctx`,
+/// which the callbacks receive mutably. The channel handshake callbacks accept the handshake by
+/// default, while every module must decide how to process the packets it receives.
+pub trait Module<Ctx>: fmt::Debug + Send + Sync {
+    fn on_chan_open_init(
+        &self,
+        _ctx: &mut Ctx,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _channel_end: &ChannelEnd,
+    ) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+
+    fn on_chan_open_try(
+        &self,
+        _ctx: &mut Ctx,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _channel_end: &ChannelEnd,
+        _counterparty_version: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+
+    fn on_chan_open_ack(
+        &self,
+        _ctx: &mut Ctx,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _counterparty_version: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+
+    fn on_chan_open_confirm(
+        &self,
+        _ctx: &mut Ctx,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+    ) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+
+    fn on_chan_close_init(
+        &self,
+        _ctx: &mut Ctx,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+    ) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+
+    fn on_chan_close_confirm(
+        &self,
+        _ctx: &mut Ctx,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+    ) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+
+    /// Processes a packet received by this chain, and returns the acknowledgement to be written.
+    /// An empty acknowledgement means the module will write it asynchronously.
+    fn on_recv_packet(&self, ctx: &mut Ctx, packet: &Packet, relayer: &Signer) -> Vec<u8>;
+
+    fn on_acknowledgement_packet(
+        &self,
+        _ctx: &mut Ctx,
+        _packet: &Packet,
+        _acknowledgement: &[u8],
+        _relayer: &Signer,
+    ) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+
+    fn on_timeout_packet(
+        &self,
+        _ctx: &mut Ctx,
+        _packet: &Packet,
+        _relayer: &Signer,
+    ) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+
+    /// Processes a message of the application itself (e.g. an ICS20 `MsgTransfer`), as opposed
+    /// to the IBC messages whose processing the callbacks above complete. The module presents
+    /// the `capabilities` it holds to the core handlers it invokes. Returns `None` if the module
+    /// does not handle messages of this type.
+    fn deliver(
+        &self,
+        _ctx: &mut Ctx,
+        _capabilities: &ModuleCapabilities,
+        _msg: &Any,
+    ) -> Option<Result<HandlerOutput<()>, Box<dyn Error>>> {
+        None
+    }
+}
+
+/// The capabilities which the router holds on behalf of the module bound to a port: the
+/// capability returned when binding the port, and those created for the channels opened on it.
+/// The router presents them to the core handlers when routing messages to the module.
+#[derive(Clone, Debug)]
+pub struct ModuleCapabilities {
+    port_id: PortId,
+    port_cap: Capability,
+    channel_caps: BTreeMap<ChannelId, Capability>,
+}
+
+impl ModuleCapabilities {
+    pub fn port_id(&self) -> &PortId {
+        &self.port_id
+    }
+
+    pub fn port_capability(&self) -> &Capability {
+        &self.port_cap
+    }
+
+    /// Returns the capability of the given channel of the module port, if the module holds it.
+    pub fn channel_capability(&self, channel_id: &ChannelId) -> Option<&Capability> {
+        self.channel_caps.get(channel_id)
+    }
+}
+
+/// The module bound to a port, together with the capabilities it holds.
+struct Route<Ctx> {
+    module: Arc<dyn Module<Ctx>>,
+    capabilities: ModuleCapabilities,
+}
+
+impl<Ctx> Clone for Route<Ctx> {
+    fn clone(&self) -> Self {
+        Self {
+            module: self.module.clone(),
+            capabilities: self.capabilities.clone(),
+        }
+    }
+}
+
+/// Maps each port to the application module bound to it.
+pub struct Router<Ctx> {
+    routes: BTreeMap<PortId, Route<Ctx>>,
+}
+
+impl<Ctx> Router<Ctx> {
+    pub fn new() -> Self {
+        Self {
+            routes: BTreeMap::new(),
+        }
+    }
+
+    /// Binds `module` to the given port, whose capability `port_cap` the router holds on behalf
+    /// of the module. Fails if a module is already bound to that port.
+    pub fn add_route(
+        &mut self,
+        port_id: PortId,
+        port_cap: Capability,
+        module: impl Module<Ctx> + 'static,
+    ) -> Result<(), RoutingError> {
+        if self.routes.contains_key(&port_id) {
+            return Err(Kind::RouteAlreadyExists(port_id).into());
+        }
+
+        let capabilities = ModuleCapabilities {
+            port_id: port_id.clone(),
+            port_cap,
+            channel_caps: BTreeMap::new(),
+        };
+        self.routes.insert(
+            port_id,
+            Route {
+                module: Arc::new(module),
+                capabilities,
+            },
+        );
+        Ok(())
+    }
+
+    /// Hands the capability of a newly opened channel over to the module bound to its port.
+    pub fn claim_channel_capability(
+        &mut self,
+        port_id: &PortId,
+        channel_id: ChannelId,
+        channel_cap: Capability,
+    ) -> Result<(), RoutingError> {
+        let route = self
+            .routes
+            .get_mut(port_id)
+            .ok_or_else(|| Kind::RouteNotFound(port_id.clone()))?;
+
+        route
+            .capabilities
+            .channel_caps
+            .insert(channel_id, channel_cap);
+        Ok(())
+    }
+
+    /// Returns the module bound to the given port, if any.
+    pub fn get_route(&self, port_id: &PortId) -> Option<Arc<dyn Module<Ctx>>> {
+        self.routes.get(port_id).map(|route| route.module.clone())
+    }
+
+    /// Returns the capabilities held by the module bound to the given port, if any.
+    pub fn capabilities(&self, port_id: &PortId) -> Option<&ModuleCapabilities> {
+        self.routes.get(port_id).map(|route| &route.capabilities)
+    }
+
+    pub fn has_route(&self, port_id: &PortId) -> bool {
+        self.routes.contains_key(port_id)
+    }
+
+    /// Returns all the modules bound to a port, together with the capabilities they hold.
+    pub fn modules(&self) -> impl Iterator<Item = (Arc<dyn Module<Ctx>>, ModuleCapabilities)> + '_ {
+        self.routes
+            .values()
+            .map(|route| (route.module.clone(), route.capabilities.clone()))
+    }
+}
+
+impl<Ctx> Default for Router<Ctx> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Ctx> Clone for Router<Ctx> {
+    fn clone(&self) -> Self {
+        Self {
+            routes: self.routes.clone(),
+        }
+    }
+}
+
+impl<Ctx> fmt::Debug for Router<Ctx> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(
+                self.routes
+                    .iter()
+                    .map(|(port_id, route)| (port_id, &route.module)),
+            )
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::application::ics20_fungible_token_transfer::module::Ics20Module;
+    use crate::ics05_port::capabilities::CapabilityName;
+    use crate::ics05_port::context::{CapabilityKeeper, PortKeeper};
+    use crate::ics24_host::identifier::{ChannelId, PortId};
+    use crate::ics26_routing::router::Router;
+    use crate::mock::context::MockContext;
+
+    #[test]
+    fn router_routes() {
+        let mut ctx = MockContext::default();
+        let port_cap = ctx.bind_port(&PortId::default()).unwrap();
+
+        let mut router = Router::<MockContext>::new();
+        assert!(!router.has_route(&PortId::default()));
+
+        router
+            .add_route(PortId::default(), port_cap.clone(), Ics20Module)
+            .unwrap();
+        assert!(router.has_route(&PortId::default()));
+        assert!(router.get_route(&PortId::default()).is_some());
+
+        // A port can only be bound to a single module.
+        assert!(router
+            .add_route(PortId::default(), port_cap.clone(), Ics20Module)
+            .is_err());
+
+        // The router holds the capabilities of the module on its behalf.
+        let channel_cap = ctx
+            .new_capability(CapabilityName::channel(
+                &PortId::default(),
+                &ChannelId::default(),
+            ))
+            .unwrap();
+        router
+            .claim_channel_capability(
+                &PortId::default(),
+                ChannelId::default(),
+                channel_cap.clone(),
+            )
+            .unwrap();
+
+        let capabilities = router.capabilities(&PortId::default()).unwrap();
+        assert_eq!(capabilities.port_capability(), &port_cap);
+        assert_eq!(
+            capabilities.channel_capability(&ChannelId::default()),
+            Some(&channel_cap)
+        );
+        assert!(router
+            .claim_channel_capability(
+                &PortId::from_str("other").unwrap(),
+                ChannelId::default(),
+                channel_cap
+            )
+            .is_err());
+    }
+}
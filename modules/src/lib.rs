@@ -39,7 +39,9 @@ pub mod ics02_client;
 pub mod ics03_connection;
 pub mod ics04_channel;
 pub mod ics05_port;
+pub mod ics06_solomachine;
 pub mod ics07_tendermint;
+pub mod ics09_localhost;
 pub mod ics18_relayer;
 pub mod ics23_commitment;
 pub mod ics24_host;
@@ -33,15 +33,32 @@ pub struct MockClientRecord {
 
 /// A mock of a client state. For an example of a real structure that this mocks, you can see
 /// `ClientState` of ics07_tendermint/client_state.rs.
-// TODO: `MockClientState` should evolve, at the very least needs a `is_frozen` boolean field.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize)]
-pub struct MockClientState(pub MockHeader);
+pub struct MockClientState {
+    pub header: MockHeader,
+    /// The height at which the client was frozen because of misbehaviour, if any.
+    pub frozen_height: Option<Height>,
+}
 
 impl Protobuf<RawMockClientState> for MockClientState {}
 
 impl MockClientState {
+    pub fn new(header: MockHeader) -> Self {
+        Self {
+            header,
+            frozen_height: None,
+        }
+    }
+
     pub fn latest_height(&self) -> Height {
-        (self.0).height
+        self.header.height
+    }
+
+    pub fn with_frozen_height(self, frozen_height: Height) -> Self {
+        Self {
+            frozen_height: Some(frozen_height),
+            ..self
+        }
     }
 }
 
@@ -55,7 +72,7 @@ impl TryFrom<RawMockClientState> for MockClientState {
     type Error = Error;
 
     fn try_from(raw: RawMockClientState) -> Result<Self, Self::Error> {
-        Ok(MockClientState(raw.header.unwrap().try_into()?))
+        Ok(MockClientState::new(raw.header.unwrap().try_into()?))
     }
 }
 
@@ -63,8 +80,8 @@ impl From<MockClientState> for RawMockClientState {
     fn from(value: MockClientState) -> Self {
         RawMockClientState {
             header: Some(ibc_proto::ibc::mock::Header {
-                height: Some(value.0.height().into()),
-                timestamp: (value.0).timestamp,
+                height: Some(value.header.height().into()),
+                timestamp: value.header.timestamp,
             }),
         }
     }
@@ -80,12 +97,11 @@ impl ClientState for MockClientState {
     }
 
     fn latest_height(&self) -> Height {
-        self.0.height()
+        self.header.height()
     }
 
     fn is_frozen(&self) -> bool {
-        // TODO
-        false
+        self.frozen_height.is_some()
     }
 
     fn wrap_any(self) -> AnyClientState {
@@ -95,7 +111,7 @@ impl ClientState for MockClientState {
 
 impl From<MockConsensusState> for MockClientState {
     fn from(cs: MockConsensusState) -> Self {
-        Self(cs.0)
+        Self::new(cs.0)
     }
 }
 
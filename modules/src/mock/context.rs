@@ -7,7 +7,11 @@ use std::error::Error;
 use prost_types::Any;
 use sha2::Digest;
 
-use crate::application::ics20_fungible_token_transfer::context::Ics20Context;
+use crate::application::ics20_fungible_token_transfer::context::{BankKeeper, Ics20Context};
+use crate::application::ics20_fungible_token_transfer::denom::{Coin, DenomTrace};
+use crate::application::ics20_fungible_token_transfer::error::{
+    Error as Ics20Error, Kind as Ics20Kind,
+};
 use crate::events::IbcEvent;
 use crate::ics02_client::client_consensus::{AnyConsensusState, AnyConsensusStateWithHeight};
 use crate::ics02_client::client_state::AnyClientState;
@@ -22,9 +26,15 @@ use crate::ics04_channel::channel::ChannelEnd;
 use crate::ics04_channel::context::{ChannelKeeper, ChannelReader};
 use crate::ics04_channel::error::{Error as Ics4Error, Kind as Ics4Kind};
 use crate::ics04_channel::packet::{Receipt, Sequence};
-use crate::ics05_port::capabilities::Capability;
-use crate::ics05_port::context::PortReader;
+use crate::ics05_port::capabilities::{Capability, CapabilityName};
+use crate::ics05_port::context::{CapabilityKeeper, CapabilityReader, PortKeeper, PortReader};
+use crate::ics05_port::error::Error as Ics05Error;
+use crate::ics06_solomachine::client_state::test_util::{
+    get_dummy_keypair, get_dummy_solomachine_client_state,
+};
 use crate::ics07_tendermint::client_state::test_util::get_dummy_tendermint_client_state;
+use crate::ics09_localhost::client_state::ClientState as LocalhostClientState;
+use crate::ics09_localhost::consensus_state::ConsensusState as LocalhostConsensusState;
 use crate::ics18_relayer::context::Ics18Context;
 use crate::ics18_relayer::error::{Error as Ics18Error, Kind as Ics18ErrorKind};
 use crate::ics23_commitment::commitment::CommitmentPrefix;
@@ -32,6 +42,7 @@ use crate::ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId,
 use crate::ics26_routing::context::Ics26Context;
 use crate::ics26_routing::handler::{deliver, dispatch};
 use crate::ics26_routing::msgs::Ics26Envelope;
+use crate::ics26_routing::router::{Module, Router};
 use crate::mock::client_state::{MockClientRecord, MockClientState, MockConsensusState};
 use crate::mock::header::MockHeader;
 use crate::mock::host::{HostBlock, HostType};
@@ -94,16 +105,28 @@ pub struct MockContext {
     /// Tracks the sequence number for the next packet to be acknowledged.
     next_sequence_ack: HashMap<(PortId, ChannelId), Sequence>,
 
-    packet_acknowledgement: HashMap<(PortId, ChannelId, Sequence), String>,
+    packet_acknowledgement: HashMap<(PortId, ChannelId, Sequence), Vec<u8>>,
 
     /// Maps ports to their capabilities
-    port_capabilities: HashMap<PortId, Capability>,
+    capabilities: HashMap<CapabilityName, Capability>,
+
+    /// Counter for capabilities (managed by the capability keeper)
+    capability_counter: u64,
 
     /// Constant-size commitments to packets data fields
-    packet_commitment: HashMap<(PortId, ChannelId, Sequence), String>,
+    packet_commitment: HashMap<(PortId, ChannelId, Sequence), Vec<u8>>,
 
     // Used by unordered channel
     packet_receipt: HashMap<(PortId, ChannelId, Sequence), Receipt>,
+
+    /// In-memory bank, mapping accounts and denominations to balances
+    balances: HashMap<(Signer, String), u64>,
+
+    /// ICS20 denomination traces, indexed by their hash
+    denom_traces: HashMap<String, DenomTrace>,
+
+    /// Maps ports to the application modules bound to them
+    router: Router<MockContext>,
 }
 
 /// Returns a MockContext with bare minimum initialization: no clients, no connections and no channels are
@@ -172,10 +195,14 @@ impl MockContext {
             next_sequence_send: Default::default(),
             next_sequence_recv: Default::default(),
             next_sequence_ack: Default::default(),
-            port_capabilities: Default::default(),
+            capabilities: Default::default(),
+            capability_counter: 0,
             packet_commitment: Default::default(),
             packet_receipt: Default::default(),
             packet_acknowledgement: Default::default(),
+            balances: Default::default(),
+            denom_traces: Default::default(),
+            router: Default::default(),
             connection_ids_counter: 0,
             channel_ids_counter: 0,
         }
@@ -207,7 +234,7 @@ impl MockContext {
         let (client_state, consensus_state) = match client_type {
             // If it's a mock client, create the corresponding mock states.
             ClientType::Mock => (
-                Some(MockClientState(MockHeader::new(client_state_height)).into()),
+                Some(MockClientState::new(MockHeader::new(client_state_height)).into()),
                 MockConsensusState(MockHeader::new(cs_height)).into(),
             ),
             // If it's a Tendermint client, we need TM states.
@@ -223,6 +250,27 @@ impl MockContext {
                 // Return the tuple.
                 (Some(client_state), consensus_state)
             }
+            // If it's a solo machine client, its sequence is the revision height.
+            ClientType::Solomachine => {
+                let client_state = get_dummy_solomachine_client_state(
+                    &get_dummy_keypair(1),
+                    client_state_height.revision_height,
+                );
+                let consensus_state = client_state.consensus_state.clone();
+
+                (
+                    Some(AnyClientState::Solomachine(client_state)),
+                    AnyConsensusState::Solomachine(consensus_state),
+                )
+            }
+            // A localhost client tracks this very chain.
+            ClientType::Localhost => (
+                Some(AnyClientState::Localhost(LocalhostClientState::new(
+                    self.host_chain_id.clone(),
+                    client_state_height,
+                ))),
+                AnyConsensusState::Localhost(LocalhostConsensusState::new()),
+            ),
         };
         let consensus_states = vec![(cs_height, consensus_state)].into_iter().collect();
 
@@ -246,20 +294,30 @@ impl MockContext {
     }
 
     pub fn with_port_capability(mut self, port_id: PortId) -> Self {
-        self.port_capabilities.insert(port_id, Capability::new());
+        self.add_port(port_id);
         self
     }
 
     /// Associates a channel (in an arbitrary state) to this context.
+    /// The channel is also given a capability, as if it went through the opening handshake.
     pub fn with_channel(
-        self,
+        mut self,
         port_id: PortId,
         chan_id: ChannelId,
         channel_end: ChannelEnd,
     ) -> Self {
-        let mut channels = self.channels.clone();
-        channels.insert((port_id, chan_id), channel_end);
-        Self { channels, ..self }
+        let name = CapabilityName::channel(&port_id, &chan_id);
+        if self.get_capability(&name).is_none() {
+            let channel_cap = self.new_capability(name).unwrap();
+            if self.router.has_route(&port_id) {
+                self.router
+                    .claim_channel_capability(&port_id, chan_id.clone(), channel_cap)
+                    .unwrap();
+            }
+        }
+
+        self.channels.insert((port_id, chan_id), channel_end);
+        self
     }
 
     pub fn with_send_sequence(
@@ -308,6 +366,51 @@ impl MockContext {
         Self { timestamp, ..self }
     }
 
+    /// Binds the given application module to `port_id`, and hands it the capabilities of the
+    /// port and of the channels already opened on it.
+    pub fn with_module(
+        mut self,
+        port_id: PortId,
+        module: impl Module<MockContext> + 'static,
+    ) -> Self {
+        self.add_port(port_id.clone());
+        let port_cap = self.port_capability(&port_id);
+        self.router
+            .add_route(port_id.clone(), port_cap, module)
+            .unwrap();
+
+        let channel_ids: Vec<ChannelId> = self
+            .channels
+            .keys()
+            .filter(|(channel_port_id, _)| *channel_port_id == port_id)
+            .map(|(_, channel_id)| channel_id.clone())
+            .collect();
+        for channel_id in channel_ids {
+            let channel_cap = self.channel_capability(&port_id, &channel_id);
+            self.router
+                .claim_channel_capability(&port_id, channel_id, channel_cap)
+                .unwrap();
+        }
+
+        self
+    }
+
+    /// Returns the balance of the given account once credited with `coin`.
+    fn credited_balance(&self, account: &Signer, coin: &Coin) -> Result<u64, Ics20Error> {
+        self.balances
+            .get(&(account.clone(), coin.denom.clone()))
+            .copied()
+            .unwrap_or_default()
+            .checked_add(coin.amount)
+            .ok_or_else(|| Ics20Kind::BalanceOverflow(account.clone(), coin.to_string()).into())
+    }
+
+    /// Credits `coin` to the given account in the in-memory bank.
+    pub fn with_balance(mut self, account: Signer, coin: Coin) -> Self {
+        *self.balances.entry((account, coin.denom)).or_default() += coin.amount;
+        self
+    }
+
     pub fn with_height(self, target_height: Height) -> Self {
         if target_height.revision_number > self.latest_height.revision_number {
             unimplemented!()
@@ -333,7 +436,7 @@ impl MockContext {
         port_id: PortId,
         chan_id: ChannelId,
         seq: Sequence,
-        data: String,
+        data: Vec<u8>,
     ) -> Self {
         let mut packet_commitment = self.packet_commitment.clone();
         packet_commitment.insert((port_id, chan_id, seq), data);
@@ -415,8 +518,33 @@ impl MockContext {
         Ok(())
     }
 
+    /// Binds the given port, unless it is already bound.
     pub fn add_port(&mut self, port_id: PortId) {
-        self.port_capabilities.insert(port_id, Capability::new());
+        if self.lookup_module_by_port(&port_id).is_none() {
+            self.bind_port(&port_id).unwrap();
+        }
+    }
+
+    /// Returns the capability of the given port, as held by the module bound to it, or a
+    /// capability which authenticates nothing if the port is not bound.
+    pub fn port_capability(&self, port_id: &PortId) -> Capability {
+        self.lookup_module_by_port(port_id)
+            .unwrap_or_else(|| Capability::new(u64::MAX))
+    }
+
+    /// Returns the capability of the given channel, as held by the module bound to its port, or
+    /// a capability which authenticates nothing if the channel has no capability.
+    pub fn channel_capability(&self, port_id: &PortId, channel_id: &ChannelId) -> Capability {
+        self.get_capability(&CapabilityName::channel(port_id, channel_id))
+            .unwrap_or_else(|| Capability::new(u64::MAX))
+    }
+
+    /// Returns the balance of the given account for denomination `denom`.
+    pub fn balance(&self, account: &Signer, denom: &str) -> u64 {
+        self.balances
+            .get(&(account.clone(), denom.to_string()))
+            .cloned()
+            .unwrap_or_default()
     }
 
     pub fn consensus_states(&self, client_id: &ClientId) -> Vec<AnyConsensusStateWithHeight> {
@@ -431,20 +559,82 @@ impl MockContext {
     }
 }
 
-impl Ics26Context for MockContext {}
+impl Ics26Context for MockContext {
+    fn router(&self) -> &Router<Self> {
+        &self.router
+    }
+
+    fn router_mut(&mut self) -> &mut Router<Self> {
+        &mut self.router
+    }
+}
+
+impl Ics20Context for MockContext {
+    fn get_denom_trace(&self, hash: &str) -> Option<DenomTrace> {
+        self.denom_traces.get(hash).cloned()
+    }
+
+    fn store_denom_trace(&mut self, denom_trace: DenomTrace) -> Result<(), Ics20Error> {
+        self.denom_traces.insert(denom_trace.hash(), denom_trace);
+        Ok(())
+    }
+}
+
+impl BankKeeper for MockContext {
+    fn send_coins(&mut self, from: &Signer, to: &Signer, coin: &Coin) -> Result<(), Ics20Error> {
+        // Check that the recipient can be credited before debiting the sender.
+        self.credited_balance(to, coin)?;
+        self.burn_coins(from, coin)?;
+        self.mint_coins(to, coin)
+    }
+
+    fn mint_coins(&mut self, account: &Signer, coin: &Coin) -> Result<(), Ics20Error> {
+        let balance = self.credited_balance(account, coin)?;
+        self.balances
+            .insert((account.clone(), coin.denom.clone()), balance);
+        Ok(())
+    }
+
+    fn burn_coins(&mut self, account: &Signer, coin: &Coin) -> Result<(), Ics20Error> {
+        let balance = self
+            .balances
+            .get_mut(&(account.clone(), coin.denom.clone()))
+            .filter(|balance| **balance >= coin.amount)
+            .ok_or_else(|| Ics20Kind::InsufficientFunds(account.clone(), coin.to_string()))?;
+        *balance -= coin.amount;
+        Ok(())
+    }
+}
+
+impl CapabilityReader for MockContext {
+    fn get_capability(&self, name: &CapabilityName) -> Option<Capability> {
+        self.capabilities.get(name).cloned()
+    }
 
-impl Ics20Context for MockContext {}
+    fn capability_counter(&self) -> u64 {
+        self.capability_counter
+    }
+}
 
-impl PortReader for MockContext {
-    fn lookup_module_by_port(&self, port_id: &PortId) -> Option<Capability> {
-        self.port_capabilities.get(port_id).cloned()
+impl CapabilityKeeper for MockContext {
+    fn store_capability(
+        &mut self,
+        name: CapabilityName,
+        capability: Capability,
+    ) -> Result<(), Ics05Error> {
+        self.capabilities.insert(name, capability);
+        Ok(())
     }
 
-    fn authenticate(&self, _cap: &Capability, _port_id: &PortId) -> bool {
-        true
+    fn increase_capability_counter(&mut self) {
+        self.capability_counter += 1;
     }
 }
 
+impl PortReader for MockContext {}
+
+impl PortKeeper for MockContext {}
+
 impl ChannelReader for MockContext {
     fn channel_end(&self, pcid: &(PortId, ChannelId)) -> Option<ChannelEnd> {
         self.channels.get(pcid).cloned()
@@ -470,20 +660,6 @@ impl ChannelReader for MockContext {
         ClientReader::consensus_state(self, client_id, height)
     }
 
-    fn authenticated_capability(&self, port_id: &PortId) -> Result<Capability, Ics4Error> {
-        let cap = PortReader::lookup_module_by_port(self, port_id);
-        match cap {
-            Some(key) => {
-                if !PortReader::authenticate(self, &key, port_id) {
-                    Err(Ics4Kind::InvalidPortCapability.into())
-                } else {
-                    Ok(key)
-                }
-            }
-            None => Err(Ics4Kind::NoPortCapability(port_id.clone()).into()),
-        }
-    }
-
     fn get_next_sequence_send(&self, port_channel_id: &(PortId, ChannelId)) -> Option<Sequence> {
         self.next_sequence_send.get(port_channel_id).cloned()
     }
@@ -496,7 +672,7 @@ impl ChannelReader for MockContext {
         self.next_sequence_ack.get(port_channel_id).cloned()
     }
 
-    fn get_packet_commitment(&self, key: &(PortId, ChannelId, Sequence)) -> Option<String> {
+    fn get_packet_commitment(&self, key: &(PortId, ChannelId, Sequence)) -> Option<Vec<u8>> {
         self.packet_commitment.get(key).cloned()
     }
 
@@ -504,13 +680,12 @@ impl ChannelReader for MockContext {
         self.packet_receipt.get(key).cloned()
     }
 
-    fn get_packet_acknowledgement(&self, key: &(PortId, ChannelId, Sequence)) -> Option<String> {
+    fn get_packet_acknowledgement(&self, key: &(PortId, ChannelId, Sequence)) -> Option<Vec<u8>> {
         self.packet_acknowledgement.get(key).cloned()
     }
 
-    fn hash(&self, input: String) -> String {
-        let r = sha2::Sha256::digest(input.as_bytes());
-        format!("{:x}", r)
+    fn hash(&self, input: Vec<u8>) -> Vec<u8> {
+        sha2::Sha256::digest(&input).to_vec()
     }
 
     fn host_height(&self) -> Height {
@@ -534,9 +709,9 @@ impl ChannelKeeper for MockContext {
         timeout_height: Height,
         data: Vec<u8>,
     ) -> Result<(), Ics4Error> {
-        let input = format!("{:?},{:?},{:?}", timeout_timestamp, timeout_height, data);
-        self.packet_commitment
-            .insert(key, ChannelReader::hash(self, input));
+        let commitment =
+            ChannelReader::packet_commitment(self, &data, timeout_height, timeout_timestamp);
+        self.packet_commitment.insert(key, commitment);
         Ok(())
     }
 
@@ -545,9 +720,8 @@ impl ChannelKeeper for MockContext {
         key: (PortId, ChannelId, Sequence),
         ack: Vec<u8>,
     ) -> Result<(), Ics4Error> {
-        let input = format!("{:?}", ack);
-        self.packet_acknowledgement
-            .insert(key, ChannelReader::hash(self, input));
+        let commitment = ChannelReader::ack_commitment(self, &ack);
+        self.packet_acknowledgement.insert(key, commitment);
         Ok(())
     }
 
@@ -722,6 +896,14 @@ impl ClientReader for MockContext {
         }
     }
 
+    fn host_height(&self) -> Height {
+        self.latest_height
+    }
+
+    fn host_timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
     fn client_counter(&self) -> u64 {
         self.client_ids_counter
     }
@@ -812,11 +994,35 @@ impl Ics18Context for MockContext {
 
 #[cfg(test)]
 mod tests {
+    use subtle_encoding::hex;
+
+    use crate::ics04_channel::context::ChannelReader;
     use crate::ics24_host::identifier::ChainId;
     use crate::mock::context::MockContext;
     use crate::mock::host::HostType;
     use crate::Height;
 
+    #[test]
+    fn packet_commitments_match_ibc_go() {
+        let ctx = MockContext::default();
+
+        let commitment = ctx.packet_commitment(
+            b"packet data",
+            Height::new(1, 100),
+            1_620_000_000_000_000_000,
+        );
+        assert_eq!(
+            hex::encode(commitment),
+            b"bd0866b9474fe432cd2c05bd94806d0b39adf6616247cbdb1e9ca05240296028".to_vec()
+        );
+
+        let ack_commitment = ctx.ack_commitment(br#"{"result":"AQ=="}"#);
+        assert_eq!(
+            hex::encode(ack_commitment),
+            b"08f7557ed51826fe18d84512bf24ec75001edbaf2123a477df72a0a9f3640a7c".to_vec()
+        );
+    }
+
     #[test]
     fn test_history_manipulation() {
         pub struct Test {
@@ -1,10 +1,10 @@
 //! Host chain types and methods, used by context mock.
 
-use std::convert::TryFrom;
-
-use tendermint::chain::Id as TMChainId;
 use tendermint_testgen::light_block::TmLightBlock;
-use tendermint_testgen::{Generator, LightBlock as TestgenLightBlock};
+use tendermint_testgen::{
+    Commit as TestgenCommit, Generator, Header as TestgenHeader, LightBlock as TestgenLightBlock,
+    Validator as TestgenValidator,
+};
 
 use crate::ics02_client::client_consensus::AnyConsensusState;
 use crate::ics02_client::header::AnyHeader;
@@ -58,11 +58,26 @@ impl HostBlock {
         }
     }
 
+    /// Generates a Tendermint light block at `height`, signed by a fixed validator set. The chain
+    /// identifier is set before signing, so that the block passes light client verification. The
+    /// block time (in seconds since the Unix epoch) equals the height, keeping BFT time monotonic.
     pub fn generate_tm_block(chain_id: ChainId, height: u64) -> TmLightBlock {
-        let mut block = TestgenLightBlock::new_default(height).generate().unwrap();
-        block.signed_header.header.chain_id = TMChainId::try_from(chain_id.to_string()).unwrap();
+        let validators = [
+            TestgenValidator::new("1").voting_power(50),
+            TestgenValidator::new("2").voting_power(50),
+        ];
+        let header = TestgenHeader::new(&validators)
+            .height(height)
+            .chain_id(chain_id.as_str())
+            .next_validators(&validators)
+            .time(height);
+        let commit = TestgenCommit::new(header.clone(), 1);
 
-        block
+        TestgenLightBlock::new(header, commit)
+            .validators(&validators)
+            .next_validators(&validators)
+            .generate()
+            .unwrap()
     }
 }
 
@@ -108,6 +108,7 @@ impl CompileCmd {
             .format(false)
             .out_dir(out_dir)
             .extern_path(".tendermint", "::tendermint_proto")
+            .extern_path(".ics23", "::ics23")
             .compile(&protos, &includes);
 
         match compilation {
@@ -185,6 +186,7 @@ impl CompileCmd {
             .format(false)
             .out_dir(out_dir)
             .extern_path(".tendermint", "::tendermint_proto")
+            .extern_path(".ics23", "::ics23")
             .compile(&protos, &includes);
 
         match compilation {
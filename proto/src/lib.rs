@@ -149,6 +149,6 @@ pub mod ibc {
     }
 }
 
-pub mod ics23 {
-    include!("prost/ics23.rs");
-}
+/// The ICS23 proof types are those of the `ics23` crate, which also implements their
+/// verification.
+pub use ics23;
@@ -29,5 +29,5 @@ pub struct MerklePath {
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct MerkleProof {
     #[prost(message, repeated, tag="1")]
-    pub proofs: ::prost::alloc::vec::Vec<super::super::super::super::ics23::CommitmentProof>,
+    pub proofs: ::prost::alloc::vec::Vec<::ics23::CommitmentProof>,
 }
@@ -24,7 +24,7 @@ pub struct ClientState {
     pub latest_height: ::core::option::Option<super::super::super::core::client::v1::Height>,
     /// Proof specifications used in verifying counterparty state
     #[prost(message, repeated, tag="8")]
-    pub proof_specs: ::prost::alloc::vec::Vec<super::super::super::super::ics23::ProofSpec>,
+    pub proof_specs: ::prost::alloc::vec::Vec<::ics23::ProofSpec>,
     /// Path at which next upgraded client will be committed.
     /// Each element corresponds to the key for a single CommitmentProof in the chained proof.
     /// NOTE: ClientState must stored under `{upgradePath}/{upgradeHeight}/clientState`
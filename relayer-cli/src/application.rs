@@ -1,5 +1,7 @@
 //! Cli Abscissa Application
 
+use std::path::PathBuf;
+
 use abscissa_core::terminal::component::Terminal;
 use abscissa_core::{
     application::{self, AppCell},
@@ -44,6 +46,9 @@ pub struct CliApp {
 
     /// Toggle json output on/off. Changed with the global config option `-j` / `--json`.
     json_output: bool,
+
+    /// Path to the configuration file, from which the configuration is reloaded.
+    config_path: Option<PathBuf>,
 }
 
 /// Initialize a new application instance.
@@ -56,6 +61,7 @@ impl Default for CliApp {
             config: None,
             state: application::State::default(),
             json_output: false,
+            config_path: None,
         }
     }
 }
@@ -65,6 +71,11 @@ impl CliApp {
     pub fn json_output(&self) -> bool {
         self.json_output
     }
+
+    /// Path to the configuration file, if any
+    pub fn config_path(&self) -> Option<&PathBuf> {
+        self.config_path.as_ref()
+    }
 }
 
 impl Application for CliApp {
@@ -122,8 +133,11 @@ impl Application for CliApp {
     ) -> Result<Vec<Box<dyn Component<Self>>>, FrameworkError> {
         let terminal = Terminal::new(self.term_colors(command));
 
-        let config = command
-            .config_path()
+        self.config_path = command.config_path();
+
+        let config = self
+            .config_path
+            .clone()
             .map(|path| self.load_config(&path))
             .transpose()?
             .unwrap_or_default();
@@ -131,7 +145,7 @@ impl Application for CliApp {
         // Update the `json_output` flag used by `conclude::Output`
         self.json_output = command.json;
 
-        if command.json {
+        if command.json || config.global.log_json {
             // Enable JSON by using the crate-level `Tracing`
             let tracing = JsonTracing::new(config.global)?;
             Ok(vec![Box::new(terminal), Box::new(tracing)])
@@ -0,0 +1,63 @@
+//! Reference remote signer, which signs the transactions of relayers configured with a
+//! `remote_signer` using the keys of its own key rings, for testing.
+
+#![deny(warnings, missing_docs, trivial_casts, unused_qualifications)]
+#![forbid(unsafe_code)]
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use anomaly::BoxError;
+use gumdrop::Options;
+
+use ibc_relayer::config;
+use ibc_relayer::keyring::remote::{LocalSigner, SignerAddress};
+use ibc_relayer::keyring::KeyRing;
+
+/// Command-line options of the signer
+#[derive(Debug, Options)]
+struct SignerOptions {
+    #[options(help = "print this help message")]
+    help: bool,
+
+    #[options(
+        short = "c",
+        required,
+        help = "path to the configuration of the chains whose keys are served"
+    )]
+    config: PathBuf,
+
+    #[options(
+        short = "a",
+        required,
+        help = "address to listen on: unix://<path> or tcp://<host>:<port>"
+    )]
+    address: String,
+}
+
+/// Serve the keys of the chains in the configuration
+fn main() {
+    let opts = SignerOptions::parse_args_default_or_exit();
+
+    tracing_subscriber::fmt::init();
+
+    if let Err(e) = run(opts) {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+fn run(opts: SignerOptions) -> Result<(), BoxError> {
+    let config = config::parse(&opts.config)?;
+    let address: SignerAddress = opts.address.parse()?;
+
+    let mut keyrings = HashMap::new();
+    for chain in config.chains {
+        let keyring = KeyRing::new(chain.keyring_backend, chain.clone())?;
+        keyrings.insert(chain.id, keyring);
+    }
+
+    LocalSigner::new(keyrings).serve(&address)?;
+
+    Ok(())
+}
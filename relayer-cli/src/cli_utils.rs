@@ -1,4 +1,4 @@
-use ibc::ics24_host::identifier::ChainId;
+use ibc::ics24_host::identifier::{ChainId, PortId};
 use ibc_relayer::chain::runtime::ChainRuntime;
 use ibc_relayer::chain::CosmosSdkChain;
 use ibc_relayer::{chain::handle::ChainHandle, config::Config};
@@ -27,6 +27,23 @@ impl ChainHandlePair {
 
         Ok(ChainHandlePair { src, dst })
     }
+
+    /// Let the handles sign with the keys configured for the path
+    /// from `src_port_id` on the source chain, if any.
+    pub fn with_path_keys(self, config: &Config, src_port_id: &PortId) -> Self {
+        let (src_key, dst_key) = config.path_keys(&self.src.id(), &self.dst.id(), src_port_id);
+
+        ChainHandlePair {
+            src: match src_key {
+                Some(key_name) => self.src.with_key(key_name),
+                None => self.src,
+            },
+            dst: match dst_key {
+                Some(key_name) => self.dst.with_key(key_name),
+                None => self.dst,
+            },
+        }
+    }
 }
 
 /// Spawns a chain runtime from the configuration and given a chain identifier.
@@ -12,8 +12,9 @@ impl Runnable for ValidateCmd {
         let config = app_config();
         info!("Loaded configuration: {:?}", *config);
 
-        // TODO: Validate configuration
-
-        Output::with_success().exit();
+        match config.validate() {
+            Ok(()) => Output::with_success().exit(),
+            Err(e) => Output::error(format!("{}", e)).exit(),
+        }
     }
 }
@@ -2,8 +2,11 @@
 use abscissa_core::{Command, Help, Options, Runnable};
 
 mod add;
+mod delete;
 mod list;
+mod migrate;
 mod restore;
+mod show;
 
 /// `keys` subcommand
 #[derive(Command, Debug, Options, Runnable)]
@@ -16,11 +19,23 @@ pub enum KeysCmd {
     #[options(help = "Adds a key to a configured chain")]
     Add(add::KeysAddCmd),
 
+    /// The `keys delete` subcommand
+    #[options(help = "Delete a key of a configured chain")]
+    Delete(delete::KeysDeleteCmd),
+
     /// The `keys list` subcommand
     #[options(help = "List keys configured on a chain")]
     List(list::KeysListCmd),
 
+    /// The `keys migrate` subcommand
+    #[options(help = "Encrypt the keys stored in plaintext by the test backend")]
+    Migrate(migrate::KeysMigrateCmd),
+
     /// The `keys restore` subcommand
     #[options(help = "restore a key to a configured chain using a mnemonic")]
     Restore(restore::KeyRestoreCmd),
+
+    /// The `keys show` subcommand
+    #[options(help = "Show the account and public key of a key of a configured chain")]
+    Show(show::KeysShowCmd),
 }
@@ -9,7 +9,7 @@ use anomaly::BoxError;
 use ibc::ics24_host::identifier::ChainId;
 use ibc_relayer::{
     config::{ChainConfig, Config},
-    keyring::{KeyEntry, KeyRing, Store},
+    keyring::{KeyEntry, KeyRing},
 };
 
 use crate::application::app_config;
@@ -22,6 +22,12 @@ pub struct KeysAddCmd {
 
     #[options(short = "f", required, help = "path to the key file")]
     file: PathBuf,
+
+    #[options(
+        short = "n",
+        help = "name of the key (defaults to the `key_name` of the chain configuration)"
+    )]
+    name: Option<String>,
 }
 
 impl KeysAddCmd {
@@ -31,7 +37,10 @@ impl KeysAddCmd {
             .ok_or_else(|| format!("chain '{}' not found in configuration file", self.chain_id))?;
 
         Ok(KeysAddOptions {
-            name: chain_config.key_name.clone(),
+            name: self
+                .name
+                .clone()
+                .unwrap_or_else(|| chain_config.key_name.clone()),
             config: chain_config.clone(),
             file: self.file.clone(),
         })
@@ -55,7 +64,7 @@ impl Runnable for KeysAddCmd {
         };
 
         let chain_id = opts.config.id.clone();
-        let key = add_key(opts.config, &opts.file);
+        let key = add_key(opts.config, &opts.name, &opts.file);
 
         match key {
             Ok(key) => Output::success_msg(format!(
@@ -68,13 +77,13 @@ impl Runnable for KeysAddCmd {
     }
 }
 
-pub fn add_key(config: ChainConfig, file: &Path) -> Result<KeyEntry, BoxError> {
-    let mut keyring = KeyRing::new(Store::Test, config)?;
+pub fn add_key(config: ChainConfig, key_name: &str, file: &Path) -> Result<KeyEntry, BoxError> {
+    let mut keyring = KeyRing::new(config.keyring_backend, config)?;
 
     let key_contents = fs::read_to_string(file).map_err(|_| "error reading the key file")?;
     let key = keyring.key_from_seed_file(&key_contents)?;
 
-    keyring.add_key(key.clone())?;
+    keyring.add_key(key_name, key.clone())?;
 
     Ok(key)
 }
@@ -0,0 +1,66 @@
+use abscissa_core::{Command, Options, Runnable};
+use anomaly::BoxError;
+
+use ibc::ics24_host::identifier::ChainId;
+use ibc_relayer::{
+    config::{ChainConfig, Config},
+    keyring::KeyRing,
+};
+
+use crate::application::app_config;
+use crate::conclude::Output;
+
+#[derive(Clone, Command, Debug, Options)]
+pub struct KeysDeleteCmd {
+    #[options(free, required, help = "identifier of the chain")]
+    chain_id: ChainId,
+
+    #[options(short = "n", required, help = "name of the key to delete")]
+    name: String,
+}
+
+impl KeysDeleteCmd {
+    fn options(&self, config: &Config) -> Result<KeysDeleteOptions, String> {
+        let chain_config = config
+            .find_chain(&self.chain_id)
+            .ok_or_else(|| format!("chain '{}' not found in configuration file", self.chain_id))?;
+
+        Ok(KeysDeleteOptions {
+            name: self.name.clone(),
+            config: chain_config.clone(),
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct KeysDeleteOptions {
+    pub name: String,
+    pub config: ChainConfig,
+}
+
+impl Runnable for KeysDeleteCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let opts = match self.options(&config) {
+            Err(err) => return Output::error(err).exit(),
+            Ok(result) => result,
+        };
+
+        let chain_id = opts.config.id.clone();
+
+        match delete_key(opts.config, &opts.name) {
+            Ok(()) => {
+                Output::success_msg(format!("Deleted key '{}' on chain {}", opts.name, chain_id))
+                    .exit()
+            }
+            Err(e) => Output::error(format!("{}", e)).exit(),
+        }
+    }
+}
+
+pub fn delete_key(config: ChainConfig, key_name: &str) -> Result<(), BoxError> {
+    let mut keyring = KeyRing::new(config.keyring_backend, config)?;
+    keyring.remove_key(key_name)?;
+    Ok(())
+}
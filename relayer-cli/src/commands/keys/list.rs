@@ -4,7 +4,7 @@ use anomaly::BoxError;
 use ibc::ics24_host::identifier::ChainId;
 use ibc_relayer::{
     config::{ChainConfig, Config},
-    keyring::{KeyEntry, KeyRing, Store},
+    keyring::{KeyEntry, KeyRing},
 };
 
 use crate::application::app_config;
@@ -38,14 +38,25 @@ impl Runnable for KeysListCmd {
         };
 
         let chain_config = opts.chain_config.clone();
-        let key = list_keys(opts.chain_config);
-
-        match key {
-            Ok(key) => Output::success_msg(format!(
-                "chain: {} -> {} ({})",
-                chain_config.id, chain_config.key_name, key.account,
-            ))
-            .exit(),
+        let keys = list_keys(opts.chain_config);
+
+        match keys {
+            Ok(keys) if keys.is_empty() => {
+                Output::success_msg(format!("chain: {} -> no keys", chain_config.id)).exit()
+            }
+            Ok(keys) => {
+                let mut msg = format!("chain: {}", chain_config.id);
+                for (name, key) in keys {
+                    let default = if name == chain_config.key_name {
+                        " [default]"
+                    } else {
+                        ""
+                    };
+                    msg.push_str(&format!("\n- {} ({}){}", name, key.account, default));
+                }
+
+                Output::success_msg(msg).exit()
+            }
             Err(e) => Output::error(format!("{}", e)).exit(),
         }
     }
@@ -56,8 +67,8 @@ pub struct KeysListOptions {
     pub chain_config: ChainConfig,
 }
 
-pub fn list_keys(config: ChainConfig) -> Result<KeyEntry, BoxError> {
-    let keyring = KeyRing::new(Store::Test, config)?;
-    let key_entry = keyring.get_key()?;
-    Ok(key_entry)
+pub fn list_keys(config: ChainConfig) -> Result<Vec<(String, KeyEntry)>, BoxError> {
+    let keyring = KeyRing::new(config.keyring_backend, config)?;
+    let keys = keyring.keys()?;
+    Ok(keys)
 }
@@ -0,0 +1,70 @@
+use abscissa_core::{Command, Options, Runnable};
+
+use ibc::ics24_host::identifier::ChainId;
+use ibc_relayer::{
+    config::{ChainConfig, Config},
+    keyring::{self, Store},
+};
+
+use crate::application::app_config;
+use crate::conclude::Output;
+
+#[derive(Clone, Command, Debug, Options)]
+pub struct KeysMigrateCmd {
+    #[options(free, required, help = "identifier of the chain")]
+    chain_id: ChainId,
+}
+
+impl KeysMigrateCmd {
+    fn options(&self, config: &Config) -> Result<KeysMigrateOptions, String> {
+        let chain_config = config
+            .find_chain(&self.chain_id)
+            .ok_or_else(|| format!("chain '{}' not found in configuration file", self.chain_id))?;
+
+        Ok(KeysMigrateOptions {
+            config: chain_config.clone(),
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct KeysMigrateOptions {
+    pub config: ChainConfig,
+}
+
+impl Runnable for KeysMigrateCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let opts = match self.options(&config) {
+            Err(err) => return Output::error(err).exit(),
+            Ok(result) => result,
+        };
+
+        let chain_id = opts.config.id.clone();
+        let backend = opts.config.keyring_backend;
+
+        match keyring::migrate_test_keys(opts.config) {
+            Ok(keys) => {
+                let mut msg = format!(
+                    "Encrypted the keys of chain {} with the file backend:",
+                    chain_id
+                );
+
+                for (name, key) in keys {
+                    msg.push_str(&format!("\n- {} ({})", name, key.account));
+                }
+
+                if backend != Store::File {
+                    msg.push_str(&format!(
+                        "\nSet `keyring_backend = 'file'` for chain {} in the configuration to use them",
+                        chain_id
+                    ));
+                }
+
+                Output::success_msg(msg).exit()
+            }
+            Err(e) => Output::error(format!("{}", e)).exit(),
+        }
+    }
+}
@@ -4,7 +4,7 @@ use anomaly::BoxError;
 use ibc::ics24_host::identifier::ChainId;
 use ibc_relayer::{
     config::{ChainConfig, Config},
-    keyring::{KeyEntry, KeyRing, Store},
+    keyring::{KeyEntry, KeyRing},
 };
 
 use crate::application::app_config;
@@ -17,10 +17,17 @@ pub struct KeyRestoreCmd {
 
     #[options(short = "m", required, help = "mnemonic to restore the key from")]
     mnemonic: String,
+
+    #[options(
+        short = "n",
+        help = "name of the key (defaults to the `key_name` of the chain configuration)"
+    )]
+    name: Option<String>,
 }
 
 #[derive(Clone, Debug)]
 pub struct KeysRestoreOptions {
+    pub name: String,
     pub mnemonic: String,
     pub config: ChainConfig,
 }
@@ -32,6 +39,10 @@ impl KeyRestoreCmd {
             .ok_or_else(|| format!("chain '{}' not found in configuration file", self.chain_id))?;
 
         Ok(KeysRestoreOptions {
+            name: self
+                .name
+                .clone()
+                .unwrap_or_else(|| chain_config.key_name.clone()),
             mnemonic: self.mnemonic.clone(),
             config: chain_config.clone(),
         })
@@ -47,9 +58,9 @@ impl Runnable for KeyRestoreCmd {
             Ok(result) => result,
         };
 
-        let key_name = opts.config.key_name.clone();
+        let key_name = opts.name.clone();
         let chain_id = opts.config.id.clone();
-        let key = restore_key(&opts.mnemonic, opts.config);
+        let key = restore_key(&opts.mnemonic, &opts.name, opts.config);
 
         match key {
             Ok(key) => Output::success_msg(format!(
@@ -62,10 +73,14 @@ impl Runnable for KeyRestoreCmd {
     }
 }
 
-pub fn restore_key(mnemonic: &str, config: ChainConfig) -> Result<KeyEntry, BoxError> {
-    let mut keyring = KeyRing::new(Store::Test, config)?;
+pub fn restore_key(
+    mnemonic: &str,
+    key_name: &str,
+    config: ChainConfig,
+) -> Result<KeyEntry, BoxError> {
+    let mut keyring = KeyRing::new(config.keyring_backend, config)?;
     let key_entry = keyring.key_from_mnemonic(mnemonic)?;
-    keyring.add_key(key_entry.clone())?;
+    keyring.add_key(key_name, key_entry.clone())?;
 
     Ok(key_entry)
 }
@@ -0,0 +1,82 @@
+use abscissa_core::{Command, Options, Runnable};
+use anomaly::BoxError;
+use serde::Serialize;
+
+use ibc::ics24_host::identifier::ChainId;
+use ibc_relayer::{
+    config::{ChainConfig, Config},
+    keyring::KeyRing,
+};
+
+use crate::application::app_config;
+use crate::conclude::Output;
+
+#[derive(Clone, Command, Debug, Options)]
+pub struct KeysShowCmd {
+    #[options(free, required, help = "identifier of the chain")]
+    chain_id: ChainId,
+
+    #[options(
+        short = "n",
+        help = "name of the key (defaults to the `key_name` of the chain configuration)"
+    )]
+    name: Option<String>,
+}
+
+impl KeysShowCmd {
+    fn options(&self, config: &Config) -> Result<KeysShowOptions, String> {
+        let chain_config = config
+            .find_chain(&self.chain_id)
+            .ok_or_else(|| format!("chain '{}' not found in configuration file", self.chain_id))?;
+
+        Ok(KeysShowOptions {
+            name: self
+                .name
+                .clone()
+                .unwrap_or_else(|| chain_config.key_name.clone()),
+            config: chain_config.clone(),
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct KeysShowOptions {
+    pub name: String,
+    pub config: ChainConfig,
+}
+
+/// The public information about a key.
+#[derive(Clone, Debug, Serialize)]
+pub struct KeyInfo {
+    pub name: String,
+    pub account: String,
+    /// Hex-encoded compressed public key
+    pub public_key: String,
+}
+
+impl Runnable for KeysShowCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let opts = match self.options(&config) {
+            Err(err) => return Output::error(err).exit(),
+            Ok(result) => result,
+        };
+
+        match show_key(opts.config, &opts.name) {
+            Ok(info) => Output::success(info).exit(),
+            Err(e) => Output::error(format!("{}", e)).exit(),
+        }
+    }
+}
+
+pub fn show_key(config: ChainConfig, key_name: &str) -> Result<KeyInfo, BoxError> {
+    let keyring = KeyRing::new(config.keyring_backend, config)?;
+    let key = keyring.get_key(key_name)?;
+
+    Ok(KeyInfo {
+        name: key_name.to_string(),
+        account: key.account,
+        public_key: hex::encode(key.public_key.public_key.to_bytes()),
+    })
+}
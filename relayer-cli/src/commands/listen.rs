@@ -8,7 +8,7 @@ use tokio::runtime::Runtime as TokioRuntime;
 use tendermint_rpc::query::{EventType, Query};
 
 use ibc::ics24_host::identifier::ChainId;
-use ibc_relayer::{config::ChainConfig, event::monitor::*};
+use ibc_relayer::{chain::nodes::Nodes, config::ChainConfig, event::monitor::*};
 
 use crate::prelude::*;
 
@@ -74,12 +74,11 @@ fn subscribe(
     queries: Vec<Query>,
     rt: Arc<TokioRuntime>,
 ) -> Result<(EventMonitor, channel::Receiver<EventBatch>), BoxError> {
-    let (mut event_monitor, rx) = EventMonitor::new(
-        chain_config.id.clone(),
-        chain_config.websocket_addr.clone(),
-        rt,
-    )
-    .map_err(|e| format!("could not initialize event monitor: {}", e))?;
+    let nodes = Nodes::new(chain_config, &rt)
+        .map_err(|e| format!("could not initialize the nodes of the chain: {}", e))?;
+
+    let (mut event_monitor, rx) = EventMonitor::new(chain_config.id.clone(), nodes, rt)
+        .map_err(|e| format!("could not initialize event monitor: {}", e))?;
 
     event_monitor.set_queries(queries);
 
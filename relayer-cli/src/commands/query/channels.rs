@@ -41,7 +41,12 @@ impl Runnable for QueryChannelsCmd {
             pagination: ibc_proto::cosmos::base::query::pagination::all(),
         };
 
-        let res = chain.query_channels(req);
+        let res = chain.query_channels(req).map(|channels| {
+            channels
+                .into_iter()
+                .map(|ch| ch.channel_id)
+                .collect::<Vec<_>>()
+        });
 
         match res {
             Ok(ce) => Output::success(ce).exit(),
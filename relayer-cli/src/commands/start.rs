@@ -3,6 +3,7 @@ use abscissa_core::{Command, Options, Runnable};
 use ibc::ics24_host::identifier::{ChainId, ChannelId, PortId};
 use ibc_relayer::link::LinkParameters;
 use ibc_relayer::relay::{channel_relay, relay_on_new_link};
+use ibc_relayer::store::Store;
 
 use crate::cli_utils::ChainHandlePair;
 use crate::conclude::Output;
@@ -27,13 +28,30 @@ impl Runnable for StartCmd {
     fn run(&self) {
         let config = app_config();
 
+        if let Err(e) = config.validate() {
+            return Output::error(format!("{}", e)).exit();
+        }
+
         let chains = match ChainHandlePair::spawn(&config, &self.src_chain_id, &self.dst_chain_id) {
             Ok(chains) => chains,
             Err(e) => return Output::error(format!("{}", e)).exit(),
         };
 
+        let store = match config
+            .global
+            .state_dir
+            .as_ref()
+            .map(Store::open)
+            .transpose()
+        {
+            Ok(store) => store,
+            Err(e) => return Output::error(format!("{}", e)).exit(),
+        };
+
         match (&self.src_port_id, &self.src_channel_id) {
             (Some(src_port_id), Some(src_channel_id)) => {
+                let chains = chains.with_path_keys(&config, src_port_id);
+
                 match channel_relay(
                     chains.src,
                     chains.dst,
@@ -41,6 +59,7 @@ impl Runnable for StartCmd {
                         src_port_id: src_port_id.clone(),
                         src_channel_id: src_channel_id.clone(),
                     },
+                    store,
                 ) {
                     Ok(()) => Output::success(()).exit(),
                     Err(e) => Output::error(e.to_string()).exit(),
@@ -54,12 +73,15 @@ impl Runnable for StartCmd {
                     Some((connection, path)) => {
                         info!("Start relayer on {:?}", self);
 
+                        let chains = chains.with_path_keys(&config, &path.a_port);
+
                         match relay_on_new_link(
                             chains.src,
                             chains.dst,
                             connection.delay,
                             path.ordering,
                             path.clone(),
+                            store,
                         ) {
                             Ok(()) => Output::success(()).exit(),
                             Err(e) => Output::error(e.to_string()).exit(),
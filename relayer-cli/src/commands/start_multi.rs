@@ -1,11 +1,20 @@
+use std::thread;
+
 use abscissa_core::{Command, Options, Runnable};
+use crossbeam_channel::Sender;
+use itertools::Itertools;
+use signal_hook::{consts::SIGHUP, iterator::Signals};
 
 use ibc::ics24_host::identifier::ChainId;
-use ibc_relayer::{config::Config, supervisor::Supervisor};
+use ibc_relayer::{
+    config::{self, Config},
+    rest,
+    supervisor::{Supervisor, SupervisorCmd},
+    telemetry,
+};
 
 use crate::conclude::Output;
 use crate::prelude::*;
-use crate::registry::Registry;
 
 #[derive(Clone, Command, Debug, Options)]
 pub struct StartMultiCmd {
@@ -35,6 +44,11 @@ impl StartMultiCmd {
     fn cmd(&self) -> Result<Output, BoxError> {
         let options = self.validate_options()?;
         let config = &*app_config();
+        config.validate()?;
+
+        if config.telemetry.enabled {
+            telemetry::spawn(&config.telemetry.host, config.telemetry.port)?;
+        }
 
         match options {
             Opts::Specified(chain_a, chain_b) => start_specified(config, chain_a, chain_b),
@@ -59,47 +73,90 @@ fn start_specified(
 ) -> Result<Output, BoxError> {
     info!("spawning supervisor for chains {} and {}", chain_a, chain_b);
 
-    let mut registry = Registry::new(&config);
-    let chain_a = registry.get_or_spawn(chain_a)?;
-    let chain_b = registry.get_or_spawn(chain_b)?;
-
-    let supervisor = Supervisor::spawn(chain_a, chain_b)?;
+    let chain_ids = vec![chain_a.clone(), chain_b.clone()];
+    let supervisor = Supervisor::spawn(config.clone(), &chain_ids)?;
+    spawn_rest_server(config, &supervisor)?;
+    spawn_config_reloader(supervisor.sender(), move |_| Ok(chain_ids.clone()))?;
     supervisor.run()?;
 
     Ok(Output::success_msg("ok"))
 }
 
 fn start_all_connections(config: &Config) -> Result<Output, BoxError> {
-    let connections = config
-        .connections
-        .as_ref()
-        .filter(|conns| !conns.is_empty())
-        .ok_or("no connections configured")?;
-
-    let mut registry = Registry::new(config);
-
-    let result = crossbeam_utils::thread::scope(|s| {
-        for conn in connections {
-            info!(
-                "spawning supervisor for chains {} and {}",
-                conn.a_chain, conn.b_chain
-            );
-
-            let chain_a = registry.get_or_spawn(&conn.a_chain)?;
-            let chain_b = registry.get_or_spawn(&conn.b_chain)?;
-
-            s.spawn(|_| {
-                let supervisor = Supervisor::spawn(chain_a, chain_b).unwrap();
-                supervisor.run()
-            });
-        }
+    let chain_ids = connected_chains(config)?;
 
-        Ok(())
-    });
+    info!(
+        "spawning supervisor for chains {}",
+        chain_ids.iter().join(", ")
+    );
 
-    match result {
-        Ok(Ok(())) => Ok(Output::success_msg("ok")),
-        Ok(Err(e)) => Err(e),
-        Err(e) => std::panic::resume_unwind(e),
+    let supervisor = Supervisor::spawn(config.clone(), &chain_ids)?;
+    spawn_rest_server(config, &supervisor)?;
+    spawn_config_reloader(supervisor.sender(), connected_chains)?;
+    supervisor.run()?;
+
+    Ok(Output::success_msg("ok"))
+}
+
+/// Serves the REST API of the supervisor, if enabled.
+fn spawn_rest_server(config: &Config, supervisor: &Supervisor) -> Result<(), BoxError> {
+    if config.rest.enabled {
+        rest::spawn(&config.rest.host, config.rest.port, supervisor.sender())?;
     }
+
+    Ok(())
+}
+
+/// The chains of the connections defined in the configuration.
+fn connected_chains(config: &Config) -> Result<Vec<ChainId>, BoxError> {
+    let chain_ids = config.chains_in_connections();
+
+    if chain_ids.is_empty() {
+        return Err("no connections configured".into());
+    }
+
+    Ok(chain_ids)
+}
+
+/// Spawns a thread which reloads the configuration file upon `SIGHUP`, and sends the new
+/// configuration to the supervisor, along with the chains to relay between, as given
+/// by `chain_ids`. A configuration which fails to parse is refused.
+fn spawn_config_reloader<F>(supervisor: Sender<SupervisorCmd>, chain_ids: F) -> Result<(), BoxError>
+where
+    F: Fn(&Config) -> Result<Vec<ChainId>, BoxError> + Send + 'static,
+{
+    let config_path = match app_reader().config_path() {
+        Some(path) => path.clone(),
+        None => {
+            warn!("no configuration file, reloading the configuration is disabled");
+            return Ok(());
+        }
+    };
+
+    let mut signals = Signals::new(&[SIGHUP])?;
+
+    thread::spawn(move || {
+        for _ in signals.forever() {
+            info!("reloading the configuration from {}", config_path.display());
+
+            let cmd = config::parse(&config_path)
+                .map_err(BoxError::from)
+                .and_then(|config| {
+                    config.validate()?;
+                    let chain_ids = chain_ids(&config)?;
+                    Ok(SupervisorCmd::UpdateConfig { config, chain_ids })
+                });
+
+            match cmd {
+                Ok(cmd) => {
+                    if supervisor.send(cmd).is_err() {
+                        break;
+                    }
+                }
+                Err(e) => error!("refusing to reload the configuration: {}", e),
+            }
+        }
+    });
+
+    Ok(())
 }
@@ -12,7 +12,7 @@ use ibc::ics02_client::client_state::ClientState;
 use ibc::ics02_client::header::Header;
 use ibc::ics03_connection::connection::{ConnectionEnd, State};
 use ibc::ics03_connection::version::{get_compatible_versions, Version};
-use ibc::ics04_channel::channel::ChannelEnd;
+use ibc::ics04_channel::channel::{ChannelEnd, IdentifiedChannelEnd};
 use ibc::ics04_channel::packet::{PacketMsgType, Sequence};
 use ibc::ics23_commitment::commitment::{CommitmentPrefix, CommitmentProofBytes};
 use ibc::ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId};
@@ -40,6 +40,7 @@ use crate::light_client::LightClient;
 
 pub(crate) mod cosmos;
 pub mod handle;
+pub mod nodes;
 pub mod runtime;
 
 #[cfg(test)]
@@ -98,18 +99,35 @@ pub trait Chain: Sized {
     /// Returns the chain's identifier
     fn id(&self) -> &ChainId;
 
+    /// Returns the chain's configuration
+    fn config(&self) -> &ChainConfig;
+
     /// Returns the chain's keybase
     fn keybase(&self) -> &KeyRing;
 
     /// Returns the chain's keybase, mutably
     fn keybase_mut(&mut self) -> &mut KeyRing;
 
-    /// Sends one or more transactions with `msgs` to chain.
-    fn send_msgs(&mut self, proto_msgs: Vec<Any>) -> Result<Vec<IbcEvent>, Error>;
+    /// Sends one or more transactions with `msgs` to chain, signed with the key `key_name`.
+    fn send_msgs(&mut self, proto_msgs: Vec<Any>, key_name: &str) -> Result<Vec<IbcEvent>, Error>;
+
+    /// Sends each of the given batches of messages in a transaction of its own, signed with the
+    /// key `key_name`, and returns the events of each transaction, in order. By default, the
+    /// transactions are sent one after the other.
+    fn send_txs(
+        &mut self,
+        txs: Vec<Vec<Any>>,
+        key_name: &str,
+    ) -> Vec<Result<Vec<IbcEvent>, Error>> {
+        txs.into_iter()
+            .map(|proto_msgs| self.send_msgs(proto_msgs, key_name))
+            .collect()
+    }
 
-    fn get_signer(&mut self) -> Result<Signer, Error>;
+    /// The signer of the messages signed with the key `key_name`.
+    fn get_signer(&mut self, key_name: &str) -> Result<Signer, Error>;
 
-    fn get_key(&mut self) -> Result<KeyEntry, Error>;
+    fn get_key(&mut self, key_name: &str) -> Result<KeyEntry, Error>;
 
     // Queries
 
@@ -171,8 +189,11 @@ pub trait Chain: Sized {
         request: QueryConnectionChannelsRequest,
     ) -> Result<Vec<ChannelId>, Error>;
 
-    /// Performs a query to retrieve the identifiers of all channels.
-    fn query_channels(&self, request: QueryChannelsRequest) -> Result<Vec<ChannelId>, Error>;
+    /// Performs a query to retrieve all channels, along with their identifiers.
+    fn query_channels(
+        &self,
+        request: QueryChannelsRequest,
+    ) -> Result<Vec<IdentifiedChannelEnd>, Error>;
 
     fn query_channel(
         &self,
@@ -1,6 +1,12 @@
 use std::{
-    convert::TryFrom, convert::TryInto, future::Future, str::FromStr, sync::Arc, thread,
-    time::Duration,
+    collections::HashMap,
+    convert::TryFrom,
+    convert::TryInto,
+    future::Future,
+    str::FromStr,
+    sync::Arc,
+    thread,
+    time::{Duration, Instant},
 };
 
 use anomaly::fail;
@@ -9,16 +15,19 @@ use bitcoin::hashes::hex::ToHex;
 use crossbeam_channel as channel;
 use prost::Message;
 use prost_types::Any;
-use tendermint::abci::Path as TendermintABCIPath;
+use tendermint::abci::{transaction::Hash as TxHash, Code, Path as TendermintABCIPath};
 use tendermint::account::Id as AccountId;
 use tendermint::block::Height;
 use tendermint::consensus::Params;
 use tendermint_light_client::types::LightBlock as TMLightBlock;
 use tendermint_proto::Protobuf;
+use tendermint_rpc::endpoint::broadcast::tx_sync::Response as BroadcastTxSyncResponse;
+use tendermint_rpc::endpoint::tx::Response as TxResponse;
 use tendermint_rpc::query::Query;
-use tendermint_rpc::{endpoint::broadcast::tx_commit::Response, Client, HttpClient, Order};
+use tendermint_rpc::{Client, HttpClient, Order};
 use tokio::runtime::Runtime as TokioRuntime;
 use tonic::codegen::http::Uri;
+use tracing::{info, warn};
 
 use ibc::downcast;
 use ibc::events::{from_tx_response_event, IbcEvent};
@@ -28,7 +37,7 @@ use ibc::ics02_client::client_consensus::{
 use ibc::ics02_client::client_state::AnyClientState;
 use ibc::ics02_client::events as ClientEvents;
 use ibc::ics03_connection::connection::ConnectionEnd;
-use ibc::ics04_channel::channel::{ChannelEnd, QueryPacketEventDataRequest};
+use ibc::ics04_channel::channel::{ChannelEnd, IdentifiedChannelEnd, QueryPacketEventDataRequest};
 use ibc::ics04_channel::events as ChannelEvents;
 use ibc::ics04_channel::packet::{PacketMsgType, Sequence};
 use ibc::ics07_tendermint::client_state::{AllowUpdate, ClientState};
@@ -47,7 +56,10 @@ use ibc::Height as ICSHeight;
 use ibc_proto::cosmos::auth::v1beta1::{BaseAccount, QueryAccountRequest};
 use ibc_proto::cosmos::base::v1beta1::Coin;
 use ibc_proto::cosmos::tx::v1beta1::mode_info::{Single, Sum};
-use ibc_proto::cosmos::tx::v1beta1::{AuthInfo, Fee, ModeInfo, SignDoc, SignerInfo, TxBody, TxRaw};
+use ibc_proto::cosmos::tx::v1beta1::service_client::ServiceClient;
+use ibc_proto::cosmos::tx::v1beta1::{
+    AuthInfo, Fee, ModeInfo, SignDoc, SignerInfo, SimulateRequest, Tx, TxBody, TxRaw,
+};
 use ibc_proto::cosmos::upgrade::v1beta1::{
     QueryCurrentPlanRequest, QueryUpgradedConsensusStateRequest,
 };
@@ -62,11 +74,13 @@ use ibc_proto::ibc::core::connection::v1::{
     QueryClientConnectionsRequest, QueryConnectionsRequest,
 };
 
+use crate::chain::nodes::Nodes;
 use crate::chain::QueryResponse;
-use crate::config::ChainConfig;
+use crate::config::{ChainConfig, GasPrice};
 use crate::error::{Error, Kind};
 use crate::event::monitor::{EventBatch, EventMonitor};
-use crate::keyring::{KeyEntry, KeyRing, Store};
+use crate::keyring::remote::RemoteSigner;
+use crate::keyring::{KeyEntry, KeyParams, KeyRing, PublicKeyInfo, Store, TxSigner};
 use crate::light_client::tendermint::LightClient as TmLightClient;
 use crate::light_client::LightClient;
 
@@ -74,17 +88,56 @@ use super::Chain;
 use tendermint_rpc::endpoint::tx_search::ResultTx;
 
 // TODO size this properly
-const DEFAULT_MAX_GAS: u64 = 300000;
-const DEFAULT_MAX_MSG_NUM: usize = 30;
-const DEFAULT_MAX_TX_SIZE: usize = 2 * 1048576; // 2 MBytes
+const DEFAULT_GAS: u64 = 300000;
+const DEFAULT_MAX_GAS: u64 = 3000000;
+const DEFAULT_GAS_ADJUSTMENT: f64 = 1.1;
 const DEFAULT_GAS_FEE_AMOUNT: u64 = 1000;
 
+/// Number of times a transaction that ran out of gas is resent with a doubled gas limit.
+const MAX_OUT_OF_GAS_RETRIES: u32 = 3;
+/// Error code of the Cosmos SDK for transactions that run out of gas.
+const SDK_OUT_OF_GAS_CODE: u32 = 11;
+/// Error code of the Cosmos SDK for transactions signed with a wrong account sequence.
+const SDK_WRONG_SEQUENCE_CODE: u32 = 32;
+
+/// Time to wait for a broadcast transaction to be committed.
+const TX_CONFIRMATION_TIMEOUT: Duration = Duration::from_secs(30);
+/// Interval between two queries of the transactions waiting to be committed.
+const TX_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Type URL of the accounts of the Cosmos SDK.
+const BASE_ACCOUNT_TYPE_URL: &str = "/cosmos.auth.v1beta1.BaseAccount";
+/// Type URL of the accounts of Ethermint-based chains, which wrap a `BaseAccount`.
+const ETH_ACCOUNT_TYPE_URL: &str = "/ethermint.types.v1.EthAccount";
+
+/// The account of Ethermint-based chains, as defined in `ethermint/types/v1/account.proto`.
+#[derive(Clone, PartialEq, prost::Message)]
+struct EthAccount {
+    #[prost(message, optional, tag = "1")]
+    base_account: Option<BaseAccount>,
+    #[prost(string, tag = "2")]
+    code_hash: String,
+}
+
 pub struct CosmosSdkChain {
     config: ChainConfig,
-    rpc_client: HttpClient,
-    grpc_addr: Uri,
+    nodes: Arc<Nodes>,
     rt: Arc<TokioRuntime>,
     keybase: KeyRing,
+    remote_signer: Option<RemoteSigner>,
+    /// The accounts of the keys signing transactions, by address, with their next sequence
+    accounts: HashMap<String, BaseAccount>,
+}
+
+/// A transaction which was accepted in the mempool, and is waiting to be committed.
+struct PendingTx {
+    hash: TxHash,
+    /// The account which signed the transaction
+    account: String,
+    msgs: Vec<Any>,
+    gas_limit: u64,
+    /// The number of times the transaction was resent after running out of gas
+    retries: u32,
 }
 
 impl CosmosSdkChain {
@@ -93,12 +146,7 @@ impl CosmosSdkChain {
         crate::time!("unbonding_period");
 
         let mut client = self
-            .block_on(
-                ibc_proto::cosmos::staking::v1beta1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
-                ),
-            )
-            .map_err(|e| Kind::Grpc.context(e))?;
+            .grpc_client(ibc_proto::cosmos::staking::v1beta1::query_client::QueryClient::connect)?;
 
         let request =
             tonic::Request::new(ibc_proto::cosmos::staking::v1beta1::QueryParamsRequest {});
@@ -118,11 +166,23 @@ impl CosmosSdkChain {
     }
 
     fn rpc_client(&self) -> &HttpClient {
-        &self.rpc_client
+        &self.nodes.active().rpc_client
     }
 
-    pub fn config(&self) -> &ChainConfig {
-        &self.config
+    /// Connect a gRPC client to the active node or, if it cannot be reached,
+    /// to the first of the other nodes of the chain which can.
+    fn grpc_client<C, F, Fut>(&self, connect: F) -> Result<C, Error>
+    where
+        F: Fn(Uri) -> Fut,
+        Fut: Future<Output = Result<C, tonic::transport::Error>>,
+    {
+        self.block_on(self.nodes.failover(|node| connect(node.grpc_addr.clone())))
+            .map_err(|e| Kind::Grpc.context(e).into())
+    }
+
+    /// The error kind of a failed RPC request to the active node.
+    fn rpc_error(&self) -> Kind {
+        Kind::Rpc(self.nodes.active().rpc_addr.clone())
     }
 
     /// Query the consensus parameters via an RPC query
@@ -131,65 +191,312 @@ impl CosmosSdkChain {
         crate::time!("query_consensus_params");
 
         Ok(self
-            .block_on(self.rpc_client().genesis())
-            .map_err(|e| Kind::Rpc(self.config.rpc_addr.clone()).context(e))?
+            .block_on(self.nodes.failover(|node| node.rpc_client.genesis()))
+            .map_err(|e| self.rpc_error().context(e))?
             .consensus_params)
     }
 
+    /// The signer of the transactions: the remote signer if the chain has one,
+    /// or else the key ring.
+    fn signer(&self) -> &dyn TxSigner {
+        match &self.remote_signer {
+            Some(remote_signer) => remote_signer,
+            None => &self.keybase,
+        }
+    }
+
     /// Run a future to completion on the Tokio runtime.
     fn block_on<F: Future>(&self, f: F) -> F::Output {
         crate::time!("block_on");
         self.rt.block_on(f)
     }
 
-    fn send_tx(&self, proto_msgs: Vec<Any>) -> Result<Vec<IbcEvent>, Error> {
-        crate::time!("send_tx");
+    /// The account of `address`, whose sequence is cached and bumped locally for every
+    /// transaction accepted by `CheckTx`, so that several transactions can be in flight at once.
+    /// The account is queried if it is not cached.
+    fn account(&mut self, address: &str) -> Result<BaseAccount, Error> {
+        if let Some(account) = self.accounts.get(address) {
+            return Ok(account.clone());
+        }
+
+        let account = self
+            .block_on(query_account(self, address.to_string()))
+            .map_err(|e| Kind::Grpc.context(e))?;
+
+        self.accounts.insert(address.to_string(), account.clone());
 
+        Ok(account)
+    }
+
+    /// Drop the cached account of `address`, whose sequence is then queried again.
+    fn resync_account(&mut self, address: &str) {
+        self.accounts.remove(address);
+    }
+
+    /// Estimates the gas limit of a transaction with the given messages, sent with the key
+    /// `key_name` at the cached sequence of its account.
+    fn estimate_tx_gas(&mut self, proto_msgs: &[Any], key_name: &str) -> Result<u64, Error> {
         let key = self
-            .keybase()
-            .get_key()
+            .signer()
+            .public_key(key_name)
             .map_err(|e| Kind::KeyBase.context(e))?;
 
-        // Create TxBody
-        let body = TxBody {
-            messages: proto_msgs.to_vec(),
-            memo: "".to_string(),
-            timeout_height: 0_u64,
-            extension_options: Vec::<Any>::new(),
-            non_critical_extension_options: Vec::<Any>::new(),
-        };
+        let account = self.account(&key.account)?;
+        self.estimate_gas(&key, &account, &encode_tx_body(proto_msgs))
+    }
+
+    /// Signs a transaction with the given messages and gas limit, and broadcasts it with
+    /// `broadcast_tx_sync`, which returns as soon as the transaction is accepted by `CheckTx`.
+    fn broadcast_tx(
+        &mut self,
+        proto_msgs: Vec<Any>,
+        key_name: &str,
+        gas_limit: u64,
+    ) -> Result<PendingTx, Error> {
+        crate::time!("broadcast_tx");
+
+        let key = self
+            .signer()
+            .public_key(key_name)
+            .map_err(|e| Kind::KeyBase.context(e))?;
+
+        let body_buf = encode_tx_body(&proto_msgs);
+
+        let mut resynced = false;
+
+        loop {
+            let account = self.account(&key.account)?;
+            let tx_raw = self.sign_tx(key_name, &key, &account, body_buf.clone(), gas_limit)?;
+
+            let mut txraw_buf = Vec::new();
+            prost::Message::encode(&tx_raw, &mut txraw_buf).unwrap();
+
+            crate::time!("TxRAW {:?}", hex::encode(txraw_buf.clone()));
+
+            let response = self
+                .block_on(broadcast_tx_sync(self, txraw_buf))
+                .map_err(|e| self.rpc_error().context(e))?;
+
+            match response.code {
+                Code::Ok => {
+                    if let Some(account) = self.accounts.get_mut(&key.account) {
+                        account.sequence += 1;
+                    }
+
+                    return Ok(PendingTx {
+                        hash: response.hash,
+                        account: key.account,
+                        msgs: proto_msgs,
+                        gas_limit,
+                        retries: 0,
+                    });
+                }
+
+                // The cached sequence is out of date, e.g. because the account sent
+                // transactions of its own, or a transaction was dropped from the mempool.
+                Code::Err(SDK_WRONG_SEQUENCE_CODE) if !resynced => {
+                    warn!(
+                        "[{}] account sequence {} of {} is out of date, resyncing it",
+                        self.id(),
+                        account.sequence,
+                        key.account
+                    );
+
+                    self.resync_account(&key.account);
+                    resynced = true;
+                }
+
+                Code::Err(_) => {
+                    return Err(Kind::MessageTransaction(format!(
+                        "check_tx reports error: log={:?}",
+                        response.log
+                    ))
+                    .into());
+                }
+            }
+        }
+    }
+
+    /// Waits for the pending transactions to be committed, by polling the node for each of them
+    /// until they are all committed, or until [`TX_CONFIRMATION_TIMEOUT`] has elapsed.
+    /// The results are in the order of `pending`.
+    fn wait_for_txs(
+        &mut self,
+        pending: Vec<Result<PendingTx, Error>>,
+    ) -> Vec<Result<(PendingTx, TxResponse), Error>> {
+        crate::time!("wait_for_txs");
+
+        let deadline = Instant::now() + TX_CONFIRMATION_TIMEOUT;
+        // The accounts whose cached sequence may be out of date
+        let mut out_of_sync = vec![];
+
+        // The transactions which are still waited for when the deadline passes keep the
+        // timeout error they start with.
+        let mut results: Vec<Result<(PendingTx, TxResponse), Error>> =
+            Vec::with_capacity(pending.len());
+        let mut waiting = vec![];
+
+        for (index, tx) in pending.into_iter().enumerate() {
+            match tx {
+                Ok(tx) => {
+                    results.push(Err(Kind::TxConfirmationTimeout(
+                        tx.hash.to_string(),
+                        TX_CONFIRMATION_TIMEOUT,
+                    )
+                    .into()));
+                    waiting.push((index, tx));
+                }
+                Err(e) => results.push(Err(e)),
+            }
+        }
+
+        while !waiting.is_empty() {
+            // A transaction is not found until it is committed
+            waiting = waiting
+                .into_iter()
+                .filter_map(|(index, tx)| {
+                    match self.block_on(self.rpc_client().tx(tx.hash, false)) {
+                        Ok(response) => {
+                            if response.tx_result.code == Code::Err(SDK_WRONG_SEQUENCE_CODE) {
+                                out_of_sync.push(tx.account.clone());
+                            }
+
+                            results[index] = Ok((tx, response));
+                            None
+                        }
+                        Err(_) => Some((index, tx)),
+                    }
+                })
+                .collect();
+
+            if waiting.is_empty() {
+                break;
+            }
+
+            if Instant::now() >= deadline {
+                out_of_sync.extend(waiting.drain(..).map(|(_, tx)| tx.account));
+                break;
+            }
+
+            thread::sleep(TX_POLL_INTERVAL);
+        }
+
+        // The transactions which timed out may have been dropped along with their sequence
+        // numbers, and those which failed with a wrong sequence were signed with a stale one.
+        for account in out_of_sync {
+            self.resync_account(&account);
+        }
+
+        results
+    }
 
-        // A protobuf serialization of a TxBody
-        let mut body_buf = Vec::new();
-        prost::Message::encode(&body, &mut body_buf).unwrap();
+    /// The events of a committed transaction. A transaction which ran out of gas is resent
+    /// with a higher gas limit, and waited for again. Once a transaction of a pipeline was resent,
+    /// the following ones which failed are resent as well, in order, since they may have failed
+    /// only because they were committed before it, e.g. on an ORDERED channel. `resent` tells
+    /// whether a previous transaction of the pipeline was resent.
+    fn committed_tx_events(
+        &mut self,
+        key_name: &str,
+        mut tx: PendingTx,
+        mut response: TxResponse,
+        resent: &mut bool,
+    ) -> Result<Vec<IbcEvent>, Error> {
+        loop {
+            let gas_limit = if response.tx_result.code == Code::Err(SDK_OUT_OF_GAS_CODE)
+                && tx.gas_limit < self.max_gas()
+                && tx.retries < MAX_OUT_OF_GAS_RETRIES
+            {
+                let new_gas_limit = std::cmp::min(tx.gas_limit.saturating_mul(2), self.max_gas());
+                warn!(
+                    "[{}] transaction ran out of gas with gas limit {}, retrying with gas limit {}",
+                    self.id(),
+                    tx.gas_limit,
+                    new_gas_limit
+                );
+
+                new_gas_limit
+            } else if *resent && !response.tx_result.code.is_ok() && tx.retries == 0 {
+                warn!(
+                    "[{}] transaction {} failed after a previous transaction was resent, resending it",
+                    self.id(),
+                    tx.hash
+                );
+
+                tx.gas_limit
+            } else {
+                break;
+            };
 
+            *resent = true;
+
+            let retries = tx.retries + 1;
+            let retry = self
+                .broadcast_tx(tx.msgs, key_name, gas_limit)
+                .map(|retry| PendingTx { retries, ..retry });
+
+            let (confirmed_tx, confirmed_response) =
+                self.wait_for_txs(vec![retry]).pop().unwrap_or_else(|| {
+                    Err(
+                        Kind::MessageTransaction("the resent transaction has no result".into())
+                            .into(),
+                    )
+                })?;
+            tx = confirmed_tx;
+            response = confirmed_response;
+        }
+
+        let tx_hash = response.hash;
+        let events = tx_result_to_event(&self.config.id, response)?;
+
+        for event in &events {
+            if let Some(packet) = event.packet() {
+                info!(
+                    chain_id = %self.id(),
+                    tx_hash = %tx_hash,
+                    port_id = %packet.source_port,
+                    channel_id = %packet.source_channel,
+                    sequence = %packet.sequence,
+                    "committed {}",
+                    event
+                );
+            }
+        }
+
+        Ok(events)
+    }
+
+    /// Encodes the auth info of a transaction signed by the public key `key` at the sequence of
+    /// `account`, with the given gas limit. The fee is derived from the gas limit.
+    fn encode_auth_info(
+        &self,
+        key: &PublicKeyInfo,
+        account: &BaseAccount,
+        gas_limit: u64,
+    ) -> Vec<u8> {
         let mut pk_buf = Vec::new();
-        prost::Message::encode(&key.public_key.public_key.to_bytes(), &mut pk_buf).unwrap();
+        prost::Message::encode(&key.public_key, &mut pk_buf).unwrap();
 
-        crate::time!("PK {:?}", hex::encode(key.public_key.public_key.to_bytes()));
+        crate::time!("PK {:?}", hex::encode(&key.public_key));
 
         // Create a MsgSend proto Any message
         let pk_any = Any {
-            type_url: "/cosmos.crypto.secp256k1.PubKey".to_string(),
+            type_url: self.config.key_algorithm.public_key_type_url().to_string(),
             value: pk_buf,
         };
 
-        let acct_response = self
-            .block_on(query_account(self, key.account))
-            .map_err(|e| Kind::Grpc.context(e))?;
-
         let single = Single { mode: 1 };
         let sum_single = Some(Sum::Single(single));
         let mode = Some(ModeInfo { sum: sum_single });
         let signer_info = SignerInfo {
             public_key: Some(pk_any),
             mode_info: mode,
-            sequence: acct_response.sequence,
+            sequence: account.sequence,
         };
 
         let fee = Some(Fee {
-            amount: vec![self.fee()],
-            gas_limit: self.gas(),
+            amount: vec![self.fee(gas_limit)],
+            gas_limit,
             payer: "".to_string(),
             granter: "".to_string(),
         });
@@ -202,49 +509,124 @@ impl CosmosSdkChain {
         // A protobuf serialization of a AuthInfo
         let mut auth_buf = Vec::new();
         prost::Message::encode(&auth_info, &mut auth_buf).unwrap();
+        auth_buf
+    }
+
+    /// Builds and signs a transaction with the given body and gas limit, with the key `key_name`
+    /// whose public key is `key`, at the sequence of `account`.
+    fn sign_tx(
+        &self,
+        key_name: &str,
+        key: &PublicKeyInfo,
+        account: &BaseAccount,
+        body_buf: Vec<u8>,
+        gas_limit: u64,
+    ) -> Result<TxRaw, Error> {
+        let auth_buf = self.encode_auth_info(key, account, gas_limit);
 
         let sign_doc = SignDoc {
             body_bytes: body_buf.clone(),
             auth_info_bytes: auth_buf.clone(),
             chain_id: self.config.clone().id.to_string(),
-            account_number: acct_response.account_number,
+            account_number: account.account_number,
         };
 
         // A protobuf serialization of a SignDoc
         let mut signdoc_buf = Vec::new();
         prost::Message::encode(&sign_doc, &mut signdoc_buf).unwrap();
 
-        // Sign doc and broadcast
+        // Sign doc
         let signed = self
-            .keybase
-            .sign_msg(signdoc_buf)
+            .signer()
+            .sign(key_name, signdoc_buf)
             .map_err(|e| Kind::KeyBase.context(e))?;
 
-        let tx_raw = TxRaw {
+        Ok(TxRaw {
             body_bytes: body_buf,
             auth_info_bytes: auth_buf,
             signatures: vec![signed],
+        })
+    }
+
+    /// Estimates the gas limit of a transaction with the given body, by simulating it and
+    /// adjusting the gas it consumes. Falls back to the configured gas limit if the
+    /// simulation fails. The simulated transaction is not signed: the node does not verify
+    /// signatures in simulations, and only needs one per signer to account for their cost.
+    fn estimate_gas(
+        &self,
+        key: &PublicKeyInfo,
+        account: &BaseAccount,
+        body_buf: &[u8],
+    ) -> Result<u64, Error> {
+        let tx_raw = TxRaw {
+            body_bytes: body_buf.to_vec(),
+            auth_info_bytes: self.encode_auth_info(key, account, self.max_gas()),
+            signatures: vec![vec![]],
         };
 
-        let mut txraw_buf = Vec::new();
-        prost::Message::encode(&tx_raw, &mut txraw_buf).unwrap();
+        match self.simulate(tx_raw) {
+            Ok(gas_used) => Ok(adjust_gas(gas_used, self.gas_adjustment(), self.max_gas())),
+            Err(e) => {
+                warn!(
+                    "[{}] failed to simulate transaction, using the default gas limit {}: {}",
+                    self.id(),
+                    self.default_gas(),
+                    e
+                );
+                Ok(self.default_gas())
+            }
+        }
+    }
 
-        crate::time!("TxRAW {:?}", hex::encode(txraw_buf.clone()));
+    /// Simulates the transaction through the `Simulate` gRPC endpoint and returns the gas it
+    /// consumes.
+    fn simulate(&self, tx_raw: TxRaw) -> Result<u64, Error> {
+        crate::time!("simulate");
+
+        let tx = Tx {
+            body: Some(
+                TxBody::decode(tx_raw.body_bytes.as_slice()).map_err(|e| Kind::Grpc.context(e))?,
+            ),
+            auth_info: Some(
+                AuthInfo::decode(tx_raw.auth_info_bytes.as_slice())
+                    .map_err(|e| Kind::Grpc.context(e))?,
+            ),
+            signatures: tx_raw.signatures,
+        };
+
+        let mut client = self.grpc_client(ServiceClient::connect)?;
+
+        let request = tonic::Request::new(SimulateRequest { tx: Some(tx) });
 
         let response = self
-            .block_on(broadcast_tx_commit(self, txraw_buf))
-            .map_err(|e| Kind::Rpc(self.config.rpc_addr.clone()).context(e))?;
+            .block_on(client.simulate(request))
+            .map_err(|e| Kind::Grpc.context(e))?
+            .into_inner();
 
-        let res = tx_result_to_event(&self.config.id, response)?;
+        let gas_info = response
+            .gas_info
+            .ok_or_else(|| Kind::Grpc.context("none gas info".to_string()))?;
 
-        Ok(res)
+        Ok(gas_info.gas_used)
+    }
+
+    fn default_gas(&self) -> u64 {
+        self.config.gas.unwrap_or(DEFAULT_GAS)
     }
 
-    fn gas(&self) -> u64 {
-        self.config.gas.unwrap_or(DEFAULT_MAX_GAS)
+    fn max_gas(&self) -> u64 {
+        self.config.max_gas.unwrap_or(DEFAULT_MAX_GAS)
     }
 
-    fn fee(&self) -> Coin {
+    fn gas_adjustment(&self) -> f64 {
+        self.config.gas_adjustment.unwrap_or(DEFAULT_GAS_ADJUSTMENT)
+    }
+
+    fn fee(&self, gas_limit: u64) -> Coin {
+        if let Some(gas_price) = &self.config.gas_price {
+            return calculate_fee(gas_limit, gas_price);
+        }
+
         let amount = self
             .config
             .clone()
@@ -258,11 +640,15 @@ impl CosmosSdkChain {
     }
 
     fn max_msg_num(&self) -> usize {
-        self.config.max_msg_num.unwrap_or(DEFAULT_MAX_MSG_NUM)
+        self.config
+            .max_msg_num
+            .unwrap_or_else(crate::config::default::max_msg_num)
     }
 
     fn max_tx_size(&self) -> usize {
-        self.config.max_tx_size.unwrap_or(DEFAULT_MAX_TX_SIZE)
+        self.config
+            .max_tx_size
+            .unwrap_or_else(crate::config::default::max_tx_size)
     }
 
     fn query(&self, data: Path, height: ICSHeight, prove: bool) -> Result<QueryResponse, Error> {
@@ -323,37 +709,39 @@ impl Chain for CosmosSdkChain {
     type ClientState = ClientState;
 
     fn bootstrap(config: ChainConfig, rt: Arc<TokioRuntime>) -> Result<Self, Error> {
-        let rpc_client = HttpClient::new(config.rpc_addr.clone())
-            .map_err(|e| Kind::Rpc(config.rpc_addr.clone()).context(e))?;
-
-        // Initialize key store and load key
-        let keybase =
-            KeyRing::new(Store::Test, config.clone()).map_err(|e| Kind::KeyBase.context(e))?;
+        let nodes = Nodes::new(&config, &rt)?;
+
+        let remote_signer = config.remote_signer.as_ref().map(|signer| {
+            RemoteSigner::new(
+                signer.address.clone(),
+                signer.timeout,
+                config.id.clone(),
+                KeyParams::new(&config),
+            )
+        });
 
-        let grpc_addr =
-            Uri::from_str(&config.grpc_addr.to_string()).map_err(|e| Kind::Grpc.context(e))?;
+        // Initialize key store and load key. The keys are held by the remote signer if there
+        // is one, in which case the key store is left empty.
+        let store = match remote_signer {
+            Some(_) => Store::Memory,
+            None => config.keyring_backend,
+        };
+        let keybase = KeyRing::new(store, config.clone()).map_err(|e| Kind::KeyBase.context(e))?;
 
         Ok(Self {
             rt,
             config,
             keybase,
-            rpc_client,
-            grpc_addr,
+            remote_signer,
+            accounts: HashMap::new(),
+            nodes,
         })
     }
 
     fn init_light_client(&self) -> Result<Box<dyn LightClient<Self>>, Error> {
-        use tendermint_light_client::types::PeerId;
-
         crate::time!("init_light_client");
 
-        let peer_id: PeerId = self
-            .rt
-            .block_on(self.rpc_client.status())
-            .map(|s| s.node_info.id)
-            .map_err(|e| Kind::Rpc(self.config.rpc_addr.clone()).context(e))?;
-
-        let light_client = TmLightClient::from_config(&self.config, peer_id)?;
+        let light_client = TmLightClient::from_config(&self.config, self.nodes.clone());
 
         Ok(Box::new(light_client))
     }
@@ -370,11 +758,8 @@ impl Chain for CosmosSdkChain {
     > {
         crate::time!("init_event_monitor");
 
-        let (mut event_monitor, event_receiver) = EventMonitor::new(
-            self.config.id.clone(),
-            self.config.websocket_addr.clone(),
-            rt,
-        )?;
+        let (mut event_monitor, event_receiver) =
+            EventMonitor::new(self.config.id.clone(), self.nodes.clone(), rt)?;
 
         event_monitor.subscribe().unwrap();
         let monitor_thread = thread::spawn(move || event_monitor.run());
@@ -386,6 +771,10 @@ impl Chain for CosmosSdkChain {
         &self.config().id
     }
 
+    fn config(&self) -> &ChainConfig {
+        &self.config
+    }
+
     fn keybase(&self) -> &KeyRing {
         &self.keybase
     }
@@ -394,48 +783,91 @@ impl Chain for CosmosSdkChain {
         &mut self.keybase
     }
 
-    /// Send one or more transactions that include all the specified messages
-    fn send_msgs(&mut self, proto_msgs: Vec<Any>) -> Result<Vec<IbcEvent>, Error> {
+    /// Send one or more transactions that include all the specified messages.
+    /// The messages are split into transactions of at most `max_msg_num` messages and
+    /// `max_tx_size` bytes, which are sent with [`Self::send_txs`]. The transactions which
+    /// fail are reported together in the returned error.
+    fn send_msgs(&mut self, proto_msgs: Vec<Any>, key_name: &str) -> Result<Vec<IbcEvent>, Error> {
         crate::time!("send_msgs");
 
         if proto_msgs.is_empty() {
             return Ok(vec![IbcEvent::Empty("No messages to send".to_string())]);
         }
-        let mut res = vec![];
 
-        let mut n = 0;
-        let mut size = 0;
-        let mut msg_batch = vec![];
-        for msg in proto_msgs.iter() {
-            msg_batch.append(&mut vec![msg.clone()]);
-            let mut buf = Vec::new();
-            prost::Message::encode(msg, &mut buf).unwrap();
-            n += 1;
-            size += buf.len();
-            if n >= self.max_msg_num() || size >= self.max_tx_size() {
-                let mut result = self.send_tx(msg_batch)?;
-                res.append(&mut result);
-                n = 0;
-                size = 0;
-                msg_batch = vec![];
+        let batches = split_msgs(proto_msgs, self.max_msg_num(), self.max_tx_size());
+        let batch_count = batches.len();
+
+        let mut res = vec![];
+        let mut failures = vec![];
+        for (i, events) in self.send_txs(batches, key_name).into_iter().enumerate() {
+            match events {
+                Ok(mut result) => res.append(&mut result),
+                Err(e) => failures.push(format!("transaction {}/{}: {}", i + 1, batch_count, e)),
             }
         }
-        if !msg_batch.is_empty() {
-            let mut result = self.send_tx(msg_batch)?;
-            res.append(&mut result);
+
+        if !failures.is_empty() {
+            return Err(Kind::MessageTransaction(format!(
+                "failed to send {} of {} transactions: {}",
+                failures.len(),
+                batch_count,
+                failures.join("; ")
+            ))
+            .into());
         }
 
         Ok(res)
     }
 
+    /// Sends each batch of messages in a transaction of its own. The transactions are all
+    /// broadcast before waiting for any of them to be committed, so that they can be committed
+    /// in the same block.
+    fn send_txs(
+        &mut self,
+        txs: Vec<Vec<Any>>,
+        key_name: &str,
+    ) -> Vec<Result<Vec<IbcEvent>, Error>> {
+        crate::time!("send_txs");
+
+        // The gas of every transaction is estimated before any of them is broadcast, so that
+        // the simulations run at the committed sequence of the account, which the transactions
+        // in flight would otherwise get ahead of.
+        let gas_limits: Vec<_> = txs
+            .iter()
+            .map(|proto_msgs| self.estimate_tx_gas(proto_msgs, key_name))
+            .collect();
+
+        let pending: Vec<_> = txs
+            .into_iter()
+            .zip(gas_limits)
+            .map(|(proto_msgs, gas_limit)| {
+                gas_limit.and_then(|gas_limit| self.broadcast_tx(proto_msgs, key_name, gas_limit))
+            })
+            .collect();
+
+        let confirmed = self.wait_for_txs(pending);
+
+        // All the transactions are waited for, so that the failure of one of them is reported
+        // along with that of the others. They are looked at in order, so that a transaction
+        // which is resent is committed before the following ones are.
+        let mut resent = false;
+        confirmed
+            .into_iter()
+            .map(|result| {
+                result.and_then(|(tx, response)| {
+                    self.committed_tx_events(key_name, tx, response, &mut resent)
+                })
+            })
+            .collect()
+    }
+
     /// Get the account for the signer
-    fn get_signer(&mut self) -> Result<Signer, Error> {
+    fn get_signer(&mut self, key_name: &str) -> Result<Signer, Error> {
         crate::time!("get_signer");
 
-        // Get the key from key seed file
         let key = self
-            .keybase()
-            .get_key()
+            .signer()
+            .public_key(key_name)
             .map_err(|e| Kind::KeyBase.context(e))?;
 
         let bech32 = encode_to_bech32(&key.address.to_hex(), &self.config.account_prefix)?;
@@ -443,13 +875,19 @@ impl Chain for CosmosSdkChain {
     }
 
     /// Get the signing key
-    fn get_key(&mut self) -> Result<KeyEntry, Error> {
+    fn get_key(&mut self, key_name: &str) -> Result<KeyEntry, Error> {
         crate::time!("get_key");
 
+        if self.remote_signer.is_some() {
+            return Err(Kind::KeyBase
+                .context("the key is held by the remote signer of the chain")
+                .into());
+        }
+
         // Get the key from key seed file
         let key = self
             .keybase()
-            .get_key()
+            .get_key(key_name)
             .map_err(|e| Kind::KeyBase.context(e))?;
 
         Ok(key)
@@ -469,14 +907,17 @@ impl Chain for CosmosSdkChain {
         crate::time!("query_latest_height");
 
         let status = self
-            .block_on(self.rpc_client().status())
-            .map_err(|e| Kind::Rpc(self.config.rpc_addr.clone()).context(e))?;
+            .block_on(self.nodes.failover(|node| node.rpc_client.status()))
+            .map_err(|e| self.rpc_error().context(e))?;
 
         if status.sync_info.catching_up {
+            self.nodes.report_failure();
+
+            let rpc_addr = &self.nodes.active().rpc_addr;
             fail!(
-                Kind::LightClient(self.config.rpc_addr.to_string()),
+                Kind::LightClient(rpc_addr.to_string()),
                 "node at {} running chain {} not caught up",
-                self.config().rpc_addr,
+                rpc_addr,
                 self.config().id,
             );
         }
@@ -490,13 +931,8 @@ impl Chain for CosmosSdkChain {
     fn query_clients(&self, request: QueryClientStatesRequest) -> Result<Vec<ClientId>, Error> {
         crate::time!("query_chain_clients");
 
-        let mut client = self
-            .block_on(
-                ibc_proto::ibc::core::client::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
-                ),
-            )
-            .map_err(|e| Kind::Grpc.context(e))?;
+        let mut client =
+            self.grpc_client(ibc_proto::ibc::core::client::v1::query_client::QueryClient::connect)?;
 
         let request = tonic::Request::new(request);
         let response = self
@@ -541,12 +977,7 @@ impl Chain for CosmosSdkChain {
         crate::time!("query_upgraded_client_state");
 
         let mut client = self
-            .block_on(
-                ibc_proto::cosmos::upgrade::v1beta1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
-                ),
-            )
-            .map_err(|e| Kind::Grpc.context(e))?;
+            .grpc_client(ibc_proto::cosmos::upgrade::v1beta1::query_client::QueryClient::connect)?;
 
         let req = tonic::Request::new(QueryCurrentPlanRequest {});
         let response = self
@@ -589,12 +1020,7 @@ impl Chain for CosmosSdkChain {
             Height::try_from(height.revision_height).map_err(|e| Kind::InvalidHeight.context(e))?;
 
         let mut client = self
-            .block_on(
-                ibc_proto::cosmos::upgrade::v1beta1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
-                ),
-            )
-            .map_err(|e| Kind::Grpc.context(e))?;
+            .grpc_client(ibc_proto::cosmos::upgrade::v1beta1::query_client::QueryClient::connect)?;
 
         let req = tonic::Request::new(QueryUpgradedConsensusStateRequest {
             last_height: tm_height.into(),
@@ -634,13 +1060,8 @@ impl Chain for CosmosSdkChain {
     ) -> Result<Vec<AnyConsensusStateWithHeight>, Error> {
         crate::time!("query_chain_clients");
 
-        let mut client = self
-            .block_on(
-                ibc_proto::ibc::core::client::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
-                ),
-            )
-            .map_err(|e| Kind::Grpc.context(e))?;
+        let mut client =
+            self.grpc_client(ibc_proto::ibc::core::client::v1::query_client::QueryClient::connect)?;
 
         let request = tonic::Request::new(request);
         let response = self
@@ -665,13 +1086,9 @@ impl Chain for CosmosSdkChain {
     ) -> Result<Vec<ConnectionId>, Error> {
         crate::time!("query_connections");
 
-        let mut client = self
-            .block_on(
-                ibc_proto::ibc::core::connection::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
-                ),
-            )
-            .map_err(|e| Kind::Grpc.context(e))?;
+        let mut client = self.grpc_client(
+            ibc_proto::ibc::core::connection::v1::query_client::QueryClient::connect,
+        )?;
 
         let request = tonic::Request::new(request);
 
@@ -698,13 +1115,9 @@ impl Chain for CosmosSdkChain {
     ) -> Result<Vec<ConnectionId>, Error> {
         crate::time!("query_connections");
 
-        let mut client = self
-            .block_on(
-                ibc_proto::ibc::core::connection::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
-                ),
-            )
-            .map_err(|e| Kind::Grpc.context(e))?;
+        let mut client = self.grpc_client(
+            ibc_proto::ibc::core::connection::v1::query_client::QueryClient::connect,
+        )?;
 
         let request = tonic::Request::new(request);
 
@@ -742,12 +1155,7 @@ impl Chain for CosmosSdkChain {
         crate::time!("query_connection_channels");
 
         let mut client = self
-            .block_on(
-                ibc_proto::ibc::core::channel::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
-                ),
-            )
-            .map_err(|e| Kind::Grpc.context(e))?;
+            .grpc_client(ibc_proto::ibc::core::channel::v1::query_client::QueryClient::connect)?;
 
         let request = tonic::Request::new(request);
 
@@ -768,16 +1176,14 @@ impl Chain for CosmosSdkChain {
         Ok(vec_ids)
     }
 
-    fn query_channels(&self, request: QueryChannelsRequest) -> Result<Vec<ChannelId>, Error> {
-        crate::time!("query_connections");
+    fn query_channels(
+        &self,
+        request: QueryChannelsRequest,
+    ) -> Result<Vec<IdentifiedChannelEnd>, Error> {
+        crate::time!("query_channels");
 
         let mut client = self
-            .block_on(
-                ibc_proto::ibc::core::channel::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
-                ),
-            )
-            .map_err(|e| Kind::Grpc.context(e))?;
+            .grpc_client(ibc_proto::ibc::core::channel::v1::query_client::QueryClient::connect)?;
 
         let request = tonic::Request::new(request);
 
@@ -786,16 +1192,16 @@ impl Chain for CosmosSdkChain {
             .map_err(|e| Kind::Grpc.context(e))?
             .into_inner();
 
-        // TODO: add warnings for any identifiers that fail to parse (below).
+        // TODO: add warnings for any channels that fail to parse (below).
         //      similar to the parsing in `query_connection_channels`.
 
-        let ids = response
+        let channels = response
             .channels
-            .iter()
-            .filter_map(|ch| ChannelId::from_str(ch.channel_id.as_str()).ok())
+            .into_iter()
+            .filter_map(|ch| IdentifiedChannelEnd::try_from(ch).ok())
             .collect();
 
-        Ok(ids)
+        Ok(channels)
     }
 
     fn query_channel(
@@ -821,12 +1227,7 @@ impl Chain for CosmosSdkChain {
         crate::time!("query_packet_commitments");
 
         let mut client = self
-            .block_on(
-                ibc_proto::ibc::core::channel::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
-                ),
-            )
-            .map_err(|e| Kind::Grpc.context(e))?;
+            .grpc_client(ibc_proto::ibc::core::channel::v1::query_client::QueryClient::connect)?;
 
         let request = tonic::Request::new(request);
 
@@ -854,12 +1255,7 @@ impl Chain for CosmosSdkChain {
         crate::time!("query_unreceived_packets");
 
         let mut client = self
-            .block_on(
-                ibc_proto::ibc::core::channel::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
-                ),
-            )
-            .map_err(|e| Kind::Grpc.context(e))?;
+            .grpc_client(ibc_proto::ibc::core::channel::v1::query_client::QueryClient::connect)?;
 
         let request = tonic::Request::new(request);
 
@@ -880,12 +1276,7 @@ impl Chain for CosmosSdkChain {
         crate::time!("query_packet_acknowledgements");
 
         let mut client = self
-            .block_on(
-                ibc_proto::ibc::core::channel::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
-                ),
-            )
-            .map_err(|e| Kind::Grpc.context(e))?;
+            .grpc_client(ibc_proto::ibc::core::channel::v1::query_client::QueryClient::connect)?;
 
         let request = tonic::Request::new(request);
 
@@ -913,12 +1304,7 @@ impl Chain for CosmosSdkChain {
         crate::time!("query_unreceived_acknowledgements");
 
         let mut client = self
-            .block_on(
-                ibc_proto::ibc::core::channel::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
-                ),
-            )
-            .map_err(|e| Kind::Grpc.context(e))?;
+            .grpc_client(ibc_proto::ibc::core::channel::v1::query_client::QueryClient::connect)?;
 
         let request = tonic::Request::new(request);
 
@@ -938,12 +1324,7 @@ impl Chain for CosmosSdkChain {
         crate::time!("query_next_sequence_receive");
 
         let mut client = self
-            .block_on(
-                ibc_proto::ibc::core::channel::v1::query_client::QueryClient::connect(
-                    self.grpc_addr.clone(),
-                ),
-            )
-            .map_err(|e| Kind::Grpc.context(e))?;
+            .grpc_client(ibc_proto::ibc::core::channel::v1::query_client::QueryClient::connect)?;
 
         let request = tonic::Request::new(request);
 
@@ -977,15 +1358,18 @@ impl Chain for CosmosSdkChain {
 
                 for seq in &request.sequences {
                     // query first (and only) Tx that includes the event specified in the query request
+                    let query = packet_query(&request, *seq);
                     let response = self
-                        .block_on(self.rpc_client.tx_search(
-                            packet_query(&request, *seq),
-                            false,
-                            1,
-                            1, // get only the first Tx matching the query
-                            Order::Ascending,
-                        ))
-                        .map_err(|e| Kind::Grpc.context(e))?;
+                        .block_on(self.nodes.failover(|node| {
+                            node.rpc_client.tx_search(
+                                query.clone(),
+                                false,
+                                1,
+                                1, // get only the first Tx matching the query
+                                Order::Ascending,
+                            )
+                        }))
+                        .map_err(|e| self.rpc_error().context(e))?;
 
                     assert!(
                         response.txs.len() <= 1,
@@ -1013,15 +1397,18 @@ impl Chain for CosmosSdkChain {
                 // same header as the first one, otherwise a subsequent transaction would have
                 // failed on chain. Therefore only one Tx is of interest and current API returns
                 // the first one.
+                let query = header_query(&request);
                 let mut response = self
-                    .block_on(self.rpc_client.tx_search(
-                        header_query(&request),
-                        false,
-                        1,
-                        1, // get only the first Tx matching the query
-                        Order::Ascending,
-                    ))
-                    .map_err(|e| Kind::Grpc.context(e))?;
+                    .block_on(self.nodes.failover(|node| {
+                        node.rpc_client.tx_search(
+                            query.clone(),
+                            false,
+                            1,
+                            1, // get only the first Tx matching the query
+                            Order::Ascending,
+                        )
+                    }))
+                    .map_err(|e| self.rpc_error().context(e))?;
 
                 if response.txs.is_empty() {
                     return Ok(vec![]);
@@ -1368,16 +1755,17 @@ async fn abci_query(
 
     // Use the Tendermint-rs RPC client to do the query.
     let response = chain
-        .rpc_client()
-        .abci_query(Some(path), data.into_bytes(), height, prove)
+        .nodes
+        .failover(|node| {
+            node.rpc_client
+                .abci_query(Some(path.clone()), data.as_bytes(), height, prove)
+        })
         .await
-        .map_err(|e| Kind::Rpc(chain.config.rpc_addr.clone()).context(e))?;
+        .map_err(|e| chain.rpc_error().context(e))?;
 
     if !response.code.is_ok() {
         // Fail with response log.
-        return Err(Kind::Rpc(chain.config.rpc_addr.clone())
-            .context(response.log.to_string())
-            .into());
+        return Err(chain.rpc_error().context(response.log.to_string()).into());
     }
 
     if prove && response.proof.is_none() {
@@ -1396,68 +1784,149 @@ async fn abci_query(
     Ok(response)
 }
 
-/// Perform a `broadcast_tx_commit`, and return the corresponding deserialized response data.
-async fn broadcast_tx_commit(
+/// Perform a `broadcast_tx_sync`, and return the corresponding deserialized response data.
+async fn broadcast_tx_sync(
     chain: &CosmosSdkChain,
     data: Vec<u8>,
-) -> Result<Response, anomaly::Error<Kind>> {
+) -> Result<BroadcastTxSyncResponse, anomaly::Error<Kind>> {
     let response = chain
-        .rpc_client()
-        .broadcast_tx_commit(data.into())
+        .nodes
+        .failover(|node| node.rpc_client.broadcast_tx_sync(data.clone().into()))
         .await
-        .map_err(|e| Kind::Rpc(chain.config.rpc_addr.clone()).context(e))?;
+        .map_err(|e| chain.rpc_error().context(e))?;
 
     Ok(response)
 }
 
+/// The protobuf encoding of the body of a transaction with the given messages.
+fn encode_tx_body(proto_msgs: &[Any]) -> Vec<u8> {
+    let body = TxBody {
+        messages: proto_msgs.to_vec(),
+        memo: "".to_string(),
+        timeout_height: 0_u64,
+        extension_options: Vec::<Any>::new(),
+        non_critical_extension_options: Vec::<Any>::new(),
+    };
+
+    let mut body_buf = Vec::new();
+    prost::Message::encode(&body, &mut body_buf).unwrap();
+    body_buf
+}
+
+/// Splits the messages into batches of at most `max_msg_num` messages and `max_tx_size` bytes.
+/// A message larger than `max_tx_size` is sent in a batch of its own.
+fn split_msgs(msgs: Vec<Any>, max_msg_num: usize, max_tx_size: usize) -> Vec<Vec<Any>> {
+    let mut batches = vec![];
+    let mut batch = vec![];
+    let mut size = 0;
+
+    for msg in msgs {
+        let msg_size = prost::Message::encoded_len(&msg);
+
+        if !batch.is_empty() && (batch.len() >= max_msg_num || size + msg_size > max_tx_size) {
+            batches.push(std::mem::take(&mut batch));
+            size = 0;
+        }
+
+        size += msg_size;
+        batch.push(msg);
+    }
+
+    if !batch.is_empty() {
+        batches.push(batch);
+    }
+
+    batches
+}
+
+/// Multiplies the gas used by a simulation with the gas adjustment, without exceeding `max_gas`.
+/// The configuration is validated, but an adjustment below 1 or not finite is still never
+/// applied, as it would make the transaction run out of gas.
+fn adjust_gas(gas_used: u64, gas_adjustment: f64, max_gas: u64) -> u64 {
+    let gas_adjustment = if gas_adjustment.is_finite() {
+        gas_adjustment.max(1.0)
+    } else {
+        1.0
+    };
+
+    let adjusted = (gas_used as f64 * gas_adjustment).ceil() as u64;
+    std::cmp::min(adjusted, max_gas)
+}
+
+/// The fee for the gas limit at the given gas price, rounded up.
+fn calculate_fee(gas_limit: u64, gas_price: &GasPrice) -> Coin {
+    let amount = (gas_limit as f64 * gas_price.price).ceil() as u64;
+
+    Coin {
+        denom: gas_price.denom.clone(),
+        amount: amount.to_string(),
+    }
+}
+
 /// Uses the GRPC client to retrieve the account sequence
 async fn query_account(chain: &CosmosSdkChain, address: String) -> Result<BaseAccount, Error> {
-    let mut client = ibc_proto::cosmos::auth::v1beta1::query_client::QueryClient::connect(
-        chain.grpc_addr.clone(),
-    )
-    .await
-    .map_err(|e| Kind::Grpc.context(e))?;
+    let mut client = chain
+        .nodes
+        .failover(|node| {
+            ibc_proto::cosmos::auth::v1beta1::query_client::QueryClient::connect(
+                node.grpc_addr.clone(),
+            )
+        })
+        .await
+        .map_err(|e| Kind::Grpc.context(e))?;
 
-    let request = tonic::Request::new(QueryAccountRequest { address });
+    let request = tonic::Request::new(QueryAccountRequest {
+        address: address.clone(),
+    });
 
-    let response = client.account(request).await;
+    let account = client
+        .account(request)
+        .await
+        .map_err(|e| Kind::Grpc.context(e))?
+        .into_inner()
+        .account
+        .ok_or_else(|| Kind::Grpc.context(format!("no account found for {}", address)))?;
 
-    let base_account = BaseAccount::decode(
-        response
-            .map_err(|e| Kind::Grpc.context(e))?
-            .into_inner()
-            .account
-            .unwrap()
-            .value
-            .as_slice(),
-    )
-    .map_err(|e| Kind::Grpc.context(e))?;
+    decode_account(account)
+}
 
-    Ok(base_account)
+/// The `BaseAccount` of an account, which is either a `BaseAccount` or an Ethermint `EthAccount`.
+fn decode_account(account: Any) -> Result<BaseAccount, Error> {
+    match account.type_url.as_str() {
+        BASE_ACCOUNT_TYPE_URL => {
+            BaseAccount::decode(account.value.as_slice()).map_err(|e| Kind::Grpc.context(e).into())
+        }
+        ETH_ACCOUNT_TYPE_URL => EthAccount::decode(account.value.as_slice())
+            .map_err(|e| Kind::Grpc.context(e))?
+            .base_account
+            .ok_or_else(|| {
+                Kind::Grpc
+                    .context("Ethermint account without a base account")
+                    .into()
+            }),
+        type_url => Err(Kind::Grpc
+            .context(format!("unsupported account type '{}'", type_url))
+            .into()),
+    }
 }
 
+/// The IBC events of a committed transaction, or a `ChainError` event if it failed.
 pub fn tx_result_to_event(
     chain_id: &ChainId,
-    response: Response,
+    response: TxResponse,
 ) -> Result<Vec<IbcEvent>, anomaly::Error<Kind>> {
     let mut result = vec![];
 
-    // Verify the return codes from check_tx and deliver_tx
-    if response.check_tx.code.is_err() {
-        return Ok(vec![IbcEvent::ChainError(format!(
-            "check_tx reports error: log={:?}",
-            response.check_tx.log
-        ))]);
-    }
-    if response.deliver_tx.code.is_err() {
+    // Verify the return code from deliver_tx
+    if response.tx_result.code.is_err() {
         return Ok(vec![IbcEvent::ChainError(format!(
             "deliver_tx reports error: log={:?}",
-            response.deliver_tx.log
+            response.tx_result.log
         ))]);
     }
 
     let height = ICSHeight::new(chain_id.version(), u64::from(response.height));
-    for event in response.deliver_tx.events {
+    for event in response.tx_result.events {
         if let Some(ibc_ev) = from_tx_response_event(height, &event) {
             result.push(ibc_ev);
         }
@@ -1474,3 +1943,102 @@ fn encode_to_bech32(address: &str, account_prefix: &str) -> Result<String, Error
 
     Ok(encoded)
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::config::GasPrice;
+
+    use ibc_proto::cosmos::auth::v1beta1::BaseAccount;
+    use prost_types::Any;
+
+    use super::{
+        adjust_gas, calculate_fee, decode_account, split_msgs, EthAccount, BASE_ACCOUNT_TYPE_URL,
+        ETH_ACCOUNT_TYPE_URL,
+    };
+
+    #[test]
+    fn adjusted_gas_is_capped() {
+        assert_eq!(adjust_gas(100_000, 1.1, 300_000), 110_000);
+        assert_eq!(adjust_gas(100_001, 1.5, 300_000), 150_002);
+        assert_eq!(adjust_gas(250_000, 1.3, 300_000), 300_000);
+
+        // Adjustments which would lower the simulated gas are ignored.
+        assert_eq!(adjust_gas(100_000, 0.5, 300_000), 100_000);
+        assert_eq!(adjust_gas(100_000, f64::NAN, 300_000), 100_000);
+        assert_eq!(adjust_gas(100_000, f64::INFINITY, 300_000), 100_000);
+    }
+
+    #[test]
+    fn fee_is_rounded_up() {
+        let gas_price = GasPrice::new(0.025, "stake".to_string());
+
+        let fee = calculate_fee(200_000, &gas_price);
+        assert_eq!(fee.denom, "stake");
+        assert_eq!(fee.amount, "5000");
+
+        let fee = calculate_fee(200_001, &gas_price);
+        assert_eq!(fee.amount, "5001");
+    }
+
+    #[test]
+    fn msgs_are_split_by_count_and_size() {
+        let msg = |len: usize| Any {
+            type_url: "/test".to_string(),
+            value: vec![0; len],
+        };
+        let size = |msg: &Any| prost::Message::encoded_len(msg);
+
+        let batches = split_msgs((0..7).map(|_| msg(10)).collect(), 3, 1024);
+        let lengths: Vec<usize> = batches.iter().map(|b| b.len()).collect();
+        assert_eq!(lengths, vec![3, 3, 1]);
+
+        let max_tx_size = 2 * size(&msg(100));
+        let batches = split_msgs(
+            vec![msg(100), msg(100), msg(100), msg(500)],
+            30,
+            max_tx_size,
+        );
+        let lengths: Vec<usize> = batches.iter().map(|b| b.len()).collect();
+        assert_eq!(lengths, vec![2, 1, 1]);
+        assert!(batches
+            .iter()
+            .filter(|b| b.len() > 1)
+            .all(|b| b.iter().map(size).sum::<usize>() <= max_tx_size));
+    }
+
+    #[test]
+    fn accounts_are_decoded_by_type() {
+        fn any(type_url: &str, msg: &impl prost::Message) -> Any {
+            let mut value = vec![];
+            prost::Message::encode(msg, &mut value).unwrap();
+            Any {
+                type_url: type_url.to_string(),
+                value,
+            }
+        }
+
+        let base_account = BaseAccount {
+            address: "evmos1npvwllfr9dqr8erajqqr6s0vxnk2ak55re90dz".to_string(),
+            pub_key: None,
+            account_number: 7,
+            sequence: 42,
+        };
+
+        let account = any(BASE_ACCOUNT_TYPE_URL, &base_account);
+        assert_eq!(decode_account(account).unwrap(), base_account);
+
+        let eth_account = EthAccount {
+            base_account: Some(base_account.clone()),
+            code_hash: "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+                .to_string(),
+        };
+        let account = any(ETH_ACCOUNT_TYPE_URL, &eth_account);
+        assert_eq!(decode_account(account).unwrap(), base_account);
+
+        let account = any(
+            "/cosmos.vesting.v1beta1.DelayedVestingAccount",
+            &base_account,
+        );
+        assert!(decode_account(account).is_err());
+    }
+}
@@ -14,7 +14,7 @@ use ibc::{
     ics02_client::header::AnyHeader,
     ics03_connection::{connection::ConnectionEnd, version::Version},
     ics04_channel::{
-        channel::ChannelEnd,
+        channel::{ChannelEnd, IdentifiedChannelEnd},
         packet::{PacketMsgType, Sequence},
     },
     ics23_commitment::commitment::CommitmentPrefix,
@@ -24,14 +24,16 @@ use ibc::{
     Height,
 };
 use ibc_proto::ibc::core::channel::v1::{
-    PacketState, QueryNextSequenceReceiveRequest, QueryPacketAcknowledgementsRequest,
-    QueryPacketCommitmentsRequest, QueryUnreceivedAcksRequest, QueryUnreceivedPacketsRequest,
+    PacketState, QueryChannelsRequest, QueryNextSequenceReceiveRequest,
+    QueryPacketAcknowledgementsRequest, QueryPacketCommitmentsRequest, QueryUnreceivedAcksRequest,
+    QueryUnreceivedPacketsRequest,
 };
 use ibc_proto::ibc::core::client::v1::QueryClientStatesRequest;
 use ibc_proto::ibc::core::client::v1::QueryConsensusStatesRequest;
 use ibc_proto::ibc::core::commitment::v1::MerkleProof;
 pub use prod::ProdChainHandle;
 
+use crate::config::ChainConfig;
 use crate::connection::ConnectionMsgType;
 use crate::keyring::KeyEntry;
 use crate::{error::Error, event::monitor::EventBatch};
@@ -62,17 +64,30 @@ pub enum ChainRequest {
 
     SendMsgs {
         proto_msgs: Vec<prost_types::Any>,
+        key_name: Option<String>,
         reply_to: ReplyTo<Vec<IbcEvent>>,
     },
 
+    SendTxs {
+        txs: Vec<Vec<prost_types::Any>>,
+        key_name: Option<String>,
+        reply_to: ReplyTo<Vec<Result<Vec<IbcEvent>, Error>>>,
+    },
+
     Signer {
+        key_name: Option<String>,
         reply_to: ReplyTo<Signer>,
     },
 
     Key {
+        key_name: Option<String>,
         reply_to: ReplyTo<KeyEntry>,
     },
 
+    Config {
+        reply_to: ReplyTo<ChainConfig>,
+    },
+
     ModuleVersion {
         port_id: PortId,
         reply_to: ReplyTo<String>,
@@ -155,6 +170,11 @@ pub enum ChainRequest {
         reply_to: ReplyTo<ConnectionEnd>,
     },
 
+    QueryChannels {
+        request: QueryChannelsRequest,
+        reply_to: ReplyTo<Vec<IdentifiedChannelEnd>>,
+    },
+
     QueryChannel {
         port_id: PortId,
         channel_id: ChannelId,
@@ -236,13 +256,29 @@ pub trait ChainHandle: DynClone + Send + Sync + Debug {
 
     fn subscribe(&self) -> Result<Subscription, Error>;
 
+    /// Stop the chain runtime, after which all requests to it fail.
+    fn shutdown(&self) -> Result<(), Error>;
+
+    /// A handle to the same chain runtime, which signs the transactions it sends with the
+    /// key `key_name` instead of the key configured for the chain.
+    fn with_key(&self, key_name: String) -> Box<dyn ChainHandle>;
+
     /// Send a transaction with `msgs` to chain.
     fn send_msgs(&self, proto_msgs: Vec<prost_types::Any>) -> Result<Vec<IbcEvent>, Error>;
 
+    /// Send each batch of messages in a transaction of its own, all of them being in flight at
+    /// once. Returns the events of each transaction, in order.
+    fn send_txs(
+        &self,
+        txs: Vec<Vec<prost_types::Any>>,
+    ) -> Result<Vec<Result<Vec<IbcEvent>, Error>>, Error>;
+
     fn get_signer(&self) -> Result<Signer, Error>;
 
     fn get_key(&self) -> Result<KeyEntry, Error>;
 
+    fn config(&self) -> Result<ChainConfig, Error>;
+
     fn module_version(&self, port_id: &PortId) -> Result<String, Error>;
 
     fn query_latest_height(&self) -> Result<Height, Error>;
@@ -285,6 +321,11 @@ pub trait ChainHandle: DynClone + Send + Sync + Debug {
         request: QueryNextSequenceReceiveRequest,
     ) -> Result<Sequence, Error>;
 
+    fn query_channels(
+        &self,
+        request: QueryChannelsRequest,
+    ) -> Result<Vec<IdentifiedChannelEnd>, Error>;
+
     fn query_channel(
         &self,
         port_id: &PortId,
@@ -13,7 +13,7 @@ use ibc::{
     ics02_client::header::AnyHeader,
     ics03_connection::connection::ConnectionEnd,
     ics03_connection::version::Version,
-    ics04_channel::channel::ChannelEnd,
+    ics04_channel::channel::{ChannelEnd, IdentifiedChannelEnd},
     ics23_commitment::commitment::CommitmentPrefix,
     ics24_host::identifier::ChainId,
     ics24_host::identifier::ChannelId,
@@ -23,13 +23,15 @@ use ibc::{
     Height,
 };
 use ibc_proto::ibc::core::channel::v1::{
-    PacketState, QueryNextSequenceReceiveRequest, QueryPacketAcknowledgementsRequest,
-    QueryPacketCommitmentsRequest, QueryUnreceivedAcksRequest, QueryUnreceivedPacketsRequest,
+    PacketState, QueryChannelsRequest, QueryNextSequenceReceiveRequest,
+    QueryPacketAcknowledgementsRequest, QueryPacketCommitmentsRequest, QueryUnreceivedAcksRequest,
+    QueryUnreceivedPacketsRequest,
 };
 use ibc_proto::ibc::core::client::v1::{QueryClientStatesRequest, QueryConsensusStatesRequest};
 use ibc_proto::ibc::core::commitment::v1::MerkleProof;
 
 use crate::{
+    config::ChainConfig,
     connection::ConnectionMsgType,
     error::{Error, Kind},
     keyring::KeyEntry,
@@ -44,6 +46,10 @@ pub struct ProdChainHandle {
 
     /// The handle's channel for sending requests to the runtime
     runtime_sender: channel::Sender<ChainRequest>,
+
+    /// The key signing the transactions sent through this handle,
+    /// the key configured for the chain if `None`
+    key_name: Option<String>,
 }
 
 impl ProdChainHandle {
@@ -51,6 +57,7 @@ impl ProdChainHandle {
         Self {
             chain_id,
             runtime_sender: sender,
+            key_name: None,
         }
     }
 
@@ -79,19 +86,52 @@ impl ChainHandle for ProdChainHandle {
         self.send(|reply_to| ChainRequest::Subscribe { reply_to })
     }
 
+    fn shutdown(&self) -> Result<(), Error> {
+        self.send(|reply_to| ChainRequest::Terminate { reply_to })
+    }
+
+    fn with_key(&self, key_name: String) -> Box<dyn ChainHandle> {
+        Box::new(Self {
+            key_name: Some(key_name),
+            ..self.clone()
+        })
+    }
+
     fn send_msgs(&self, proto_msgs: Vec<prost_types::Any>) -> Result<Vec<IbcEvent>, Error> {
         self.send(|reply_to| ChainRequest::SendMsgs {
             proto_msgs,
+            key_name: self.key_name.clone(),
+            reply_to,
+        })
+    }
+
+    fn send_txs(
+        &self,
+        txs: Vec<Vec<prost_types::Any>>,
+    ) -> Result<Vec<Result<Vec<IbcEvent>, Error>>, Error> {
+        self.send(|reply_to| ChainRequest::SendTxs {
+            txs,
+            key_name: self.key_name.clone(),
             reply_to,
         })
     }
 
     fn get_signer(&self) -> Result<Signer, Error> {
-        self.send(|reply_to| ChainRequest::Signer { reply_to })
+        self.send(|reply_to| ChainRequest::Signer {
+            key_name: self.key_name.clone(),
+            reply_to,
+        })
     }
 
     fn get_key(&self) -> Result<KeyEntry, Error> {
-        self.send(|reply_to| ChainRequest::Key { reply_to })
+        self.send(|reply_to| ChainRequest::Key {
+            key_name: self.key_name.clone(),
+            reply_to,
+        })
+    }
+
+    fn config(&self) -> Result<ChainConfig, Error> {
+        self.send(|reply_to| ChainRequest::Config { reply_to })
     }
 
     fn module_version(&self, port_id: &PortId) -> Result<String, Error> {
@@ -169,6 +209,13 @@ impl ChainHandle for ProdChainHandle {
         self.send(|reply_to| ChainRequest::QueryNextSequenceReceive { request, reply_to })
     }
 
+    fn query_channels(
+        &self,
+        request: QueryChannelsRequest,
+    ) -> Result<Vec<IdentifiedChannelEnd>, Error> {
+        self.send(|reply_to| ChainRequest::QueryChannels { request, reply_to })
+    }
+
     fn query_channel(
         &self,
         port_id: &PortId,
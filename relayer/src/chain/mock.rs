@@ -13,7 +13,7 @@ use ibc::events::IbcEvent;
 use ibc::ics02_client::client_consensus::AnyConsensusStateWithHeight;
 use ibc::ics02_client::client_state::AnyClientState;
 use ibc::ics03_connection::connection::ConnectionEnd;
-use ibc::ics04_channel::channel::ChannelEnd;
+use ibc::ics04_channel::channel::{ChannelEnd, IdentifiedChannelEnd};
 use ibc::ics04_channel::packet::{PacketMsgType, Sequence};
 use ibc::ics07_tendermint::client_state::{AllowUpdate, ClientState as TendermintClientState};
 use ibc::ics07_tendermint::consensus_state::ConsensusState as TendermintConsensusState;
@@ -94,6 +94,10 @@ impl Chain for MockChain {
         &self.config.id
     }
 
+    fn config(&self) -> &ChainConfig {
+        &self.config
+    }
+
     fn keybase(&self) -> &KeyRing {
         unimplemented!()
     }
@@ -102,7 +106,7 @@ impl Chain for MockChain {
         unimplemented!()
     }
 
-    fn send_msgs(&mut self, proto_msgs: Vec<Any>) -> Result<Vec<IbcEvent>, Error> {
+    fn send_msgs(&mut self, proto_msgs: Vec<Any>, _key_name: &str) -> Result<Vec<IbcEvent>, Error> {
         // Use the ICS18Context interface to submit the set of messages.
         let events = self
             .context
@@ -112,11 +116,11 @@ impl Chain for MockChain {
         Ok(events)
     }
 
-    fn get_signer(&mut self) -> Result<Signer, Error> {
+    fn get_signer(&mut self, _key_name: &str) -> Result<Signer, Error> {
         Ok(get_dummy_account_id())
     }
 
-    fn get_key(&mut self) -> Result<KeyEntry, Error> {
+    fn get_key(&mut self, _key_name: &str) -> Result<KeyEntry, Error> {
         unimplemented!()
     }
 
@@ -184,7 +188,10 @@ impl Chain for MockChain {
         unimplemented!()
     }
 
-    fn query_channels(&self, _request: QueryChannelsRequest) -> Result<Vec<ChannelId>, Error> {
+    fn query_channels(
+        &self,
+        _request: QueryChannelsRequest,
+    ) -> Result<Vec<IdentifiedChannelEnd>, Error> {
         unimplemented!()
     }
 
@@ -359,8 +366,15 @@ pub mod test_utils {
             rpc_timeout: crate::config::default::rpc_timeout(),
             account_prefix: "".to_string(),
             key_name: "".to_string(),
+            keyring_backend: Default::default(),
+            hd_path: Default::default(),
+            key_algorithm: Default::default(),
+            remote_signer: None,
             store_prefix: "".to_string(),
             gas: None,
+            max_gas: None,
+            gas_adjustment: None,
+            gas_price: None,
             fee_denom: "stake".to_string(),
             fee_amount: Some(1000),
             max_msg_num: None,
@@ -368,6 +382,8 @@ pub mod test_utils {
             clock_drift: Duration::from_secs(5),
             trusting_period: Duration::from_secs(14 * 24 * 60 * 60), // 14 days
             trust_threshold: Default::default(),
+            fallback_nodes: vec![],
+            packet_filter: Default::default(),
         }
     }
 }
@@ -0,0 +1,365 @@
+//! Health checking of the full nodes of a chain, and failover between them.
+//!
+//! The nodes are checked with a `/status` RPC query, and the one used by the relayer is
+//! switched whenever it is unreachable, catching up, or lagging behind the others by
+//! more than [`MAX_NODE_LAG`] blocks. The node it is switched to is the first healthy one,
+//! in the order they are configured, the primary node first.
+
+use std::fmt::Display;
+use std::future::Future;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tendermint::node::Id as PeerId;
+use tendermint_rpc::{Client, HttpClient, Url};
+use tokio::runtime::Runtime as TokioRuntime;
+use tokio::sync::Notify;
+use tonic::codegen::http::Uri;
+use tracing::{debug, error, warn};
+
+use ibc::ics24_host::identifier::ChainId;
+
+use crate::config::{ChainConfig, NodeConfig};
+use crate::error::{Error, Kind};
+
+/// Interval between two health checks of the nodes of a chain
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Number of blocks a node can lag behind the most up-to-date node and still be used
+pub const MAX_NODE_LAG: u64 = 5;
+
+/// A full node of a chain
+pub struct Node {
+    pub rpc_addr: Url,
+    pub websocket_addr: Url,
+    pub grpc_addr: Uri,
+    pub rpc_client: HttpClient,
+    peer_id: Mutex<Option<PeerId>>,
+}
+
+impl Node {
+    fn new(config: &NodeConfig) -> Result<Self, Error> {
+        let rpc_client = HttpClient::new(config.rpc_addr.clone())
+            .map_err(|e| Kind::Rpc(config.rpc_addr.clone()).context(e))?;
+
+        let grpc_addr =
+            Uri::from_str(&config.grpc_addr.to_string()).map_err(|e| Kind::Grpc.context(e))?;
+
+        Ok(Self {
+            rpc_addr: config.rpc_addr.clone(),
+            websocket_addr: config.websocket_addr.clone(),
+            grpc_addr,
+            rpc_client,
+            peer_id: Mutex::new(None),
+        })
+    }
+
+    /// The identifier of the node, as of its last health check.
+    pub fn peer_id(&self) -> Option<PeerId> {
+        *self.peer_id.lock().unwrap()
+    }
+
+    async fn status(&self, timeout: Duration) -> Option<NodeStatus> {
+        let status = match tokio::time::timeout(timeout, self.rpc_client.status()).await {
+            Ok(Ok(status)) => status,
+            Ok(Err(e)) => {
+                debug!("node at {} is unreachable: {}", self.rpc_addr, e);
+                return None;
+            }
+            Err(_) => {
+                debug!("node at {} did not respond in {:?}", self.rpc_addr, timeout);
+                return None;
+            }
+        };
+
+        *self.peer_id.lock().unwrap() = Some(status.node_info.id);
+
+        Some(NodeStatus {
+            latest_height: status.sync_info.latest_block_height.value(),
+            catching_up: status.sync_info.catching_up,
+        })
+    }
+}
+
+/// The status of a node, as reported by its `/status` RPC endpoint
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct NodeStatus {
+    pub latest_height: u64,
+    pub catching_up: bool,
+}
+
+/// The full nodes of a chain, one of which is active, i.e. used for queries and transactions.
+pub struct Nodes {
+    chain_id: ChainId,
+    nodes: Vec<Node>,
+    active: AtomicUsize,
+    timeout: Duration,
+    recheck: Notify,
+}
+
+impl Nodes {
+    /// Create the nodes of the chain `config`, select the active one, and keep
+    /// checking their health in the background if the chain has fallback nodes.
+    pub fn new(config: &ChainConfig, rt: &TokioRuntime) -> Result<Arc<Self>, Error> {
+        let nodes = config
+            .nodes()
+            .iter()
+            .map(Node::new)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let nodes = Arc::new(Self {
+            chain_id: config.id.clone(),
+            nodes,
+            active: AtomicUsize::new(0),
+            timeout: config.rpc_timeout,
+            recheck: Notify::new(),
+        });
+
+        rt.block_on(nodes.check_health());
+
+        if nodes.nodes.len() > 1 {
+            let weak = Arc::downgrade(&nodes);
+            rt.spawn(async move {
+                while let Some(nodes) = weak.upgrade() {
+                    let _ =
+                        tokio::time::timeout(HEALTH_CHECK_INTERVAL, nodes.recheck.notified()).await;
+                    nodes.check_health().await;
+                }
+            });
+        }
+
+        Ok(nodes)
+    }
+
+    /// The node currently in use.
+    pub fn active(&self) -> &Node {
+        &self.nodes[self.active.load(Ordering::SeqCst)]
+    }
+
+    /// The nodes of the chain, the active one first.
+    pub fn by_preference(&self) -> impl Iterator<Item = &Node> {
+        let active = self.active.load(Ordering::SeqCst);
+        std::iter::once(&self.nodes[active]).chain(
+            self.nodes
+                .iter()
+                .enumerate()
+                .filter(move |(index, _)| *index != active)
+                .map(|(_, node)| node),
+        )
+    }
+
+    /// Report that a request to the active node failed,
+    /// which triggers a health check of the nodes.
+    pub fn report_failure(&self) {
+        if self.nodes.len() > 1 {
+            self.recheck.notify_one();
+        }
+    }
+
+    /// Run `request` against the active node and, should it fail, against each of the
+    /// other nodes in turn until one of them succeeds. The failure of the active node is
+    /// reported, and its error is returned if none of the nodes succeeds.
+    pub async fn failover<'a, T, E, F, Fut>(&'a self, mut request: F) -> Result<T, E>
+    where
+        F: FnMut(&'a Node) -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+        E: Display,
+    {
+        let mut nodes = self.by_preference();
+        let active = nodes.next().expect("a chain has at least one node");
+
+        let error = match request(active).await {
+            Ok(response) => return Ok(response),
+            Err(e) => e,
+        };
+
+        self.report_failure();
+
+        for node in nodes {
+            warn!(
+                chain.id = %self.chain_id,
+                "request to node {} failed: {}, retrying with node {}",
+                active.rpc_addr, error, node.rpc_addr
+            );
+
+            match request(node).await {
+                Ok(response) => return Ok(response),
+                Err(e) => debug!("request to node {} failed: {}", node.rpc_addr, e),
+            }
+        }
+
+        Err(error)
+    }
+
+    /// Check the status of all the nodes, and switch the active node
+    /// if it is not healthy, provided that another one is.
+    pub async fn check_health(&self) {
+        let statuses =
+            futures::future::join_all(self.nodes.iter().map(|node| node.status(self.timeout)))
+                .await;
+
+        let current = self.active.load(Ordering::SeqCst);
+
+        match select_node(current, &statuses, MAX_NODE_LAG) {
+            Some(selected) if selected != current => {
+                warn!(
+                    chain.id = %self.chain_id,
+                    "switching from node {} ({}) to node {} ({})",
+                    self.nodes[current].rpc_addr,
+                    describe(statuses[current]),
+                    self.nodes[selected].rpc_addr,
+                    describe(statuses[selected]),
+                );
+
+                self.active.store(selected, Ordering::SeqCst);
+            }
+            Some(_) => {}
+            None => {
+                error!(
+                    chain.id = %self.chain_id,
+                    "none of the nodes is healthy, keeping node {}",
+                    self.nodes[current].rpc_addr
+                );
+            }
+        }
+    }
+}
+
+fn describe(status: Option<NodeStatus>) -> String {
+    match status {
+        None => "unreachable".to_string(),
+        Some(status) if status.catching_up => {
+            format!("catching up at height {}", status.latest_height)
+        }
+        Some(status) => format!("at height {}", status.latest_height),
+    }
+}
+
+/// Select the node to use given the status of every node, or `None` if none is healthy.
+/// A node is healthy when it is reachable, not catching up, and at most `max_lag` blocks
+/// behind the most up-to-date node. The current node is kept as long as it is healthy,
+/// otherwise the first healthy node is selected.
+fn select_node(current: usize, statuses: &[Option<NodeStatus>], max_lag: u64) -> Option<usize> {
+    let best_height = statuses
+        .iter()
+        .flatten()
+        .filter(|status| !status.catching_up)
+        .map(|status| status.latest_height)
+        .max()?;
+
+    let is_healthy = |index: usize| match statuses[index] {
+        Some(status) => !status.catching_up && status.latest_height + max_lag >= best_height,
+        None => false,
+    };
+
+    if is_healthy(current) {
+        return Some(current);
+    }
+
+    (0..statuses.len()).find(|index| is_healthy(*index))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+    use std::time::Duration;
+
+    use tokio::sync::Notify;
+
+    use ibc::ics24_host::identifier::ChainId;
+
+    use super::{select_node, Node, NodeStatus, Nodes};
+    use crate::config::NodeConfig;
+
+    fn at(latest_height: u64) -> Option<NodeStatus> {
+        Some(NodeStatus {
+            latest_height,
+            catching_up: false,
+        })
+    }
+
+    fn catching_up(latest_height: u64) -> Option<NodeStatus> {
+        Some(NodeStatus {
+            latest_height,
+            catching_up: true,
+        })
+    }
+
+    #[test]
+    fn select_healthy_node() {
+        // The current node is kept while it is healthy
+        assert_eq!(select_node(1, &[at(100), at(98)], 5), Some(1));
+        // Switch away from an unreachable node
+        assert_eq!(select_node(0, &[None, at(100)], 5), Some(1));
+        // Switch away from a node which is catching up
+        assert_eq!(select_node(0, &[catching_up(200), at(100)], 5), Some(1));
+        // Switch away from a node which is lagging behind, to the first healthy node
+        assert_eq!(select_node(2, &[at(97), at(100), at(90)], 5), Some(0));
+        // No node is healthy
+        assert_eq!(select_node(0, &[None, catching_up(100)], 5), None);
+    }
+
+    fn nodes(count: u16, active: usize) -> Nodes {
+        let nodes = (0..count)
+            .map(|index| {
+                let port = 26657 + index;
+                Node::new(&NodeConfig {
+                    rpc_addr: format!("http://127.0.0.1:{}", port).parse().unwrap(),
+                    websocket_addr: format!("ws://127.0.0.1:{}/websocket", port)
+                        .parse()
+                        .unwrap(),
+                    grpc_addr: format!("http://127.0.0.1:{}", 9090 + index)
+                        .parse()
+                        .unwrap(),
+                })
+                .unwrap()
+            })
+            .collect();
+
+        Nodes {
+            chain_id: ChainId::new("chain".to_string(), 0),
+            nodes,
+            active: AtomicUsize::new(active),
+            timeout: Duration::from_secs(1),
+            recheck: Notify::new(),
+        }
+    }
+
+    #[test]
+    fn requests_fail_over_to_the_other_nodes() {
+        let nodes = nodes(3, 1);
+        let port = |node: &Node| node.rpc_addr.port();
+
+        // The active node is tried first
+        let mut tried = vec![];
+        let response = futures::executor::block_on(nodes.failover(|node| {
+            tried.push(port(node));
+            async move { Ok::<_, String>(port(node)) }
+        }));
+        assert_eq!(response, Ok(26658));
+        assert_eq!(tried, vec![26658]);
+
+        // The other nodes are tried in turn when it fails
+        let mut tried = vec![];
+        let response = futures::executor::block_on(nodes.failover(|node| {
+            tried.push(port(node));
+            async move {
+                match port(node) {
+                    26659 => Ok(port(node)),
+                    _ => Err(format!("node {} is down", port(node))),
+                }
+            }
+        }));
+        assert_eq!(response, Ok(26659));
+        assert_eq!(tried, vec![26658, 26657, 26659]);
+
+        // The error of the active node is returned when all of them fail
+        let response =
+            futures::executor::block_on(nodes.failover(|node| async move {
+                Err::<u16, _>(format!("node {} is down", port(node)))
+            }));
+        assert_eq!(response, Err("node 26658 is down".to_string()));
+    }
+}
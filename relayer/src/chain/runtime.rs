@@ -1,4 +1,4 @@
-use std::{sync::Arc, thread};
+use std::{sync::Arc, thread, time::Instant};
 
 use crossbeam_channel as channel;
 use tokio::runtime::Runtime as TokioRuntime;
@@ -15,7 +15,7 @@ use ibc::{
     },
     ics03_connection::connection::ConnectionEnd,
     ics03_connection::version::Version,
-    ics04_channel::channel::ChannelEnd,
+    ics04_channel::channel::{ChannelEnd, IdentifiedChannelEnd},
     ics04_channel::packet::{PacketMsgType, Sequence},
     ics23_commitment::commitment::CommitmentPrefix,
     ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId},
@@ -27,8 +27,9 @@ use ibc::{
 use ibc_proto::ibc::core::client::v1::{QueryClientStatesRequest, QueryConsensusStatesRequest};
 use ibc_proto::ibc::core::{
     channel::v1::{
-        PacketState, QueryNextSequenceReceiveRequest, QueryPacketAcknowledgementsRequest,
-        QueryPacketCommitmentsRequest, QueryUnreceivedAcksRequest, QueryUnreceivedPacketsRequest,
+        PacketState, QueryChannelsRequest, QueryNextSequenceReceiveRequest,
+        QueryPacketAcknowledgementsRequest, QueryPacketCommitmentsRequest,
+        QueryUnreceivedAcksRequest, QueryUnreceivedPacketsRequest,
     },
     commitment::v1::MerkleProof,
 };
@@ -40,6 +41,7 @@ use crate::{
     event::{bus::EventBus, monitor::EventBatch},
     keyring::KeyEntry,
     light_client::LightClient,
+    telemetry,
 };
 
 use super::{
@@ -170,16 +172,24 @@ impl<C: Chain + Send + 'static> ChainRuntime<C> {
                             self.subscribe(reply_to)?
                         },
 
-                        Ok(ChainRequest::SendMsgs { proto_msgs, reply_to }) => {
-                            self.send_msgs(proto_msgs, reply_to)?
+                        Ok(ChainRequest::SendMsgs { proto_msgs, key_name, reply_to }) => {
+                            self.send_msgs(proto_msgs, key_name, reply_to)?
                         },
 
-                        Ok(ChainRequest::Signer { reply_to }) => {
-                            self.get_signer(reply_to)?
+                        Ok(ChainRequest::SendTxs { txs, key_name, reply_to }) => {
+                            self.send_txs(txs, key_name, reply_to)?
+                        },
+
+                        Ok(ChainRequest::Signer { key_name, reply_to }) => {
+                            self.get_signer(key_name, reply_to)?
+                        }
+
+                        Ok(ChainRequest::Key { key_name, reply_to }) => {
+                            self.get_key(key_name, reply_to)?
                         }
 
-                        Ok(ChainRequest::Key { reply_to }) => {
-                            self.get_key(reply_to)?
+                        Ok(ChainRequest::Config { reply_to }) => {
+                            self.config(reply_to)?
                         }
 
                         Ok(ChainRequest::ModuleVersion { port_id, reply_to }) => {
@@ -246,6 +256,10 @@ impl<C: Chain + Send + 'static> ChainRuntime<C> {
                             self.query_connection(connection_id, height, reply_to)?
                         },
 
+                        Ok(ChainRequest::QueryChannels { request, reply_to }) => {
+                            self.query_channels(request, reply_to)?
+                        },
+
                         Ok(ChainRequest::QueryChannel { port_id, channel_id, height, reply_to }) => {
                             self.query_channel(port_id, channel_id, height, reply_to)?
                         },
@@ -299,6 +313,11 @@ impl<C: Chain + Send + 'static> ChainRuntime<C> {
         Ok(())
     }
 
+    /// The key `key_name` requested by a handle, or else the key configured for the chain.
+    fn key_name(&self, key_name: Option<String>) -> String {
+        key_name.unwrap_or_else(|| self.chain.config().key_name.clone())
+    }
+
     fn subscribe(&mut self, reply_to: ReplyTo<Subscription>) -> Result<(), Error> {
         let subscription = self.event_bus.subscribe();
 
@@ -312,9 +331,17 @@ impl<C: Chain + Send + 'static> ChainRuntime<C> {
     fn send_msgs(
         &mut self,
         proto_msgs: Vec<prost_types::Any>,
+        key_name: Option<String>,
         reply_to: ReplyTo<Vec<IbcEvent>>,
     ) -> Result<(), Error> {
-        let result = self.chain.send_msgs(proto_msgs);
+        let key_name = self.key_name(key_name);
+
+        let start = Instant::now();
+        let result = self.chain.send_msgs(proto_msgs, &key_name);
+
+        if let Some(metrics) = telemetry::metrics() {
+            metrics.record_tx_result(self.chain.id(), start.elapsed(), &result);
+        }
 
         reply_to
             .send(result)
@@ -323,6 +350,30 @@ impl<C: Chain + Send + 'static> ChainRuntime<C> {
         Ok(())
     }
 
+    fn send_txs(
+        &mut self,
+        txs: Vec<Vec<prost_types::Any>>,
+        key_name: Option<String>,
+        reply_to: ReplyTo<Vec<Result<Vec<IbcEvent>, Error>>>,
+    ) -> Result<(), Error> {
+        let key_name = self.key_name(key_name);
+
+        let start = Instant::now();
+        let results = self.chain.send_txs(txs, &key_name);
+
+        if let Some(metrics) = telemetry::metrics() {
+            for result in &results {
+                metrics.record_tx_result(self.chain.id(), start.elapsed(), result);
+            }
+        }
+
+        reply_to
+            .send(Ok(results))
+            .map_err(|e| Kind::Channel.context(e))?;
+
+        Ok(())
+    }
+
     fn query_latest_height(&self, reply_to: ReplyTo<Height>) -> Result<(), Error> {
         let latest_height = self.chain.query_latest_height();
 
@@ -333,8 +384,13 @@ impl<C: Chain + Send + 'static> ChainRuntime<C> {
         Ok(())
     }
 
-    fn get_signer(&mut self, reply_to: ReplyTo<Signer>) -> Result<(), Error> {
-        let result = self.chain.get_signer();
+    fn get_signer(
+        &mut self,
+        key_name: Option<String>,
+        reply_to: ReplyTo<Signer>,
+    ) -> Result<(), Error> {
+        let key_name = self.key_name(key_name);
+        let result = self.chain.get_signer(&key_name);
 
         reply_to
             .send(result)
@@ -343,8 +399,23 @@ impl<C: Chain + Send + 'static> ChainRuntime<C> {
         Ok(())
     }
 
-    fn get_key(&mut self, reply_to: ReplyTo<KeyEntry>) -> Result<(), Error> {
-        let result = self.chain.get_key();
+    fn get_key(
+        &mut self,
+        key_name: Option<String>,
+        reply_to: ReplyTo<KeyEntry>,
+    ) -> Result<(), Error> {
+        let key_name = self.key_name(key_name);
+        let result = self.chain.get_key(&key_name);
+
+        reply_to
+            .send(result)
+            .map_err(|e| Kind::Channel.context(e))?;
+
+        Ok(())
+    }
+
+    fn config(&self, reply_to: ReplyTo<ChainConfig>) -> Result<(), Error> {
+        let result = Ok(self.chain.config().clone());
 
         reply_to
             .send(result)
@@ -596,6 +667,20 @@ impl<C: Chain + Send + 'static> ChainRuntime<C> {
         Ok(())
     }
 
+    fn query_channels(
+        &self,
+        request: QueryChannelsRequest,
+        reply_to: ReplyTo<Vec<IdentifiedChannelEnd>>,
+    ) -> Result<(), Error> {
+        let result = self.chain.query_channels(request);
+
+        reply_to
+            .send(result)
+            .map_err(|e| Kind::Channel.context(e))?;
+
+        Ok(())
+    }
+
     fn query_channel(
         &self,
         port_id: PortId,
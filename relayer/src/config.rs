@@ -1,13 +1,23 @@
 //! Relayer configuration
 
-use std::{fs, fs::File, io::Write, path::Path, time::Duration};
-
+use std::{
+    convert::TryFrom,
+    fmt, fs,
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration,
+};
+
+use itertools::Itertools;
 use serde_derive::{Deserialize, Serialize};
 use tendermint_light_client::types::TrustThreshold;
 
-use ibc::ics24_host::identifier::{ChainId, PortId};
+use ibc::ics24_host::identifier::{ChainId, ChannelId, PortId};
 
 use crate::error;
+use crate::keyring;
 use ibc::ics04_channel::channel::Order;
 
 /// Defaults for various fields
@@ -33,11 +43,31 @@ pub mod default {
     pub fn channel_ordering() -> Order {
         Order::Unordered
     }
+
+    pub fn max_msg_num() -> usize {
+        30
+    }
+
+    pub fn max_tx_size() -> usize {
+        2 * 1048576 // 2 MBytes
+    }
+
+    pub fn remote_signer_timeout() -> Duration {
+        Duration::from_secs(10)
+    }
+
+    pub fn clear_packets_interval() -> Duration {
+        Duration::from_secs(0) // only clear pending packets on startup
+    }
 }
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct Config {
     pub global: GlobalConfig,
+    #[serde(default)]
+    pub telemetry: TelemetryConfig,
+    #[serde(default)]
+    pub rest: RestConfig,
     #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
     pub chains: Vec<ChainConfig>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -71,6 +101,96 @@ impl Config {
             }
         })
     }
+
+    /// The names of the keys signing the transactions which relay the packets sent from
+    /// `src_port` on `src_chain` to `dst_chain`, on the source and on the destination chain
+    /// respectively. They are taken from the first connection between the chains, and from
+    /// its path with that port if any. `None` stands for the `key_name` of the chain.
+    pub fn path_keys(
+        &self,
+        src_chain: &ChainId,
+        dst_chain: &ChainId,
+        src_port: &PortId,
+    ) -> (Option<String>, Option<String>) {
+        for conn in self.connections.iter().flatten() {
+            let src_is_a = if conn.a_chain == *src_chain && conn.b_chain == *dst_chain {
+                true
+            } else if conn.b_chain == *src_chain && conn.a_chain == *dst_chain {
+                false
+            } else {
+                continue;
+            };
+
+            let path = conn.paths.iter().flatten().find(|path| {
+                if src_is_a {
+                    path.a_port == *src_port
+                } else {
+                    path.b_port == *src_port
+                }
+            });
+
+            let a_key = path
+                .and_then(|path| path.a_key_name.clone())
+                .or_else(|| conn.a_key_name.clone());
+            let b_key = path
+                .and_then(|path| path.b_key_name.clone())
+                .or_else(|| conn.b_key_name.clone());
+
+            return if src_is_a {
+                (a_key, b_key)
+            } else {
+                (b_key, a_key)
+            };
+        }
+
+        (None, None)
+    }
+
+    /// The identifiers of the chains of all configured connections, without duplicates.
+    pub fn chains_in_connections(&self) -> Vec<ChainId> {
+        self.connections
+            .iter()
+            .flatten()
+            .flat_map(|conn| vec![conn.a_chain.clone(), conn.b_chain.clone()])
+            .unique()
+            .collect()
+    }
+
+    /// Checks that the chain identifiers are unique, that the gas adjustments are at least 1,
+    /// and that the connections are between configured chains.
+    pub fn validate(&self) -> Result<(), error::Error> {
+        for (i, chain) in self.chains.iter().enumerate() {
+            if self.chains[..i].iter().any(|c| c.id == chain.id) {
+                return Err(error::Kind::Config
+                    .context(format!("duplicate chain id {}", chain.id))
+                    .into());
+            }
+
+            if let Some(gas_adjustment) = chain.gas_adjustment {
+                if !gas_adjustment.is_finite() || gas_adjustment < 1.0 {
+                    return Err(error::Kind::Config
+                        .context(format!(
+                            "invalid gas adjustment {} for chain {}",
+                            gas_adjustment, chain.id
+                        ))
+                        .into());
+                }
+            }
+        }
+
+        for chain_id in self.chains_in_connections() {
+            if self.find_chain(&chain_id).is_none() {
+                return Err(error::Kind::Config
+                    .context(format!(
+                        "connection with chain {}, which is missing from the configuration",
+                        chain_id
+                    ))
+                    .into());
+            }
+        }
+
+        Ok(())
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -93,6 +213,22 @@ pub struct GlobalConfig {
     /// All valid log levels, as defined in tracing:
     /// https://docs.rs/tracing-core/0.1.17/tracing_core/struct.Level.html
     pub log_level: String,
+
+    /// Emit the logs as JSON objects, one per line, with the fields of the
+    /// log events and of their enclosing spans, e.g. the identifiers of the
+    /// relayed channel and the sequence of the relayed packet.
+    #[serde(default)]
+    pub log_json: bool,
+
+    /// How often the workers of the supervisor look for the pending packets of
+    /// their channel, besides the scan of the open channels performed on startup.
+    /// Zero disables the periodic scan.
+    #[serde(default = "default::clear_packets_interval", with = "humantime_serde")]
+    pub clear_packets_interval: Duration,
+
+    /// Directory of the store in which the relayer persists its state, to resume
+    /// relaying after a restart. The state is not persisted if unset.
+    pub state_dir: Option<PathBuf>,
 }
 
 impl Default for GlobalConfig {
@@ -100,11 +236,51 @@ impl Default for GlobalConfig {
         Self {
             strategy: Strategy::default(),
             log_level: "info".to_string(),
+            log_json: false,
+            clear_packets_interval: default::clear_packets_interval(),
+            state_dir: None,
         }
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+/// Settings of the telemetry service, which serves Prometheus metrics on
+/// `http://<host>:<port>/metrics`.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct TelemetryConfig {
+    pub enabled: bool,
+    pub host: String,
+    pub port: u16,
+}
+
+impl Default for TelemetryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            host: "127.0.0.1".to_string(),
+            port: 3001,
+        }
+    }
+}
+
+/// Settings of the REST API, which is served on `http://<host>:<port>`.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct RestConfig {
+    pub enabled: bool,
+    pub host: String,
+    pub port: u16,
+}
+
+impl Default for RestConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            host: "127.0.0.1".to_string(),
+            port: 3000,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub struct ChainConfig {
     pub id: ChainId,
     pub rpc_addr: tendermint_rpc::Url,
@@ -114,8 +290,28 @@ pub struct ChainConfig {
     pub rpc_timeout: Duration,
     pub account_prefix: String,
     pub key_name: String,
+    /// The backend storing the key of the relayer on this chain: `test` for a plaintext key
+    /// file, for testing only, or `file` for a key file encrypted with a passphrase.
+    #[serde(default)]
+    pub keyring_backend: keyring::Store,
+    /// The BIP-32 path from which keys are derived from a mnemonic, whose second component
+    /// is the coin type, e.g. `m/44'/60'/0'/0/0` for coin type 60.
+    #[serde(default)]
+    pub hd_path: keyring::HdPath,
+    /// The algorithm of the keys: `secp256k1`, or `eth_secp256k1` for Ethermint-based chains.
+    #[serde(default)]
+    pub key_algorithm: keyring::KeyAlgorithm,
     pub store_prefix: String,
+    /// The gas limit of a transaction whose gas consumption could not be simulated.
     pub gas: Option<u64>,
+    /// The gas limit of a transaction never exceeds this value, even after adjustment.
+    pub max_gas: Option<u64>,
+    /// The simulated gas consumption of a transaction is multiplied by this factor, which is at
+    /// least 1.
+    pub gas_adjustment: Option<f64>,
+    /// If set, the transaction fee is the gas limit times the gas price, otherwise it is
+    /// `fee_amount` of `fee_denom`.
+    pub gas_price: Option<GasPrice>,
     pub fee_denom: String,
     pub fee_amount: Option<u64>,
     pub max_msg_num: Option<usize>,
@@ -126,6 +322,197 @@ pub struct ChainConfig {
     pub trusting_period: Duration,
     #[serde(default)]
     pub trust_threshold: TrustThreshold,
+    /// Full nodes of the chain to fail over to when the node at `rpc_addr` is down
+    /// or lagging behind.
+    #[serde(default)]
+    pub fallback_nodes: Vec<NodeConfig>,
+    #[serde(default)]
+    pub packet_filter: PacketFilter,
+    /// If set, transactions are signed by this remote signer, which holds the keys of the
+    /// relayer on this chain, instead of with the keys of `keyring_backend`.
+    pub remote_signer: Option<RemoteSignerConfig>,
+}
+
+impl ChainConfig {
+    /// The addresses of all the full nodes of the chain, the primary one first.
+    pub fn nodes(&self) -> Vec<NodeConfig> {
+        let primary = NodeConfig {
+            rpc_addr: self.rpc_addr.clone(),
+            websocket_addr: self.websocket_addr.clone(),
+            grpc_addr: self.grpc_addr.clone(),
+        };
+
+        std::iter::once(primary)
+            .chain(self.fallback_nodes.iter().cloned())
+            .collect()
+    }
+}
+
+/// The addresses of a full node of a chain, e.g.:
+///
+/// ```toml
+/// [[chains.fallback_nodes]]
+/// rpc_addr = 'http://127.0.0.1:26557'
+/// websocket_addr = 'ws://127.0.0.1:26557/websocket'
+/// grpc_addr = 'http://127.0.0.1:9091'
+/// ```
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct NodeConfig {
+    pub rpc_addr: tendermint_rpc::Url,
+    pub websocket_addr: tendermint_rpc::Url,
+    pub grpc_addr: tendermint_rpc::Url,
+}
+
+/// The remote signer of the transactions of a chain, e.g.:
+///
+/// ```toml
+/// [chains.remote_signer]
+/// address = 'unix:///run/hermes/signer.sock'
+/// timeout = '10s'
+/// ```
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct RemoteSignerConfig {
+    /// `unix://<path>` for a Unix socket, or `tcp://<host>:<port>`
+    pub address: keyring::remote::SignerAddress,
+    #[serde(default = "default::remote_signer_timeout", with = "humantime_serde")]
+    pub timeout: Duration,
+}
+
+/// Selects the channels of a chain whose packets are relayed, by their port and channel
+/// identifiers, e.g.:
+///
+/// ```toml
+/// [chains.packet_filter]
+/// policy = 'allow'
+/// list = [['transfer', 'channel-0'], ['ica*', 'channel-1']]
+/// ```
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "policy", content = "list", rename_all = "snake_case")]
+pub enum PacketFilter {
+    /// Only relay the packets of the listed channels.
+    Allow(Vec<(PortPattern, ChannelId)>),
+    /// Relay the packets of all channels but the listed ones.
+    Deny(Vec<(PortPattern, ChannelId)>),
+    /// Relay the packets of all channels.
+    AllowAll,
+}
+
+impl Default for PacketFilter {
+    fn default() -> Self {
+        Self::AllowAll
+    }
+}
+
+impl PacketFilter {
+    /// Whether the packets of the channel `channel_id` bound to `port_id` may be relayed.
+    pub fn is_allowed(&self, port_id: &PortId, channel_id: &ChannelId) -> bool {
+        let listed = |list: &[(PortPattern, ChannelId)]| {
+            list.iter()
+                .any(|(port, channel)| channel == channel_id && port.matches(port_id))
+        };
+
+        match self {
+            Self::Allow(list) => listed(list),
+            Self::Deny(list) => !listed(list),
+            Self::AllowAll => true,
+        }
+    }
+}
+
+/// A port identifier, in which `*` stands for any sequence of characters.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct PortPattern(String);
+
+impl PortPattern {
+    pub fn new(pattern: String) -> Self {
+        Self(pattern)
+    }
+
+    pub fn matches(&self, port_id: &PortId) -> bool {
+        wildcard_match(&self.0, port_id.as_str())
+    }
+}
+
+/// Matches `value` against `pattern`, in which `*` stands for any sequence of characters.
+fn wildcard_match(pattern: &str, value: &str) -> bool {
+    // Without any wildcard, the pattern must match exactly.
+    if !pattern.contains('*') {
+        return pattern == value;
+    }
+
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    if !value.starts_with(first) {
+        return false;
+    }
+
+    let mut rest = &value[first.len()..];
+    let mut parts: Vec<&str> = parts.collect();
+    let last = parts.pop().unwrap_or_default();
+
+    for part in parts {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+/// The price of a unit of gas in a given denomination, written as in `0.025stake`.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct GasPrice {
+    pub price: f64,
+    pub denom: String,
+}
+
+impl GasPrice {
+    pub fn new(price: f64, denom: String) -> Self {
+        Self { price, denom }
+    }
+}
+
+impl FromStr for GasPrice {
+    type Err = error::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let split = s
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .ok_or_else(|| {
+                error::Kind::Config.context(format!("missing denom in gas price {}", s))
+            })?;
+        let (price, denom) = s.split_at(split);
+
+        let price = price
+            .parse::<f64>()
+            .map_err(|e| error::Kind::Config.context(format!("invalid gas price {}: {}", s, e)))?;
+
+        Ok(GasPrice::new(price, denom.to_string()))
+    }
+}
+
+impl TryFrom<String> for GasPrice {
+    type Error = error::Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<GasPrice> for String {
+    fn from(value: GasPrice) -> Self {
+        value.to_string()
+    }
+}
+
+impl fmt::Display for GasPrice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.price, self.denom)
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -134,6 +521,12 @@ pub struct Connection {
     pub b_chain: ChainId,
     #[serde(default = "default::connection_delay", with = "humantime_serde")]
     pub delay: Duration,
+    /// The key signing the transactions relaying over this connection on `a_chain`,
+    /// instead of the `key_name` of the chain.
+    pub a_key_name: Option<String>,
+    /// The key signing the transactions relaying over this connection on `b_chain`,
+    /// instead of the `key_name` of the chain.
+    pub b_key_name: Option<String>,
     pub paths: Option<Vec<RelayPath>>,
 }
 
@@ -143,6 +536,12 @@ pub struct RelayPath {
     pub b_port: PortId,
     #[serde(default = "default::channel_ordering")]
     pub ordering: Order,
+    /// The key signing the transactions relaying over this path on the `a_chain`
+    /// of the connection, instead of the `a_key_name` of the connection.
+    pub a_key_name: Option<String>,
+    /// The key signing the transactions relaying over this path on the `b_chain`
+    /// of the connection, instead of the `b_key_name` of the connection.
+    pub b_key_name: Option<String>,
 }
 
 /// Attempt to load and parse the TOML config file as a `Config`.
@@ -180,7 +579,11 @@ pub(crate) fn store_writer(config: &Config, mut writer: impl Write) -> Result<()
 
 #[cfg(test)]
 mod tests {
-    use super::{parse, store_writer};
+    use std::str::FromStr;
+
+    use ibc::ics24_host::identifier::{ChainId, ChannelId, PortId};
+
+    use super::{parse, store_writer, GasPrice, PacketFilter, PortPattern};
 
     #[test]
     fn parse_valid_config() {
@@ -207,4 +610,112 @@ mod tests {
         let result = store_writer(&config, &mut buffer);
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn validate_config() {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/config/fixtures/relayer_conf_example.toml"
+        );
+
+        let mut config = parse(path).expect("could not parse config");
+        assert!(config.validate().is_ok());
+
+        for gas_adjustment in &[-0.1, 0.0, 0.9, f64::NAN, f64::INFINITY] {
+            let mut invalid = config.clone();
+            invalid.chains[0].gas_adjustment = Some(*gas_adjustment);
+            assert!(invalid.validate().is_err());
+        }
+
+        let duplicate = config.chains[0].clone();
+        config.chains.push(duplicate);
+        assert!(config.validate().is_err());
+
+        config.chains.clear();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn keys_of_paths() {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/config/fixtures/relayer_conf_example.toml"
+        );
+
+        let config = parse(path).expect("could not parse config");
+        let chain_a = ChainId::from_str("chain_A").unwrap();
+        let chain_b = ChainId::from_str("chain_B").unwrap();
+        let chain_c = ChainId::from_str("chain_C").unwrap();
+        let transfer = PortId::from_str("transfer").unwrap();
+        let other = PortId::from_str("other").unwrap();
+
+        let key = |name: &str| Some(name.to_string());
+
+        // The key of the path takes precedence over the key of the connection
+        assert_eq!(
+            config.path_keys(&chain_a, &chain_b, &transfer),
+            (key("transfer_a"), key("relayer_b"))
+        );
+        assert_eq!(
+            config.path_keys(&chain_b, &chain_a, &transfer),
+            (key("relayer_b"), key("transfer_a"))
+        );
+        assert_eq!(
+            config.path_keys(&chain_a, &chain_b, &other),
+            (None, key("relayer_b"))
+        );
+        assert_eq!(
+            config.path_keys(&chain_a, &chain_c, &transfer),
+            (None, None)
+        );
+    }
+
+    #[test]
+    fn parse_gas_price() {
+        let gas_price: GasPrice = "0.025stake".parse().unwrap();
+        assert_eq!(gas_price, GasPrice::new(0.025, "stake".to_string()));
+        assert_eq!(gas_price.to_string(), "0.025stake");
+
+        assert!("stake".parse::<GasPrice>().is_err());
+        assert!("0.025".parse::<GasPrice>().is_err());
+        assert!("0.0.25stake".parse::<GasPrice>().is_err());
+    }
+
+    #[test]
+    fn packet_filter_policies() {
+        let transfer = PortId::from_str("transfer").unwrap();
+        let ica = PortId::from_str("icacontroller-alice").unwrap();
+        let channel_0 = ChannelId::from_str("channel-0").unwrap();
+        let channel_1 = ChannelId::from_str("channel-1").unwrap();
+
+        let list = vec![
+            (PortPattern::new("transfer".to_string()), channel_0.clone()),
+            (PortPattern::new("ica*".to_string()), channel_1.clone()),
+        ];
+
+        let allow = PacketFilter::Allow(list.clone());
+        assert!(allow.is_allowed(&transfer, &channel_0));
+        assert!(allow.is_allowed(&ica, &channel_1));
+        assert!(!allow.is_allowed(&transfer, &channel_1));
+        assert!(!allow.is_allowed(&ica, &channel_0));
+
+        let deny = PacketFilter::Deny(list);
+        assert!(!deny.is_allowed(&transfer, &channel_0));
+        assert!(deny.is_allowed(&transfer, &channel_1));
+
+        assert!(PacketFilter::AllowAll.is_allowed(&ica, &channel_0));
+    }
+
+    #[test]
+    fn port_pattern_wildcards() {
+        let port = PortId::from_str("icacontroller-alice").unwrap();
+
+        assert!(PortPattern::new("*".to_string()).matches(&port));
+        assert!(PortPattern::new("ica*".to_string()).matches(&port));
+        assert!(PortPattern::new("*alice".to_string()).matches(&port));
+        assert!(PortPattern::new("ica*-*e".to_string()).matches(&port));
+        assert!(!PortPattern::new("ica".to_string()).matches(&port));
+        assert!(!PortPattern::new("*bob".to_string()).matches(&port));
+        assert!(!PortPattern::new("icacontroller-alice*e".to_string()).matches(&port));
+    }
 }
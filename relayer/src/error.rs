@@ -138,6 +138,10 @@ pub enum Kind {
     #[error("Message transaction failure: {0}")]
     MessageTransaction(String),
 
+    /// Transaction not committed in time
+    #[error("transaction {0} was not committed within {1:?}")]
+    TxConfirmationTimeout(String, std::time::Duration),
+
     /// Failed query
     #[error("Query error occurred (failed to finish query for {0})")]
     Query(String),
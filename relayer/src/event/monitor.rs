@@ -5,14 +5,19 @@ use crossbeam_channel as channel;
 use futures::stream::StreamExt;
 use futures::{stream::select_all, Stream};
 use itertools::Itertools;
-use tendermint_rpc::{query::EventType, query::Query, SubscriptionClient, WebSocketClient};
+use tendermint_rpc::{
+    query::EventType, query::Query, Client, Order, SubscriptionClient, WebSocketClient,
+    WebSocketClientDriver,
+};
 use tokio::runtime::Runtime as TokioRuntime;
 use tokio::task::JoinHandle;
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
 
 use ibc::{events::IbcEvent, ics24_host::identifier::ChainId};
 
+use crate::chain::nodes::Nodes;
 use crate::error::{Error, Kind};
+use crate::telemetry;
 use ibc::ics02_client::height::Height;
 
 /// A batch of events from a chain at a specific height
@@ -29,6 +34,9 @@ impl EventBatch {
     }
 }
 
+/// Maximum number of transactions fetched per page when backfilling missed heights
+const BACKFILL_PAGE_SIZE: u8 = 100;
+
 type SubscriptionResult = Result<tendermint_rpc::event::Event, tendermint_rpc::Error>;
 type SubscriptionStream = dyn Stream<Item = SubscriptionResult> + Send + Sync + Unpin;
 
@@ -42,6 +50,11 @@ type SubscriptionStream = dyn Stream<Item = SubscriptionResult> + Send + Sync +
 ///
 /// Those can be extending or overriden using
 /// [`EventMonitor::add_query`] and [`EventMonitor::set_queries`].
+///
+/// The monitor keeps track of the last height it has seen events for. When the
+/// next event is for a later height than the one which follows it, e.g. after
+/// the websocket was disconnected, the IBC events of the missed heights are
+/// fetched through `tx_search` and emitted, in height order, before that event.
 pub struct EventMonitor {
     chain_id: ChainId,
     /// WebSocket to collect events from
@@ -50,8 +63,11 @@ pub struct EventMonitor {
     websocket_driver_handle: JoinHandle<tendermint_rpc::Result<()>>,
     /// Channel to handler where the monitor for this chain sends the events
     tx_batch: channel::Sender<EventBatch>,
-    /// Node Address
-    node_addr: tendermint_rpc::Url,
+    /// Full nodes of the chain, the active one is preferred to collect events
+    /// and to backfill the events of missed heights
+    nodes: Arc<Nodes>,
+    /// Last height for which events were collected
+    last_height: Option<Height>,
     /// Queries
     event_queries: Vec<Query>,
     /// All subscriptions combined in a single stream
@@ -64,17 +80,12 @@ impl EventMonitor {
     /// Create an event monitor, and connect to a node
     pub fn new(
         chain_id: ChainId,
-        node_addr: tendermint_rpc::Url,
+        nodes: Arc<Nodes>,
         rt: Arc<TokioRuntime>,
     ) -> Result<(Self, channel::Receiver<EventBatch>), Error> {
         let (tx, rx) = channel::unbounded();
 
-        let ws_addr = node_addr.clone();
-        let (websocket_client, websocket_driver) = rt.block_on(async move {
-            WebSocketClient::new(ws_addr.clone())
-                .await
-                .map_err(|e| Kind::Websocket(ws_addr).context(e))
-        })?;
+        let (websocket_client, websocket_driver) = connect(&nodes, &rt)?;
 
         let websocket_driver_handle = rt.spawn(websocket_driver.run());
 
@@ -88,7 +99,8 @@ impl EventMonitor {
             websocket_driver_handle,
             event_queries,
             tx_batch: tx,
-            node_addr,
+            nodes,
+            last_height: None,
             subscriptions: Box::new(futures::stream::empty()),
         };
 
@@ -129,10 +141,11 @@ impl EventMonitor {
     }
 
     fn try_reconnect(&mut self) -> Result<(), BoxError> {
+        // Fail over to another node if the one we were connected to is down
+        self.rt.block_on(self.nodes.check_health());
+
         // Try to reconnect
-        let (mut websocket_client, websocket_driver) = self
-            .rt
-            .block_on(WebSocketClient::new(self.node_addr.clone()))?;
+        let (mut websocket_client, websocket_driver) = connect(&self.nodes, &self.rt)?;
 
         let mut websocket_driver_handle = self.rt.spawn(websocket_driver.run());
 
@@ -189,6 +202,10 @@ impl EventMonitor {
                         debug!("Error on reconnecting: {}", e);
                         panic!("Abort during reconnection");
                     });
+
+                    if let Some(metrics) = telemetry::metrics() {
+                        metrics.event_monitor_reconnect(&self.chain_id);
+                    }
                 }
             }
         }
@@ -204,7 +221,12 @@ impl EventMonitor {
                     Ok(ibc_events) => {
                         let events_by_height = ibc_events.into_iter().into_group_map();
 
-                        for (height, events) in events_by_height {
+                        for (height, events) in events_by_height
+                            .into_iter()
+                            .sorted_by_key(|(height, _)| *height)
+                        {
+                            self.backfill(height)?;
+
                             let batch = EventBatch {
                                 chain_id: self.chain_id.clone(),
                                 height,
@@ -229,4 +251,164 @@ impl EventMonitor {
 
         Ok(())
     }
+
+    /// Emit the IBC events of the heights between the last height seen
+    /// by the monitor and `height`, if any, and record `height` as seen.
+    fn backfill(&mut self, height: Height) -> Result<(), BoxError> {
+        let missed = self
+            .last_height
+            .and_then(|last_height| missed_heights(last_height, height));
+
+        if self
+            .last_height
+            .map_or(true, |last_height| last_height < height)
+        {
+            self.last_height = Some(height);
+        }
+
+        let (from, to) = match missed {
+            Some(range) => range,
+            None => return Ok(()),
+        };
+
+        warn!(
+            chain.id = %self.chain_id,
+            "missed events from height {} to {}, backfilling them", from, to
+        );
+
+        match self.fetch_events(from, to) {
+            Ok(batches) => {
+                for batch in batches {
+                    self.tx_batch.send(batch)?;
+                }
+            }
+            Err(e) => {
+                error!(
+                    chain.id = %self.chain_id,
+                    "failed to backfill events from height {} to {}: {}", from, to, e
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Fetch the IBC events emitted from height `from` to height `to`, both inclusive,
+    /// grouped in batches sorted by height.
+    fn fetch_events(&self, from: Height, to: Height) -> Result<Vec<EventBatch>, Error> {
+        let query =
+            Query::gte("tx.height", from.revision_height).and_lte("tx.height", to.revision_height);
+
+        let mut events = vec![];
+        let mut fetched = 0;
+        let mut page = 1;
+
+        let node = self.nodes.active();
+
+        loop {
+            let response = self
+                .rt
+                .block_on(node.rpc_client.tx_search(
+                    query.clone(),
+                    false,
+                    page,
+                    BACKFILL_PAGE_SIZE,
+                    Order::Ascending,
+                ))
+                .map_err(|e| Kind::Rpc(node.rpc_addr.clone()).context(e))?;
+
+            if response.txs.is_empty() {
+                break;
+            }
+
+            fetched += response.txs.len();
+            for tx in response.txs {
+                events.extend(crate::event::rpc::get_tx_events(&self.chain_id, tx));
+            }
+
+            if fetched >= response.total_count as usize {
+                break;
+            }
+
+            page += 1;
+        }
+
+        let batches = events
+            .into_iter()
+            .into_group_map()
+            .into_iter()
+            .sorted_by_key(|(height, _)| *height)
+            .map(|(height, events)| EventBatch {
+                chain_id: self.chain_id.clone(),
+                height,
+                events,
+            })
+            .collect();
+
+        Ok(batches)
+    }
+}
+
+/// Connect to the websocket of the first reachable node, trying the active node first.
+fn connect(
+    nodes: &Nodes,
+    rt: &TokioRuntime,
+) -> Result<(WebSocketClient, WebSocketClientDriver), Error> {
+    let mut last_error = None;
+
+    for node in nodes.by_preference() {
+        match rt.block_on(WebSocketClient::new(node.websocket_addr.clone())) {
+            Ok(connection) => {
+                debug!("connected to websocket {}", node.websocket_addr);
+                return Ok(connection);
+            }
+            Err(e) => {
+                warn!(
+                    "failed to connect to websocket {}: {}",
+                    node.websocket_addr, e
+                );
+                last_error = Some(Kind::Websocket(node.websocket_addr.clone()).context(e));
+            }
+        }
+    }
+
+    Err(last_error.expect("a chain has at least one node").into())
+}
+
+/// The range of heights, both inclusive, which were missed between
+/// `last_height` and `height`, if any.
+fn missed_heights(last_height: Height, height: Height) -> Option<(Height, Height)> {
+    if last_height.revision_number != height.revision_number
+        || height.revision_height <= last_height.revision_height + 1
+    {
+        return None;
+    }
+
+    Some((
+        last_height.increment(),
+        height.with_revision_height(height.revision_height - 1),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use ibc::ics02_client::height::Height;
+
+    use super::missed_heights;
+
+    #[test]
+    fn missed_heights_range() {
+        assert_eq!(missed_heights(Height::new(0, 10), Height::new(0, 10)), None);
+        assert_eq!(missed_heights(Height::new(0, 10), Height::new(0, 11)), None);
+        assert_eq!(missed_heights(Height::new(0, 10), Height::new(0, 9)), None);
+        assert_eq!(missed_heights(Height::new(0, 10), Height::new(1, 20)), None);
+        assert_eq!(
+            missed_heights(Height::new(0, 10), Height::new(0, 12)),
+            Some((Height::new(0, 11), Height::new(0, 11)))
+        );
+        assert_eq!(
+            missed_heights(Height::new(0, 10), Height::new(0, 20)),
+            Some((Height::new(0, 11), Height::new(0, 19)))
+        );
+    }
 }
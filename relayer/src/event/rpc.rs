@@ -1,6 +1,7 @@
 use std::{collections::HashMap, convert::TryFrom};
 
 use anomaly::BoxError;
+use tendermint_rpc::endpoint::tx_search::ResultTx;
 use tendermint_rpc::event::{Event as RpcEvent, EventData as RpcEventData};
 use tracing::warn;
 
@@ -59,6 +60,25 @@ pub fn get_all_events(
     Ok(vals)
 }
 
+/// Extract the IBC events emitted by a transaction returned by a `tx_search` query.
+pub fn get_tx_events(chain_id: &ChainId, tx: ResultTx) -> Vec<(Height, IbcEvent)> {
+    let height = Height::new(chain_id.version(), u64::from(tx.height));
+
+    tx.tx_result
+        .events
+        .iter()
+        .filter_map(|event| {
+            ClientEvents::try_from_tx(event)
+                .or_else(|| ConnectionEvents::try_from_tx(event))
+                .or_else(|| ChannelEvents::try_from_tx(event))
+        })
+        .map(|mut event| {
+            event.set_height(height);
+            (height, event)
+        })
+        .collect()
+}
+
 pub fn build_event(mut object: RawObject) -> Result<IbcEvent, BoxError> {
     match object.action.as_str() {
         // Client events
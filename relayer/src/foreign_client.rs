@@ -17,6 +17,9 @@ use ibc::ics02_client::msgs::create_client::MsgCreateAnyClient;
 use ibc::ics02_client::msgs::misbehavior::MsgSubmitAnyMisbehaviour;
 use ibc::ics02_client::msgs::update_client::MsgUpdateAnyClient;
 use ibc::ics02_client::msgs::upgrade_client::MsgUpgradeAnyClient;
+use ibc::ics09_localhost::client_state::ClientState as LocalhostClientState;
+use ibc::ics09_localhost::consensus_state::ConsensusState as LocalhostConsensusState;
+use ibc::ics09_localhost::header::Header as LocalhostHeader;
 use ibc::ics24_host::identifier::{ChainId, ClientId};
 use ibc::query::QueryTxRequest;
 use ibc::tx_msg::Msg;
@@ -252,6 +255,23 @@ impl ForeignClient {
             ))
         })?;
 
+        // A client of a chain on itself is a loopback client, which needs no light client data.
+        if self.is_loopback() {
+            let client_state = LocalhostClientState::new(self.src_chain.id(), latest_height);
+            let msg = MsgCreateAnyClient::new(
+                client_state.wrap_any(),
+                LocalhostConsensusState::new().wrap_any(),
+                signer,
+            )
+            .map_err(|e| {
+                ForeignClientError::ClientCreate(format!(
+                    "failed while building the create client message: {}",
+                    e
+                ))
+            })?;
+            return Ok(msg);
+        }
+
         let client_state = self
             .src_chain
             .build_client_state(latest_height)
@@ -343,6 +363,10 @@ impl ForeignClient {
             thread::sleep(Duration::from_millis(100))
         }
 
+        if self.is_loopback() {
+            return self.build_update_loopback_client(target_height);
+        }
+
         // Get the latest client state on destination.
         let client_state = self
             .dst_chain()
@@ -418,6 +442,51 @@ impl ForeignClient {
         Ok(vec![new_msg.to_any()])
     }
 
+    /// Returns true if the source and destination chains are the same chain, in which case the
+    /// client is an ICS 09 localhost client.
+    pub fn is_loopback(&self) -> bool {
+        self.src_chain.id() == self.dst_chain.id()
+    }
+
+    /// Returns a vector with a message for advancing the localhost client to `target_height`.
+    /// The header only carries the height, the host chain checks it against its own height.
+    fn build_update_loopback_client(
+        &self,
+        target_height: Height,
+    ) -> Result<Vec<Any>, ForeignClientError> {
+        // The latest height of the client is that of its client state, regardless of the order
+        // in which the chain returns the consensus states.
+        let client_height = self
+            .dst_chain()
+            .query_client_state(&self.id, Height::zero())
+            .map_err(|e| {
+                ForeignClientError::ClientUpdate(format!(
+                    "failed querying client state on dst chain {} with error: {}",
+                    self.id, e
+                ))
+            })?
+            .latest_height();
+
+        if client_height >= target_height {
+            return Ok(vec![]);
+        }
+
+        let signer = self.dst_chain().get_signer().map_err(|e| {
+            ForeignClientError::ClientUpdate(format!(
+                "failed getting signer for dst chain ({}) with error: {}",
+                self.dst_chain.id(),
+                e
+            ))
+        })?;
+        let new_msg = MsgUpdateAnyClient {
+            client_id: self.id.clone(),
+            header: LocalhostHeader::new(target_height).wrap_any(),
+            signer,
+        };
+
+        Ok(vec![new_msg.to_any()])
+    }
+
     pub fn build_latest_update_client_and_send(&self) -> Result<IbcEvent, ForeignClientError> {
         self.build_update_client_and_send(Height::zero(), Height::zero())
     }
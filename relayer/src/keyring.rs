@@ -1,30 +1,179 @@
+pub mod encryption;
 pub mod errors;
+pub mod remote;
 
-use std::convert::{TryFrom, TryInto};
-use std::fs::{self, File};
+use std::collections::{BTreeMap, HashMap};
+use std::convert::TryFrom;
+use std::fmt;
+use std::fs;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
 
 use bech32::{ToBase32, Variant};
 use bip39::{Language, Mnemonic, Seed};
 use bitcoin::{
     network::constants::Network,
-    secp256k1::Secp256k1,
+    secp256k1::{self, Secp256k1},
     util::bip32::{DerivationPath, ExtendedPrivKey, ExtendedPubKey},
 };
-use hdpath::StandardHDPath;
-use k256::ecdsa::{signature::Signer, Signature, SigningKey};
+use k256::ecdsa::signature::{DigestSigner, DigestVerifier, Signer, Verifier};
+use k256::ecdsa::{Signature, SigningKey, VerifyingKey};
 use ripemd160::Ripemd160;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
+use sha3::Keccak256;
+use zeroize::Zeroizing;
 
 use crate::config::ChainConfig;
 
+use encryption::{EncryptedKeyFile, Passphrase};
 use errors::{Error, Kind};
 
 pub const KEYSTORE_DEFAULT_FOLDER: &str = ".hermes/keys/";
-pub const KEYSTORE_DISK_BACKEND: &str = "keyring-test"; // TODO: Change to "keyring"
+pub const KEYSTORE_DISK_BACKEND: &str = "keyring-test";
+pub const KEYSTORE_FILE_BACKEND: &str = "keyring-file";
 pub const KEYSTORE_FILE_EXTENSION: &str = "json";
 
+/// The HD path of the keys of the Cosmos Hub, with coin type 118
+pub const DEFAULT_HD_PATH: &str = "m/44'/118'/0'/0/0";
+
+/// A BIP-32 derivation path, e.g. `m/44'/60'/0'/0/0` for coin type 60
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct HdPath(DerivationPath);
+
+impl Default for HdPath {
+    fn default() -> Self {
+        DEFAULT_HD_PATH.parse().unwrap()
+    }
+}
+
+impl FromStr for HdPath {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        DerivationPath::from_str(s)
+            .map(Self)
+            .map_err(|e| Kind::InvalidHdPath(s.to_string()).context(e).into())
+    }
+}
+
+impl TryFrom<String> for HdPath {
+    type Error = Error;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<HdPath> for String {
+    fn from(hd_path: HdPath) -> Self {
+        hd_path.to_string()
+    }
+}
+
+impl fmt::Display for HdPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// The algorithm of the keys of a chain:
+/// - `secp256k1`: the keys of the Cosmos SDK
+/// - `eth_secp256k1`: the keys of Ethermint-based chains, whose address is derived from the
+///   Keccak-256 hash of the public key, and which sign the Keccak-256 hash of transactions
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyAlgorithm {
+    Secp256k1,
+    EthSecp256k1,
+}
+
+impl Default for KeyAlgorithm {
+    fn default() -> Self {
+        Self::Secp256k1
+    }
+}
+
+impl KeyAlgorithm {
+    /// The type URL of the protobuf encoding of the public keys, in transactions
+    pub fn public_key_type_url(self) -> &'static str {
+        match self {
+            Self::Secp256k1 => "/cosmos.crypto.secp256k1.PubKey",
+            Self::EthSecp256k1 => "/ethermint.crypto.v1.ethsecp256k1.PubKey",
+        }
+    }
+
+    /// The address of the compressed public key `public_key`
+    pub fn address(self, public_key: &[u8]) -> Result<Vec<u8>, Error> {
+        match self {
+            Self::Secp256k1 => Ok(get_address(public_key)),
+            Self::EthSecp256k1 => {
+                let public_key = secp256k1::PublicKey::from_slice(public_key)
+                    .map_err(|e| Kind::InvalidKey.context(e))?;
+
+                // The address is the last 20 bytes of the hash of the uncompressed public
+                // key, without its prefix byte
+                let hash = Keccak256::digest(&public_key.serialize_uncompressed()[1..]);
+                Ok(hash[12..].to_vec())
+            }
+        }
+    }
+
+    /// Sign `msg`, or rather its hash, with `signing_key`
+    fn sign(self, signing_key: &SigningKey, msg: &[u8]) -> Signature {
+        match self {
+            Self::Secp256k1 => signing_key.sign(msg),
+            Self::EthSecp256k1 => signing_key.sign_digest(Keccak256::new().chain(msg)),
+        }
+    }
+
+    /// Check that `signature` is a valid signature of `msg` by the compressed public key
+    /// `public_key`
+    pub fn verify(self, public_key: &[u8], msg: &[u8], signature: &[u8]) -> Result<(), Error> {
+        let invalid = || Kind::InvalidSignature;
+
+        let verifying_key = VerifyingKey::from_sec1_bytes(public_key).map_err(|_| invalid())?;
+        let signature = Signature::try_from(signature).map_err(|_| invalid())?;
+
+        let result = match self {
+            Self::Secp256k1 => verifying_key.verify(msg, &signature),
+            Self::EthSecp256k1 => {
+                verifying_key.verify_digest(Keccak256::new().chain(msg), &signature)
+            }
+        };
+
+        result.map_err(|_| invalid().into())
+    }
+}
+
+/// The parameters of the keys of a chain: the prefix of their account, the HD path from which
+/// they are derived from a mnemonic, and their algorithm
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyParams {
+    pub account_prefix: String,
+    pub hd_path: HdPath,
+    pub algorithm: KeyAlgorithm,
+}
+
+impl KeyParams {
+    /// The parameters of the keys of the chain `chain_config`
+    pub fn new(chain_config: &ChainConfig) -> Self {
+        Self {
+            account_prefix: chain_config.account_prefix.clone(),
+            hd_path: chain_config.hd_path.clone(),
+            algorithm: chain_config.key_algorithm,
+        }
+    }
+
+    /// The bech32 account of `address`
+    fn account(&self, address: &[u8]) -> Result<String, Error> {
+        bech32::encode(&self.account_prefix, address.to_base32(), Variant::Bech32)
+            .map_err(|e| Kind::Bech32Account.context(e).into())
+    }
+}
+
 /// Key entry stores the Private Key and Public Key as well the address
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct KeyEntry {
@@ -50,10 +199,9 @@ pub struct KeyFile {
     pub mnemonic: String,
 }
 
-impl TryFrom<KeyFile> for KeyEntry {
-    type Error = Error;
-
-    fn try_from(key_file: KeyFile) -> Result<Self, Self::Error> {
+impl KeyEntry {
+    /// The key of the seed file `key_file`, derived with `params` from its mnemonic
+    pub fn from_key_file(key_file: KeyFile, params: &KeyParams) -> Result<Self, Error> {
         // Decode the Bech32-encoded address from the key file
         let keyfile_address_bytes = decode_bech32(&key_file.address)?;
 
@@ -61,7 +209,7 @@ impl TryFrom<KeyFile> for KeyEntry {
         let mut keyfile_pubkey_bytes = decode_bech32(&key_file.pubkey)?;
 
         // Decode the private key from the mnemonic
-        let private_key = private_key_from_mnemonic(&key_file.mnemonic)?;
+        let private_key = private_key_from_mnemonic(&key_file.mnemonic, &params.hd_path)?;
         let public_key = ExtendedPubKey::from_private(&Secp256k1::new(), &private_key);
         let public_key_bytes = public_key.public_key.to_bytes();
 
@@ -92,72 +240,129 @@ impl TryFrom<KeyFile> for KeyEntry {
     }
 }
 
+/// The public part of a signing key, which is what a transaction is built with.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PublicKeyInfo {
+    /// Compressed secp256k1 public key
+    pub public_key: Vec<u8>,
+
+    /// Address
+    pub address: Vec<u8>,
+
+    /// Account Bech32 format
+    pub account: String,
+}
+
+impl PublicKeyInfo {
+    /// The public key info of the compressed secp256k1 public key `public_key`,
+    /// whose address and account are derived according to `params`.
+    pub fn new(public_key: Vec<u8>, params: &KeyParams) -> Result<Self, Error> {
+        let address = params.algorithm.address(&public_key)?;
+        let account = params.account(&address)?;
+
+        Ok(Self {
+            public_key,
+            address,
+            account,
+        })
+    }
+}
+
+/// Signs transactions with named keys, which are either held by the relayer in a
+/// [`KeyRing`], or by a [`remote::RemoteSigner`].
+pub trait TxSigner {
+    /// The public key of the key named `key_name`.
+    fn public_key(&self, key_name: &str) -> Result<PublicKeyInfo, Error>;
+
+    /// Sign `sign_doc`, the protobuf encoding of a `SignDoc`, with the key named `key_name`.
+    fn sign(&self, key_name: &str, sign_doc: Vec<u8>) -> Result<Vec<u8>, Error>;
+}
+
 pub trait KeyStore {
-    fn get_key(&self) -> Result<KeyEntry, Error>;
-    fn add_key(&mut self, key_entry: KeyEntry) -> Result<(), Error>;
+    /// The key named `key_name`.
+    fn get_key(&self, key_name: &str) -> Result<KeyEntry, Error>;
+
+    /// Store `key_entry` under the name `key_name`.
+    fn add_key(&mut self, key_name: &str, key_entry: KeyEntry) -> Result<(), Error>;
+
+    /// Delete the key named `key_name`.
+    fn remove_key(&mut self, key_name: &str) -> Result<(), Error>;
+
+    /// All the keys of the store, along with their names, sorted by name.
+    fn keys(&self) -> Result<Vec<(String, KeyEntry)>, Error>;
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Memory {
-    account_prefix: String,
-    key_entry: Option<KeyEntry>,
+    params: KeyParams,
+    keys: BTreeMap<String, KeyEntry>,
 }
 
 impl Memory {
-    pub fn new(account_prefix: String, key_entry: Option<KeyEntry>) -> Self {
+    pub fn new(params: KeyParams) -> Self {
         Self {
-            account_prefix,
-            key_entry,
+            params,
+            keys: BTreeMap::new(),
         }
     }
 }
 
 impl KeyStore for Memory {
-    fn get_key(&self) -> Result<KeyEntry, Error> {
-        self.key_entry
-            .clone()
+    fn get_key(&self, key_name: &str) -> Result<KeyEntry, Error> {
+        self.keys
+            .get(key_name)
+            .cloned()
             .ok_or_else(|| Kind::KeyNotFound.into())
     }
 
-    fn add_key(&mut self, key_entry: KeyEntry) -> Result<(), Error> {
-        if self.key_entry.is_some() {
+    fn add_key(&mut self, key_name: &str, key_entry: KeyEntry) -> Result<(), Error> {
+        if self.keys.contains_key(key_name) {
             return Err(Kind::ExistingKey.into());
         }
 
-        self.key_entry = Some(key_entry);
+        self.keys.insert(key_name.to_string(), key_entry);
 
         Ok(())
     }
+
+    fn remove_key(&mut self, key_name: &str) -> Result<(), Error> {
+        self.keys
+            .remove(key_name)
+            .map(|_| ())
+            .ok_or_else(|| Kind::KeyNotFound.into())
+    }
+
+    fn keys(&self) -> Result<Vec<(String, KeyEntry)>, Error> {
+        Ok(self
+            .keys
+            .iter()
+            .map(|(name, key)| (name.clone(), key.clone()))
+            .collect())
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Test {
-    key_name: String,
-    account_prefix: String,
+    params: KeyParams,
     store: PathBuf,
 }
 
 impl Test {
-    pub fn new(key_name: String, account_prefix: String, store: PathBuf) -> Self {
-        Self {
-            key_name,
-            account_prefix,
-            store,
-        }
+    pub fn new(params: KeyParams, store: PathBuf) -> Self {
+        Self { params, store }
     }
 }
 
 impl KeyStore for Test {
-    fn get_key(&self) -> Result<KeyEntry, Error> {
-        let mut filename = self.store.join(&self.key_name);
-        filename.set_extension(KEYSTORE_FILE_EXTENSION);
+    fn get_key(&self, key_name: &str) -> Result<KeyEntry, Error> {
+        let filename = key_file_path(&self.store, key_name)?;
 
         if !filename.as_path().exists() {
-            return Err(Kind::KeyStore.context("cannot find key file").into());
+            return Err(Kind::KeyNotFound.context("cannot find key file").into());
         }
 
         let file =
-            File::open(filename).map_err(|_| Kind::KeyStore.context("cannot open key file"))?;
+            fs::File::open(filename).map_err(|_| Kind::KeyStore.context("cannot open key file"))?;
 
         let key_entry = serde_json::from_reader(file)
             .map_err(|_| Kind::KeyStore.context("cannot ready key file"))?;
@@ -165,11 +370,10 @@ impl KeyStore for Test {
         Ok(key_entry)
     }
 
-    fn add_key(&mut self, key_entry: KeyEntry) -> Result<(), Error> {
-        let mut filename = self.store.join(&self.key_name);
-        filename.set_extension(KEYSTORE_FILE_EXTENSION);
+    fn add_key(&mut self, key_name: &str, key_entry: KeyEntry) -> Result<(), Error> {
+        let filename = key_file_path(&self.store, key_name)?;
 
-        let file = File::create(filename)
+        let file = fs::File::create(filename)
             .map_err(|_| Kind::KeyStore.context("error creating the key file"))?;
 
         serde_json::to_writer_pretty(file, &key_entry)
@@ -177,55 +381,226 @@ impl KeyStore for Test {
 
         Ok(())
     }
+
+    fn remove_key(&mut self, key_name: &str) -> Result<(), Error> {
+        remove_key_file(&self.store, key_name)
+    }
+
+    fn keys(&self) -> Result<Vec<(String, KeyEntry)>, Error> {
+        key_names(&self.store)?
+            .into_iter()
+            .map(|name| self.get_key(&name).map(|key| (name, key)))
+            .collect()
+    }
+}
+
+/// The keys decrypted by a [`File`] key store, by name, so that the passphrase
+/// is only run through scrypt once per key.
+#[derive(Clone, Default)]
+struct DecryptedKeys(Arc<Mutex<HashMap<String, KeyEntry>>>);
+
+impl DecryptedKeys {
+    fn get(&self, key_name: &str) -> Option<KeyEntry> {
+        self.0.lock().unwrap().get(key_name).cloned()
+    }
+
+    fn insert(&self, key_name: &str, key_entry: KeyEntry) {
+        self.0
+            .lock()
+            .unwrap()
+            .insert(key_name.to_string(), key_entry);
+    }
+
+    fn remove(&self, key_name: &str) {
+        self.0.lock().unwrap().remove(key_name);
+    }
+}
+
+impl fmt::Debug for DecryptedKeys {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set()
+            .entries(self.0.lock().unwrap().keys())
+            .finish()
+    }
+}
+
+/// Key store which encrypts the keys on disk with a passphrase,
+/// see the [`encryption`] module.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct File {
+    params: KeyParams,
+    store: PathBuf,
+    #[serde(skip)]
+    passphrase: Passphrase,
+    #[serde(skip)]
+    decrypted: DecryptedKeys,
+}
+
+impl File {
+    pub fn new(params: KeyParams, store: PathBuf, passphrase: Passphrase) -> Self {
+        Self {
+            params,
+            store,
+            passphrase,
+            decrypted: DecryptedKeys::default(),
+        }
+    }
+}
+
+impl KeyStore for File {
+    fn get_key(&self, key_name: &str) -> Result<KeyEntry, Error> {
+        if let Some(key_entry) = self.decrypted.get(key_name) {
+            return Ok(key_entry);
+        }
+
+        let filename = key_file_path(&self.store, key_name)?;
+
+        if !filename.as_path().exists() {
+            return Err(Kind::KeyNotFound.context("cannot find key file").into());
+        }
+
+        let file =
+            fs::File::open(filename).map_err(|_| Kind::KeyStore.context("cannot open key file"))?;
+
+        let key_file: EncryptedKeyFile = serde_json::from_reader(file)
+            .map_err(|_| Kind::KeyStore.context("cannot read key file"))?;
+
+        let plaintext = key_file.decrypt(&self.passphrase)?;
+
+        let key_entry: KeyEntry = serde_json::from_slice(&plaintext)
+            .map_err(|_| Kind::KeyStore.context("cannot read decrypted key"))?;
+
+        self.decrypted.insert(key_name, key_entry.clone());
+
+        Ok(key_entry)
+    }
+
+    fn add_key(&mut self, key_name: &str, key_entry: KeyEntry) -> Result<(), Error> {
+        let filename = key_file_path(&self.store, key_name)?;
+
+        if filename.as_path().exists() {
+            return Err(Kind::ExistingKey.into());
+        }
+
+        let plaintext = serde_json::to_vec(&key_entry)
+            .map(Zeroizing::new)
+            .map_err(|_| Kind::KeyStore.context("error serializing the key"))?;
+
+        let key_file =
+            EncryptedKeyFile::encrypt(key_entry.account.clone(), &plaintext, &self.passphrase)?;
+
+        let file = create_private_file(&filename)
+            .map_err(|_| Kind::KeyStore.context("error creating the key file"))?;
+
+        serde_json::to_writer_pretty(file, &key_file)
+            .map_err(|_| Kind::KeyStore.context("error writing the key file"))?;
+
+        self.decrypted.insert(key_name, key_entry);
+
+        Ok(())
+    }
+
+    fn remove_key(&mut self, key_name: &str) -> Result<(), Error> {
+        self.decrypted.remove(key_name);
+        remove_key_file(&self.store, key_name)
+    }
+
+    fn keys(&self) -> Result<Vec<(String, KeyEntry)>, Error> {
+        key_names(&self.store)?
+            .into_iter()
+            .map(|name| self.get_key(&name).map(|key| (name, key)))
+            .collect()
+    }
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+/// The backend in which the keys of a chain are stored:
+///
+/// - `memory`: the keys are kept in memory only
+/// - `test`: the keys are stored in plaintext on disk, for testing only
+/// - `file`: the keys are stored on disk, encrypted with a passphrase
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Store {
     Memory,
     Test,
+    File,
+}
+
+impl Default for Store {
+    fn default() -> Self {
+        Self::Test
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum KeyRing {
     Memory(Memory),
     Test(Test),
+    File(File),
 }
 
 impl KeyRing {
     pub fn new(store: Store, chain_config: ChainConfig) -> Result<Self, Error> {
         match store {
-            Store::Memory => Ok(Self::Memory(Memory::new(chain_config.account_prefix, None))),
+            Store::Memory => Ok(Self::Memory(Memory::new(KeyParams::new(&chain_config)))),
 
             Store::Test => {
-                let keys_folder = disk_store_path(chain_config.id.as_str()).map_err(|e| {
-                    Kind::KeyStore.context(format!("failed to compute keys folder path: {:?}", e))
-                })?;
-
-                // Create keys folder if it does not exist
-                fs::create_dir_all(&keys_folder).map_err(|e| {
-                    Kind::KeyStore.context(format!("failed to create keys folder: {:?}", e))
-                })?;
+                let keys_folder = keys_folder(chain_config.id.as_str(), KEYSTORE_DISK_BACKEND)?;
 
                 Ok(Self::Test(Test::new(
-                    chain_config.key_name,
-                    chain_config.account_prefix,
+                    KeyParams::new(&chain_config),
                     keys_folder,
                 )))
             }
+
+            Store::File => {
+                let keys_folder = keys_folder(chain_config.id.as_str(), KEYSTORE_FILE_BACKEND)?;
+
+                // Confirm the passphrase when it is used to encrypt the first key
+                let first_key = key_names(&keys_folder)?.is_empty();
+                let passphrase = Passphrase::read(
+                    &format!("Passphrase of the keys of chain {}: ", chain_config.id),
+                    first_key,
+                )?;
+
+                Ok(Self::File(File::new(
+                    KeyParams::new(&chain_config),
+                    keys_folder,
+                    passphrase,
+                )))
+            }
         }
     }
 
-    pub fn get_key(&self) -> Result<KeyEntry, Error> {
+    pub fn get_key(&self, key_name: &str) -> Result<KeyEntry, Error> {
         match self {
-            KeyRing::Memory(m) => m.get_key(),
-            KeyRing::Test(d) => d.get_key(),
+            KeyRing::Memory(m) => m.get_key(key_name),
+            KeyRing::Test(d) => d.get_key(key_name),
+            KeyRing::File(f) => f.get_key(key_name),
         }
     }
 
-    pub fn add_key(&mut self, key_entry: KeyEntry) -> Result<(), Error> {
+    pub fn add_key(&mut self, key_name: &str, key_entry: KeyEntry) -> Result<(), Error> {
         match self {
-            KeyRing::Memory(m) => m.add_key(key_entry),
-            KeyRing::Test(d) => d.add_key(key_entry),
+            KeyRing::Memory(m) => m.add_key(key_name, key_entry),
+            KeyRing::Test(d) => d.add_key(key_name, key_entry),
+            KeyRing::File(f) => f.add_key(key_name, key_entry),
+        }
+    }
+
+    pub fn remove_key(&mut self, key_name: &str) -> Result<(), Error> {
+        match self {
+            KeyRing::Memory(m) => m.remove_key(key_name),
+            KeyRing::Test(d) => d.remove_key(key_name),
+            KeyRing::File(f) => f.remove_key(key_name),
+        }
+    }
+
+    pub fn keys(&self) -> Result<Vec<(String, KeyEntry)>, Error> {
+        match self {
+            KeyRing::Memory(m) => m.keys(),
+            KeyRing::Test(d) => d.keys(),
+            KeyRing::File(f) => f.keys(),
         }
     }
 
@@ -234,23 +609,25 @@ impl KeyRing {
         let key_file: KeyFile =
             serde_json::from_str(key_file_content).map_err(|e| Kind::InvalidKey.context(e))?;
 
-        key_file.try_into()
+        KeyEntry::from_key_file(key_file, self.params())
     }
 
     /// Add a key entry in the store using a mnemonic.
     pub fn key_from_mnemonic(&self, mnemonic_words: &str) -> Result<KeyEntry, Error> {
         // Get the private key from the mnemonic
-        let private_key = private_key_from_mnemonic(mnemonic_words)?;
+        let private_key = private_key_from_mnemonic(mnemonic_words, &self.params().hd_path)?;
 
         // Get the public Key from the private key
         let public_key = ExtendedPubKey::from_private(&Secp256k1::new(), &private_key);
 
         // Get address from the public Key
-        let address = get_address(public_key);
+        let address = self
+            .params()
+            .algorithm
+            .address(&public_key.public_key.to_bytes())?;
 
         // Compute Bech32 account
-        let account = bech32::encode(self.account_prefix(), address.to_base32(), Variant::Bech32)
-            .map_err(|e| Kind::Bech32Account.context(e))?;
+        let account = self.params().account(&address)?;
 
         Ok(KeyEntry {
             public_key,
@@ -260,47 +637,104 @@ impl KeyRing {
         })
     }
 
-    /// Sign a message
-    pub fn sign_msg(&self, msg: Vec<u8>) -> Result<Vec<u8>, Error> {
-        let key = self.get_key()?;
+    /// Sign a message with the key `key_name`
+    pub fn sign_msg(&self, key_name: &str, msg: Vec<u8>) -> Result<Vec<u8>, Error> {
+        let key = self.get_key(key_name)?;
 
         let private_key_bytes = key.private_key.private_key.to_bytes();
         let signing_key = SigningKey::from_bytes(private_key_bytes.as_slice()).map_err(|_| {
             Kind::InvalidKey.context("could not build signing key from private key bytes")
         })?;
 
-        let signature: Signature = signing_key.sign(&msg);
+        let signature = self.params().algorithm.sign(&signing_key, &msg);
         Ok(signature.as_ref().to_vec())
     }
 
     pub fn account_prefix(&self) -> &str {
+        &self.params().account_prefix
+    }
+
+    /// The parameters of the keys of the key ring
+    pub fn params(&self) -> &KeyParams {
         match self {
-            KeyRing::Memory(m) => &m.account_prefix,
-            KeyRing::Test(d) => &d.account_prefix,
+            KeyRing::Memory(m) => &m.params,
+            KeyRing::Test(d) => &d.params,
+            KeyRing::File(f) => &f.params,
         }
     }
 }
 
-/// Decode an extended private key from a mnemonic
-fn private_key_from_mnemonic(mnemonic_words: &str) -> Result<ExtendedPrivKey, Error> {
+impl TxSigner for KeyRing {
+    fn public_key(&self, key_name: &str) -> Result<PublicKeyInfo, Error> {
+        let key = self.get_key(key_name)?;
+
+        Ok(PublicKeyInfo {
+            public_key: key.public_key.public_key.to_bytes(),
+            address: key.address,
+            account: key.account,
+        })
+    }
+
+    fn sign(&self, key_name: &str, sign_doc: Vec<u8>) -> Result<Vec<u8>, Error> {
+        self.sign_msg(key_name, sign_doc)
+    }
+}
+
+/// Move the keys of the chain `chain_config` from the plaintext `test` backend to the encrypted
+/// `file` backend. Each plaintext key file is deleted once its key is encrypted.
+pub fn migrate_test_keys(chain_config: ChainConfig) -> Result<Vec<(String, KeyEntry)>, Error> {
+    let mut test = KeyRing::new(Store::Test, chain_config.clone())?;
+    let keys = test.keys()?;
+
+    if keys.is_empty() {
+        return Err(Kind::KeyNotFound
+            .context("no key is stored by the test backend")
+            .into());
+    }
+
+    let mut file = KeyRing::new(Store::File, chain_config)?;
+
+    for (key_name, key_entry) in &keys {
+        file.add_key(key_name, key_entry.clone())?;
+
+        // Check that the encrypted key can be read back before deleting the plaintext one
+        if file.get_key(key_name)? != *key_entry {
+            return Err(Kind::KeyStore
+                .context(format!(
+                    "the encrypted key '{}' does not match the plaintext key",
+                    key_name
+                ))
+                .into());
+        }
+
+        test.remove_key(key_name)?;
+    }
+
+    Ok(keys)
+}
+
+/// Decode an extended private key from a mnemonic, at the derivation path `hd_path`
+fn private_key_from_mnemonic(
+    mnemonic_words: &str,
+    hd_path: &HdPath,
+) -> Result<ExtendedPrivKey, Error> {
     let mnemonic = Mnemonic::from_phrase(mnemonic_words, Language::English)
         .map_err(|e| Kind::InvalidMnemonic.context(e))?;
 
     let seed = Seed::new(&mnemonic, "");
 
-    // Get Private Key from seed and standard derivation path
-    let hd_path = StandardHDPath::try_from("m/44'/118'/0'/0/0").unwrap();
+    // Get Private Key from seed and derivation path
     let private_key = ExtendedPrivKey::new_master(Network::Bitcoin, seed.as_bytes())
-        .and_then(|k| k.derive_priv(&Secp256k1::new(), &DerivationPath::from(hd_path)))
+        .and_then(|k| k.derive_priv(&Secp256k1::new(), &hd_path.0))
         .map_err(|e| Kind::PrivateKey.context(e))?;
 
     Ok(private_key)
 }
 
-/// Return an address from a Public Key
-fn get_address(pk: ExtendedPubKey) -> Vec<u8> {
+/// Return an address from the bytes of a public key
+fn get_address(pk: &[u8]) -> Vec<u8> {
     let mut hasher = Sha256::new();
-    hasher.update(pk.public_key.to_bytes().as_slice());
+    hasher.update(pk);
 
     // Read hash digest over the public key bytes & consume hasher
     let pk_hash = hasher.finalize();
@@ -323,14 +757,176 @@ fn decode_bech32(input: &str) -> Result<Vec<u8>, Error> {
     Ok(bytes)
 }
 
-fn disk_store_path(folder_name: &str) -> Result<PathBuf, Error> {
+fn disk_store_path(folder_name: &str, backend: &str) -> Result<PathBuf, Error> {
     let home = dirs_next::home_dir()
         .ok_or_else(|| Kind::KeyStore.context("cannot retrieve home folder location"))?;
 
     let folder = Path::new(home.as_path())
         .join(KEYSTORE_DEFAULT_FOLDER)
         .join(folder_name)
-        .join(KEYSTORE_DISK_BACKEND);
+        .join(backend);
 
     Ok(folder)
 }
+
+/// The folder of the keys of `backend` for the chain `folder_name`, created if it does not exist.
+fn keys_folder(folder_name: &str, backend: &str) -> Result<PathBuf, Error> {
+    let keys_folder = disk_store_path(folder_name, backend).map_err(|e| {
+        Kind::KeyStore.context(format!("failed to compute keys folder path: {:?}", e))
+    })?;
+
+    // Create keys folder if it does not exist
+    fs::create_dir_all(&keys_folder)
+        .map_err(|e| Kind::KeyStore.context(format!("failed to create keys folder: {:?}", e)))?;
+
+    Ok(keys_folder)
+}
+
+/// The path of the file of the key named `key_name` in the folder `store`. The name must not
+/// contain path separators, nor be `.` or `..`, so that the file stays within `store`.
+/// The extension is appended to the whole name, so that names with dots get distinct files.
+fn key_file_path(store: &Path, key_name: &str) -> Result<PathBuf, Error> {
+    if key_name.is_empty()
+        || key_name == "."
+        || key_name == ".."
+        || key_name.contains(|c: char| c == '/' || c == '\\')
+    {
+        return Err(Kind::InvalidKeyName(key_name.to_string()).into());
+    }
+
+    Ok(store.join(format!("{}.{}", key_name, KEYSTORE_FILE_EXTENSION)))
+}
+
+/// The names of the keys stored in the folder `store`, sorted.
+fn key_names(store: &Path) -> Result<Vec<String>, Error> {
+    let entries = fs::read_dir(store)
+        .map_err(|e| Kind::KeyStore.context(format!("cannot read keys folder: {}", e)))?;
+
+    let mut names = vec![];
+    for entry in entries {
+        let path = entry
+            .map_err(|e| Kind::KeyStore.context(format!("cannot read keys folder: {}", e)))?
+            .path();
+
+        if path
+            .extension()
+            .map_or(false, |ext| ext == KEYSTORE_FILE_EXTENSION)
+        {
+            if let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) {
+                names.push(name.to_string());
+            }
+        }
+    }
+
+    names.sort();
+    Ok(names)
+}
+
+fn remove_key_file(store: &Path, key_name: &str) -> Result<(), Error> {
+    let filename = key_file_path(store, key_name)?;
+
+    if !filename.as_path().exists() {
+        return Err(Kind::KeyNotFound.context("cannot find key file").into());
+    }
+
+    fs::remove_file(filename).map_err(|e| {
+        Kind::KeyStore
+            .context(format!("cannot delete key file: {}", e))
+            .into()
+    })
+}
+
+/// Create a file which only its owner can read and write.
+fn create_private_file(path: &Path) -> std::io::Result<fs::File> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    options.open(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::{key_file_path, HdPath, KeyAlgorithm, KeyParams, KeyRing, Memory, TxSigner};
+
+    const MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon \
+                            abandon abandon abandon abandon abandon about";
+
+    #[test]
+    fn eth_secp256k1_keys() {
+        let params = KeyParams {
+            account_prefix: "evmos".to_string(),
+            hd_path: "m/44'/60'/0'/0/0".parse().unwrap(),
+            algorithm: KeyAlgorithm::EthSecp256k1,
+        };
+
+        let mut keyring = KeyRing::Memory(Memory::new(params));
+        let key = keyring.key_from_mnemonic(MNEMONIC).unwrap();
+
+        // The Ethereum address of the first account of the mnemonic
+        assert_eq!(
+            hex::encode(&key.address),
+            "9858effd232b4033e47d90003d41ec34ecaeda94"
+        );
+
+        keyring.add_key("relayer", key).unwrap();
+
+        let public_key = keyring.public_key("relayer").unwrap();
+        let signature = keyring.sign("relayer", b"sign doc".to_vec()).unwrap();
+
+        let algorithm = KeyAlgorithm::EthSecp256k1;
+        assert!(algorithm
+            .verify(&public_key.public_key, b"sign doc", &signature)
+            .is_ok());
+
+        // The signature is of the Keccak-256 hash, not of the SHA-256 hash
+        assert!(KeyAlgorithm::Secp256k1
+            .verify(&public_key.public_key, b"sign doc", &signature)
+            .is_err());
+    }
+
+    #[test]
+    fn invalid_hd_path() {
+        assert!("m/44'/118'/0'/0/0".parse::<HdPath>().is_ok());
+        assert!("m/44'/coin'/0'/0/0".parse::<HdPath>().is_err());
+    }
+
+    #[test]
+    fn key_names_stay_within_the_store() {
+        let store = Path::new("/keys");
+
+        assert_eq!(
+            key_file_path(store, "relayer").unwrap(),
+            Path::new("/keys/relayer.json")
+        );
+
+        for key_name in &["", ".", "..", "../x", "a/b", "/etc/x", "..\\x"] {
+            assert!(key_file_path(store, key_name).is_err(), "{}", key_name);
+        }
+    }
+
+    #[test]
+    fn key_names_with_dots_get_distinct_files() {
+        let store = Path::new("/keys");
+
+        assert_eq!(
+            key_file_path(store, "a.b").unwrap(),
+            Path::new("/keys/a.b.json")
+        );
+        assert_ne!(
+            key_file_path(store, "a.b").unwrap(),
+            key_file_path(store, "a.c").unwrap()
+        );
+        assert_ne!(
+            key_file_path(store, "a.b").unwrap(),
+            key_file_path(store, "a").unwrap()
+        );
+    }
+}
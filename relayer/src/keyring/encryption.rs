@@ -0,0 +1,294 @@
+//! Encryption of the keys stored on disk by the `file` keyring backend.
+//!
+//! A key is encrypted with XChaCha20-Poly1305, under a symmetric key derived from a passphrase
+//! with scrypt. The passphrase is read from the `HERMES_KEYRING_PASSPHRASE` environment variable,
+//! from the file whose path is in the `HERMES_KEYRING_PASSPHRASE_FILE` environment variable, or
+//! else prompted for on the terminal.
+
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use chacha20poly1305::aead::{Aead, NewAead, Payload};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use rand::{rngs::OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use zeroize::{Zeroize, Zeroizing};
+
+use super::errors::{Error, Kind};
+
+/// Environment variable holding the passphrase of the encrypted keys
+pub const PASSPHRASE_ENV: &str = "HERMES_KEYRING_PASSPHRASE";
+
+/// Environment variable holding the path of a file which contains the passphrase
+/// of the encrypted keys
+pub const PASSPHRASE_FILE_ENV: &str = "HERMES_KEYRING_PASSPHRASE_FILE";
+
+/// Name of the cipher the keys are encrypted with, recorded in the key files
+const CIPHER: &str = "xchacha20-poly1305";
+
+const SALT_LEN: usize = 32;
+const NONCE_LEN: usize = 24;
+const KEY_LEN: usize = 32;
+
+/// Cost parameters of scrypt, as recommended for interactive use. They are also the highest
+/// accepted from a key file, as higher ones could exhaust the memory or CPU of the relayer.
+const SCRYPT_LOG_N: u8 = 15;
+const SCRYPT_R: u32 = 8;
+const SCRYPT_P: u32 = 1;
+
+/// The passphrase from which keys are encrypted, which is never printed,
+/// and is wiped from memory when dropped.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct Passphrase(String);
+
+impl Passphrase {
+    pub fn new(passphrase: impl Into<String>) -> Self {
+        Self(passphrase.into())
+    }
+
+    /// Read the passphrase from the `HERMES_KEYRING_PASSPHRASE` environment variable, or the
+    /// file named by `HERMES_KEYRING_PASSPHRASE_FILE`, or else prompt for it with `prompt`.
+    /// If `confirm` is set, the passphrase is prompted for twice, e.g. to encrypt a new key.
+    pub fn read(prompt: &str, confirm: bool) -> Result<Self, Error> {
+        let passphrase = if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV) {
+            Self(passphrase)
+        } else if let Some(path) = std::env::var_os(PASSPHRASE_FILE_ENV) {
+            Self(read_passphrase_file(Path::new(&path))?)
+        } else {
+            let passphrase = Self(prompt_passphrase(prompt)?);
+
+            if confirm && Self(prompt_passphrase("Repeat the passphrase: ")?) != passphrase {
+                return Err(Kind::Passphrase
+                    .context("the passphrases do not match")
+                    .into());
+            }
+
+            passphrase
+        };
+
+        if passphrase.0.is_empty() {
+            return Err(Kind::Passphrase.context("the passphrase is empty").into());
+        }
+
+        Ok(passphrase)
+    }
+}
+
+impl Drop for Passphrase {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl fmt::Debug for Passphrase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Passphrase(<redacted>)")
+    }
+}
+
+fn read_passphrase_file(path: &Path) -> Result<String, Error> {
+    let mut contents = fs::read_to_string(path).map_err(|e| {
+        Kind::Passphrase.context(format!(
+            "cannot read passphrase file {}: {}",
+            path.display(),
+            e
+        ))
+    })?;
+
+    // Truncate in place rather than copy the passphrase
+    let len = contents.trim_end_matches(&['\r', '\n'][..]).len();
+    contents.truncate(len);
+
+    Ok(contents)
+}
+
+fn prompt_passphrase(prompt: &str) -> Result<String, Error> {
+    rpassword::read_password_from_tty(Some(prompt)).map_err(|e| {
+        Kind::Passphrase
+            .context(format!("cannot prompt for the passphrase: {}", e))
+            .into()
+    })
+}
+
+/// Parameters of the scrypt derivation of the encryption key from the passphrase
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KdfParams {
+    /// Hex-encoded salt
+    pub salt: String,
+    pub log_n: u8,
+    pub r: u32,
+    pub p: u32,
+}
+
+/// The contents of a key file of the `file` backend.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EncryptedKeyFile {
+    /// Bech32 account of the key, in clear so that it can be listed without the passphrase.
+    /// It is authenticated along with the encrypted key.
+    pub account: String,
+    pub cipher: String,
+    pub kdf: KdfParams,
+    /// Hex-encoded nonce
+    pub nonce: String,
+    /// Hex-encoded encrypted key
+    pub ciphertext: String,
+}
+
+impl EncryptedKeyFile {
+    /// Encrypt `plaintext`, the serialized key of `account`, with `passphrase`.
+    pub fn encrypt(
+        account: String,
+        plaintext: &[u8],
+        passphrase: &Passphrase,
+    ) -> Result<Self, Error> {
+        Self::encrypt_with(account, plaintext, passphrase, SCRYPT_LOG_N)
+    }
+
+    fn encrypt_with(
+        account: String,
+        plaintext: &[u8],
+        passphrase: &Passphrase,
+        log_n: u8,
+    ) -> Result<Self, Error> {
+        let mut salt = [0u8; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+
+        let mut nonce = [0u8; NONCE_LEN];
+        OsRng.fill_bytes(&mut nonce);
+
+        let kdf = KdfParams {
+            salt: hex::encode(salt),
+            log_n,
+            r: SCRYPT_R,
+            p: SCRYPT_P,
+        };
+
+        let key = derive_key(passphrase, &salt, &kdf)?;
+
+        let payload = Payload {
+            msg: plaintext,
+            aad: account.as_bytes(),
+        };
+
+        let ciphertext = XChaCha20Poly1305::new(Key::from_slice(&key[..]))
+            .encrypt(XNonce::from_slice(&nonce), payload)
+            .map_err(|_| Kind::Encryption.context("cannot encrypt the key"))?;
+
+        Ok(Self {
+            account,
+            cipher: CIPHER.to_string(),
+            kdf,
+            nonce: hex::encode(nonce),
+            ciphertext: hex::encode(ciphertext),
+        })
+    }
+
+    /// Decrypt the serialized key with `passphrase`.
+    /// The key is wiped from memory once the result is dropped.
+    pub fn decrypt(&self, passphrase: &Passphrase) -> Result<Zeroizing<Vec<u8>>, Error> {
+        if self.cipher != CIPHER {
+            return Err(Kind::Decryption
+                .context(format!("unsupported cipher '{}'", self.cipher))
+                .into());
+        }
+
+        let salt = decode_hex("salt", &self.kdf.salt)?;
+        let nonce = decode_hex("nonce", &self.nonce)?;
+        let ciphertext = decode_hex("ciphertext", &self.ciphertext)?;
+
+        if nonce.len() != NONCE_LEN {
+            return Err(Kind::Decryption.context("invalid nonce length").into());
+        }
+
+        let key = derive_key(passphrase, &salt, &self.kdf)?;
+
+        let payload = Payload {
+            msg: ciphertext.as_slice(),
+            aad: self.account.as_bytes(),
+        };
+
+        XChaCha20Poly1305::new(Key::from_slice(&key[..]))
+            .decrypt(XNonce::from_slice(&nonce), payload)
+            .map(Zeroizing::new)
+            .map_err(|_| {
+                Kind::Decryption
+                    .context("wrong passphrase, or corrupted key file")
+                    .into()
+            })
+    }
+}
+
+fn derive_key(
+    passphrase: &Passphrase,
+    salt: &[u8],
+    kdf: &KdfParams,
+) -> Result<Zeroizing<[u8; KEY_LEN]>, Error> {
+    if kdf.log_n > SCRYPT_LOG_N || kdf.r > SCRYPT_R || kdf.p > SCRYPT_P {
+        return Err(Kind::Decryption
+            .context(format!(
+                "scrypt parameters log_n={}, r={}, p={} exceed log_n={}, r={}, p={}",
+                kdf.log_n, kdf.r, kdf.p, SCRYPT_LOG_N, SCRYPT_R, SCRYPT_P
+            ))
+            .into());
+    }
+
+    let params = scrypt::Params::new(kdf.log_n, kdf.r, kdf.p)
+        .map_err(|_| Kind::Encryption.context("invalid scrypt parameters"))?;
+
+    let mut key = Zeroizing::new([0u8; KEY_LEN]);
+    scrypt::scrypt(passphrase.0.as_bytes(), salt, &params, &mut *key)
+        .map_err(|_| Kind::Encryption.context("cannot derive the encryption key"))?;
+
+    Ok(key)
+}
+
+fn decode_hex(field: &str, value: &str) -> Result<Vec<u8>, Error> {
+    hex::decode(value).map_err(|e| {
+        Kind::Decryption
+            .context(format!("invalid {} in key file: {}", field, e))
+            .into()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EncryptedKeyFile, Passphrase};
+
+    // Low scrypt cost, to keep the tests fast
+    const TEST_LOG_N: u8 = 4;
+
+    #[test]
+    fn encrypted_key_roundtrip() {
+        let passphrase = Passphrase::new("correct horse battery staple");
+        let account = "cosmos1tc3vcuxyyac0dmayf887t95tdg7qpyql48w7gj".to_string();
+        let plaintext = br#"{"private_key":"..."}"#;
+
+        let key_file =
+            EncryptedKeyFile::encrypt_with(account, plaintext, &passphrase, TEST_LOG_N).unwrap();
+
+        assert!(!key_file.ciphertext.contains(&hex::encode(plaintext)));
+        assert_eq!(*key_file.decrypt(&passphrase).unwrap(), plaintext.to_vec());
+
+        // A wrong passphrase is rejected
+        assert!(key_file.decrypt(&Passphrase::new("wrong")).is_err());
+
+        // The account is authenticated along with the key
+        let mut tampered = key_file.clone();
+        tampered.account = "cosmos1yq9x4ks8c9s7xp7ph2kgxmvy5mwm4yu9f8mk6r".to_string();
+        assert!(tampered.decrypt(&passphrase).is_err());
+
+        // Costlier scrypt parameters than the defaults are rejected before deriving the key
+        let mut costly = key_file.clone();
+        costly.kdf.log_n = 40;
+        assert!(costly.decrypt(&passphrase).is_err());
+
+        let mut costly = key_file.clone();
+        costly.kdf.r = 1 << 20;
+        assert!(costly.decrypt(&passphrase).is_err());
+
+        let mut costly = key_file;
+        costly.kdf.p = 1 << 20;
+        assert!(costly.decrypt(&passphrase).is_err());
+    }
+}
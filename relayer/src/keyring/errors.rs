@@ -8,12 +8,21 @@ pub enum Kind {
     #[error("invalid key")]
     InvalidKey,
 
+    #[error("invalid key name '{0}'")]
+    InvalidKeyName(String),
+
     #[error("key not found")]
     KeyNotFound,
 
     #[error("key already exists")]
     ExistingKey,
 
+    #[error("invalid HD path '{0}'")]
+    InvalidHdPath(String),
+
+    #[error("invalid signature")]
+    InvalidSignature,
+
     #[error("invalid mnemonic")]
     InvalidMnemonic,
 
@@ -31,6 +40,18 @@ pub enum Kind {
 
     #[error("key store error")]
     KeyStore,
+
+    #[error("cannot read the passphrase")]
+    Passphrase,
+
+    #[error("cannot encrypt key")]
+    Encryption,
+
+    #[error("cannot decrypt key")]
+    Decryption,
+
+    #[error("remote signer error")]
+    RemoteSigner,
 }
 
 impl Kind {
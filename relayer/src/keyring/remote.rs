@@ -0,0 +1,529 @@
+//! Remote signing of transactions, by a signer which holds the keys of the relayer so that they
+//! never enter its process, e.g. a signing service fronting an HSM.
+//!
+//! The relayer connects to the signer over a Unix socket (`unix://<path>`) or a TCP connection
+//! (`tcp://<host>:<port>`), and sends it requests as JSON documents on a single line. Each request
+//! is answered with a JSON document on a single line:
+//!
+//! - `{"method":"public_key","chain_id":"ibc-0","key_name":"testkey"}` is answered with
+//!   `{"public_key":"<hex>"}`, the compressed secp256k1 public key of the key.
+//! - `{"method":"sign","chain_id":"ibc-0","key_name":"testkey","sign_doc":"<hex>"}`, where
+//!   `sign_doc` is the protobuf encoding of a `SignDoc`, is answered with `{"signature":"<hex>"}`,
+//!   the 64-byte secp256k1 ECDSA signature of the SHA-256 digest of the `SignDoc`, with a low `s`
+//!   as required by the Cosmos SDK. With `eth_secp256k1` keys, the digest is Keccak-256.
+//!
+//! A request which fails is answered with `{"error":"<message>"}`. The relayer checks every
+//! signature against the public key of the key, which it requests once, before using it.
+//!
+//! [`LocalSigner`] is a reference implementation of the signer, serving the keys of key rings.
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+#[cfg(unix)]
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use k256::ecdsa::VerifyingKey;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use ibc::ics24_host::identifier::ChainId;
+
+use super::errors::{Error, Kind};
+use super::{KeyParams, KeyRing, PublicKeyInfo, TxSigner};
+
+/// Time after which the reference signer closes an idle connection
+const IDLE_CONNECTION_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// The address of a remote signer: `unix://<path>` or `tcp://<host>:<port>`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum SignerAddress {
+    Unix(PathBuf),
+    Tcp(String),
+}
+
+impl FromStr for SignerAddress {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(path) = s.strip_prefix("unix://") {
+            Ok(Self::Unix(PathBuf::from(path)))
+        } else if let Some(addr) = s.strip_prefix("tcp://") {
+            Ok(Self::Tcp(addr.to_string()))
+        } else {
+            Err(Kind::RemoteSigner
+                .context(format!(
+                    "invalid signer address '{}', expected unix://<path> or tcp://<host>:<port>",
+                    s
+                ))
+                .into())
+        }
+    }
+}
+
+impl TryFrom<String> for SignerAddress {
+    type Error = Error;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<SignerAddress> for String {
+    fn from(address: SignerAddress) -> Self {
+        address.to_string()
+    }
+}
+
+impl fmt::Display for SignerAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unix(path) => write!(f, "unix://{}", path.display()),
+            Self::Tcp(addr) => write!(f, "tcp://{}", addr),
+        }
+    }
+}
+
+/// A request to the remote signer
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "method", rename_all = "snake_case")]
+pub enum SignerRequest {
+    PublicKey {
+        chain_id: ChainId,
+        key_name: String,
+    },
+    Sign {
+        chain_id: ChainId,
+        key_name: String,
+        /// Hex-encoded `SignDoc`
+        sign_doc: String,
+    },
+}
+
+/// A response of the remote signer, whose values are hex-encoded
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignerResponse {
+    PublicKey(String),
+    Signature(String),
+    Error(String),
+}
+
+/// A connection to a signer, over which requests and responses are exchanged.
+enum Connection {
+    #[cfg(unix)]
+    Unix(UnixStream),
+    Tcp(TcpStream),
+}
+
+impl Connection {
+    fn open(address: &SignerAddress, timeout: Duration) -> io::Result<Self> {
+        let connection = match address {
+            #[cfg(unix)]
+            SignerAddress::Unix(path) => {
+                let stream = UnixStream::connect(path)?;
+                stream.set_read_timeout(Some(timeout))?;
+                stream.set_write_timeout(Some(timeout))?;
+                Self::Unix(stream)
+            }
+            #[cfg(not(unix))]
+            SignerAddress::Unix(_) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    "Unix sockets are not supported on this platform",
+                ))
+            }
+            SignerAddress::Tcp(addr) => {
+                let stream = TcpStream::connect(addr)?;
+                stream.set_read_timeout(Some(timeout))?;
+                stream.set_write_timeout(Some(timeout))?;
+                Self::Tcp(stream)
+            }
+        };
+
+        Ok(connection)
+    }
+}
+
+impl Read for Connection {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            #[cfg(unix)]
+            Self::Unix(stream) => stream.read(buf),
+            Self::Tcp(stream) => stream.read(buf),
+        }
+    }
+}
+
+impl Write for Connection {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            #[cfg(unix)]
+            Self::Unix(stream) => stream.write(buf),
+            Self::Tcp(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            #[cfg(unix)]
+            Self::Unix(stream) => stream.flush(),
+            Self::Tcp(stream) => stream.flush(),
+        }
+    }
+}
+
+/// The public keys sent by a remote signer, by key name, so that each is only requested once.
+#[derive(Clone, Debug, Default)]
+struct PublicKeys(Arc<Mutex<HashMap<String, PublicKeyInfo>>>);
+
+impl PublicKeys {
+    fn get(&self, key_name: &str) -> Option<PublicKeyInfo> {
+        self.0.lock().unwrap().get(key_name).cloned()
+    }
+
+    fn insert(&self, key_name: &str, public_key: PublicKeyInfo) {
+        self.0
+            .lock()
+            .unwrap()
+            .insert(key_name.to_string(), public_key);
+    }
+}
+
+/// Signs the transactions of a chain by delegating to a remote signer.
+#[derive(Clone, Debug)]
+pub struct RemoteSigner {
+    address: SignerAddress,
+    timeout: Duration,
+    chain_id: ChainId,
+    params: KeyParams,
+    public_keys: PublicKeys,
+}
+
+impl RemoteSigner {
+    pub fn new(
+        address: SignerAddress,
+        timeout: Duration,
+        chain_id: ChainId,
+        params: KeyParams,
+    ) -> Self {
+        Self {
+            address,
+            timeout,
+            chain_id,
+            params,
+            public_keys: PublicKeys::default(),
+        }
+    }
+
+    /// Send `request` to the signer over a new connection, and wait for its response.
+    fn request(&self, request: &SignerRequest) -> Result<SignerResponse, Error> {
+        let io_error =
+            |e: io::Error| Kind::RemoteSigner.context(format!("signer at {}: {}", self.address, e));
+
+        let mut connection = Connection::open(&self.address, self.timeout).map_err(io_error)?;
+
+        let mut line = serde_json::to_string(request).map_err(|e| Kind::RemoteSigner.context(e))?;
+        line.push('\n');
+        connection.write_all(line.as_bytes()).map_err(io_error)?;
+        connection.flush().map_err(io_error)?;
+
+        let mut line = String::new();
+        BufReader::new(connection)
+            .read_line(&mut line)
+            .map_err(io_error)?;
+
+        if line.is_empty() {
+            return Err(Kind::RemoteSigner
+                .context(format!(
+                    "signer at {} closed the connection without responding",
+                    self.address
+                ))
+                .into());
+        }
+
+        match serde_json::from_str(&line).map_err(|e| Kind::RemoteSigner.context(e))? {
+            SignerResponse::Error(e) => Err(Kind::RemoteSigner
+                .context(format!("signer at {}: {}", self.address, e))
+                .into()),
+            response => Ok(response),
+        }
+    }
+}
+
+impl TxSigner for RemoteSigner {
+    fn public_key(&self, key_name: &str) -> Result<PublicKeyInfo, Error> {
+        if let Some(public_key) = self.public_keys.get(key_name) {
+            return Ok(public_key);
+        }
+
+        let response = self.request(&SignerRequest::PublicKey {
+            chain_id: self.chain_id.clone(),
+            key_name: key_name.to_string(),
+        })?;
+
+        let public_key = match response {
+            SignerResponse::PublicKey(public_key) => decode_hex("public key", &public_key)?,
+            response => return Err(unexpected_response(response)),
+        };
+
+        // Only compressed public keys are accepted by the Cosmos SDK
+        if public_key.len() != 33 || VerifyingKey::from_sec1_bytes(&public_key).is_err() {
+            return Err(Kind::RemoteSigner
+                .context("the signer sent an invalid compressed secp256k1 public key")
+                .into());
+        }
+
+        let public_key = PublicKeyInfo::new(public_key, &self.params)?;
+        self.public_keys.insert(key_name, public_key.clone());
+
+        Ok(public_key)
+    }
+
+    fn sign(&self, key_name: &str, sign_doc: Vec<u8>) -> Result<Vec<u8>, Error> {
+        let response = self.request(&SignerRequest::Sign {
+            chain_id: self.chain_id.clone(),
+            key_name: key_name.to_string(),
+            sign_doc: hex::encode(&sign_doc),
+        })?;
+
+        let signature = match response {
+            SignerResponse::Signature(signature) => decode_hex("signature", &signature)?,
+            response => return Err(unexpected_response(response)),
+        };
+
+        // A signature which the chain would reject fails here, with a clearer error
+        let public_key = self.public_key(key_name)?;
+        self.params
+            .algorithm
+            .verify(&public_key.public_key, &sign_doc, &signature)
+            .map_err(|e| Kind::RemoteSigner.context(e))?;
+
+        Ok(signature)
+    }
+}
+
+fn unexpected_response(response: SignerResponse) -> Error {
+    Kind::RemoteSigner
+        .context(format!(
+            "unexpected response from the signer: {:?}",
+            response
+        ))
+        .into()
+}
+
+fn decode_hex(field: &str, value: &str) -> Result<Vec<u8>, Error> {
+    hex::decode(value).map_err(|e| {
+        Kind::RemoteSigner
+            .context(format!("invalid {} from the signer: {}", field, e))
+            .into()
+    })
+}
+
+/// A signer which serves the keys of the key rings of some chains. It is a reference
+/// implementation of the remote signer, for testing.
+pub struct LocalSigner {
+    keyrings: HashMap<ChainId, KeyRing>,
+}
+
+impl LocalSigner {
+    pub fn new(keyrings: HashMap<ChainId, KeyRing>) -> Self {
+        Self { keyrings }
+    }
+
+    /// Serve the requests of the connections accepted at `address`, one connection at a time.
+    pub fn serve(&self, address: &SignerAddress) -> Result<(), Error> {
+        let bind_error = |e: io::Error| {
+            Kind::RemoteSigner.context(format!("cannot listen on {}: {}", address, e))
+        };
+
+        match address {
+            #[cfg(unix)]
+            SignerAddress::Unix(path) => {
+                let listener = UnixListener::bind(path).map_err(bind_error)?;
+                info!("signer listening on {}", address);
+
+                for stream in listener.incoming() {
+                    let result = stream.and_then(|stream| {
+                        stream.set_read_timeout(Some(IDLE_CONNECTION_TIMEOUT))?;
+                        let reader = BufReader::new(stream.try_clone()?);
+                        self.serve_connection(reader, stream)
+                    });
+
+                    if let Err(e) = result {
+                        warn!("signer connection failed: {}", e);
+                    }
+                }
+            }
+            #[cfg(not(unix))]
+            SignerAddress::Unix(_) => {
+                return Err(bind_error(io::Error::new(
+                    io::ErrorKind::Other,
+                    "Unix sockets are not supported on this platform",
+                ))
+                .into())
+            }
+            SignerAddress::Tcp(addr) => {
+                let listener = TcpListener::bind(addr).map_err(bind_error)?;
+                info!("signer listening on {}", address);
+
+                for stream in listener.incoming() {
+                    let result = stream.and_then(|stream| {
+                        stream.set_read_timeout(Some(IDLE_CONNECTION_TIMEOUT))?;
+                        let reader = BufReader::new(stream.try_clone()?);
+                        self.serve_connection(reader, stream)
+                    });
+
+                    if let Err(e) = result {
+                        warn!("signer connection failed: {}", e);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Answer the requests read from `reader`, one per line, until the connection is closed.
+    pub fn serve_connection(&self, reader: impl BufRead, mut writer: impl Write) -> io::Result<()> {
+        for line in reader.lines() {
+            let response = match serde_json::from_str(&line?) {
+                Ok(request) => self.respond(request),
+                Err(e) => SignerResponse::Error(format!("invalid request: {}", e)),
+            };
+
+            let mut line = serde_json::to_string(&response)?;
+            line.push('\n');
+            writer.write_all(line.as_bytes())?;
+            writer.flush()?;
+        }
+
+        Ok(())
+    }
+
+    fn respond(&self, request: SignerRequest) -> SignerResponse {
+        match self.handle(request) {
+            Ok(response) => response,
+            Err(e) => SignerResponse::Error(e.to_string()),
+        }
+    }
+
+    fn handle(&self, request: SignerRequest) -> Result<SignerResponse, Error> {
+        match request {
+            SignerRequest::PublicKey { chain_id, key_name } => {
+                let public_key = self.keyring(&chain_id)?.public_key(&key_name)?;
+                Ok(SignerResponse::PublicKey(hex::encode(
+                    public_key.public_key,
+                )))
+            }
+            SignerRequest::Sign {
+                chain_id,
+                key_name,
+                sign_doc,
+            } => {
+                let sign_doc = hex::decode(&sign_doc)
+                    .map_err(|e| Kind::RemoteSigner.context(format!("invalid sign doc: {}", e)))?;
+
+                let signature = self.keyring(&chain_id)?.sign(&key_name, sign_doc)?;
+                info!(chain_id = %chain_id, key_name = %key_name, "signed a transaction");
+
+                Ok(SignerResponse::Signature(hex::encode(signature)))
+            }
+        }
+    }
+
+    fn keyring(&self, chain_id: &ChainId) -> Result<&KeyRing, Error> {
+        self.keyrings.get(chain_id).ok_or_else(|| {
+            Kind::KeyNotFound
+                .context(format!("no keys for chain {}", chain_id))
+                .into()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::io::BufReader;
+    use std::str::FromStr;
+    use std::thread;
+    use std::time::Duration;
+
+    use ibc::ics24_host::identifier::ChainId;
+
+    use super::{LocalSigner, RemoteSigner, SignerAddress};
+    use crate::keyring::{KeyParams, KeyRing, Memory, TxSigner};
+
+    const MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon \
+                            abandon abandon abandon abandon abandon about";
+
+    #[test]
+    fn parse_signer_address() {
+        assert_eq!(
+            SignerAddress::from_str("unix:///run/signer.sock").unwrap(),
+            SignerAddress::Unix("/run/signer.sock".into())
+        );
+        assert_eq!(
+            SignerAddress::from_str("tcp://127.0.0.1:9090").unwrap(),
+            SignerAddress::Tcp("127.0.0.1:9090".to_string())
+        );
+        assert!(SignerAddress::from_str("http://127.0.0.1:9090").is_err());
+    }
+
+    #[test]
+    fn remote_signing_matches_local_signing() {
+        let chain_id = ChainId::from_str("ibc-0").unwrap();
+
+        let params = KeyParams {
+            account_prefix: "cosmos".to_string(),
+            hd_path: Default::default(),
+            algorithm: Default::default(),
+        };
+
+        let mut keyring = KeyRing::Memory(Memory::new(params.clone()));
+        let key = keyring.key_from_mnemonic(MNEMONIC).unwrap();
+        keyring.add_key("relayer", key).unwrap();
+
+        let mut keyrings = HashMap::new();
+        keyrings.insert(chain_id.clone(), keyring.clone());
+        let local_signer = LocalSigner::new(keyrings);
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = SignerAddress::Tcp(listener.local_addr().unwrap().to_string());
+
+        // Serve the connections of the two requests below, the public key being requested once
+        thread::spawn(move || {
+            for stream in listener.incoming().take(2) {
+                let stream = stream.unwrap();
+                let reader = BufReader::new(stream.try_clone().unwrap());
+                local_signer.serve_connection(reader, stream).unwrap();
+            }
+        });
+
+        let remote_signer = RemoteSigner::new(address, Duration::from_secs(5), chain_id, params);
+
+        assert_eq!(
+            remote_signer.public_key("relayer").unwrap(),
+            keyring.public_key("relayer").unwrap()
+        );
+
+        let sign_doc = b"sign doc".to_vec();
+        assert_eq!(
+            remote_signer.sign("relayer", sign_doc.clone()).unwrap(),
+            keyring.sign("relayer", sign_doc).unwrap()
+        );
+
+        // The public key is not requested again, the signer only serves two connections
+        assert_eq!(
+            remote_signer.public_key("relayer").unwrap(),
+            keyring.public_key("relayer").unwrap()
+        );
+    }
+}
@@ -24,8 +24,12 @@ pub mod keyring;
 pub mod light_client;
 pub mod link;
 pub mod macros;
+pub mod registry;
 pub mod relay;
+pub mod rest;
+pub mod store;
 pub mod supervisor;
+pub mod telemetry;
 pub mod transfer;
 pub mod upgrade_chain;
 pub mod util;
@@ -1,4 +1,6 @@
 use std::convert::TryFrom;
+use std::sync::Arc;
+use std::time::Duration;
 
 use tendermint_light_client::{
     components::{self, io::AtHeight},
@@ -9,7 +11,6 @@ use tendermint_light_client::{
     types::Height as TMHeight,
     types::{LightBlock, PeerId, Status},
 };
-use tendermint_rpc as rpc;
 
 use ibc::{
     downcast,
@@ -26,15 +27,15 @@ use ibc::{
 
 use crate::error::Kind;
 use crate::{
-    chain::CosmosSdkChain,
+    chain::{nodes::Nodes, CosmosSdkChain},
     config::ChainConfig,
     error::{self, Error},
 };
 
 pub struct LightClient {
     chain_id: ChainId,
-    peer_id: PeerId,
-    io: components::io::ProdIo,
+    nodes: Arc<Nodes>,
+    rpc_timeout: Duration,
 }
 
 impl super::LightClient<CosmosSdkChain> for LightClient {
@@ -139,17 +140,29 @@ impl super::LightClient<CosmosSdkChain> for LightClient {
 }
 
 impl LightClient {
-    pub fn from_config(config: &ChainConfig, peer_id: PeerId) -> Result<Self, Error> {
-        let rpc_client = rpc::HttpClient::new(config.rpc_addr.clone())
-            .map_err(|e| error::Kind::LightClient(config.rpc_addr.to_string()).context(e))?;
+    /// Create a light client for the chain `config`, which fetches
+    /// light blocks from the active node among `nodes`.
+    pub fn from_config(config: &ChainConfig, nodes: Arc<Nodes>) -> Self {
+        Self {
+            chain_id: config.id.clone(),
+            nodes,
+            rpc_timeout: config.rpc_timeout,
+        }
+    }
 
-        let io = components::io::ProdIo::new(peer_id, rpc_client, Some(config.rpc_timeout));
+    /// The identifier of the active node, and the component to fetch light blocks from it.
+    fn io(&self) -> Result<(PeerId, components::io::ProdIo), Error> {
+        let node = self.nodes.active();
 
-        Ok(Self {
-            chain_id: config.id.clone(),
-            peer_id,
-            io,
-        })
+        let peer_id = node.peer_id().ok_or_else(|| {
+            error::Kind::LightClient(node.rpc_addr.to_string())
+                .context("the node has never been reachable")
+        })?;
+
+        let io =
+            components::io::ProdIo::new(peer_id, node.rpc_client.clone(), Some(self.rpc_timeout));
+
+        Ok((peer_id, io))
     }
 
     fn prepare_client(&self, client_state: &AnyClientState) -> Result<TmLightClient, Error> {
@@ -172,14 +185,10 @@ impl LightClient {
             clock_drift: client_state.max_clock_drift,
         };
 
+        let (peer_id, io) = self.io()?;
+
         Ok(TmLightClient::new(
-            self.peer_id,
-            params,
-            clock,
-            scheduler,
-            verifier,
-            hasher,
-            self.io.clone(),
+            peer_id, params, clock, scheduler, verifier, hasher, io,
         ))
     }
 
@@ -198,7 +207,11 @@ impl LightClient {
     fn fetch_light_block(&self, height: AtHeight) -> Result<LightBlock, Error> {
         use tendermint_light_client::components::io::Io;
 
-        self.io.fetch_light_block(height).map_err(|e| {
+        let (_, io) = self.io()?;
+
+        io.fetch_light_block(height).map_err(|e| {
+            self.nodes.report_failure();
+
             error::Kind::LightClient(self.chain_id.to_string())
                 .context(e)
                 .into()
@@ -1,11 +1,13 @@
 use std::collections::HashMap;
 use std::fmt;
+use std::mem::{self, Discriminant};
 use std::thread;
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime};
 
 use prost_types::Any;
+use serde_derive::{Deserialize, Serialize};
 use thiserror::Error;
-use tracing::{debug, error, info, trace, warn};
+use tracing::{debug, debug_span, error, info, trace, warn, Span};
 
 use ibc::{
     downcast,
@@ -33,11 +35,13 @@ use ibc_proto::ibc::core::channel::v1::{
 
 use crate::chain::handle::ChainHandle;
 use crate::channel::{Channel, ChannelError, ChannelSide};
+use crate::config::default;
 use crate::connection::ConnectionError;
 use crate::error::Error;
 use crate::event::monitor::EventBatch;
 use crate::foreign_client::{ForeignClient, ForeignClientError};
 use crate::relay::MAX_ITER;
+use crate::store::{Checkpoint, PathId, Store, StoredOperationalData, StoredTransitMessage};
 use ibc::events::VecIbcEvents;
 
 #[derive(Debug, Error)]
@@ -70,7 +74,7 @@ pub enum LinkError {
     SendError(Box<IbcEvent>),
 }
 
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
 pub enum OperationalDataTarget {
     Source,
     Destination,
@@ -124,39 +128,133 @@ impl OperationalData {
         self.batch.iter().map(|gm| gm.event.clone()).collect()
     }
 
+    /// Converts this operational data into its persisted form.
+    fn to_stored(&self) -> StoredOperationalData {
+        StoredOperationalData {
+            proofs_height: self.proofs_height,
+            target: self.target,
+            scheduled_at: SystemTime::now() - self.scheduled_time.elapsed(),
+            batch: self
+                .batch
+                .iter()
+                .map(|gm| StoredTransitMessage {
+                    event: gm.event.clone(),
+                    type_url: gm.msg.type_url.clone(),
+                    value: gm.msg.value.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Summarizes this operational data, for inspection.
+    fn summary(&self) -> OperationalDataSummary {
+        OperationalDataSummary {
+            target: self.target,
+            proofs_height: self.proofs_height,
+            scheduled_secs_ago: self.scheduled_time.elapsed().as_secs(),
+            events: self.events(),
+        }
+    }
+
+    /// Restores operational data from its persisted form. The time elapsed since it was
+    /// scheduled counts towards the connection delay.
+    fn from_stored(stored: StoredOperationalData) -> Self {
+        let elapsed = stored.scheduled_at.elapsed().unwrap_or_default();
+        let now = Instant::now();
+
+        OperationalData {
+            proofs_height: stored.proofs_height,
+            batch: stored
+                .batch
+                .into_iter()
+                .map(|gm| TransitMessage {
+                    event: gm.event,
+                    msg: Any {
+                        type_url: gm.type_url,
+                        value: gm.value,
+                    },
+                })
+                .collect(),
+            target: stored.target,
+            scheduled_time: now.checked_sub(elapsed).unwrap_or(now),
+        }
+    }
+
+    /// Splits this operational data into chunks which can each be sent in one transaction, along
+    /// with a client update: a chunk has at most `max_msg_num - 1` messages and `max_tx_size`
+    /// bytes of messages. The caller deducts the size of the client update from `max_tx_size`.
+    fn chunks(&self, max_msg_num: usize, max_tx_size: usize) -> Vec<OperationalData> {
+        // Leave room for the client update message.
+        let max_msg_num = std::cmp::max(max_msg_num.saturating_sub(1), 1);
+
+        let mut chunks = vec![];
+        let mut chunk = OperationalData {
+            batch: vec![],
+            ..self.clone()
+        };
+        let mut size = 0;
+
+        for transit_msg in self.batch.iter() {
+            let msg_size = prost::Message::encoded_len(&transit_msg.msg);
+
+            if !chunk.batch.is_empty()
+                && (chunk.batch.len() >= max_msg_num || size + msg_size > max_tx_size)
+            {
+                let next = OperationalData {
+                    batch: vec![],
+                    ..chunk.clone()
+                };
+                chunks.push(std::mem::replace(&mut chunk, next));
+                size = 0;
+            }
+
+            size += msg_size;
+            chunk.batch.push(transit_msg.clone());
+        }
+
+        if !chunk.batch.is_empty() {
+            chunks.push(chunk);
+        }
+
+        chunks
+    }
+
     /// Returns all the messages in this operational data, plus prepending the client update message
     /// if necessary.
     fn assemble_msgs(&self, relay_path: &RelayPath) -> Result<Vec<Any>, LinkError> {
+        // For zero delay we prepend the client update msgs.
+        let client_update = if relay_path.zero_delay() {
+            self.client_update(relay_path)?
+        } else {
+            None
+        };
+
+        Ok(self.assemble_msgs_with_update(relay_path, client_update))
+    }
+
+    /// Returns all the messages in this operational data, prepending `client_update` if any,
+    /// e.g. a client update built once for all the chunks of an operational data.
+    fn assemble_msgs_with_update(
+        &self,
+        relay_path: &RelayPath,
+        client_update: Option<Any>,
+    ) -> Vec<Any> {
         if self.batch.is_empty() {
             warn!("assemble_msgs() method call on an empty OperationalData!");
-            return Ok(vec![]);
+            return vec![];
         }
 
         let mut msgs: Vec<Any> = self.batch.iter().map(|gm| gm.msg.clone()).collect();
 
-        // For zero delay we prepend the client update msgs.
-        if relay_path.zero_delay() {
-            let update_height = self.proofs_height.increment();
-
+        if let Some(client_update) = client_update {
             info!(
                 "[{}] prepending {} client update @ height {}",
-                relay_path, self.target, update_height
+                relay_path,
+                self.target,
+                self.proofs_height.increment()
             );
 
-            // Fetch the client update message. Vector may be empty if the client already has the header
-            // for the requested height.
-            let mut client_update_opt = match self.target {
-                OperationalDataTarget::Source => {
-                    relay_path.build_update_client_on_src(update_height)?
-                }
-                OperationalDataTarget::Destination => {
-                    relay_path.build_update_client_on_dst(update_height)?
-                }
-            };
-
-            if let Some(client_update) = client_update_opt.pop() {
-                msgs.insert(0, client_update);
-            }
+            msgs.insert(0, client_update);
         }
 
         info!(
@@ -165,7 +263,24 @@ impl OperationalData {
             msgs.len()
         );
 
-        Ok(msgs)
+        msgs
+    }
+
+    /// Returns the message updating the client on the target chain to the height after the
+    /// proofs height, or `None` if the client already has the header for that height.
+    fn client_update(&self, relay_path: &RelayPath) -> Result<Option<Any>, LinkError> {
+        let update_height = self.proofs_height.increment();
+
+        let mut client_update = match self.target {
+            OperationalDataTarget::Source => {
+                relay_path.build_update_client_on_src(update_height)?
+            }
+            OperationalDataTarget::Destination => {
+                relay_path.build_update_client_on_dst(update_height)?
+            }
+        };
+
+        Ok(client_update.pop())
     }
 }
 
@@ -181,12 +296,24 @@ impl fmt::Display for OperationalData {
     }
 }
 
+/// Summary of operational data which is scheduled but not sent yet.
+#[derive(Clone, Debug, Serialize)]
+pub struct OperationalDataSummary {
+    pub target: OperationalDataTarget,
+    pub proofs_height: Height,
+    pub scheduled_secs_ago: u64,
+    pub events: Vec<IbcEvent>,
+}
+
 pub struct RelayPath {
     src_chain: Box<dyn ChainHandle>,
     dst_chain: Box<dyn ChainHandle>,
     channel: Channel,
     clear_packets: bool,
 
+    // Store in which the operational data and the processed events are persisted, if any.
+    store: Option<Store>,
+
     // Operational data, targeting both the source and destination chain.
     // These vectors of operational data are ordered decreasingly by their age, with element at
     // position `0` being the oldest.
@@ -207,6 +334,7 @@ impl RelayPath {
             dst_chain,
             channel,
             clear_packets: true,
+            store: None,
             src_operational_data: Default::default(),
             dst_operational_data: Default::default(),
         }
@@ -345,11 +473,46 @@ impl RelayPath {
         Ok(new_msg.to_any())
     }
 
+    /// Whether the packet filters of the source and destination chains allow relaying
+    /// the packets of this path's channel.
+    fn packets_allowed(&self) -> bool {
+        let allowed = |chain: &dyn ChainHandle, port_id: &PortId, channel_id: &ChannelId| {
+            match chain.config() {
+                Ok(config) => config.packet_filter.is_allowed(port_id, channel_id),
+                Err(e) => {
+                    warn!(
+                        "[{}] failed to get the configuration of {}: {}",
+                        self,
+                        chain.id(),
+                        e
+                    );
+                    false
+                }
+            }
+        };
+
+        allowed(
+            self.src_chain.as_ref(),
+            self.src_port_id(),
+            self.src_channel_id(),
+        ) && allowed(
+            self.dst_chain.as_ref(),
+            self.dst_port_id(),
+            self.dst_channel_id(),
+        )
+    }
+
     // Determines if the events received are relevant and should be processed.
-    // Only events for a port/channel matching one of the channel ends should be processed.
+    // Only events for a port/channel matching one of the channel ends should be processed,
+    // provided that the packet filters of both chains allow this channel.
     fn filter_events(&self, events: &[IbcEvent]) -> Vec<IbcEvent> {
         let mut result = vec![];
 
+        if !self.packets_allowed() {
+            debug!("[{}] channel is excluded by the packet filter", self);
+            return result;
+        }
+
         for event in events.iter() {
             match event {
                 IbcEvent::SendPacket(send_packet_ev) => {
@@ -387,6 +550,14 @@ impl RelayPath {
     }
 
     fn relay_pending_packets(&mut self, height: Height) -> Result<(), LinkError> {
+        if !self.packets_allowed() {
+            info!(
+                "[{}] channel is excluded by the packet filter, not clearing old packets",
+                self
+            );
+            return Ok(());
+        }
+
         info!("[{}] clearing old packets", self);
         for _i in 0..MAX_ITER {
             if self
@@ -400,6 +571,22 @@ impl RelayPath {
         Err(LinkError::OldPacketClearingFailed)
     }
 
+    /// Summarizes the operational data scheduled on this path, targeting
+    /// the source chain first, from the oldest to the newest.
+    pub fn operational_data_summary(&self) -> Vec<OperationalDataSummary> {
+        self.src_operational_data
+            .iter()
+            .chain(self.dst_operational_data.iter())
+            .map(OperationalData::summary)
+            .collect()
+    }
+
+    /// Prevents this path from clearing pending packets by itself, for when these packets
+    /// are found and scheduled by the caller instead.
+    pub fn disable_packet_clearing(&mut self) {
+        self.clear_packets = false;
+    }
+
     /// Should not run more than once per execution.
     pub fn clear_packets(&mut self, above_height: Height) -> Result<(), LinkError> {
         if self.clear_packets {
@@ -414,18 +601,96 @@ impl RelayPath {
     }
 
     /// Generate & schedule operational data from the input `batch` of IBC events.
-    pub fn update_schedule(&mut self, batch: EventBatch) -> Result<(), LinkError> {
+    /// The events which this path processed before the relayer restarted are skipped, and the
+    /// others are recorded as processed once their operational data is checkpointed.
+    pub fn update_schedule(&mut self, mut batch: EventBatch) -> Result<(), LinkError> {
         self.clear_packets(batch.height)?;
 
+        if let Some(store) = &self.store {
+            batch.events = store.unprocessed(&self.path_id(), &batch)?;
+        }
+
         // Collect relevant events from the incoming batch & adjust their height.
         let events = self.filter_events(&batch.events);
 
         // Transform the events into operational data items
-        self.events_to_operational_data(events)
+        self.events_to_operational_data(events)?;
+
+        if let Some(store) = &self.store {
+            store.record_processed(&self.path_id(), &batch)?;
+        }
+
+        Ok(())
+    }
+
+    /// Restores the operational data which this path had scheduled before a restart from
+    /// `store`, in which its operational data and processed events are persisted from now on.
+    pub fn restore_from(&mut self, store: Store) -> Result<(), LinkError> {
+        if let Some(checkpoint) = store.checkpoint(&self.path_id())? {
+            for stored in checkpoint.operational_data {
+                let od = OperationalData::from_stored(stored);
+                match od.target {
+                    OperationalDataTarget::Source => self.src_operational_data.push(od),
+                    OperationalDataTarget::Destination => self.dst_operational_data.push(od),
+                }
+            }
+
+            info!(
+                "[{}] restored {} op. data item(s)",
+                self,
+                self.src_operational_data.len() + self.dst_operational_data.len(),
+            );
+        }
+
+        self.store = Some(store);
+        Ok(())
+    }
+
+    fn path_id(&self) -> PathId {
+        PathId {
+            chain_id: self.src_chain.id(),
+            port_id: self.src_port_id().clone(),
+            channel_id: self.src_channel_id().clone(),
+        }
+    }
+
+    /// Saves the operational data of this path to its store, if any.
+    fn save_checkpoint(&self) {
+        if let Some(store) = &self.store {
+            let checkpoint = Checkpoint {
+                operational_data: self
+                    .src_operational_data
+                    .iter()
+                    .chain(self.dst_operational_data.iter())
+                    .map(OperationalData::to_stored)
+                    .collect(),
+            };
+
+            if let Err(e) = store.set_checkpoint(&self.path_id(), &checkpoint) {
+                warn!("[{}] failed to save operational data: {}", self, e);
+            }
+        }
+    }
+
+    /// Whether a message is already scheduled for the packet of the given event.
+    fn is_scheduled(&self, event: &IbcEvent) -> bool {
+        let key = match packet_event_key(event) {
+            Some(key) => key,
+            None => return false,
+        };
+
+        self.src_operational_data
+            .iter()
+            .chain(self.dst_operational_data.iter())
+            .flat_map(|od| od.batch.iter())
+            .any(|gm| packet_event_key(&gm.event) == Some(key))
     }
 
     /// Produces and schedules operational data for this relaying path based on the input events.
-    fn events_to_operational_data(&mut self, events: Vec<IbcEvent>) -> Result<(), LinkError> {
+    fn events_to_operational_data(&mut self, mut events: Vec<IbcEvent>) -> Result<(), LinkError> {
+        // Skip the packets which are scheduled already, e.g. found again while clearing packets.
+        events.retain(|event| !self.is_scheduled(event));
+
         // Obtain the operational data for the source chain (mostly timeout packets) and for the
         // destination chain (e.g., receive packet messages).
         let (src_opt, dst_opt) = self.generate_operational_data(events)?;
@@ -471,6 +736,13 @@ impl RelayPath {
         let mut dst_od = OperationalData::new(src_height, OperationalDataTarget::Destination);
 
         for event in input {
+            // Attach the sequence of the packet to the logs pertaining to this event
+            let span = match event.packet() {
+                Some(packet) => debug_span!("packet", sequence = %packet.sequence),
+                None => Span::none(),
+            };
+            let _entered = span.enter();
+
             debug!("[{}] {} => {}", self, self.src_chain.id(), event);
             let (dst_msg, src_msg) = match event {
                 IbcEvent::CloseInitChannel(_) => (
@@ -561,10 +833,127 @@ impl RelayPath {
         Ok((src_od_res, dst_od_res))
     }
 
-    /// Returns the events generated by the target chain
+    /// Returns the events generated by the target chain.
+    /// The operational data is relayed in chunks which respect the `max_msg_num` and `max_tx_size`
+    /// limits of the target chain, and which are all sent at once. Each chunk gets a client update
+    /// if the client on the target chain does not have the consensus state for its proofs yet,
+    /// and is retried on its own if its transaction fails.
+    /// Fails if no chunk could be relayed. Otherwise, the chunks which could not be relayed are
+    /// scheduled again, and relayed with the next scheduled operational data.
     fn relay_from_operational_data(
         &mut self,
         initial_od: OperationalData,
+    ) -> Result<Vec<IbcEvent>, LinkError> {
+        let target = match initial_od.target {
+            OperationalDataTarget::Source => &self.src_chain,
+            OperationalDataTarget::Destination => &self.dst_chain,
+        };
+        let config = target
+            .config()
+            .map_err(|e| LinkError::QueryError(target.id(), e))?;
+
+        // The client update is built once, and prepended to each chunk, where it counts towards
+        // the size of its transaction.
+        let client_update = if self.zero_delay() {
+            initial_od.client_update(self)?
+        } else {
+            None
+        };
+        let update_size = client_update.as_ref().map_or(0, |client_update| {
+            prost::Message::encoded_len(client_update)
+        });
+
+        let chunks = initial_od.chunks(
+            config.max_msg_num.unwrap_or_else(default::max_msg_num),
+            config
+                .max_tx_size
+                .unwrap_or_else(default::max_tx_size)
+                .saturating_sub(update_size),
+        );
+        let chunk_count = chunks.len();
+
+        info!(
+            "[{}] relay op. data to {} in {} transaction(s), proofs height {}, (delayed by: {:?})",
+            self,
+            initial_od.target,
+            chunk_count,
+            initial_od.proofs_height,
+            initial_od.scheduled_time.elapsed(),
+        );
+
+        // The chunks are sent together, each in a transaction of its own, so that they are all
+        // in flight at once.
+        let txs = chunks
+            .iter()
+            .map(|chunk| chunk.assemble_msgs_with_update(self, client_update.clone()))
+            .collect();
+        let results = target.send_txs(txs)?;
+
+        let mut events = vec![];
+        let mut failed = vec![];
+        for (i, (chunk, result)) in chunks.into_iter().zip(results).enumerate() {
+            // A chunk whose transaction failed is retried on its own, from regenerated
+            // operational data.
+            let relayed = result
+                .map_err(LinkError::from)
+                .and_then(|tx_events| self.check_tx_events(tx_events));
+
+            let relayed = match relayed {
+                Err(LinkError::SendError(ev)) => {
+                    error!("[{}] error {}", self, ev);
+                    match self.regenerate_operational_data(chunk.clone()) {
+                        None => Ok(vec![]), // Nothing to retry
+                        Some(new_od) => self.relay_chunk_from_operational_data(new_od),
+                    }
+                }
+                relayed => relayed,
+            };
+
+            match relayed {
+                Ok(mut chunk_events) => events.append(&mut chunk_events),
+                Err(e) => {
+                    error!(
+                        "[{}] failed to relay chunk {}/{} of {}: {}",
+                        self,
+                        i + 1,
+                        chunk_count,
+                        initial_od,
+                        e
+                    );
+                    failed.push((chunk, e));
+                }
+            }
+        }
+
+        // If no chunk could be relayed, the whole operational data stays scheduled.
+        if failed.len() == chunk_count {
+            if let Some((_, e)) = failed.pop() {
+                return Err(e);
+            }
+        }
+
+        for (chunk, _) in failed {
+            self.reschedule_operational_data(chunk);
+        }
+
+        Ok(events)
+    }
+
+    /// Schedules again a chunk of operational data which could not be relayed, keeping its
+    /// scheduled time, such that it is relayed with the next scheduled operational data.
+    fn reschedule_operational_data(&mut self, od: OperationalData) {
+        info!("[{}] rescheduling {} which could not be relayed", self, od);
+
+        match od.target {
+            OperationalDataTarget::Source => self.src_operational_data.push(od),
+            OperationalDataTarget::Destination => self.dst_operational_data.push(od),
+        };
+    }
+
+    /// Returns the events generated by the target chain for a single chunk of operational data
+    fn relay_chunk_from_operational_data(
+        &mut self,
+        initial_od: OperationalData,
     ) -> Result<Vec<IbcEvent>, LinkError> {
         // We will operate on potentially different operational data if the initial one fails.
         let mut odata = initial_od;
@@ -604,7 +993,7 @@ impl RelayPath {
         Ok(vec![])
     }
 
-    /// Helper for managing retries of the `relay_from_operational_data` method.
+    /// Helper for managing retries of the `relay_chunk_from_operational_data` method.
     /// Expects as input the initial operational data that failed to send.
     ///
     /// Return value:
@@ -701,15 +1090,20 @@ impl RelayPath {
         let msgs = odata.assemble_msgs(self)?;
 
         let tx_events = target.send_msgs(msgs)?;
+        self.check_tx_events(tx_events)
+    }
+
+    /// Checks the events of a transaction sent for operational data: a `ChainError` event means
+    /// that the transaction failed, and that its messages may be retried.
+    fn check_tx_events(&self, tx_events: Vec<IbcEvent>) -> Result<Vec<IbcEvent>, LinkError> {
         info!("[{}] result {}\n", self, VecIbcEvents(tx_events.clone()));
 
         let ev = tx_events
-            .clone()
-            .into_iter()
+            .iter()
             .find(|event| matches!(event, IbcEvent::ChainError(_)));
 
         match ev {
-            Some(ev) => Err(LinkError::SendError(Box::new(ev))),
+            Some(ev) => Err(LinkError::SendError(Box::new(ev.clone()))),
             None => Ok(tx_events),
         }
     }
@@ -1228,17 +1622,39 @@ impl RelayPath {
     /// of corresponding packets to the target chain.
     pub fn execute_schedule(&mut self) -> Result<(), LinkError> {
         let (src_ods, dst_ods) = self.try_fetch_scheduled_operational_data();
+
         for od in src_ods {
-            self.relay_from_operational_data(od)?;
+            self.relay_scheduled_operational_data(od)?;
         }
 
         for od in dst_ods {
-            self.relay_from_operational_data(od)?;
+            self.relay_scheduled_operational_data(od)?;
         }
 
         Ok(())
     }
 
+    /// Relays an operational data fetched from the schedule, which is only removed from the
+    /// schedule, and from the checkpoint, once it is relayed. An operational data which fails
+    /// to be relayed thus stays scheduled, and is retried later or after a restart.
+    fn relay_scheduled_operational_data(
+        &mut self,
+        od: OperationalData,
+    ) -> Result<Vec<IbcEvent>, LinkError> {
+        let target = od.target;
+        let events = self.relay_from_operational_data(od)?;
+
+        // The relayed op. data is still the oldest one for its target, any op. data scheduled
+        // while relaying it was appended to the schedule.
+        match target {
+            OperationalDataTarget::Source => self.src_operational_data.remove(0),
+            OperationalDataTarget::Destination => self.dst_operational_data.remove(0),
+        };
+        self.save_checkpoint();
+
+        Ok(events)
+    }
+
     /// Refreshes the scheduled batches.
     /// Verifies if any sendPacket messages timed-out. If so, moves them from destination op. data
     /// to source operational data, and adjusts the events and messages accordingly.
@@ -1310,6 +1726,9 @@ impl RelayPath {
         // Retain only the non-empty ones.
         self.dst_operational_data
             .retain(|odata| !odata.batch.is_empty());
+
+        self.save_checkpoint();
+
         Ok(())
     }
 
@@ -1348,43 +1767,41 @@ impl RelayPath {
             OperationalDataTarget::Destination => self.dst_operational_data.push(od),
         };
 
+        self.save_checkpoint();
+
         Ok(())
     }
 
     /// Pulls out the operational elements with elapsed delay period and that can
     /// now be processed. Does not block: if no OD fulfilled the delay period (or none is
     /// scheduled), returns immediately with `vec![]`.
-    fn try_fetch_scheduled_operational_data(
-        &mut self,
-    ) -> (Vec<OperationalData>, Vec<OperationalData>) {
+    /// The elements stay scheduled until `relay_scheduled_operational_data` relays them.
+    fn try_fetch_scheduled_operational_data(&self) -> (Vec<OperationalData>, Vec<OperationalData>) {
         // The first elements of the op. data vector contain the oldest entry.
-        // Remove and return the elements with elapsed delay.
+        // Return the elements with elapsed delay.
 
         let src_ods: Vec<OperationalData> = self
             .src_operational_data
             .iter()
-            .filter(|op| op.scheduled_time.elapsed() > self.channel.connection_delay)
+            .take_while(|op| op.scheduled_time.elapsed() > self.channel.connection_delay)
             .cloned()
             .collect();
 
-        self.src_operational_data = self.src_operational_data[src_ods.len()..].to_owned();
-
         let dst_ods: Vec<OperationalData> = self
             .dst_operational_data
             .iter()
-            .filter(|op| op.scheduled_time.elapsed() > self.channel.connection_delay)
+            .take_while(|op| op.scheduled_time.elapsed() > self.channel.connection_delay)
             .cloned()
             .collect();
 
-        self.dst_operational_data = self.dst_operational_data[dst_ods.len()..].to_owned();
-
         (src_ods, dst_ods)
     }
 
     /// Fetches an operational data that has fulfilled its predefined delay period. May _block_
     /// waiting for the delay period to pass.
     /// Returns `None` if there is no operational data scheduled.
-    fn fetch_scheduled_operational_data(&mut self) -> Option<OperationalData> {
+    /// The operational data stays scheduled until `relay_scheduled_operational_data` relays it.
+    fn fetch_scheduled_operational_data(&self) -> Option<OperationalData> {
         if let Some(odata) = self
             .src_operational_data
             .first()
@@ -1415,16 +1832,25 @@ impl RelayPath {
                 }
             }
 
-            let od = match odata.target {
-                OperationalDataTarget::Source => self.src_operational_data.remove(0),
-                OperationalDataTarget::Destination => self.dst_operational_data.remove(0),
-            };
-            return Some(od);
+            return Some(odata.clone());
         }
         None
     }
 }
 
+/// Identifies the packet of a packet event, along with the kind of event, such that two
+/// events with the same key lead to relaying the same message.
+fn packet_event_key(event: &IbcEvent) -> Option<(Discriminant<IbcEvent>, Sequence)> {
+    let sequence = match event {
+        IbcEvent::SendPacket(e) => e.packet.sequence,
+        IbcEvent::WriteAcknowledgement(e) => e.packet.sequence,
+        IbcEvent::TimeoutPacket(e) => e.packet.sequence,
+        _ => return None,
+    };
+
+    Some((mem::discriminant(event), sequence))
+}
+
 impl fmt::Display for RelayPath {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{} -> {}", self.src_chain.id(), self.dst_chain.id())
@@ -1454,6 +1880,13 @@ impl Link {
         }
     }
 
+    /// Restores the operational data of both relaying paths from `store`, and records the
+    /// events processed by each path there from now on.
+    pub fn restore_from(&mut self, store: Store) -> Result<(), LinkError> {
+        self.a_to_b.restore_from(store.clone())?;
+        self.b_to_a.restore_from(store)
+    }
+
     pub fn relay(&mut self) -> Result<(), LinkError> {
         info!(
             "relaying packets on path {} <-> {} with delay of {:?}",
@@ -1462,8 +1895,14 @@ impl Link {
             self.a_to_b.channel.connection_delay
         );
 
+        // Over a loopback channel, both relay paths take their events from the same chain, to
+        // which a single subscription suffices.
         let events_a = self.a_to_b.src_chain().subscribe()?;
-        let events_b = self.b_to_a.src_chain().subscribe()?;
+        let events_b = if self.is_loopback() {
+            None
+        } else {
+            Some(self.b_to_a.src_chain().subscribe()?)
+        };
 
         loop {
             if self.is_closed()? {
@@ -1473,14 +1912,18 @@ impl Link {
 
             // Input new events to the relay path, and schedule any batch associated with them
             if let Ok(batch) = events_a.try_recv() {
-                self.a_to_b.update_schedule(batch.unwrap_or_clone())?;
+                let batch = batch.unwrap_or_clone();
+                if events_b.is_none() {
+                    self.b_to_a.update_schedule(batch.clone())?;
+                }
+                self.a_to_b.update_schedule(batch)?;
             }
 
             // Refresh the scheduled batches and execute any outstanding ones.
             self.a_to_b.refresh_schedule()?;
             self.a_to_b.execute_schedule()?;
 
-            if let Ok(batch) = events_b.try_recv() {
+            if let Some(Ok(batch)) = events_b.as_ref().map(|events_b| events_b.try_recv()) {
                 self.b_to_a.update_schedule(batch.unwrap_or_clone())?;
             }
 
@@ -1492,6 +1935,12 @@ impl Link {
         }
     }
 
+    /// Whether both ends of the channel are on the same chain, which then relays to itself
+    /// over ICS 09 localhost clients.
+    pub fn is_loopback(&self) -> bool {
+        self.a_to_b.src_chain().id() == self.a_to_b.dst_chain().id()
+    }
+
     pub fn is_closed(&self) -> Result<bool, LinkError> {
         let a_channel = self
             .a_to_b
@@ -1620,7 +2069,7 @@ impl Link {
 
         // Block waiting for all of the scheduled data (until `None` is returned)
         while let Some(odata) = self.a_to_b.fetch_scheduled_operational_data() {
-            let mut last_res = self.a_to_b.relay_from_operational_data(odata)?;
+            let mut last_res = self.a_to_b.relay_scheduled_operational_data(odata)?;
             results.append(&mut last_res);
         }
 
@@ -1634,7 +2083,7 @@ impl Link {
 
         // Block waiting for all of the scheduled data
         while let Some(odata) = self.a_to_b.fetch_scheduled_operational_data() {
-            let mut last_res = self.a_to_b.relay_from_operational_data(odata)?;
+            let mut last_res = self.a_to_b.relay_scheduled_operational_data(odata)?;
             results.append(&mut last_res);
         }
 
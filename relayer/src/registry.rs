@@ -0,0 +1,121 @@
+//! Registry for keeping track of [`ChainHandle`]s indexed by a `ChainId`.
+
+use std::collections::HashMap;
+
+use ibc::ics24_host::identifier::ChainId;
+
+use crate::{
+    chain::{handle::ChainHandle, runtime::ChainRuntime, CosmosSdkChain},
+    config::Config,
+    error::{Error, Kind},
+};
+
+/// Spawns the runtime of the chain with the given identifier, configured in the given
+/// [`Config`], and returns its handle.
+pub type ChainSpawner = fn(&Config, &ChainId) -> Result<Box<dyn ChainHandle>, Error>;
+
+/// Registry for keeping track of [`ChainHandle`]s indexed by a `ChainId`.
+///
+/// The purpose of this type is to avoid spawning multiple runtimes for a single `ChainId`.
+pub struct Registry {
+    config: Config,
+    handles: HashMap<ChainId, Box<dyn ChainHandle>>,
+    spawner: ChainSpawner,
+}
+
+impl Registry {
+    /// Construct a new [`Registry`] using the provided [`Config`]
+    pub fn new(config: Config) -> Self {
+        Self::with_spawner(config, spawn_chain_runtime)
+    }
+
+    /// Construct a new [`Registry`] using the provided [`Config`], whose chain runtimes are
+    /// spawned by `spawner`.
+    pub fn with_spawner(config: Config, spawner: ChainSpawner) -> Self {
+        Self {
+            config,
+            handles: HashMap::new(),
+            spawner,
+        }
+    }
+
+    /// Return the configuration from which the chain runtimes are spawned.
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Replace the configuration from which the chain runtimes are spawned.
+    /// The runtimes spawned already are left untouched.
+    pub fn set_config(&mut self, config: Config) {
+        self.config = config;
+    }
+
+    /// Return the number of chains whose runtime has been spawned.
+    pub fn size(&self) -> usize {
+        self.handles.len()
+    }
+
+    /// Return the handles of the chains whose runtime has been spawned.
+    pub fn chains(&self) -> Vec<Box<dyn ChainHandle>> {
+        self.handles.values().cloned().collect()
+    }
+
+    /// Get the [`ChainHandle`] associated with the given [`ChainId`], if its runtime
+    /// has been spawned already.
+    pub fn get(&self, chain_id: &ChainId) -> Option<Box<dyn ChainHandle>> {
+        self.handles.get(chain_id).cloned()
+    }
+
+    /// Get the [`ChainHandle`] associated with the given [`ChainId`].
+    ///
+    /// If there is no handle yet, this will first spawn the runtime and then
+    /// return its handle.
+    pub fn get_or_spawn(&mut self, chain_id: &ChainId) -> Result<Box<dyn ChainHandle>, Error> {
+        if !self.handles.contains_key(chain_id) {
+            let handle = (self.spawner)(&self.config, chain_id)?;
+            self.handles.insert(chain_id.clone(), handle);
+        }
+
+        let handle = self.handles.get(chain_id).unwrap();
+        Ok(handle.clone())
+    }
+
+    /// Spawn the runtime of the given chain as configured in `config`, rather than in the
+    /// configuration of the registry, and return its handle without registering it.
+    pub fn spawn(
+        &self,
+        config: &Config,
+        chain_id: &ChainId,
+    ) -> Result<Box<dyn ChainHandle>, Error> {
+        (self.spawner)(config, chain_id)
+    }
+
+    /// Register the handle of a chain runtime spawned with [`Registry::spawn`].
+    /// The runtime of the chain registered before, if any, must have been shut down.
+    pub fn insert(&mut self, handle: Box<dyn ChainHandle>) {
+        self.handles.insert(handle.id(), handle);
+    }
+
+    /// Stop the runtime of the given chain, if it has been spawned, and forget its handle.
+    pub fn shutdown(&mut self, chain_id: &ChainId) -> Result<(), Error> {
+        match self.handles.remove(chain_id) {
+            Some(handle) => handle.shutdown(),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Spawns a chain runtime from the configuration and given a chain identifier.
+/// Returns the corresponding handle if successful.
+fn spawn_chain_runtime(config: &Config, chain_id: &ChainId) -> Result<Box<dyn ChainHandle>, Error> {
+    let chain_config = config.find_chain(chain_id).cloned().ok_or_else(|| {
+        Kind::Config.context(format!(
+            "missing chain for id ({}) in configuration file",
+            chain_id
+        ))
+    })?;
+
+    let (handle, _) = ChainRuntime::<CosmosSdkChain>::spawn(chain_config)?;
+
+    Ok(handle)
+}
@@ -11,6 +11,7 @@ use crate::config::RelayPath;
 use crate::connection::Connection;
 use crate::foreign_client::ForeignClient;
 use crate::link::{Link, LinkParameters};
+use crate::store::Store;
 
 pub(crate) const MAX_ITER: usize = 10;
 
@@ -21,11 +22,15 @@ pub fn relay_on_new_link(
     delay: Duration,
     ordering: Order,
     path: RelayPath,
+    store: Option<Store>,
 ) -> Result<(), BoxError> {
     // Setup the clients, connection and channel
     let channel = connect_with_new_channel(a_chain_handle, b_chain_handle, delay, ordering, path)?;
 
     let mut link = Link::new(channel);
+    if let Some(store) = store {
+        link.restore_from(store)?;
+    }
     link.relay()?;
 
     Ok(())
@@ -37,8 +42,12 @@ pub fn channel_relay(
     a_chain: Box<dyn ChainHandle>,
     b_chain: Box<dyn ChainHandle>,
     opts: LinkParameters,
+    store: Option<Store>,
 ) -> Result<(), BoxError> {
     let mut link = Link::new_from_opts(a_chain, b_chain, opts)?;
+    if let Some(store) = store {
+        link.restore_from(store)?;
+    }
     Ok(link.relay()?)
 }
 
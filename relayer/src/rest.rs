@@ -0,0 +1,321 @@
+//! REST API of the relayer, served over HTTP, to inspect and control a running supervisor:
+//!
+//! - `GET /chains`: the supervised chains, and their latest height
+//! - `GET /workers`: the workers, with the objects they relay and their scheduled
+//!   operational data
+//! - `POST /clear_packets`: schedule the clearing of the pending packets of all the open
+//!   channels
+//! - `POST /paths/<chain>/<port>/<channel>/pause`: stop relaying the packets of a channel
+//! - `POST /paths/<chain>/<port>/<channel>/resume`: resume relaying the packets of a channel
+//! - `POST /clients/<chain>/<client>/update`: update a client hosted on a chain
+//!
+//! Responses are JSON documents. Failed requests are answered with an `error` message.
+
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use anomaly::BoxError;
+use crossbeam_channel::{RecvTimeoutError, Sender};
+use serde_json::{json, Value};
+use tiny_http::{Header, Method, Request, Response, Server};
+use tracing::{error, info};
+
+use ibc::ics02_client::client_state::ClientState;
+use ibc::ics24_host::identifier::{ChainId, ChannelId, ClientId, PortId};
+use ibc::Height;
+
+use crate::chain::handle::ChainHandle;
+use crate::foreign_client::ForeignClient;
+use crate::supervisor::SupervisorCmd;
+
+/// Time to wait for the supervisor to reply to a command.
+const SUPERVISOR_REPLY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Time to wait for the workers to report their status.
+const WORKER_STATUS_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// An endpoint of the REST API.
+#[derive(Clone, Debug, PartialEq)]
+enum Route {
+    Chains,
+    Workers,
+    ClearPackets,
+    SetPaused {
+        chain_id: ChainId,
+        port_id: PortId,
+        channel_id: ChannelId,
+        paused: bool,
+    },
+    UpdateClient {
+        chain_id: ChainId,
+        client_id: ClientId,
+    },
+}
+
+impl Route {
+    fn parse(method: &Method, url: &str) -> Option<Self> {
+        let path = url.split('?').next()?;
+        let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+
+        match (method, segments.as_slice()) {
+            (Method::Get, ["chains"]) => Some(Route::Chains),
+            (Method::Get, ["workers"]) => Some(Route::Workers),
+            (Method::Post, ["clear_packets"]) => Some(Route::ClearPackets),
+            (Method::Post, ["paths", chain_id, port_id, channel_id, action]) => {
+                let paused = match *action {
+                    "pause" => true,
+                    "resume" => false,
+                    _ => return None,
+                };
+
+                Some(Route::SetPaused {
+                    chain_id: chain_id.parse().ok()?,
+                    port_id: port_id.parse().ok()?,
+                    channel_id: channel_id.parse().ok()?,
+                    paused,
+                })
+            }
+            (Method::Post, ["clients", chain_id, client_id, "update"]) => {
+                Some(Route::UpdateClient {
+                    chain_id: chain_id.parse().ok()?,
+                    client_id: client_id.parse().ok()?,
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// A failed request, answered with the given HTTP status code.
+struct ApiError {
+    status: u16,
+    message: String,
+}
+
+impl ApiError {
+    fn not_found(message: impl ToString) -> Self {
+        Self {
+            status: 404,
+            message: message.to_string(),
+        }
+    }
+
+    fn internal(message: impl ToString) -> Self {
+        Self {
+            status: 500,
+            message: message.to_string(),
+        }
+    }
+}
+
+/// Spawns a thread which serves the REST API on `http://<host>:<port>`,
+/// forwarding the requests to the supervisor through `supervisor`.
+pub fn spawn(
+    host: &str,
+    port: u16,
+    supervisor: Sender<SupervisorCmd>,
+) -> Result<JoinHandle<()>, BoxError> {
+    let server = Server::http((host, port))?;
+
+    info!("REST API listening on http://{}:{}", host, port);
+
+    let handle = thread::spawn(move || {
+        for request in server.incoming_requests() {
+            respond(request, &supervisor);
+        }
+    });
+
+    Ok(handle)
+}
+
+fn respond(request: Request, supervisor: &Sender<SupervisorCmd>) {
+    let result = match Route::parse(request.method(), request.url()) {
+        Some(route) => handle(route, supervisor),
+        None => Err(ApiError::not_found("not found")),
+    };
+
+    let (status, body) = match result {
+        Ok(body) => (200, body),
+        Err(e) => (e.status, json!({ "error": e.message })),
+    };
+
+    let mut response = Response::from_string(body.to_string()).with_status_code(status);
+    if let Ok(header) = Header::from_bytes("Content-Type", "application/json") {
+        response.add_header(header);
+    }
+
+    if let Err(e) = request.respond(response) {
+        error!("failed to respond to REST request: {}", e);
+    }
+}
+
+fn handle(route: Route, supervisor: &Sender<SupervisorCmd>) -> Result<Value, ApiError> {
+    match route {
+        Route::Chains => {
+            let chains = query(supervisor, |reply_to| SupervisorCmd::QueryChains {
+                reply_to,
+            })?;
+
+            let chains: Vec<Value> = chains
+                .iter()
+                .map(|chain| {
+                    json!({
+                        "id": chain.id(),
+                        "latest_height": chain.query_latest_height().ok(),
+                    })
+                })
+                .collect();
+
+            Ok(json!(chains))
+        }
+        Route::Workers => {
+            let workers = query(supervisor, |reply_to| SupervisorCmd::QueryWorkers {
+                reply_to,
+            })?;
+            let deadline = Instant::now() + WORKER_STATUS_TIMEOUT;
+
+            let workers: Vec<Value> = workers
+                .into_iter()
+                .map(|worker| {
+                    json!({
+                        "object": worker.object,
+                        "queue_depth": worker.queue_depth,
+                        // Missing if the worker is busy, e.g. submitting a transaction.
+                        "status": worker.status.recv_deadline(deadline).ok(),
+                    })
+                })
+                .collect();
+
+            Ok(json!(workers))
+        }
+        Route::ClearPackets => {
+            query(supervisor, |reply_to| SupervisorCmd::ClearPackets {
+                reply_to,
+            })?;
+            Ok(json!({ "scheduled": true }))
+        }
+        Route::SetPaused {
+            chain_id,
+            port_id,
+            channel_id,
+            paused,
+        } => {
+            query(supervisor, |reply_to| SupervisorCmd::SetPaused {
+                chain_id,
+                port_id,
+                channel_id,
+                paused,
+                reply_to,
+            })?
+            .map_err(ApiError::not_found)?;
+
+            Ok(json!({ "paused": paused }))
+        }
+        Route::UpdateClient {
+            chain_id,
+            client_id,
+        } => {
+            let chains = query(supervisor, |reply_to| SupervisorCmd::QueryChains {
+                reply_to,
+            })?;
+            update_client(&chains, &chain_id, &client_id)?;
+
+            Ok(json!({ "updated": true }))
+        }
+    }
+}
+
+/// Send a command to the supervisor, and wait for its reply for at most
+/// [`SUPERVISOR_REPLY_TIMEOUT`].
+fn query<T>(
+    supervisor: &Sender<SupervisorCmd>,
+    cmd: impl FnOnce(Sender<T>) -> SupervisorCmd,
+) -> Result<T, ApiError> {
+    let (reply_to, reply) = crossbeam_channel::bounded(1);
+
+    supervisor
+        .send(cmd(reply_to))
+        .map_err(|_| ApiError::internal("the supervisor is not running"))?;
+
+    reply
+        .recv_timeout(SUPERVISOR_REPLY_TIMEOUT)
+        .map_err(|e| match e {
+            RecvTimeoutError::Timeout => ApiError::internal("the supervisor did not reply in time"),
+            RecvTimeoutError::Disconnected => ApiError::internal("the supervisor did not reply"),
+        })
+}
+
+/// Update the client `client_id` hosted on the chain `chain_id`,
+/// provided that both the host and the target chains of the client are supervised.
+fn update_client(
+    chains: &[Box<dyn ChainHandle>],
+    chain_id: &ChainId,
+    client_id: &ClientId,
+) -> Result<(), ApiError> {
+    let find_chain = |chain_id: &ChainId| {
+        chains
+            .iter()
+            .find(|chain| &chain.id() == chain_id)
+            .cloned()
+            .ok_or_else(|| ApiError::not_found(format!("chain {} is not supervised", chain_id)))
+    };
+
+    let host_chain = find_chain(chain_id)?;
+
+    let client_state = host_chain
+        .query_client_state(client_id, Height::zero())
+        .map_err(|e| ApiError::not_found(format!("client {}: {}", client_id, e)))?;
+
+    let target_chain = find_chain(&client_state.chain_id())?;
+
+    ForeignClient::find(target_chain, host_chain, client_id)
+        .and_then(|client| client.update())
+        .map_err(ApiError::internal)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use tiny_http::Method;
+
+    use ibc::ics24_host::identifier::{ChainId, ChannelId, ClientId, PortId};
+
+    use super::Route;
+
+    #[test]
+    fn parse_routes() {
+        assert_eq!(Route::parse(&Method::Get, "/chains"), Some(Route::Chains));
+        assert_eq!(
+            Route::parse(&Method::Get, "/workers/"),
+            Some(Route::Workers)
+        );
+        assert_eq!(
+            Route::parse(&Method::Post, "/clear_packets?all"),
+            Some(Route::ClearPackets)
+        );
+        assert_eq!(
+            Route::parse(&Method::Post, "/paths/ibc-0/transfer/channel-0/pause"),
+            Some(Route::SetPaused {
+                chain_id: ChainId::from_str("ibc-0").unwrap(),
+                port_id: PortId::from_str("transfer").unwrap(),
+                channel_id: ChannelId::from_str("channel-0").unwrap(),
+                paused: true,
+            })
+        );
+        assert_eq!(
+            Route::parse(&Method::Post, "/clients/ibc-1/07-tendermint-0/update"),
+            Some(Route::UpdateClient {
+                chain_id: ChainId::from_str("ibc-1").unwrap(),
+                client_id: ClientId::from_str("07-tendermint-0").unwrap(),
+            })
+        );
+
+        assert_eq!(Route::parse(&Method::Post, "/chains"), None);
+        assert_eq!(
+            Route::parse(&Method::Post, "/paths/ibc-0/transfer/channel-0/stop"),
+            None
+        );
+        assert_eq!(Route::parse(&Method::Get, "/unknown"), None);
+    }
+}
@@ -0,0 +1,289 @@
+//! Persistent state of the relayer, kept in a [`sled`] database so that a restarted relayer
+//! picks up where it left off: the operational data scheduled by each relaying path, and the
+//! events it processed at the last height of its source chain.
+
+use std::path::Path;
+use std::time::SystemTime;
+
+use serde_derive::{Deserialize, Serialize};
+
+use ibc::{
+    events::IbcEvent,
+    ics24_host::identifier::{ChainId, ChannelId, PortId},
+    Height,
+};
+
+use crate::error::{Error, Kind};
+use crate::event::monitor::EventBatch;
+use crate::link::OperationalDataTarget;
+use crate::util::sled::{self as db, KeyValueDb};
+
+/// Identifies a relaying path by its source chain, port and channel.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PathId {
+    pub chain_id: ChainId,
+    pub port_id: PortId,
+    pub channel_id: ChannelId,
+}
+
+/// The state of a relaying path: the operational data it has scheduled but not sent yet.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub operational_data: Vec<StoredOperationalData>,
+}
+
+/// The events of a chain processed by a relaying path at the last height at which it processed
+/// events, in their CBOR encoding.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+struct ProcessedEvents {
+    height: Height,
+    events: Vec<Vec<u8>>,
+}
+
+/// Operational data of a relaying path, along with the time at which it was scheduled.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StoredOperationalData {
+    pub proofs_height: Height,
+    pub target: OperationalDataTarget,
+    pub scheduled_at: SystemTime,
+    pub batch: Vec<StoredTransitMessage>,
+}
+
+/// A message which is scheduled to be sent, with the event which generated it.
+/// The message is stored in its protobuf `Any` encoding.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StoredTransitMessage {
+    pub event: IbcEvent,
+    pub type_url: String,
+    pub value: Vec<u8>,
+}
+
+/// Handle to the relayer state store, which can be shared between threads.
+#[derive(Clone)]
+pub struct Store {
+    db: sled::Db,
+    processed: KeyValueDb<PathId, ProcessedEvents>,
+    checkpoints: KeyValueDb<PathId, Checkpoint>,
+}
+
+impl Store {
+    /// Opens the store in the directory `path`, creating it if needed.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let db = sled::open(path).map_err(|e| Kind::Store.context(e))?;
+        Ok(Self::new(db))
+    }
+
+    /// Opens a store which is deleted once dropped.
+    #[cfg(test)]
+    pub fn temporary() -> Self {
+        let db = sled::Config::new().temporary(true).open().unwrap();
+        Self::new(db)
+    }
+
+    fn new(db: sled::Db) -> Self {
+        Self {
+            db,
+            processed: db::key_value("processed/"),
+            checkpoints: db::key_value("checkpoints/"),
+        }
+    }
+
+    /// The events of `batch` which the relaying path `path_id` did not process before, e.g.
+    /// before the relayer restarted.
+    ///
+    /// The events below the last height at which the path processed events are all deemed
+    /// processed. Those at that height are compared one by one with the events processed at
+    /// that height, since the events of a block arrive in several batches.
+    pub fn unprocessed(
+        &self,
+        path_id: &PathId,
+        batch: &EventBatch,
+    ) -> Result<Vec<IbcEvent>, Error> {
+        let processed = self.processed.fetch(&self.db, path_id)?.unwrap_or_default();
+
+        if batch.height < processed.height {
+            return Ok(vec![]);
+        }
+
+        let mut unprocessed = vec![];
+        for event in &batch.events {
+            let encoded = encode_event(event)?;
+            if batch.height > processed.height || !processed.events.contains(&encoded) {
+                unprocessed.push(event.clone());
+            }
+        }
+
+        Ok(unprocessed)
+    }
+
+    /// Records the events of `batch` as processed by the relaying path `path_id`, which must
+    /// only be done once the operational data built from them is checkpointed.
+    pub fn record_processed(&self, path_id: &PathId, batch: &EventBatch) -> Result<(), Error> {
+        let mut processed = self.processed.fetch(&self.db, path_id)?.unwrap_or_default();
+
+        if batch.height < processed.height || batch.events.is_empty() {
+            return Ok(());
+        }
+
+        if batch.height > processed.height {
+            processed = ProcessedEvents {
+                height: batch.height,
+                events: vec![],
+            };
+        }
+
+        for event in &batch.events {
+            let encoded = encode_event(event)?;
+            if !processed.events.contains(&encoded) {
+                processed.events.push(encoded);
+            }
+        }
+
+        self.processed.insert(&self.db, path_id, &processed)
+    }
+
+    /// The last checkpoint of the relaying path `path_id`.
+    pub fn checkpoint(&self, path_id: &PathId) -> Result<Option<Checkpoint>, Error> {
+        self.checkpoints.fetch(&self.db, path_id)
+    }
+
+    pub fn set_checkpoint(&self, path_id: &PathId, checkpoint: &Checkpoint) -> Result<(), Error> {
+        self.checkpoints.insert(&self.db, path_id, checkpoint)
+    }
+}
+
+fn encode_event(event: &IbcEvent) -> Result<Vec<u8>, Error> {
+    serde_cbor::to_vec(event).map_err(|e| Kind::Store.context(e).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use std::time::SystemTime;
+
+    use ibc::events::IbcEvent;
+    use ibc::ics02_client::events::NewBlock;
+    use ibc::ics04_channel::events::SendPacket;
+    use ibc::ics04_channel::packet::Packet;
+    use ibc::ics24_host::identifier::{ChainId, ChannelId, PortId};
+    use ibc::Height;
+
+    use super::{Checkpoint, PathId, Store, StoredOperationalData};
+    use crate::event::monitor::EventBatch;
+    use crate::link::OperationalDataTarget;
+
+    fn batch(chain_id: &ChainId, height: u64, events: Vec<IbcEvent>) -> EventBatch {
+        EventBatch {
+            chain_id: chain_id.clone(),
+            height: Height::new(0, height),
+            events,
+        }
+    }
+
+    fn send_packet(height: u64, sequence: u64) -> IbcEvent {
+        IbcEvent::SendPacket(SendPacket {
+            height: Height::new(0, height),
+            packet: Packet {
+                sequence: sequence.into(),
+                ..Default::default()
+            },
+        })
+    }
+
+    fn transfer_path(chain_id: &str, channel_id: &str) -> PathId {
+        PathId {
+            chain_id: ChainId::from_str(chain_id).unwrap(),
+            port_id: PortId::from_str("transfer").unwrap(),
+            channel_id: ChannelId::from_str(channel_id).unwrap(),
+        }
+    }
+
+    #[test]
+    fn events_are_processed_once() {
+        let store = Store::temporary();
+        let path_id = transfer_path("ibc-0", "channel-0");
+        let chain_id = &path_id.chain_id;
+        let new_block = IbcEvent::NewBlock(NewBlock::new(Height::new(0, 42)));
+
+        // The events of a block arrive in several batches.
+        let first = batch(chain_id, 42, vec![new_block.clone()]);
+        assert_eq!(store.unprocessed(&path_id, &first).unwrap().len(), 1);
+        store.record_processed(&path_id, &first).unwrap();
+
+        let second = batch(chain_id, 42, vec![send_packet(42, 1)]);
+        assert_eq!(store.unprocessed(&path_id, &second).unwrap().len(), 1);
+
+        // The events are unprocessed until they are recorded, e.g. if scheduling them failed.
+        assert_eq!(store.unprocessed(&path_id, &second).unwrap().len(), 1);
+        store.record_processed(&path_id, &second).unwrap();
+
+        // After a restart, the events of the last height are received again, along with new ones.
+        let unprocessed = store
+            .unprocessed(
+                &path_id,
+                &batch(
+                    chain_id,
+                    42,
+                    vec![new_block, send_packet(42, 1), send_packet(42, 2)],
+                ),
+            )
+            .unwrap();
+        assert_eq!(unprocessed.len(), 1);
+        assert!(
+            matches!(&unprocessed[0], IbcEvent::SendPacket(ev) if ev.packet.sequence == 2.into())
+        );
+
+        // The events below the last height were processed.
+        assert!(store
+            .unprocessed(&path_id, &batch(chain_id, 41, vec![send_packet(41, 3)]))
+            .unwrap()
+            .is_empty());
+
+        assert_eq!(
+            store
+                .unprocessed(&path_id, &batch(chain_id, 43, vec![send_packet(43, 1)]))
+                .unwrap()
+                .len(),
+            1
+        );
+
+        // Each relaying path records the events it processed on its own.
+        let other_path_id = transfer_path("ibc-0", "channel-1");
+        assert_eq!(
+            store
+                .unprocessed(
+                    &other_path_id,
+                    &batch(chain_id, 41, vec![send_packet(41, 3)])
+                )
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn checkpoints_are_restored() {
+        let store = Store::temporary();
+
+        let path_id = transfer_path("ibc-0", "channel-0");
+
+        assert!(store.checkpoint(&path_id).unwrap().is_none());
+
+        let checkpoint = Checkpoint {
+            operational_data: vec![StoredOperationalData {
+                proofs_height: Height::new(0, 39),
+                target: OperationalDataTarget::Destination,
+                scheduled_at: SystemTime::now(),
+                batch: vec![],
+            }],
+        };
+        store.set_checkpoint(&path_id, &checkpoint).unwrap();
+
+        let restored = store.checkpoint(&path_id).unwrap().unwrap();
+        assert_eq!(restored.operational_data.len(), 1);
+        assert_eq!(
+            restored.operational_data[0].target,
+            OperationalDataTarget::Destination
+        );
+    }
+}
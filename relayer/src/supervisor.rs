@@ -1,29 +1,44 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet, VecDeque},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
     thread::{self, JoinHandle},
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use anomaly::BoxError;
-use crossbeam_channel::{Receiver, Sender};
+use crossbeam_channel::{Receiver, Sender, TryRecvError};
 
 use ibc::{
-    events::IbcEvent,
+    events::{IbcEvent, IbcEventType},
     ics02_client::events::NewBlock,
     ics03_connection::connection::State as ConnectionState,
     ics04_channel::{
-        channel::State as ChannelState,
+        channel::{IdentifiedChannelEnd, QueryPacketEventDataRequest, State as ChannelState},
         events::{CloseInit, SendPacket, TimeoutPacket, WriteAcknowledgement},
+        packet::Sequence,
     },
     ics24_host::identifier::{ChainId, ChannelId, PortId},
+    query::QueryTxRequest,
     Height,
 };
-use tracing::{info, warn};
+use ibc_proto::ibc::core::channel::v1::{
+    QueryChannelsRequest, QueryPacketAcknowledgementsRequest, QueryPacketCommitmentsRequest,
+    QueryUnreceivedAcksRequest, QueryUnreceivedPacketsRequest,
+};
+use serde_derive::Serialize;
+use tracing::{debug, error, info, info_span, warn, Span};
 
 use crate::{
-    chain::handle::ChainHandle,
+    chain::handle::{ChainHandle, Subscription},
+    config::Config,
     event::monitor::EventBatch,
-    link::{Link, LinkParameters},
+    link::{Link, LinkParameters, OperationalDataSummary},
+    registry::Registry,
+    store::Store,
+    telemetry,
 };
 
 /// A command for a [`Worker`].
@@ -35,12 +50,28 @@ pub enum WorkerCmd {
         height: Height,
         new_blocks: Vec<NewBlock>,
     },
+    /// Reply with the status of the worker
+    QueryStatus { reply_to: Sender<WorkerStatus> },
+    /// Stop or resume relaying. The events received while paused are relayed upon resuming.
+    SetPaused { paused: bool },
+    /// Find the packets which are pending on the path of the worker, and relay them
+    ClearPackets,
+}
+
+/// The status of a [`Worker`].
+#[derive(Clone, Debug, Serialize)]
+pub struct WorkerStatus {
+    pub paused: bool,
+    /// The operational data scheduled by the worker, but not sent yet.
+    pub operational_data: Vec<OperationalDataSummary>,
 }
 
 /// Handle to a [`Worker`], for sending [`WorkerCmd`]s to it.
 pub struct WorkerHandle {
     tx: Sender<WorkerCmd>,
     thread_handle: JoinHandle<()>,
+    /// Whether a [`WorkerCmd::ClearPackets`] command is waiting to be processed by the worker.
+    clearing_scheduled: Arc<AtomicBool>,
 }
 
 impl WorkerHandle {
@@ -71,8 +102,38 @@ impl WorkerHandle {
         Ok(())
     }
 
-    /// Wait for the worker thread to finish.
+    /// Ask the worker for its status, which is sent back on the returned receiver.
+    pub fn query_status(&self) -> Result<Receiver<WorkerStatus>, BoxError> {
+        let (reply_to, status) = crossbeam_channel::bounded(1);
+        self.tx.send(WorkerCmd::QueryStatus { reply_to })?;
+        Ok(status)
+    }
+
+    /// Stop or resume relaying.
+    pub fn set_paused(&self, paused: bool) -> Result<(), BoxError> {
+        self.tx.send(WorkerCmd::SetPaused { paused })?;
+        Ok(())
+    }
+
+    /// Ask the worker to clear the pending packets of its path, unless it has yet to process
+    /// a previous request to do so.
+    pub fn clear_packets(&self) -> Result<(), BoxError> {
+        if !self.clearing_scheduled.swap(true, Ordering::SeqCst) {
+            self.tx.send(WorkerCmd::ClearPackets)?;
+        }
+
+        Ok(())
+    }
+
+    /// The number of commands which the worker has not processed yet.
+    pub fn queue_depth(&self) -> usize {
+        self.tx.len()
+    }
+
+    /// Wait for the worker thread to finish. The worker stops once it notices
+    /// that this handle, through which it receives commands, was dropped.
     pub fn join(self) -> thread::Result<()> {
+        drop(self.tx);
         self.thread_handle.join()
     }
 }
@@ -94,49 +155,343 @@ impl ChainHandlePair {
     }
 }
 
-/// The supervisor listens for events on a pair of chains,
-/// and dispatches the events it receives to the appropriate
-/// worker, based on the [`Object`] associated with each event.
+/// A command for the [`Supervisor`].
+pub enum SupervisorCmd {
+    /// Apply a new configuration, and relay between the given chains from now on.
+    UpdateConfig {
+        config: Config,
+        chain_ids: Vec<ChainId>,
+    },
+    /// Reply with handles to the supervised chains.
+    QueryChains {
+        reply_to: Sender<Vec<Box<dyn ChainHandle>>>,
+    },
+    /// Reply with the workers, and the receivers of their status.
+    QueryWorkers { reply_to: Sender<Vec<WorkerInfo>> },
+    /// Clear the pending packets of all the open channels, and reply once the workers in charge
+    /// of these channels were asked to.
+    ClearPackets { reply_to: Sender<()> },
+    /// Pause or resume relaying the packets of the channel `channel_id` bound
+    /// to `port_id` on the chain `chain_id`.
+    SetPaused {
+        chain_id: ChainId,
+        port_id: PortId,
+        channel_id: ChannelId,
+        paused: bool,
+        reply_to: Sender<Result<(), String>>,
+    },
+}
+
+/// A worker of the [`Supervisor`], in reply to [`SupervisorCmd::QueryWorkers`].
+pub struct WorkerInfo {
+    pub object: Object,
+    pub queue_depth: usize,
+    /// Receives the status of the worker, once it gets to answer.
+    pub status: Receiver<WorkerStatus>,
+}
+
+/// The supervisor listens for events on multiple chains, and dispatches the
+/// events it receives to the appropriate worker, based on the [`Object`]
+/// associated with each event.
+///
+/// Each chain has a single runtime and event subscription, shared by all the
+/// workers relaying to or from it.
 pub struct Supervisor {
-    chains: ChainHandlePair,
+    registry: Registry,
+    store: Option<Store>,
+    subscriptions: HashMap<ChainId, Subscription>,
     workers: HashMap<Object, WorkerHandle>,
+    /// The objects whose workers are paused, which stay paused when respawned.
+    paused: HashSet<Object>,
+    cmd_tx: Sender<SupervisorCmd>,
+    cmd_rx: Receiver<SupervisorCmd>,
 }
 
 impl Supervisor {
-    /// Spawn a supervisor which listens for events on the two given chains.
-    pub fn spawn(
-        chain_a: Box<dyn ChainHandle>,
-        chain_b: Box<dyn ChainHandle>,
-    ) -> Result<Self, BoxError> {
-        let chains = ChainHandlePair {
-            a: chain_a,
-            b: chain_b,
-        };
+    /// Spawn a supervisor which listens for events on the given chains, and relays
+    /// between any pair of them.
+    pub fn spawn(config: Config, chain_ids: &[ChainId]) -> Result<Self, BoxError> {
+        let store = config
+            .global
+            .state_dir
+            .as_ref()
+            .map(Store::open)
+            .transpose()?;
+
+        let mut registry = Registry::new(config);
+        for chain_id in chain_ids {
+            registry.get_or_spawn(chain_id)?;
+        }
 
-        Ok(Self {
-            chains,
+        Ok(Self::from_registry(registry, store))
+    }
+
+    /// A supervisor of the chains whose runtime was spawned by `registry`.
+    fn from_registry(registry: Registry, store: Option<Store>) -> Self {
+        let (cmd_tx, cmd_rx) = crossbeam_channel::unbounded();
+
+        Self {
+            registry,
+            store,
+            subscriptions: HashMap::new(),
             workers: HashMap::new(),
-        })
+            paused: HashSet::new(),
+            cmd_tx,
+            cmd_rx,
+        }
+    }
+
+    /// Returns a sender of commands to this supervisor, e.g. to reload its configuration.
+    pub fn sender(&self) -> Sender<SupervisorCmd> {
+        self.cmd_tx.clone()
     }
 
     /// Run the supervisor event loop.
     pub fn run(mut self) -> Result<(), BoxError> {
-        let subscription_a = self.chains.a.subscribe()?;
-        let subscription_b = self.chains.b.subscribe()?;
+        for chain in self.registry.chains() {
+            self.subscriptions.insert(chain.id(), chain.subscribe()?);
+        }
+
+        // Packets sent or acknowledged while the relayer was not running would
+        // otherwise only be relayed once new events show up on their channel.
+        self.clear_pending_packets(false);
+        let mut last_cleared = Instant::now();
 
         loop {
-            for batch in subscription_a.try_iter() {
-                self.process_batch(self.chains.a.clone(), batch.unwrap_or_clone())?;
+            let cmds: Vec<SupervisorCmd> = self.cmd_rx.try_iter().collect();
+            for cmd in cmds {
+                self.handle_cmd(cmd);
+            }
+
+            for chain in self.registry.chains() {
+                let batches: Vec<_> = match self.subscriptions.get(&chain.id()) {
+                    Some(subscription) => subscription.try_iter().collect(),
+                    None => continue,
+                };
+
+                for batch in batches {
+                    self.process_batch(chain.clone(), batch.unwrap_or_clone())?;
+                }
             }
 
-            for batch in subscription_b.try_iter() {
-                self.process_batch(self.chains.b.clone(), batch.unwrap_or_clone())?;
+            let clear_packets_interval = self.registry.config().global.clear_packets_interval;
+            if clear_packets_interval != Duration::from_secs(0)
+                && last_cleared.elapsed() >= clear_packets_interval
+            {
+                self.clear_workers_packets();
+                last_cleared = Instant::now();
             }
 
             std::thread::sleep(Duration::from_millis(600));
         }
     }
 
+    fn handle_cmd(&mut self, cmd: SupervisorCmd) {
+        match cmd {
+            SupervisorCmd::UpdateConfig { config, chain_ids } => {
+                match self.update_config(config, &chain_ids) {
+                    Ok(()) => info!("configuration reloaded"),
+                    Err(e) => error!("refusing to reload the configuration: {}", e),
+                }
+            }
+            SupervisorCmd::QueryChains { reply_to } => {
+                let _ = reply_to.send(self.registry.chains());
+            }
+            SupervisorCmd::QueryWorkers { reply_to } => {
+                let workers = self
+                    .workers
+                    .iter()
+                    .filter_map(|(object, worker)| {
+                        Some(WorkerInfo {
+                            object: object.clone(),
+                            queue_depth: worker.queue_depth(),
+                            status: worker.query_status().ok()?,
+                        })
+                    })
+                    .collect();
+
+                let _ = reply_to.send(workers);
+            }
+            SupervisorCmd::ClearPackets { reply_to } => {
+                self.clear_pending_packets(false);
+                let _ = reply_to.send(());
+            }
+            SupervisorCmd::SetPaused {
+                chain_id,
+                port_id,
+                channel_id,
+                paused,
+                reply_to,
+            } => {
+                let result = self.set_paused(&chain_id, &port_id, &channel_id, paused);
+                let _ = reply_to.send(result);
+            }
+        }
+    }
+
+    /// Pause or resume the worker relaying the packets of the channel `channel_id`
+    /// bound to `port_id` on the chain `chain_id`, and any worker respawned in its place.
+    fn set_paused(
+        &mut self,
+        chain_id: &ChainId,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        paused: bool,
+    ) -> Result<(), String> {
+        let worker = self.workers.iter().find(|(object, _)| match object {
+            Object::UnidirectionalChannelPath(path) => {
+                &path.src_chain_id == chain_id
+                    && &path.src_port_id == port_id
+                    && &path.src_channel_id == channel_id
+            }
+        });
+
+        match worker {
+            Some((object, worker)) => {
+                info!(
+                    "[{}] {} worker",
+                    object.short_name(),
+                    if paused { "pausing" } else { "resuming" }
+                );
+
+                if paused {
+                    self.paused.insert(object.clone());
+                } else {
+                    self.paused.remove(object);
+                }
+
+                worker.set_paused(paused).map_err(|e| e.to_string())
+            }
+            None => Err(format!(
+                "no worker relays the packets of {}/{} on {}",
+                port_id, channel_id, chain_id
+            )),
+        }
+    }
+
+    /// Apply a new configuration, after which the supervisor relays between `chain_ids`.
+    ///
+    /// Only the runtimes of the chains which are no longer supervised, or whose configuration
+    /// changed, are stopped, along with the workers relaying to or from these chains. The other
+    /// workers keep running, so that their pending operational data is neither dropped nor
+    /// submitted twice. The configuration is refused, and left unchanged, if it is invalid or if
+    /// the runtime of a new chain cannot be started.
+    fn update_config(&mut self, config: Config, chain_ids: &[ChainId]) -> Result<(), BoxError> {
+        config.validate()?;
+
+        if let Some(chain_id) = chain_ids.iter().find(|id| config.find_chain(id).is_none()) {
+            return Err(format!("chain {} is missing from the configuration", chain_id).into());
+        }
+
+        if config.telemetry != self.registry.config().telemetry {
+            warn!("the telemetry settings only apply after a restart");
+        }
+
+        if config.rest != self.registry.config().rest {
+            warn!("the REST API settings only apply after a restart");
+        }
+
+        if config.global.state_dir != self.registry.config().global.state_dir {
+            warn!("the state directory only applies after a restart");
+        }
+
+        let changed = |chain_id: &ChainId| {
+            self.registry.config().find_chain(chain_id) != config.find_chain(chain_id)
+        };
+
+        let stopped: Vec<ChainId> = self
+            .registry
+            .chains()
+            .into_iter()
+            .map(|chain| chain.id())
+            .filter(|chain_id| !chain_ids.contains(chain_id) || changed(chain_id))
+            .collect();
+
+        let started: Vec<ChainId> = chain_ids
+            .iter()
+            .filter(|chain_id| self.registry.get(chain_id).is_none() || changed(chain_id))
+            .cloned()
+            .collect();
+
+        // Start the runtimes of the new chains first, while the current ones keep running.
+        let spawned = self.spawn_chains(&config, &started)?;
+
+        let retired: Vec<Object> = self
+            .workers
+            .keys()
+            .filter(|object| {
+                stopped.contains(object.src_chain_id()) || stopped.contains(object.dst_chain_id())
+            })
+            .cloned()
+            .collect();
+
+        for object in retired {
+            self.retire_worker(&object);
+        }
+
+        for chain_id in stopped {
+            info!("[{}] stopping chain runtime", chain_id);
+
+            self.subscriptions.remove(&chain_id);
+            if let Err(e) = self.registry.shutdown(&chain_id) {
+                warn!("[{}] failed to stop chain runtime: {}", chain_id, e);
+            }
+        }
+
+        for (chain, subscription) in spawned {
+            self.subscriptions.insert(chain.id(), subscription);
+            self.registry.insert(chain);
+        }
+
+        self.registry.set_config(config);
+
+        // The objects of the (re)started chains have no worker anymore,
+        // their pending packets are cleared by the new workers.
+        self.clear_pending_packets(true);
+
+        Ok(())
+    }
+
+    /// Start the runtimes of the given chains as configured in `config`, and subscribe to their
+    /// events. If any of them fails to start, those started already are stopped.
+    fn spawn_chains(
+        &self,
+        config: &Config,
+        chain_ids: &[ChainId],
+    ) -> Result<Vec<(Box<dyn ChainHandle>, Subscription)>, BoxError> {
+        let mut spawned = vec![];
+
+        for chain_id in chain_ids {
+            info!("[{}] starting chain runtime", chain_id);
+
+            let result =
+                self.registry
+                    .spawn(config, chain_id)
+                    .and_then(|chain| match chain.subscribe() {
+                        Ok(subscription) => Ok((chain, subscription)),
+                        Err(e) => {
+                            let _ = chain.shutdown();
+                            Err(e)
+                        }
+                    });
+
+            match result {
+                Ok(chain) => spawned.push(chain),
+                Err(e) => {
+                    for (chain, _) in spawned {
+                        if let Err(e) = chain.shutdown() {
+                            warn!("[{}] failed to stop chain runtime: {}", chain.id(), e);
+                        }
+                    }
+
+                    return Err(format!("failed to start chain {}: {}", chain_id, e).into());
+                }
+            }
+        }
+
+        Ok(spawned)
+    }
+
     /// Process a batch of events received from a chain.
     fn process_batch(
         &mut self,
@@ -148,12 +503,6 @@ impl Supervisor {
         let height = batch.height;
         let chain_id = batch.chain_id.clone();
 
-        let direction = if chain_id == self.chains.a.id() {
-            Direction::AtoB
-        } else {
-            Direction::BtoA
-        };
-
         let mut collected = collect_events(src_chain.as_ref(), batch);
 
         for (object, events) in collected.per_object.drain() {
@@ -161,96 +510,306 @@ impl Supervisor {
                 continue;
             }
 
-            println!("[{}] events: {:#?}", chain_id, events);
+            for event in &events {
+                debug!(
+                    chain_id = %chain_id,
+                    height = %height,
+                    object = %object.short_name(),
+                    event = %event,
+                    "received event"
+                );
+            }
+
+            if let Some(worker) = self.worker_for_object(object.clone()) {
+                if worker
+                    .send_packet_events(height, events, chain_id.clone())
+                    .is_err()
+                {
+                    self.retire_worker(&object);
+                    continue;
+                }
 
-            if let Some(worker) = self.worker_for_object(object, direction) {
-                worker.send_packet_events(height, events, chain_id.clone())?;
+                record_queue_depth(&object, worker.queue_depth());
             }
         }
 
         if collected.has_new_blocks() {
-            for worker in self.workers.values() {
-                worker.send_new_blocks(height, collected.new_blocks.clone())?;
+            let objects: Vec<Object> = self
+                .workers
+                .keys()
+                .filter(|object| object.src_chain_id() == &chain_id)
+                .cloned()
+                .collect();
+
+            for object in objects {
+                let sent = self.workers[&object]
+                    .send_new_blocks(height, collected.new_blocks.clone())
+                    .is_ok();
+
+                if !sent {
+                    self.retire_worker(&object);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Ask the workers in charge of the open channels of the supervised chains, which are
+    /// spawned if need be, to clear the pending packets of these channels. The workers find
+    /// these packets themselves, so as not to hold up the processing of new events.
+    ///
+    /// If `new_objects_only` is set, the channels relayed by existing workers are skipped.
+    fn clear_pending_packets(&mut self, new_objects_only: bool) {
+        for chain in self.registry.chains() {
+            let request = QueryChannelsRequest {
+                pagination: ibc_proto::cosmos::base::query::pagination::all(),
+            };
+
+            let channels = match chain.query_channels(request) {
+                Ok(channels) => channels,
+                Err(e) => {
+                    warn!("[{}] failed to query channels: {}", chain.id(), e);
+                    continue;
+                }
+            };
+
+            for channel in channels {
+                if !channel.channel_end.state_matches(&ChannelState::Open) {
+                    continue;
+                }
+
+                if let Err(e) = self.clear_channel(chain.as_ref(), &channel, new_objects_only) {
+                    warn!(
+                        "[{}] failed to clear pending packets on {}/{}: {}",
+                        chain.id(),
+                        channel.port_id,
+                        channel.channel_id,
+                        e
+                    );
+                }
             }
         }
+    }
+
+    /// Ask the worker of a single open channel of `src_chain` to clear its pending packets,
+    /// provided that its counterparty chain is supervised.
+    fn clear_channel(
+        &mut self,
+        src_chain: &dyn ChainHandle,
+        channel: &IdentifiedChannelEnd,
+        new_objects_only: bool,
+    ) -> Result<(), BoxError> {
+        if !self.packet_filter_allows(&src_chain.id(), &channel.port_id, &channel.channel_id) {
+            return Ok(());
+        }
+
+        let dst_chain_id =
+            get_counterparty_chain(src_chain, &channel.channel_id, &channel.port_id)?;
+
+        if self.registry.get(&dst_chain_id).is_none() {
+            return Ok(());
+        }
+
+        let counterparty = channel.channel_end.counterparty();
+        let dst_allowed = counterparty.channel_id().map_or(false, |dst_channel_id| {
+            self.packet_filter_allows(&dst_chain_id, counterparty.port_id(), dst_channel_id)
+        });
+
+        if !dst_allowed {
+            return Ok(());
+        }
+
+        let object = Object::from(UnidirectionalChannelPath {
+            dst_chain_id,
+            src_chain_id: src_chain.id(),
+            src_channel_id: channel.channel_id.clone(),
+            src_port_id: channel.port_id.clone(),
+        });
+
+        if new_objects_only && self.workers.contains_key(&object) {
+            return Ok(());
+        }
+
+        self.clear_object_packets(&object);
 
         Ok(())
     }
 
+    /// Ask the existing workers to clear the pending packets of their path.
+    fn clear_workers_packets(&mut self) {
+        let objects: Vec<Object> = self.workers.keys().cloned().collect();
+        for object in objects {
+            self.clear_object_packets(&object);
+        }
+    }
+
+    /// Ask the worker in charge of `object`, which is spawned if need be, to clear the pending
+    /// packets of its path.
+    fn clear_object_packets(&mut self, object: &Object) {
+        let sent = match self.worker_for_object(object.clone()) {
+            Some(worker) => worker.clear_packets().is_ok(),
+            None => true,
+        };
+
+        if !sent {
+            self.retire_worker(object);
+        }
+    }
+
     /// Get a handle to the worker in charge of handling events associated
     /// with the given [`Object`].
     ///
-    /// This function will spawn a new [`Worker`] if one does not exists already.
-    ///
-    /// The `direction` parameter indicates in which direction the worker should
-    /// relay events.
-    fn worker_for_object(&mut self, object: Object, direction: Direction) -> Option<&WorkerHandle> {
+    /// This function will spawn a new [`Worker`] if one does not exists already,
+    /// provided that both the source and destination chains of the object are
+    /// supervised.
+    fn worker_for_object(&mut self, object: Object) -> Option<&WorkerHandle> {
         if self.workers.contains_key(&object) {
             Some(&self.workers[&object])
         } else {
-            let chains = match direction {
-                Direction::AtoB => self.chains.clone(),
-                Direction::BtoA => self.chains.clone().swap(),
+            let chains = match (
+                self.registry.get(object.src_chain_id()),
+                self.registry.get(object.dst_chain_id()),
+            ) {
+                (Some(a), Some(b)) => self.with_path_keys(&object, ChainHandlePair { a, b }),
+                _ => {
+                    info!(
+                        "object {} is not relevant to the supervised chains",
+                        object.short_name()
+                    );
+
+                    return None;
+                }
             };
 
-            if object.src_chain_id() != &chains.a.id() || object.dst_chain_id() != &chains.b.id() {
-                info!(
-                    "object {:?} is not relevant to worker for chains {}/{}",
-                    object,
-                    chains.a.id(),
-                    chains.b.id()
+            let allowed = match object {
+                Object::UnidirectionalChannelPath(ref path) => self.packet_filter_allows(
+                    &path.src_chain_id,
+                    &path.src_port_id,
+                    &path.src_channel_id,
+                ),
+            };
+
+            if !allowed {
+                debug!(
+                    "object {} is excluded by the packet filter",
+                    object.short_name()
                 );
 
                 return None;
             }
 
-            let worker = Worker::spawn(chains, object.clone());
+            let worker = Worker::spawn(chains, object.clone(), self.store.clone());
+
+            if self.paused.contains(&object) {
+                info!("[{}] worker spawned paused", object.short_name());
+
+                if let Err(e) = worker.set_paused(true) {
+                    warn!("[{}] failed to pause worker: {}", object.short_name(), e);
+                }
+            }
+
             let worker = self.workers.entry(object).or_insert(worker);
             Some(worker)
         }
     }
-}
 
-/// The direction in which a [`Worker`] should relay events.
-#[derive(Copy, Clone, Debug)]
-pub enum Direction {
-    /// From chain A to chain B.
-    AtoB,
-    /// From chain B to chain A.
-    BtoA,
+    /// Let the chain handles of the worker relaying over `object` sign with the keys
+    /// configured for its path, if any.
+    fn with_path_keys(&self, object: &Object, chains: ChainHandlePair) -> ChainHandlePair {
+        let (src_key, dst_key) = match object {
+            Object::UnidirectionalChannelPath(path) => self.registry.config().path_keys(
+                &path.src_chain_id,
+                &path.dst_chain_id,
+                &path.src_port_id,
+            ),
+        };
+
+        ChainHandlePair {
+            a: match src_key {
+                Some(key_name) => chains.a.with_key(key_name),
+                None => chains.a,
+            },
+            b: match dst_key {
+                Some(key_name) => chains.b.with_key(key_name),
+                None => chains.b,
+            },
+        }
+    }
+
+    /// Whether the packet filter of `chain_id` allows relaying the packets of the channel
+    /// `channel_id` bound to `port_id`.
+    fn packet_filter_allows(
+        &self,
+        chain_id: &ChainId,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> bool {
+        self.registry
+            .config()
+            .find_chain(chain_id)
+            .map_or(false, |chain_config| {
+                chain_config.packet_filter.is_allowed(port_id, channel_id)
+            })
+    }
+
+    /// Remove the worker in charge of the given [`Object`], whose thread has exited.
+    /// A new worker is spawned when further events are associated with the object.
+    fn retire_worker(&mut self, object: &Object) {
+        if let Some(worker) = self.workers.remove(object) {
+            info!("[{}] retiring worker", object.short_name());
+
+            if worker.join().is_err() {
+                warn!("[{}] worker panicked", object.short_name());
+            }
+        }
+    }
 }
 
 /// A worker processes batches of events associated with a given [`Object`].
 pub struct Worker {
     chains: ChainHandlePair,
+    store: Option<Store>,
     rx: Receiver<WorkerCmd>,
+    clearing_scheduled: Arc<AtomicBool>,
 }
 
 impl Worker {
     /// Spawn a worker which relay events pertaining to `object` between two `chains`.
-    pub fn spawn(chains: ChainHandlePair, object: Object) -> WorkerHandle {
+    /// If a `store` is given, the worker resumes from the state it persisted there.
+    pub fn spawn(chains: ChainHandlePair, object: Object, store: Option<Store>) -> WorkerHandle {
         let (tx, rx) = crossbeam_channel::unbounded();
 
-        println!(
-            "[{}] Spawned worker for object {:#?}",
-            object.short_name(),
-            object
-        );
+        info!(object = %object.short_name(), "spawned worker");
+
+        let clearing_scheduled = Arc::new(AtomicBool::new(false));
 
-        let worker = Self { chains, rx };
+        let worker = Self {
+            chains,
+            store,
+            rx,
+            clearing_scheduled: clearing_scheduled.clone(),
+        };
         let thread_handle = std::thread::spawn(move || worker.run(object));
 
-        WorkerHandle { tx, thread_handle }
+        WorkerHandle {
+            tx,
+            thread_handle,
+            clearing_scheduled,
+        }
     }
 
     /// Run the worker event loop.
     fn run(self, object: Object) {
+        let span = object.span();
+        let _entered = span.enter();
+
         let result = match object {
             Object::UnidirectionalChannelPath(path) => self.run_uni_chan_path(path),
         };
 
         if let Err(e) = result {
-            eprintln!("worker error: {}", e);
+            error!(error = %e, "worker error");
         }
     }
 
@@ -260,8 +819,8 @@ impl Worker {
             self.chains.a.clone(),
             self.chains.b.clone(),
             LinkParameters {
-                src_port_id: path.src_port_id,
-                src_channel_id: path.src_channel_id,
+                src_port_id: path.src_port_id.clone(),
+                src_channel_id: path.src_channel_id.clone(),
             },
         )?;
 
@@ -270,20 +829,54 @@ impl Worker {
             return Ok(());
         }
 
+        // Pending packets are found by the supervisor, which sends them as packet events.
+        link.a_to_b.disable_packet_clearing();
+
+        if let Some(store) = self.store.clone() {
+            link.a_to_b.restore_from(store)?;
+        }
+
+        let object = Object::from(path);
+
+        let mut paused = false;
+        // Commands received while paused, which are processed upon resuming.
+        let mut deferred = VecDeque::new();
+
         loop {
-            if let Ok(cmd) = self.rx.try_recv() {
+            let cmd = match self.rx.try_recv() {
+                Ok(cmd) => Some(cmd),
+                Err(TryRecvError::Empty) => None,
+                Err(TryRecvError::Disconnected) => {
+                    info!("[{}] worker handle dropped, exiting", object.short_name());
+                    return Ok(());
+                }
+            };
+
+            if let Some(cmd) = cmd {
+                record_queue_depth(&object, self.rx.len());
+
                 match cmd {
-                    WorkerCmd::PacketEvents { batch } => {
-                        link.a_to_b.update_schedule(batch)?;
-                        // Refresh the scheduled batches and execute any outstanding ones.
+                    WorkerCmd::QueryStatus { reply_to } => {
+                        let _ = reply_to.send(WorkerStatus {
+                            paused,
+                            operational_data: link.a_to_b.operational_data_summary(),
+                        });
                     }
-                    WorkerCmd::NewBlocks {
-                        height,
-                        new_blocks: _,
-                    } => link.a_to_b.clear_packets(height)?,
+                    WorkerCmd::SetPaused { paused: value } => paused = value,
+                    cmd if paused => deferred.push_back(cmd),
+                    cmd => self.relay_cmd(&mut link, cmd)?,
                 }
             }
 
+            if paused {
+                thread::sleep(Duration::from_millis(100));
+                continue;
+            }
+
+            while let Some(cmd) = deferred.pop_front() {
+                self.relay_cmd(&mut link, cmd)?;
+            }
+
             // Refresh the scheduled batches and execute any outstanding ones.
             link.a_to_b.refresh_schedule()?;
             link.a_to_b.execute_schedule()?;
@@ -291,10 +884,78 @@ impl Worker {
             thread::sleep(Duration::from_millis(100))
         }
     }
+
+    /// Relay the events carried by the command `cmd` over `link`.
+    fn relay_cmd(&self, link: &mut Link, cmd: WorkerCmd) -> Result<(), BoxError> {
+        match cmd {
+            WorkerCmd::PacketEvents { batch } => link.a_to_b.update_schedule(batch)?,
+            WorkerCmd::NewBlocks {
+                height,
+                new_blocks: _,
+            } => link.a_to_b.clear_packets(height)?,
+            WorkerCmd::ClearPackets => {
+                // Requests received from now on are for packets which may be pending after
+                // this clearing.
+                self.clearing_scheduled.store(false, Ordering::SeqCst);
+                self.clear_pending_packets(link)?;
+            }
+            WorkerCmd::QueryStatus { .. } | WorkerCmd::SetPaused { .. } => {}
+        }
+
+        Ok(())
+    }
+
+    /// Find the packets which were sent on the source channel of `link` but not received on the
+    /// destination chain, or received but whose acknowledgement was not relayed, and schedule
+    /// their [`SendPacket`] and [`WriteAcknowledgement`] events. A failure to find them is only
+    /// logged, they are looked for again when clearing next.
+    fn clear_pending_packets(&self, link: &mut Link) -> Result<(), BoxError> {
+        let src_chain = self.chains.a.as_ref();
+        let dst_chain = self.chains.b.as_ref();
+
+        let channel = IdentifiedChannelEnd {
+            port_id: link.a_to_b.src_port_id().clone(),
+            channel_id: link.a_to_b.src_channel_id().clone(),
+            channel_end: src_chain.query_channel(
+                link.a_to_b.src_port_id(),
+                link.a_to_b.src_channel_id(),
+                Height::zero(),
+            )?,
+        };
+
+        let batches = vec![
+            pending_send_packets(src_chain, dst_chain, &channel),
+            pending_write_acks(src_chain, dst_chain, &channel),
+        ];
+
+        for batch in batches {
+            let (height, events) = match batch {
+                Ok(batch) => batch,
+                Err(e) => {
+                    warn!("failed to find pending packets: {}", e);
+                    continue;
+                }
+            };
+
+            if events.is_empty() {
+                continue;
+            }
+
+            info!("found {} pending packet events", events.len());
+
+            link.a_to_b.update_schedule(EventBatch {
+                height,
+                events,
+                chain_id: src_chain.id(),
+            })?;
+        }
+
+        Ok(())
+    }
 }
 
 /// A unidirectional path from a source chain, channel and port.
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize)]
 pub struct UnidirectionalChannelPath {
     /// Destination chain identifier.
     pub dst_chain_id: ChainId,
@@ -316,6 +977,18 @@ impl UnidirectionalChannelPath {
             self.src_chain_id, self.dst_chain_id, self.src_channel_id, self.src_port_id
         )
     }
+
+    /// The span of the worker relaying over this path, whose fields
+    /// are attached to all the logs emitted by the worker.
+    pub fn span(&self) -> Span {
+        info_span!(
+            "path",
+            chain_id = %self.src_chain_id,
+            dst_chain_id = %self.dst_chain_id,
+            port_id = %self.src_port_id,
+            channel_id = %self.src_channel_id,
+        )
+    }
 }
 
 /// An object determines the amount of parallelism that can
@@ -324,7 +997,7 @@ impl UnidirectionalChannelPath {
 /// [`Worker`] is spawned and all [`IbcEvent`]s mapped
 /// to an [`Object`] are sent to the associated [`Worker`]
 /// for processing.
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize)]
 pub enum Object {
     /// See [`UnidirectionalChannelPath`].
     UnidirectionalChannelPath(UnidirectionalChannelPath),
@@ -355,6 +1028,12 @@ impl Object {
         }
     }
 
+    pub fn span(&self) -> Span {
+        match self {
+            Self::UnidirectionalChannelPath(ref path) => path.span(),
+        }
+    }
+
     /// Build the object associated with the given [`SendPacket`] event.
     pub fn for_send_packet(e: &SendPacket, src_chain: &dyn ChainHandle) -> Result<Self, BoxError> {
         let dst_chain_id =
@@ -489,6 +1168,133 @@ pub fn collect_events(src_chain: &dyn ChainHandle, batch: EventBatch) -> Collect
     collected
 }
 
+/// Rebuild the [`SendPacket`] events of the packets sent on `channel` which have not been
+/// received on `dst_chain`, along with the height on `src_chain` at which they were found.
+fn pending_send_packets(
+    src_chain: &dyn ChainHandle,
+    dst_chain: &dyn ChainHandle,
+    channel: &IdentifiedChannelEnd,
+) -> Result<(Height, Vec<IbcEvent>), BoxError> {
+    let counterparty = channel.channel_end.counterparty();
+    let dst_channel_id = counterparty
+        .channel_id()
+        .ok_or_else(|| format!("missing counterparty channel for '{}'", channel.channel_id))?;
+
+    let (commitments, height) =
+        src_chain.query_packet_commitments(QueryPacketCommitmentsRequest {
+            port_id: channel.port_id.to_string(),
+            channel_id: channel.channel_id.to_string(),
+            pagination: ibc_proto::cosmos::base::query::pagination::all(),
+        })?;
+
+    if commitments.is_empty() {
+        return Ok((height, vec![]));
+    }
+
+    let sequences: Vec<Sequence> = dst_chain
+        .query_unreceived_packets(QueryUnreceivedPacketsRequest {
+            port_id: counterparty.port_id().to_string(),
+            channel_id: dst_channel_id.to_string(),
+            packet_commitment_sequences: commitments.iter().map(|p| p.sequence).collect(),
+        })?
+        .into_iter()
+        .map(From::from)
+        .collect();
+
+    if sequences.is_empty() {
+        return Ok((height, vec![]));
+    }
+
+    let mut events = src_chain.query_txs(QueryTxRequest::Packet(QueryPacketEventDataRequest {
+        event_id: IbcEventType::SendPacket,
+        source_port_id: channel.port_id.clone(),
+        source_channel_id: channel.channel_id.clone(),
+        destination_port_id: counterparty.port_id().clone(),
+        destination_channel_id: dst_channel_id.clone(),
+        sequences,
+        height,
+    }))?;
+
+    for event in events.iter_mut() {
+        event.set_height(height);
+    }
+
+    Ok((height, events))
+}
+
+/// Rebuild the [`WriteAcknowledgement`] events of the packets received on `channel` whose
+/// acknowledgement has not been relayed to `dst_chain`, along with the height on `src_chain`
+/// at which they were found.
+fn pending_write_acks(
+    src_chain: &dyn ChainHandle,
+    dst_chain: &dyn ChainHandle,
+    channel: &IdentifiedChannelEnd,
+) -> Result<(Height, Vec<IbcEvent>), BoxError> {
+    let counterparty = channel.channel_end.counterparty();
+    let dst_channel_id = counterparty
+        .channel_id()
+        .ok_or_else(|| format!("missing counterparty channel for '{}'", channel.channel_id))?;
+
+    let (acks, height) =
+        src_chain.query_packet_acknowledgements(QueryPacketAcknowledgementsRequest {
+            port_id: channel.port_id.to_string(),
+            channel_id: channel.channel_id.to_string(),
+            pagination: ibc_proto::cosmos::base::query::pagination::all(),
+        })?;
+
+    if acks.is_empty() {
+        return Ok((height, vec![]));
+    }
+
+    let sequences: Vec<Sequence> = dst_chain
+        .query_unreceived_acknowledgement(QueryUnreceivedAcksRequest {
+            port_id: counterparty.port_id().to_string(),
+            channel_id: dst_channel_id.to_string(),
+            packet_ack_sequences: acks.iter().map(|p| p.sequence).collect(),
+        })?
+        .into_iter()
+        .map(From::from)
+        .collect();
+
+    if sequences.is_empty() {
+        return Ok((height, vec![]));
+    }
+
+    let mut events = src_chain.query_txs(QueryTxRequest::Packet(QueryPacketEventDataRequest {
+        event_id: IbcEventType::WriteAck,
+        source_port_id: counterparty.port_id().clone(),
+        source_channel_id: dst_channel_id.clone(),
+        destination_port_id: channel.port_id.clone(),
+        destination_channel_id: channel.channel_id.clone(),
+        sequences,
+        height,
+    }))?;
+
+    for event in events.iter_mut() {
+        event.set_height(height);
+    }
+
+    Ok((height, events))
+}
+
+/// Records the queue depth of the worker in charge of the given [`Object`].
+fn record_queue_depth(object: &Object, depth: usize) {
+    let metrics = match telemetry::metrics() {
+        Some(metrics) => metrics,
+        None => return,
+    };
+
+    match object {
+        Object::UnidirectionalChannelPath(path) => metrics.worker_queue_depth(
+            &path.src_chain_id,
+            &path.dst_chain_id,
+            &path.src_channel_id,
+            &path.src_port_id,
+            depth,
+        ),
+    }
+}
+
 // TODO: Memoize this result
 fn get_counterparty_chain(
     src_chain: &dyn ChainHandle,
@@ -529,3 +1335,92 @@ fn get_counterparty_chain(
 
     Ok(client_state.chain_id())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use ibc::ics24_host::identifier::ChainId;
+
+    use crate::chain::handle::ChainHandle;
+    use crate::chain::mock::test_utils::get_basic_chain_config;
+    use crate::chain::mock::MockChain;
+    use crate::chain::runtime::ChainRuntime;
+    use crate::config::Config;
+    use crate::error::{Error, Kind};
+    use crate::registry::Registry;
+
+    use super::Supervisor;
+
+    /// Spawns the runtime of a mock chain, unless the identifier of the chain starts
+    /// with `broken`.
+    fn spawn_mock_chain(
+        config: &Config,
+        chain_id: &ChainId,
+    ) -> Result<Box<dyn ChainHandle>, Error> {
+        if chain_id.as_str().starts_with("broken") {
+            return Err(Kind::Config
+                .context(format!("chain {} cannot be started", chain_id))
+                .into());
+        }
+
+        let chain_config = config
+            .find_chain(chain_id)
+            .cloned()
+            .ok_or_else(|| Kind::Config.context(format!("missing chain {}", chain_id)))?;
+
+        let (handle, _) = ChainRuntime::<MockChain>::spawn(chain_config)?;
+        Ok(handle)
+    }
+
+    fn config_with_chains(chain_ids: &[&ChainId]) -> Config {
+        Config {
+            chains: chain_ids
+                .iter()
+                .map(|chain_id| get_basic_chain_config(chain_id.as_str()))
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn config_update_is_refused_if_a_chain_fails_to_start() {
+        let chain_a = ChainId::from_str("chain_a").unwrap();
+        let chain_b = ChainId::from_str("chain_b").unwrap();
+        let broken_c = ChainId::from_str("broken_c").unwrap();
+
+        let config = config_with_chains(&[&chain_a, &chain_b]);
+        let mut registry = Registry::with_spawner(config.clone(), spawn_mock_chain);
+        registry.get_or_spawn(&chain_a).unwrap();
+        registry.get_or_spawn(&chain_b).unwrap();
+
+        let mut supervisor = Supervisor::from_registry(registry, None);
+        for chain in supervisor.registry.chains() {
+            supervisor
+                .subscriptions
+                .insert(chain.id(), chain.subscribe().unwrap());
+        }
+
+        // Chain B is no longer supervised, and chain A is reconfigured, but chain C fails
+        // to start.
+        let mut new_config = config_with_chains(&[&chain_a, &broken_c]);
+        new_config.chains[0].max_msg_num = Some(10);
+
+        let result = supervisor.update_config(new_config, &[chain_a.clone(), broken_c.clone()]);
+        assert!(result.is_err());
+
+        // The configuration, the chain runtimes and their subscriptions are left untouched.
+        assert_eq!(
+            supervisor.registry.config().find_chain(&chain_a),
+            config.find_chain(&chain_a)
+        );
+        assert!(supervisor.registry.config().find_chain(&chain_b).is_some());
+        assert!(supervisor.registry.get(&broken_c).is_none());
+
+        for chain_id in &[&chain_a, &chain_b] {
+            let chain = supervisor.registry.get(chain_id).unwrap();
+            assert!(chain.query_latest_height().is_ok());
+            assert!(supervisor.subscriptions.contains_key(chain_id));
+        }
+    }
+}
@@ -0,0 +1,317 @@
+//! Telemetry of the relayer: Prometheus metrics, and the HTTP service which exposes them
+//! on the `/metrics` endpoint.
+
+use std::panic::{self, AssertUnwindSafe};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use anomaly::BoxError;
+use once_cell::sync::OnceCell;
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounterVec, IntGaugeVec, Opts, Registry, TextEncoder,
+};
+use tiny_http::{Header, Response, Server};
+use tracing::{error, info};
+
+use ibc::events::IbcEvent;
+use ibc::ics24_host::identifier::{ChainId, ChannelId, PortId};
+
+use crate::error::Error;
+
+/// Buckets of the transaction latency histogram, in seconds.
+const TX_LATENCY_BUCKETS: &[f64] = &[0.5, 1.0, 2.0, 5.0, 10.0, 20.0, 30.0, 60.0];
+
+static METRICS: OnceCell<Metrics> = OnceCell::new();
+
+/// Returns the metrics of this relayer instance, if the telemetry service was spawned.
+/// Nothing is recorded otherwise.
+pub fn metrics() -> Option<&'static Metrics> {
+    METRICS.get()
+}
+
+/// The metrics collected by the relayer. Packet metrics are labelled with the chain on which the
+/// messages were delivered, and the channel and port on that chain.
+pub struct Metrics {
+    registry: Registry,
+    packets_relayed: IntCounterVec,
+    acks_relayed: IntCounterVec,
+    timeouts_relayed: IntCounterVec,
+    tx_latency: HistogramVec,
+    tx_failures: IntCounterVec,
+    client_updates: IntCounterVec,
+    event_monitor_reconnects: IntCounterVec,
+    worker_queue_depth: IntGaugeVec,
+}
+
+impl Metrics {
+    /// Creates the metrics, registered with a registry of their own.
+    pub fn new() -> Result<Self, prometheus::Error> {
+        let packet_labels = &["chain", "channel", "port"];
+
+        let metrics = Self {
+            registry: Registry::new(),
+            packets_relayed: IntCounterVec::new(
+                Opts::new("ibc_packets_relayed", "Number of packets received"),
+                packet_labels,
+            )?,
+            acks_relayed: IntCounterVec::new(
+                Opts::new(
+                    "ibc_acks_relayed",
+                    "Number of packet acknowledgements relayed",
+                ),
+                packet_labels,
+            )?,
+            timeouts_relayed: IntCounterVec::new(
+                Opts::new("ibc_timeouts_relayed", "Number of packet timeouts relayed"),
+                packet_labels,
+            )?,
+            tx_latency: HistogramVec::new(
+                HistogramOpts::new(
+                    "ibc_tx_latency_seconds",
+                    "Time taken to submit messages to a chain and get the results",
+                )
+                .buckets(TX_LATENCY_BUCKETS.to_vec()),
+                &["chain"],
+            )?,
+            tx_failures: IntCounterVec::new(
+                Opts::new("ibc_tx_failures", "Number of transactions which failed"),
+                &["chain"],
+            )?,
+            client_updates: IntCounterVec::new(
+                Opts::new("ibc_client_updates", "Number of client updates submitted"),
+                &["chain", "client"],
+            )?,
+            event_monitor_reconnects: IntCounterVec::new(
+                Opts::new(
+                    "ibc_event_monitor_reconnects",
+                    "Number of times the event monitor reconnected to the websocket",
+                ),
+                &["chain"],
+            )?,
+            worker_queue_depth: IntGaugeVec::new(
+                Opts::new(
+                    "ibc_worker_queue_depth",
+                    "Number of commands waiting to be processed by a worker",
+                ),
+                &["src_chain", "dst_chain", "channel", "port"],
+            )?,
+        };
+
+        metrics
+            .registry
+            .register(Box::new(metrics.packets_relayed.clone()))?;
+        metrics
+            .registry
+            .register(Box::new(metrics.acks_relayed.clone()))?;
+        metrics
+            .registry
+            .register(Box::new(metrics.timeouts_relayed.clone()))?;
+        metrics
+            .registry
+            .register(Box::new(metrics.tx_latency.clone()))?;
+        metrics
+            .registry
+            .register(Box::new(metrics.tx_failures.clone()))?;
+        metrics
+            .registry
+            .register(Box::new(metrics.client_updates.clone()))?;
+        metrics
+            .registry
+            .register(Box::new(metrics.event_monitor_reconnects.clone()))?;
+        metrics
+            .registry
+            .register(Box::new(metrics.worker_queue_depth.clone()))?;
+
+        Ok(metrics)
+    }
+
+    /// Records the outcome of sending messages to the chain `chain_id`, which took `latency`:
+    /// the failed transactions, and the packets, acknowledgements, timeouts and client updates
+    /// which were delivered.
+    pub fn record_tx_result(
+        &self,
+        chain_id: &ChainId,
+        latency: Duration,
+        result: &Result<Vec<IbcEvent>, Error>,
+    ) {
+        let chain = chain_id.as_str();
+
+        self.tx_latency
+            .with_label_values(&[chain])
+            .observe(latency.as_secs_f64());
+
+        let events = match result {
+            Ok(events) => events,
+            Err(_) => {
+                self.tx_failures.with_label_values(&[chain]).inc();
+                return;
+            }
+        };
+
+        for event in events {
+            match event {
+                IbcEvent::ReceivePacket(ev) => self
+                    .packets_relayed
+                    .with_label_values(&[
+                        chain,
+                        ev.packet.destination_channel.as_str(),
+                        ev.packet.destination_port.as_str(),
+                    ])
+                    .inc(),
+                IbcEvent::AcknowledgePacket(ev) => self
+                    .acks_relayed
+                    .with_label_values(&[
+                        chain,
+                        ev.packet.source_channel.as_str(),
+                        ev.packet.source_port.as_str(),
+                    ])
+                    .inc(),
+                IbcEvent::TimeoutPacket(ev) => self
+                    .timeouts_relayed
+                    .with_label_values(&[
+                        chain,
+                        ev.packet.source_channel.as_str(),
+                        ev.packet.source_port.as_str(),
+                    ])
+                    .inc(),
+                IbcEvent::TimeoutOnClosePacket(ev) => self
+                    .timeouts_relayed
+                    .with_label_values(&[
+                        chain,
+                        ev.packet.source_channel.as_str(),
+                        ev.packet.source_port.as_str(),
+                    ])
+                    .inc(),
+                IbcEvent::UpdateClient(ev) => self
+                    .client_updates
+                    .with_label_values(&[chain, ev.client_id().as_str()])
+                    .inc(),
+                IbcEvent::ChainError(_) => self.tx_failures.with_label_values(&[chain]).inc(),
+                _ => {}
+            }
+        }
+    }
+
+    /// Records that the event monitor of `chain_id` reconnected to the chain.
+    pub fn event_monitor_reconnect(&self, chain_id: &ChainId) {
+        self.event_monitor_reconnects
+            .with_label_values(&[chain_id.as_str()])
+            .inc();
+    }
+
+    /// Records the number of commands waiting in the queue of the worker relaying the packets
+    /// from `channel_id` and `port_id` on `src_chain_id` to `dst_chain_id`.
+    pub fn worker_queue_depth(
+        &self,
+        src_chain_id: &ChainId,
+        dst_chain_id: &ChainId,
+        channel_id: &ChannelId,
+        port_id: &PortId,
+        depth: usize,
+    ) {
+        self.worker_queue_depth
+            .with_label_values(&[
+                src_chain_id.as_str(),
+                dst_chain_id.as_str(),
+                channel_id.as_str(),
+                port_id.as_str(),
+            ])
+            .set(depth as i64);
+    }
+
+    /// Encodes the current value of all metrics in the Prometheus text format.
+    pub fn gather(&self) -> Result<Vec<u8>, BoxError> {
+        let mut buffer = Vec::new();
+        TextEncoder::new().encode(&self.registry.gather(), &mut buffer)?;
+        Ok(buffer)
+    }
+}
+
+/// Sets up the metrics of this relayer instance, and spawns a thread serving them on
+/// `http://<host>:<port>/metrics`. The failure of this thread is logged.
+pub fn spawn(host: &str, port: u16) -> Result<JoinHandle<()>, BoxError> {
+    let metrics = METRICS.get_or_try_init(Metrics::new)?;
+    let server = Server::http((host, port))?;
+
+    info!(
+        "telemetry service listening on http://{}:{}/metrics",
+        host, port
+    );
+
+    let handle = thread::Builder::new()
+        .name("telemetry".to_string())
+        .spawn(
+            move || match panic::catch_unwind(AssertUnwindSafe(|| serve(&server, metrics))) {
+                Ok(()) => error!("telemetry service stopped"),
+                Err(_) => error!("telemetry service panicked"),
+            },
+        )?;
+
+    Ok(handle)
+}
+
+/// Answers the requests received by `server` until it shuts down.
+fn serve(server: &Server, metrics: &Metrics) {
+    for request in server.incoming_requests() {
+        let response = if request.url() == "/metrics" {
+            match metrics.gather() {
+                Ok(buffer) => {
+                    let mut response = Response::from_data(buffer);
+                    if let Ok(header) =
+                        Header::from_bytes("Content-Type", TextEncoder::new().format_type())
+                    {
+                        response.add_header(header);
+                    }
+                    response
+                }
+                Err(e) => Response::from_string(e.to_string()).with_status_code(500),
+            }
+        } else {
+            Response::from_string("not found").with_status_code(404)
+        };
+
+        if let Err(e) = request.respond(response) {
+            error!("failed to respond to telemetry request: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use std::time::Duration;
+
+    use ibc::events::IbcEvent;
+    use ibc::ics04_channel::events::ReceivePacket;
+    use ibc::ics04_channel::packet::Packet;
+    use ibc::ics24_host::identifier::{ChainId, ChannelId, PortId};
+    use ibc::Height;
+
+    use super::Metrics;
+
+    #[test]
+    fn received_packets_are_counted() {
+        let chain_id = ChainId::from_str("telemetry-0").unwrap();
+        let packet = Packet {
+            destination_channel: ChannelId::from_str("channel-7").unwrap(),
+            destination_port: PortId::from_str("transfer").unwrap(),
+            ..Default::default()
+        };
+        let events = vec![
+            IbcEvent::ReceivePacket(ReceivePacket {
+                height: Height::new(0, 10),
+                packet,
+            }),
+            IbcEvent::ChainError("out of gas".to_string()),
+        ];
+
+        let metrics = Metrics::new().unwrap();
+        metrics.record_tx_result(&chain_id, Duration::from_secs(1), &Ok(events));
+
+        let output = String::from_utf8(metrics.gather().unwrap()).unwrap();
+        assert!(output.contains(
+            r#"ibc_packets_relayed{chain="telemetry-0",channel="channel-7",port="transfer"} 1"#
+        ));
+        assert!(output.contains(r#"ibc_tx_failures{chain="telemetry-0"} 1"#));
+    }
+}